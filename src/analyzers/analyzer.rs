@@ -0,0 +1,12 @@
+use crate::error::Result;
+use crate::output::types::{AnalysisResult, Diagnostic};
+
+/// An extra check run over every finished analysis, registered with
+/// `PathAnalyzerBuilder::analyzer`. Its findings are appended to the
+/// result's `diagnostics`; an error fails the analysis.
+pub trait Analyzer: Send + Sync {
+    /// Shown in logs
+    fn name(&self) -> &str;
+
+    fn analyze(&self, result: &AnalysisResult) -> Result<Vec<Diagnostic>>;
+}
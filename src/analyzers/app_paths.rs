@@ -0,0 +1,158 @@
+use crate::analyzers::registry_path::normalize;
+use crate::output::types::{
+    Diagnostic, DiagnosticKind, ExecutableInfo, PathEntry, PathScope, Severity,
+};
+use crate::platform::windows::{self, AppPath};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Compares `App Paths` registrations with PATH lookup. `Start-Process`, the
+/// Run dialog and other `ShellExecute` callers use the registered file, while
+/// terminals search PATH, so `python.exe` registered by one installer and
+/// first in PATH from another runs a different Python depending on how it is
+/// started.
+pub struct AppPathsAnalyzer;
+
+impl AppPathsAnalyzer {
+    pub fn new() -> Self {
+        AppPathsAnalyzer
+    }
+
+    /// `app_paths` in lookup order, the user's before the machine's
+    pub fn analyze(&self, app_paths: &[AppPath], path_entries: &[PathEntry]) -> Vec<Diagnostic> {
+        let mut seen = HashSet::new();
+        let mut diagnostics = Vec::new();
+
+        for app_path in app_paths {
+            // A user registration hides the machine one
+            if !seen.insert(app_path.name.to_lowercase()) {
+                continue;
+            }
+            // Commands only App Paths knows are what it is for
+            let Some(on_path) = resolve(path_entries, &app_path.name) else {
+                continue;
+            };
+
+            let target = PathBuf::from(windows::expand_windows_env_vars(&app_path.target));
+            let registered = normalize(&target.to_string_lossy());
+            if [&on_path.full_path, &on_path.resolved_path]
+                .iter()
+                .any(|path| normalize(&path.to_string_lossy()) == registered)
+            {
+                continue;
+            }
+
+            let key = match app_path.scope {
+                PathScope::User => "HKCU",
+                PathScope::Machine => "HKLM",
+            };
+            let missing = if target.exists() {
+                ""
+            } else {
+                ", which no longer exists"
+            };
+            diagnostics.push(Diagnostic {
+                kind: DiagnosticKind::AppPaths,
+                severity: Severity::Medium,
+                message: format!(
+                    "{} is registered in App Paths ({}) as {}{}, but PATH finds {}; \
+                     Start-Process and the Run dialog open the registered file while \
+                     terminals run the PATH copy",
+                    app_path.name,
+                    key,
+                    target.display(),
+                    missing,
+                    on_path.full_path.display()
+                ),
+                paths: vec![target, on_path.full_path.clone()],
+            });
+        }
+
+        diagnostics
+    }
+}
+
+impl Default for AppPathsAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The copy of `name` (with its extension) a PATH search runs
+fn resolve<'a>(path_entries: &'a [PathEntry], name: &str) -> Option<&'a ExecutableInfo> {
+    path_entries
+        .iter()
+        .flat_map(|entry| &entry.executables)
+        .filter(|exec| !exec.broken)
+        .find(|exec| file_name(&exec.full_path).eq_ignore_ascii_case(name))
+}
+
+/// The last component, split at either slash
+fn file_name(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    path.rsplit(['\\', '/'])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(dir: &str, order: usize, file_name: &str) -> PathEntry {
+        let full_path = PathBuf::from(format!(r"{}\{}", dir, file_name));
+        PathEntry {
+            path: PathBuf::from(dir),
+            order,
+            exists: true,
+            is_accessible: true,
+            executables: vec![ExecutableInfo {
+                name: file_name.rsplit_once('.').unwrap().0.to_string(),
+                full_path: full_path.clone(),
+                resolved_path: full_path,
+                path_order: order,
+                is_active: order == 0,
+                ..ExecutableInfo::fixture()
+            }],
+            sources: Vec::new(),
+            scope: None,
+        }
+    }
+
+    fn app_path(name: &str, target: &str, scope: PathScope) -> AppPath {
+        AppPath {
+            name: name.to_string(),
+            target: target.to_string(),
+            scope,
+        }
+    }
+
+    #[test]
+    fn test_app_path_diverging_from_path() {
+        let entries = vec![
+            entry(r"C:\Python312", 0, "python.exe"),
+            entry(r"C:\Program Files\Git\cmd", 1, "git.exe"),
+        ];
+        let app_paths = vec![
+            app_path("python.exe", r"C:\Python311\python.exe", PathScope::User),
+            // Hidden by the user registration
+            app_path("python.exe", r"C:\Python312\python.exe", PathScope::Machine),
+            app_path(
+                "GIT.EXE",
+                r"c:\program files\git\cmd\git.exe",
+                PathScope::Machine,
+            ),
+            app_path("chrome.exe", r"C:\Chrome\chrome.exe", PathScope::Machine),
+        ];
+
+        let diagnostics = AppPathsAnalyzer::new().analyze(&app_paths, &entries);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::AppPaths);
+        let message = &diagnostics[0].message;
+        assert!(message.starts_with(
+            r"python.exe is registered in App Paths (HKCU) as C:\Python311\python.exe, which"
+        ));
+        assert!(message.contains(r"no longer exists, but PATH finds C:\Python312\python.exe;"));
+    }
+}
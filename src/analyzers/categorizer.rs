@@ -1,5 +1,6 @@
 use crate::output::types::{ConflictCategory, ExecutableInfo, ManagerType, PlatformInfo, Severity};
 use crate::platform::wsl;
+use std::path::Path;
 
 pub struct ConflictCategorizer {
     platform: PlatformInfo,
@@ -11,6 +12,16 @@ impl ConflictCategorizer {
     }
 
     pub fn categorize(&self, _binary_name: &str, instances: &[ExecutableInfo]) -> ConflictCategory {
+        // A dangling link left ahead of a working copy, e.g. by an uninstall
+        if broken_symlink_ahead(instances).is_some() {
+            return ConflictCategory::BrokenSymlink;
+        }
+
+        // foo.bat found through PATHEXT before foo.exe
+        if pathext_shadowing(instances).is_some() {
+            return ConflictCategory::PathextShadowing;
+        }
+
         // Check for WSL vs Windows conflicts (only on WSL)
         if self.platform.is_wsl && self.is_wsl_vs_windows_conflict(instances) {
             return ConflictCategory::WslVsWindows;
@@ -45,40 +56,87 @@ impl ConflictCategorizer {
         category: ConflictCategory,
         instances: &[ExecutableInfo],
     ) -> Severity {
+        self.assess_severity_with_reason(category, instances).0
+    }
+
+    /// Severity together with a short explanation of why it was chosen
+    pub fn assess_severity_with_reason(
+        &self,
+        category: ConflictCategory,
+        instances: &[ExecutableInfo],
+    ) -> (Severity, String) {
         match category {
             ConflictCategory::WslVsWindows => {
                 // WSL/Windows mixing is typically high severity
-                Severity::High
+                (
+                    Severity::High,
+                    "WSL and Windows binaries are mixed in PATH".to_string(),
+                )
             }
             ConflictCategory::MultipleVersionManagers => {
                 // Multiple version managers can cause confusion
-                Severity::Medium
+                (
+                    Severity::Medium,
+                    "more than one version manager provides this binary".to_string(),
+                )
             }
             ConflictCategory::VersionManagerVsSystem => {
                 // Check if versions differ significantly
                 if self.has_major_version_difference(instances) {
-                    Severity::Critical
+                    (
+                        Severity::Critical,
+                        "major versions differ between the version manager and the system copy"
+                            .to_string(),
+                    )
                 } else {
-                    Severity::Medium
+                    (
+                        Severity::Medium,
+                        "a version manager and the system both provide this binary".to_string(),
+                    )
                 }
             }
-            ConflictCategory::PackageManagerVsSystem => Severity::Low,
+            ConflictCategory::PackageManagerVsSystem => (
+                Severity::Low,
+                "a package manager and the system both provide this binary".to_string(),
+            ),
             ConflictCategory::DuplicateVersions => {
                 if self.has_major_version_difference(instances) {
-                    Severity::High
+                    (Severity::High, "major versions differ".to_string())
                 } else {
-                    Severity::Low
+                    (
+                        Severity::Low,
+                        "versions differ only in minor or patch level".to_string(),
+                    )
                 }
             }
             ConflictCategory::ShadowedBinary => {
                 // Check if the shadowed binary is significantly different
                 if self.are_likely_same_binary(instances) {
-                    Severity::Info
+                    (
+                        Severity::Info,
+                        "every instance resolves to the same file".to_string(),
+                    )
                 } else {
-                    Severity::Medium
+                    (
+                        Severity::Medium,
+                        "different files share this name and no version difference was detected"
+                            .to_string(),
+                    )
                 }
             }
-            ConflictCategory::Other => Severity::Low,
+            ConflictCategory::BrokenSymlink => (
+                Severity::Low,
+                "a broken symlink comes before a working copy".to_string(),
+            ),
+            ConflictCategory::PathextShadowing => (
+                Severity::Medium,
+                "a script runs instead of the program of the same name".to_string(),
+            ),
+            ConflictCategory::PipMismatch => (
+                Severity::High,
+                "pip installs packages for a different Python than the one that runs".to_string(),
+            ),
+            ConflictCategory::Other => (Severity::Low, "uncategorized conflict".to_string()),
         }
     }
 
@@ -87,6 +145,19 @@ impl ConflictCategorizer {
         category: ConflictCategory,
         binary_name: &str,
         instances: &[ExecutableInfo],
+    ) -> Option<String> {
+        let recommendation = self.category_recommendation(category, binary_name, instances)?;
+        match brew_unlink_hint(instances) {
+            Some(hint) => Some(format!("{} {}", recommendation, hint)),
+            None => Some(recommendation),
+        }
+    }
+
+    fn category_recommendation(
+        &self,
+        category: ConflictCategory,
+        binary_name: &str,
+        instances: &[ExecutableInfo],
     ) -> Option<String> {
         match category {
             ConflictCategory::WslVsWindows => Some(format!(
@@ -100,6 +171,19 @@ impl ConflictCategorizer {
                 binary_name
             )),
             ConflictCategory::VersionManagerVsSystem => {
+                if let Some(module) = instances
+                    .iter()
+                    .filter_map(|i| i.manager.as_ref())
+                    .find(|m| m.manager_type == ManagerType::EnvironmentModule)
+                {
+                    return Some(format!(
+                        "{} comes from the loaded module {} and from the system. \
+                        Keep the module loaded where you need its version, or run \
+                        `module unload {}` to fall back to the system copy.",
+                        binary_name, module.name, module.name
+                    ));
+                }
+
                 let version_manager = instances
                     .iter()
                     .find(|i| {
@@ -112,16 +196,138 @@ impl ConflictCategorizer {
                     .map(|m| m.name.as_str())
                     .unwrap_or("version manager");
 
-                Some(format!(
-                    "Consider using {} consistently or removing the system installation of {} to avoid confusion.",
-                    version_manager, binary_name
-                ))
+                match protected_copy(instances) {
+                    Some(system) => Some(format!(
+                        "The system {} at {} is SIP-protected and cannot be removed. \
+                        Keep {}'s directory first in PATH and select versions with {} instead.",
+                        binary_name,
+                        system.display(),
+                        version_manager,
+                        version_manager
+                    )),
+                    None => Some(format!(
+                        "Consider using {} consistently or removing the system installation of {} to avoid confusion.",
+                        version_manager, binary_name
+                    )),
+                }
             }
             ConflictCategory::DuplicateVersions => Some(format!(
                 "Multiple versions of {} found. Ensure you're using the intended version.",
                 binary_name
             )),
-            _ => None,
+            ConflictCategory::PackageManagerVsSystem => {
+                let package_manager = instances
+                    .iter()
+                    .filter_map(|i| i.manager.as_ref())
+                    .find(|m| m.manager_type == ManagerType::PackageManager)
+                    .map(|m| m.name.as_str())
+                    .unwrap_or("package manager");
+
+                if let Some(system) = protected_copy(instances) {
+                    Some(format!(
+                        "{} is installed by both {} and the system. \
+                        The system copy at {} is SIP-protected and cannot be removed, \
+                        so order PATH to pick the copy you intend to use.",
+                        binary_name,
+                        package_manager,
+                        system.display()
+                    ))
+                } else if self.has_different_versions(instances) {
+                    Some(format!(
+                        "{} is installed by both {} and the system with different versions. \
+                        Order PATH so the copy you intend to use comes first, or uninstall the other one.",
+                        binary_name, package_manager
+                    ))
+                } else {
+                    Some(format!(
+                        "{} is installed by both {} and the system. \
+                        If both report the same version, keep one installation to avoid drift after upgrades.",
+                        binary_name, package_manager
+                    ))
+                }
+            }
+            ConflictCategory::ShadowedBinary => {
+                if self.are_likely_same_binary(instances) {
+                    Some(format!(
+                        "All copies of {} are the same file, through symlinks or hard links. \
+                        The conflict is harmless; remove the duplicate PATH entry to silence it.",
+                        binary_name
+                    ))
+                } else if let Some(system) = instances.get(1..).and_then(protected_copy) {
+                    let active = instances.first()?;
+                    Some(format!(
+                        "{} shadows the SIP-protected {}, which cannot be removed. \
+                        Verify they report the same version, and move {} later in PATH \
+                        if the system copy should win.",
+                        active.full_path.display(),
+                        system.display(),
+                        active
+                            .full_path
+                            .parent()
+                            .unwrap_or(&active.full_path)
+                            .display()
+                    ))
+                } else {
+                    let active = instances.first()?;
+                    Some(format!(
+                        "{} shadows {} other cop{} of {}. \
+                        Verify they report the same version and remove the stale copy.",
+                        active.full_path.display(),
+                        instances.len() - 1,
+                        if instances.len() == 2 { "y" } else { "ies" },
+                        binary_name
+                    ))
+                }
+            }
+            ConflictCategory::BrokenSymlink => {
+                let (broken, working) = broken_symlink_ahead(instances)?;
+                let target = broken
+                    .symlink_target
+                    .as_deref()
+                    .unwrap_or(&broken.resolved_path);
+                Some(format!(
+                    "{} points to {}, which no longer exists. Shells skip it and run {}; \
+                    remove the dangling link.",
+                    broken.full_path.display(),
+                    target.display(),
+                    working.full_path.display()
+                ))
+            }
+            ConflictCategory::PathextShadowing => {
+                let (script, program) = pathext_shadowing(instances)?;
+                let reason = if script.path_order == program.path_order {
+                    format!(
+                        "PATHEXT lists .{} before .{}",
+                        extension(script).to_uppercase(),
+                        extension(program).to_uppercase()
+                    )
+                } else {
+                    "its directory comes earlier in PATH".to_string()
+                };
+                Some(format!(
+                    "{} runs {} instead of {} because {}. Rename or remove the script, or \
+                    type {} in full.",
+                    binary_name,
+                    script.full_path.display(),
+                    program.full_path.display(),
+                    reason,
+                    file_name(program)
+                ))
+            }
+            ConflictCategory::PipMismatch => {
+                let python = instances.get(1)?;
+                Some(format!(
+                    "Packages installed with {} are not importable by {} ({}). Use \
+                    `{} -m pip` instead, or put the directory of the intended Python, with its \
+                    own {}, first in PATH.",
+                    binary_name,
+                    python.name,
+                    python.full_path.display(),
+                    python.name,
+                    binary_name
+                ))
+            }
+            ConflictCategory::Other => None,
         }
     }
 
@@ -155,10 +361,16 @@ impl ConflictCategorizer {
     }
 
     fn is_version_manager_vs_system_conflict(&self, instances: &[ExecutableInfo]) -> bool {
+        // A loaded module selects a version just like a version manager
         let has_version_manager = instances.iter().any(|i| {
             i.manager
                 .as_ref()
-                .map(|m| m.manager_type == ManagerType::VersionManager)
+                .map(|m| {
+                    matches!(
+                        m.manager_type,
+                        ManagerType::VersionManager | ManagerType::EnvironmentModule
+                    )
+                })
                 .unwrap_or(false)
         });
 
@@ -228,22 +440,89 @@ impl ConflictCategorizer {
         }
     }
 
+    /// Symlinks to, or hard links of, a single file
     fn are_likely_same_binary(&self, instances: &[ExecutableInfo]) -> bool {
-        if instances.len() < 2 {
-            return false;
+        match instances.split_first() {
+            Some((first, rest)) if !rest.is_empty() => rest.iter().all(|i| i.is_same_file(first)),
+            _ => false,
         }
+    }
+}
 
-        // Check if resolved paths are the same
-        let resolved_paths: std::collections::HashSet<_> =
-            instances.iter().map(|i| &i.resolved_path).collect();
+/// The first broken symlink that comes before a working copy, and the
+/// working copy the shell runs instead
+fn broken_symlink_ahead(
+    instances: &[ExecutableInfo],
+) -> Option<(&ExecutableInfo, &ExecutableInfo)> {
+    let broken = instances.iter().position(|i| i.broken)?;
+    let working = instances[broken + 1..].iter().find(|i| !i.broken)?;
+    Some((&instances[broken], working))
+}
 
-        resolved_paths.len() == 1
+/// The active copy and the program it hides, when a script found through
+/// PATHEXT (`.bat`, `.cmd`, `.vbs`, ...) runs instead of an `.exe` or `.com`
+fn pathext_shadowing(instances: &[ExecutableInfo]) -> Option<(&ExecutableInfo, &ExecutableInfo)> {
+    let is_program = |i: &ExecutableInfo| matches!(extension(i).as_str(), "exe" | "com");
+    let active = instances.iter().find(|i| !i.broken)?;
+    if is_program(active) || extension(active).is_empty() {
+        return None;
     }
+    let program = instances
+        .iter()
+        .find(|i| !i.broken && is_program(i) && !i.is_same_file(active))?;
+    Some((active, program))
+}
+
+/// Lowercase extension, split at either slash so Windows paths parse anywhere
+fn extension(instance: &ExecutableInfo) -> String {
+    file_name(instance)
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_lowercase())
+        .unwrap_or_default()
+}
+
+fn file_name(instance: &ExecutableInfo) -> String {
+    let path = instance.full_path.to_string_lossy();
+    path.rsplit(['\\', '/'])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// First instance the OS protects from removal (SIP on macOS)
+fn protected_copy(instances: &[ExecutableInfo]) -> Option<&Path> {
+    instances
+        .iter()
+        .find(|i| i.immutable)
+        .map(|i| i.full_path.as_path())
+}
+
+/// When Homebrew metadata shows the active copy comes from a linked keg,
+/// name the formula to unlink so the next copy takes over
+fn brew_unlink_hint(instances: &[ExecutableInfo]) -> Option<String> {
+    let (active, others) = instances.split_first()?;
+    let formula = active
+        .origin
+        .as_ref()
+        .filter(|o| o.source == "brew" && o.linked == Some(true))?;
+    let next = others.iter().find(|i| {
+        i.origin
+            .as_ref()
+            .map(|o| o.package != formula.package)
+            .unwrap_or(true)
+    })?;
+
+    Some(format!(
+        "To use {} instead, run `brew unlink {}`.",
+        next.full_path.display(),
+        formula.package
+    ))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::output::types::PathEntry;
 
     fn create_test_platform() -> PlatformInfo {
         PlatformInfo {
@@ -252,7 +531,204 @@ mod tests {
             is_wsl: true,
             wsl_version: Some("WSL2".to_string()),
             wsl_distro: Some("Ubuntu".to_string()),
+            wsl_interop: None,
+        }
+    }
+
+    fn instance(path: &str, manager: Option<ManagerType>) -> ExecutableInfo {
+        use crate::output::types::ManagerInfo;
+        use std::path::PathBuf;
+
+        ExecutableInfo {
+            name: "git".to_string(),
+            full_path: PathBuf::from(path),
+            size: 1000,
+            resolved_path: PathBuf::from(path),
+            manager: manager.map(|manager_type| ManagerInfo {
+                manager_type,
+                name: match manager_type {
+                    ManagerType::PackageManager => "Homebrew".to_string(),
+                    _ => "System".to_string(),
+                },
+                description: String::new(),
+            }),
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    #[test]
+    fn test_recommendations_for_common_categories() {
+        let categorizer = ConflictCategorizer::new(create_test_platform());
+
+        let package = vec![
+            instance("/opt/homebrew/bin/git", Some(ManagerType::PackageManager)),
+            instance("/usr/bin/git", Some(ManagerType::SystemInstall)),
+        ];
+        let recommendation = categorizer
+            .generate_recommendation(ConflictCategory::PackageManagerVsSystem, "git", &package)
+            .unwrap();
+        assert!(recommendation.contains("Homebrew"));
+        assert!(!recommendation.contains("brew unlink"));
+
+        let mut linked = package.clone();
+        linked[0].origin = Some(crate::output::types::PackageOrigin {
+            source: "brew".to_string(),
+            package: "git".to_string(),
+            version: Some("2.43.0".to_string()),
+            linked: Some(true),
+        });
+        let recommendation = categorizer
+            .generate_recommendation(ConflictCategory::PackageManagerVsSystem, "git", &linked)
+            .unwrap();
+        assert!(recommendation.ends_with("To use /usr/bin/git instead, run `brew unlink git`."));
+
+        let shadowed = vec![
+            instance("/usr/local/bin/git", None),
+            instance("/usr/bin/git", None),
+        ];
+        let recommendation = categorizer
+            .generate_recommendation(ConflictCategory::ShadowedBinary, "git", &shadowed)
+            .unwrap();
+        assert!(recommendation.starts_with("/usr/local/bin/git shadows 1 other copy"));
+    }
+
+    #[test]
+    fn test_recommendations_never_remove_sip_copies() {
+        let categorizer = ConflictCategorizer::new(create_test_platform());
+        let mut system = instance("/usr/bin/git", Some(ManagerType::SystemInstall));
+        system.immutable = true;
+
+        let package = vec![
+            instance("/opt/homebrew/bin/git", Some(ManagerType::PackageManager)),
+            system.clone(),
+        ];
+        let recommendation = categorizer
+            .generate_recommendation(ConflictCategory::PackageManagerVsSystem, "git", &package)
+            .unwrap();
+        assert!(recommendation.contains("SIP-protected"));
+        assert!(!recommendation.contains("uninstall"));
+
+        let shadowed = vec![instance("/usr/local/bin/git", None), system];
+        let recommendation = categorizer
+            .generate_recommendation(ConflictCategory::ShadowedBinary, "git", &shadowed)
+            .unwrap();
+        assert!(recommendation.contains("move /usr/local/bin later in PATH"));
+        assert!(!recommendation.contains("remove the stale copy"));
+    }
+
+    #[test]
+    fn test_severity_reason() {
+        let categorizer = ConflictCategorizer::new(create_test_platform());
+        let same = vec![
+            instance("/usr/bin/git", None),
+            instance("/usr/bin/git", None),
+        ];
+
+        let (severity, reason) =
+            categorizer.assess_severity_with_reason(ConflictCategory::ShadowedBinary, &same);
+        assert_eq!(severity, Severity::Info);
+        assert!(reason.contains("same file"));
+
+        // Hard links in busybox-style layouts share a device and inode
+        let mut linked = vec![instance("/bin/sh", None), instance("/usr/bin/sh", None)];
+        for link in &mut linked {
+            link.dev = Some(2049);
+            link.inode = Some(131);
         }
+        let (severity, _) =
+            categorizer.assess_severity_with_reason(ConflictCategory::ShadowedBinary, &linked);
+        assert_eq!(severity, Severity::Info);
+
+        linked[1].inode = Some(132);
+        let (severity, _) =
+            categorizer.assess_severity_with_reason(ConflictCategory::ShadowedBinary, &linked);
+        assert_eq!(severity, Severity::Medium);
+    }
+
+    #[test]
+    fn test_broken_symlink_ahead_of_working_copy() {
+        let categorizer = ConflictCategorizer::new(create_test_platform());
+        let mut broken = instance("/usr/local/bin/node", None);
+        broken.broken = true;
+        broken.symlink_target = Some(std::path::PathBuf::from("/opt/node-16/bin/node"));
+        let instances = vec![broken.clone(), instance("/usr/bin/node", None)];
+
+        let category = categorizer.categorize("node", &instances);
+        assert_eq!(category, ConflictCategory::BrokenSymlink);
+        let recommendation = categorizer
+            .generate_recommendation(category, "node", &instances)
+            .unwrap();
+        assert!(recommendation.contains("/opt/node-16/bin/node, which no longer exists"));
+        assert!(recommendation.contains("run /usr/bin/node"));
+
+        // The link never runs, so the working copy is the active one
+        let entries: Vec<PathEntry> = instances
+            .iter()
+            .enumerate()
+            .map(|(order, exec)| PathEntry {
+                path: exec.full_path.parent().unwrap().to_path_buf(),
+                order,
+                exists: true,
+                is_accessible: true,
+                executables: vec![ExecutableInfo {
+                    path_order: order,
+                    ..exec.clone()
+                }],
+                sources: Vec::new(),
+                scope: None,
+            })
+            .collect();
+        let conflicts = crate::core::ConflictDetector::new(create_test_platform())
+            .detect_conflicts(&entries)
+            .unwrap();
+        let conflict = &conflicts[0];
+        assert_eq!(
+            conflict.active_instance.full_path,
+            std::path::PathBuf::from("/usr/bin/node")
+        );
+        assert!(!conflict.instances[0].is_active);
+        assert_eq!(conflict.instances[0].shadowed_by, None);
+        assert!(conflict.instances[1].is_active);
+        assert!(conflict.description.contains("Active: /usr/bin/node"));
+
+        // A broken copy behind the working one shadows nothing
+        let instances = vec![instance("/usr/bin/node", None), broken];
+        assert_eq!(
+            categorizer.categorize("node", &instances),
+            ConflictCategory::ShadowedBinary
+        );
+    }
+
+    #[test]
+    fn test_script_shadowing_program() {
+        let categorizer = ConflictCategorizer::new(create_test_platform());
+        let at = |path: &str, order: usize| {
+            let mut instance = instance(path, None);
+            instance.path_order = order;
+            instance
+        };
+
+        let same_dir = vec![at(r"C:\tools\foo.bat", 0), at(r"C:\tools\foo.exe", 0)];
+        let category = categorizer.categorize("foo", &same_dir);
+        assert_eq!(category, ConflictCategory::PathextShadowing);
+        let recommendation = categorizer
+            .generate_recommendation(category, "foo", &same_dir)
+            .unwrap();
+        assert!(recommendation.contains("because PATHEXT lists .BAT before .EXE"));
+        assert!(recommendation.ends_with("type foo.exe in full."));
+
+        let earlier = vec![at(r"C:\scripts\foo.cmd", 0), at(r"C:\tools\foo.exe", 3)];
+        let recommendation = categorizer
+            .generate_recommendation(ConflictCategory::PathextShadowing, "foo", &earlier)
+            .unwrap();
+        assert!(recommendation.contains("because its directory comes earlier in PATH"));
+
+        // The program winning is an ordinary shadowed binary
+        let program_first = vec![at(r"C:\tools\foo.exe", 0), at(r"C:\scripts\foo.cmd", 1)];
+        assert_eq!(
+            categorizer.categorize("foo", &program_first),
+            ConflictCategory::ShadowedBinary
+        );
     }
 
     #[test]
@@ -0,0 +1,101 @@
+use crate::output::types::{Diagnostic, DiagnosticKind, PathEntry, Severity};
+use crate::platform::windows;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Flags PATH executables named after cmd.exe internal commands (`dir`,
+/// `copy`, `mklink`, ...), which cmd never runs by their bare name
+pub struct CmdInternalAnalyzer;
+
+impl CmdInternalAnalyzer {
+    pub fn new() -> Self {
+        CmdInternalAnalyzer
+    }
+
+    pub fn analyze(&self, path_entries: &[PathEntry]) -> Vec<Diagnostic> {
+        let mut by_command: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+        for exec in path_entries.iter().flat_map(|entry| &entry.executables) {
+            // `name` has its extension stripped on Windows
+            let file_name = exec.full_path.file_name().unwrap_or_default();
+            let file_name = file_name.to_string_lossy();
+            if windows::is_cmd_internal(&file_name) {
+                let command = file_name.split('.').next().unwrap_or_default();
+                by_command
+                    .entry(command.to_lowercase())
+                    .or_default()
+                    .push(exec.full_path.clone());
+            }
+        }
+
+        by_command
+            .into_iter()
+            .map(|(command, paths)| Diagnostic {
+                kind: DiagnosticKind::CmdInternal,
+                severity: Severity::Low,
+                message: format!(
+                    "`{}` is a cmd.exe internal command, so cmd never runs the executable(s) \
+                     below by that name; call them with their extension (`{}`) or full path",
+                    command,
+                    paths
+                        .first()
+                        .and_then(|p| p.file_name())
+                        .map(|name| name.to_string_lossy().to_lowercase())
+                        .unwrap_or_default()
+                ),
+                paths,
+            })
+            .collect()
+    }
+}
+
+impl Default for CmdInternalAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::types::ExecutableInfo;
+
+    fn executable(dir: &str, file_name: &str) -> ExecutableInfo {
+        let full_path = PathBuf::from(dir).join(file_name);
+        ExecutableInfo {
+            // Stripped of its extension, as the scanner does on Windows
+            name: file_name.rsplit_once('.').unwrap().0.to_string(),
+            full_path: full_path.clone(),
+            size: 1000,
+            resolved_path: full_path,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    fn entry(dir: &str, names: &[&str]) -> PathEntry {
+        PathEntry {
+            path: PathBuf::from(dir),
+            order: 0,
+            exists: true,
+            is_accessible: true,
+            executables: names.iter().map(|name| executable(dir, name)).collect(),
+            sources: Vec::new(),
+            scope: None,
+        }
+    }
+
+    #[test]
+    fn test_flags_internal_names() {
+        let entries = vec![
+            entry(
+                r"C:\Program Files\Git\usr\bin",
+                &["mkdir.exe", "ls.exe", "echo.exe"],
+            ),
+            entry(r"C:\tools", &["MKDIR.EXE", "dir.txt", "setup.exe"]),
+        ];
+
+        let diagnostics = CmdInternalAnalyzer::new().analyze(&entries);
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics[0].message.starts_with("`echo`"));
+        assert_eq!(diagnostics[1].paths.len(), 2);
+    }
+}
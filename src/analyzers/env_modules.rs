@@ -0,0 +1,183 @@
+use std::path::{Path, PathBuf};
+
+/// A module loaded through Lmod or Environment Modules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadedModule {
+    /// Full module name as `module list` shows it, e.g. "gcc/12.2.0"
+    pub name: String,
+    /// Directories the module file adds to PATH
+    pub path_dirs: Vec<PathBuf>,
+}
+
+/// The modules loaded in this shell, read from `LOADEDMODULES` and the
+/// matching module files in `_LMFILES_`
+#[derive(Debug, Clone, Default)]
+pub struct ModuleEnvironment {
+    /// "Lmod" or "Environment Modules"
+    pub system: &'static str,
+    pub modules: Vec<LoadedModule>,
+}
+
+impl ModuleEnvironment {
+    pub fn from_env() -> Self {
+        let loaded = std::env::var("LOADEDMODULES").unwrap_or_default();
+        let files = std::env::var("_LMFILES_").unwrap_or_default();
+        let mut environment =
+            Self::from_vars(&loaded, &files, |path| std::fs::read_to_string(path).ok());
+        if std::env::var_os("LMOD_CMD").is_none() && std::env::var_os("LMOD_VERSION").is_none() {
+            environment.system = "Environment Modules";
+        }
+        environment
+    }
+
+    /// Build from the two variables; `read` loads a module file. Both lists
+    /// are colon-separated and in the same order.
+    pub fn from_vars(loaded: &str, files: &str, read: impl Fn(&Path) -> Option<String>) -> Self {
+        let mut files = files.split(':').filter(|f| !f.is_empty());
+        let modules = loaded
+            .split(':')
+            .filter(|name| !name.is_empty())
+            .map(|name| LoadedModule {
+                name: name.to_string(),
+                path_dirs: files
+                    .next()
+                    .and_then(|file| read(Path::new(file)))
+                    .map(|contents| path_dirs(&contents))
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        ModuleEnvironment {
+            system: "Lmod",
+            modules,
+        }
+    }
+
+    /// The loaded module that put `path` on PATH: one whose module file adds
+    /// its directory, or else one whose name appears in the path as an
+    /// install tree (`.../gcc/12.2.0/bin/gcc`)
+    pub fn module_for(&self, path: &Path) -> Option<&LoadedModule> {
+        let dir = path.parent()?;
+        self.modules
+            .iter()
+            .find(|module| module.path_dirs.iter().any(|d| d == dir))
+            .or_else(|| {
+                let path = path.to_string_lossy();
+                self.modules
+                    .iter()
+                    .filter(|module| module.name.contains('/'))
+                    .find(|module| path.contains(&format!("/{}/", module.name)))
+            })
+    }
+}
+
+/// Literal directories a module file adds to PATH, from Lua
+/// (`prepend_path("PATH", "/x")`) or Tcl (`prepend-path PATH /x`) syntax.
+/// Entries built from variables cannot be resolved and are skipped.
+fn path_dirs(contents: &str) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for line in contents.lines().map(str::trim) {
+        let value = if let Some(args) = ["prepend_path(", "append_path("]
+            .iter()
+            .find_map(|call| line.strip_prefix(call))
+        {
+            let mut args = args.trim_end_matches(')').splitn(2, ',');
+            let var = args.next().unwrap_or("").trim().trim_matches(['"', '\'']);
+            let value = args.next().unwrap_or("").trim();
+            let quoted = value.len() >= 2
+                && (value.starts_with('"') && value.ends_with('"')
+                    || value.starts_with('\'') && value.ends_with('\''));
+            if var != "PATH" || !quoted {
+                continue;
+            }
+            &value[1..value.len() - 1]
+        } else if let Some(args) = ["prepend-path ", "append-path "]
+            .iter()
+            .find_map(|command| line.strip_prefix(command))
+        {
+            // Options such as `--delim :` come before the variable name
+            let mut words = args.split_whitespace();
+            let var = loop {
+                match words.next() {
+                    Some("-d" | "--delim") => {
+                        words.next();
+                    }
+                    Some(word) if word.starts_with('-') => {}
+                    other => break other,
+                }
+            };
+            if var != Some("PATH") {
+                continue;
+            }
+            match words.next() {
+                Some(value) => value.trim_matches(['"', '{', '}']),
+                None => continue,
+            }
+        } else {
+            continue;
+        };
+
+        dirs.extend(
+            value
+                .split(':')
+                .filter(|dir| !dir.is_empty() && !dir.contains(['$', '[']))
+                .map(PathBuf::from),
+        );
+    }
+    dirs
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_files() {
+        let read = |path: &Path| match path.to_str()? {
+            "/apps/modulefiles/gcc/12.2.0.lua" => Some(
+                "help([[GNU compilers]])\n\
+                 local root = \"/apps/gcc/12.2.0\"\n\
+                 prepend_path(\"PATH\", \"/apps/gcc/12.2.0/bin\")\n\
+                 prepend_path(\"PATH\", pathJoin(root, \"libexec\"))\n\
+                 prepend_path(\"MANPATH\", \"/apps/gcc/12.2.0/share/man\")\n"
+                    .to_string(),
+            ),
+            "/etc/modulefiles/cmake/3.27" => Some(
+                "#%Module1.0\n\
+                 set root /opt/cmake-3.27\n\
+                 prepend-path PATH $root/bin\n\
+                 append-path --delim : PATH /opt/cmake-3.27/tools\n"
+                    .to_string(),
+            ),
+            _ => None,
+        };
+        let environment = ModuleEnvironment::from_vars(
+            "gcc/12.2.0:cmake/3.27:python/3.11.4",
+            "/apps/modulefiles/gcc/12.2.0.lua:/etc/modulefiles/cmake/3.27:/missing.lua",
+            read,
+        );
+
+        assert_eq!(
+            environment.modules[0].path_dirs,
+            vec![PathBuf::from("/apps/gcc/12.2.0/bin")]
+        );
+        assert_eq!(
+            environment.modules[1].path_dirs,
+            vec![PathBuf::from("/opt/cmake-3.27/tools")]
+        );
+
+        let module = |path: &str| {
+            environment
+                .module_for(Path::new(path))
+                .map(|m| m.name.as_str())
+        };
+        assert_eq!(module("/apps/gcc/12.2.0/bin/gcc"), Some("gcc/12.2.0"));
+        assert_eq!(module("/opt/cmake-3.27/tools/ctest"), Some("cmake/3.27"));
+        // No module file, but the install tree carries the module name
+        assert_eq!(
+            module("/sw/python/3.11.4/bin/python3"),
+            Some("python/3.11.4")
+        );
+        assert_eq!(module("/usr/bin/gcc"), None);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::output::types::{ConflictCategory, ExecutableInfo, ManagerType};
+use crate::platform::shell::ShellKind;
+use crate::platform::wsl;
+use std::path::Path;
+
+/// Builds copy-pasteable commands, phrased for the user's shell, that make
+/// the preferred instance of a conflicting binary the active one
+pub struct FixCommandBuilder {
+    shell: ShellKind,
+}
+
+impl FixCommandBuilder {
+    pub fn new(shell: ShellKind) -> Self {
+        FixCommandBuilder { shell }
+    }
+
+    /// Commands for a conflict; empty when the preferred instance is
+    /// already active or there is no clear preference
+    pub fn commands_for(
+        &self,
+        category: ConflictCategory,
+        instances: &[ExecutableInfo],
+    ) -> Vec<String> {
+        let active = match instances.iter().find(|i| !i.broken).or(instances.first()) {
+            Some(active) => active,
+            None => return Vec::new(),
+        };
+
+        let preferred = match self.preferred_instance(category, instances) {
+            Some(preferred) if preferred.full_path != active.full_path => preferred,
+            _ => return Vec::new(),
+        };
+
+        match preferred.full_path.parent() {
+            Some(dir) => self.prepend_to_path(dir),
+            None => Vec::new(),
+        }
+    }
+
+    /// The instance a conflict's category says should win: the managed one
+    /// over a system install, or the Linux one over a Windows one in WSL
+    pub fn preferred_instance<'a>(
+        &self,
+        category: ConflictCategory,
+        instances: &'a [ExecutableInfo],
+    ) -> Option<&'a ExecutableInfo> {
+        let managed_by = |manager_type: ManagerType| {
+            instances.iter().find(move |i| {
+                i.manager
+                    .as_ref()
+                    .map(|m| m.manager_type == manager_type)
+                    .unwrap_or(false)
+            })
+        };
+
+        match category {
+            ConflictCategory::VersionManagerVsSystem => managed_by(ManagerType::VersionManager),
+            ConflictCategory::PackageManagerVsSystem => managed_by(ManagerType::PackageManager),
+            ConflictCategory::WslVsWindows => instances
+                .iter()
+                .find(|i| !wsl::is_windows_path_in_wsl(&i.resolved_path)),
+            _ => None,
+        }
+    }
+
+    /// Put `dir` first in PATH for this session, then persist the change
+    pub fn prepend_to_path(&self, dir: &Path) -> Vec<String> {
+        let dir = dir.display().to_string();
+
+        match self.shell {
+            ShellKind::Bash | ShellKind::Zsh | ShellKind::Sh => {
+                let export = format!("export PATH=\"{}:$PATH\"", escape_double_quoted(&dir));
+                let mut commands = vec![export.clone()];
+                if let Some(rc_file) = self.shell.rc_file() {
+                    commands.push(format!(
+                        "echo '{}' >> {}",
+                        export.replace('\'', "'\\''"),
+                        rc_file
+                    ));
+                }
+                commands
+            }
+            // fish_add_path persists through a universal variable
+            ShellKind::Fish => vec![format!(
+                "fish_add_path --move --prepend {}",
+                fish_quote(&dir)
+            )],
+            ShellKind::PowerShell => {
+                let dir = dir.replace('\'', "''");
+                vec![
+                    format!("$env:Path = '{};' + $env:Path", dir),
+                    format!(
+                        "[Environment]::SetEnvironmentVariable('Path', '{};' + \
+                         [Environment]::GetEnvironmentVariable('Path', 'User'), 'User')",
+                        dir
+                    ),
+                ]
+            }
+            ShellKind::Cmd => vec![format!("set \"PATH={};%PATH%\"", dir)],
+        }
+    }
+
+    /// Replace PATH with `value` for this session
+    pub fn set_path(&self, value: &str) -> String {
+        match self.shell {
+            ShellKind::Bash | ShellKind::Zsh | ShellKind::Sh => {
+                format!("export PATH=\"{}\"", escape_double_quoted(value))
+            }
+            ShellKind::Fish => {
+                let entries: Vec<String> = value.split(':').map(fish_quote).collect();
+                format!("set -gx PATH {}", entries.join(" "))
+            }
+            ShellKind::PowerShell => format!("$env:Path = '{}'", value.replace('\'', "''")),
+            ShellKind::Cmd => format!("set \"PATH={}\"", value),
+        }
+    }
+
+    /// Make `value` the PATH of future sessions. On Windows this stores the
+    /// whole value, machine entries included, as the user PATH.
+    pub fn persist_path(&self, value: &str) -> String {
+        let set = self.set_path(value);
+        match self.shell {
+            ShellKind::Bash | ShellKind::Zsh | ShellKind::Sh | ShellKind::Fish => format!(
+                "echo '{}' >> {}",
+                set.replace('\'', "'\\''"),
+                self.shell.rc_file().unwrap_or("~/.config/fish/config.fish")
+            ),
+            ShellKind::PowerShell => format!(
+                "[Environment]::SetEnvironmentVariable('Path', '{}', 'User')",
+                value.replace('\'', "''")
+            ),
+            ShellKind::Cmd => format!("setx PATH \"{}\"", value),
+        }
+    }
+}
+
+/// A single-quoted fish string
+pub(crate) fn fish_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn escape_double_quoted(text: &str) -> String {
+    text.chars()
+        .flat_map(|c| match c {
+            '"' | '\\' | '$' | '`' => vec!['\\', c],
+            _ => vec![c],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::types::ManagerInfo;
+    use std::path::PathBuf;
+
+    fn instance(path: &str, order: usize, manager_type: Option<ManagerType>) -> ExecutableInfo {
+        ExecutableInfo {
+            name: "python".to_string(),
+            full_path: PathBuf::from(path),
+            size: 1000,
+            resolved_path: PathBuf::from(path),
+            manager: manager_type.map(|manager_type| ManagerInfo {
+                manager_type,
+                name: "pyenv".to_string(),
+                description: String::new(),
+            }),
+            path_order: order,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    #[test]
+    fn test_prefers_version_manager_over_system() {
+        let instances = vec![
+            instance("/usr/bin/python", 0, Some(ManagerType::SystemInstall)),
+            instance(
+                "/home/me/.pyenv/shims/python",
+                1,
+                Some(ManagerType::VersionManager),
+            ),
+        ];
+
+        let commands = FixCommandBuilder::new(ShellKind::Zsh)
+            .commands_for(ConflictCategory::VersionManagerVsSystem, &instances);
+        assert_eq!(
+            commands,
+            vec![
+                "export PATH=\"/home/me/.pyenv/shims:$PATH\"".to_string(),
+                "echo 'export PATH=\"/home/me/.pyenv/shims:$PATH\"' >> ~/.zshrc".to_string(),
+            ]
+        );
+
+        // Nothing to do when the preferred instance already wins
+        let reversed: Vec<_> = instances.into_iter().rev().collect();
+        assert!(FixCommandBuilder::new(ShellKind::Zsh)
+            .commands_for(ConflictCategory::VersionManagerVsSystem, &reversed)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_prepend_per_shell() {
+        let dir = Path::new("/opt/my tools/bin");
+        assert_eq!(
+            FixCommandBuilder::new(ShellKind::Fish).prepend_to_path(dir),
+            vec!["fish_add_path --move --prepend '/opt/my tools/bin'"]
+        );
+        assert_eq!(
+            FixCommandBuilder::new(ShellKind::PowerShell).prepend_to_path(dir)[0],
+            "$env:Path = '/opt/my tools/bin;' + $env:Path"
+        );
+        assert_eq!(
+            FixCommandBuilder::new(ShellKind::Cmd).prepend_to_path(dir),
+            vec!["set \"PATH=/opt/my tools/bin;%PATH%\""]
+        );
+        assert_eq!(
+            FixCommandBuilder::new(ShellKind::Bash).prepend_to_path(Path::new("/opt/$x"))[0],
+            "export PATH=\"/opt/\\$x:$PATH\""
+        );
+    }
+}
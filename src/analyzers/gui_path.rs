@@ -0,0 +1,156 @@
+use crate::output::types::{Diagnostic, DiagnosticKind, ExecutableInfo, PathEntry, Severity};
+use crate::platform::unix::DesktopLauncher;
+use std::collections::BTreeMap;
+
+/// Compares the PATH of the login shell with the one apps launched from the
+/// desktop get. IDEs and editors started from the Dock or an application menu
+/// never read `~/.zshrc`, so a tool the terminal finds in `~/.cargo/bin` or
+/// `/opt/homebrew/bin` resolves to another copy, or to nothing, inside them.
+pub struct GuiPathAnalyzer;
+
+impl GuiPathAnalyzer {
+    pub fn new() -> Self {
+        GuiPathAnalyzer
+    }
+
+    /// `gui_entries` is the scanned GUI PATH, read from `source`;
+    /// `launchers` are `.desktop` files whose `Exec` runs a bare command
+    pub fn analyze(
+        &self,
+        source: &str,
+        gui_entries: &[PathEntry],
+        path_entries: &[PathEntry],
+        launchers: &[DesktopLauncher],
+    ) -> Vec<Diagnostic> {
+        let shell = active_copies(path_entries);
+        let gui = active_copies(gui_entries);
+        let mut diagnostics = Vec::new();
+
+        for (name, shell_exec) in &shell {
+            let Some(gui_exec) = gui.get(name) else {
+                continue;
+            };
+            if shell_exec.resolved_path == gui_exec.resolved_path {
+                continue;
+            }
+            diagnostics.push(Diagnostic {
+                kind: DiagnosticKind::GuiPath,
+                severity: Severity::Medium,
+                message: format!(
+                    "{} runs {} in a terminal but {} in apps launched from the desktop \
+                     ({} PATH)",
+                    name,
+                    shell_exec.full_path.display(),
+                    gui_exec.full_path.display(),
+                    source
+                ),
+                paths: vec![shell_exec.full_path.clone(), gui_exec.full_path.clone()],
+            });
+        }
+
+        for launcher in launchers {
+            let Some(shell_exec) = shell.get(launcher.command.as_str()) else {
+                continue;
+            };
+            if gui.contains_key(launcher.command.as_str()) {
+                continue;
+            }
+            diagnostics.push(Diagnostic {
+                kind: DiagnosticKind::GuiPath,
+                severity: Severity::High,
+                message: format!(
+                    "{} launches {}, which only the shell PATH finds ({}); the {} PATH \
+                     has no copy, so the menu entry fails",
+                    launcher.file.display(),
+                    launcher.command,
+                    shell_exec.full_path.display(),
+                    source
+                ),
+                paths: vec![launcher.file.clone(), shell_exec.full_path.clone()],
+            });
+        }
+
+        diagnostics
+    }
+}
+
+impl Default for GuiPathAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The copy of each name a PATH search runs
+fn active_copies(entries: &[PathEntry]) -> BTreeMap<&str, &ExecutableInfo> {
+    let mut active = BTreeMap::new();
+    for exec in entries
+        .iter()
+        .flat_map(|entry| &entry.executables)
+        .filter(|exec| !exec.broken)
+    {
+        active.entry(exec.name.as_str()).or_insert(exec);
+    }
+    active
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn entry(dir: &str, order: usize, names: &[&str]) -> PathEntry {
+        PathEntry {
+            path: PathBuf::from(dir),
+            order,
+            exists: true,
+            is_accessible: true,
+            executables: names
+                .iter()
+                .map(|name| {
+                    let full_path = PathBuf::from(dir).join(name);
+                    ExecutableInfo {
+                        name: name.to_string(),
+                        full_path: full_path.clone(),
+                        resolved_path: full_path,
+                        path_order: order,
+                        ..ExecutableInfo::fixture()
+                    }
+                })
+                .collect(),
+            sources: Vec::new(),
+            scope: None,
+        }
+    }
+
+    #[test]
+    fn test_binaries_resolving_differently_for_gui_apps() {
+        let shell = vec![
+            entry("/home/dev/.cargo/bin", 0, &["cargo", "rust-analyzer"]),
+            entry("/opt/homebrew/bin", 1, &["git", "node"]),
+            entry("/usr/bin", 2, &["git", "ls"]),
+        ];
+        let gui = vec![entry("/usr/bin", 0, &["git", "ls"])];
+        let launchers = vec![
+            DesktopLauncher {
+                file: PathBuf::from("/usr/share/applications/rust-analyzer.desktop"),
+                command: "rust-analyzer".to_string(),
+            },
+            DesktopLauncher {
+                file: PathBuf::from("/usr/share/applications/ls.desktop"),
+                command: "ls".to_string(),
+            },
+        ];
+
+        let diagnostics = GuiPathAnalyzer::new().analyze("launchd", &gui, &shell, &launchers);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].message,
+            "git runs /opt/homebrew/bin/git in a terminal but /usr/bin/git in apps \
+             launched from the desktop (launchd PATH)"
+        );
+        assert_eq!(diagnostics[1].severity, Severity::High);
+        assert!(diagnostics[1]
+            .message
+            .starts_with("/usr/share/applications/rust-analyzer.desktop launches rust-analyzer"));
+    }
+}
@@ -0,0 +1,149 @@
+use crate::output::types::{ExecutableInfo, PackageOrigin, VersionInfo};
+use std::path::{Path, PathBuf};
+
+/// An installed formula version: `<prefix>/Cellar/<formula>/<version>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keg {
+    pub prefix: PathBuf,
+    pub formula: String,
+    /// Version directory, including any `_N` rebuild revision
+    pub version: String,
+    /// This version is the one `brew link` put into the prefix
+    pub linked: bool,
+}
+
+/// Reads formula versions and link state from the Cellar, the same data
+/// `brew list --versions` prints, without running brew or the binaries
+pub struct HomebrewMetadata;
+
+impl HomebrewMetadata {
+    pub fn new() -> Self {
+        HomebrewMetadata
+    }
+
+    /// Fill in the version and origin of executables that live in a keg.
+    /// Versions already extracted are kept.
+    pub fn enrich(&self, executables: &mut [ExecutableInfo]) {
+        for executable in executables.iter_mut() {
+            // Links in <prefix>/bin point into the Cellar; follow them even
+            // when --resolve-symlinks is off
+            let keg = self.keg(&executable.resolved_path).or_else(|| {
+                let target = std::fs::canonicalize(&executable.full_path).ok()?;
+                self.keg(&target)
+            });
+            let Some(keg) = keg else {
+                continue;
+            };
+
+            if executable.version.is_none() {
+                executable.version = Some(VersionInfo {
+                    raw: format!("{} {}", keg.formula, keg.version),
+                    parsed: Some(without_revision(&keg.version).to_string()),
+                    extraction_method: "brew".to_string(),
+                });
+            }
+            executable.origin = Some(PackageOrigin {
+                source: "brew".to_string(),
+                package: keg.formula,
+                version: Some(keg.version),
+                linked: Some(keg.linked),
+            });
+        }
+    }
+
+    /// The keg holding `path`, if it is an installed one (it has an
+    /// `INSTALL_RECEIPT.json`)
+    pub fn keg(&self, path: &Path) -> Option<Keg> {
+        let mut prefix = PathBuf::new();
+        let mut components = path.components();
+        for component in components.by_ref() {
+            if component.as_os_str() == "Cellar" {
+                break;
+            }
+            prefix.push(component);
+        }
+        let formula = components.next()?.as_os_str().to_str()?.to_string();
+        let version = components.next()?.as_os_str().to_str()?.to_string();
+
+        let keg_dir = prefix.join("Cellar").join(&formula).join(&version);
+        if !keg_dir.join("INSTALL_RECEIPT.json").is_file() {
+            return None;
+        }
+
+        // brew link records the linked version as var/homebrew/linked/<formula>
+        let linked = std::fs::read_link(prefix.join("var/homebrew/linked").join(&formula))
+            .map(|target| target.file_name() == Some(version.as_ref()))
+            .unwrap_or(false);
+
+        Some(Keg {
+            prefix,
+            formula,
+            version,
+            linked,
+        })
+    }
+}
+
+impl Default for HomebrewMetadata {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// "3.12.1_1" -> "3.12.1"
+fn without_revision(version: &str) -> &str {
+    match version.rsplit_once('_') {
+        Some((base, revision)) if revision.chars().all(|c| c.is_ascii_digit()) => base,
+        _ => version,
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn install(prefix: &Path, formula: &str, version: &str) -> PathBuf {
+        let keg = prefix.join("Cellar").join(formula).join(version);
+        fs::create_dir_all(keg.join("bin")).unwrap();
+        fs::write(keg.join("INSTALL_RECEIPT.json"), "{}").unwrap();
+        keg
+    }
+
+    #[test]
+    fn test_kegs_and_link_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let prefix = dir.path();
+        let linked = install(prefix, "python@3.12", "3.12.1_1");
+        let unlinked = install(prefix, "python@3.11", "3.11.7");
+        fs::create_dir_all(prefix.join("var/homebrew/linked")).unwrap();
+        std::os::unix::fs::symlink(
+            "../../../Cellar/python@3.12/3.12.1_1",
+            prefix.join("var/homebrew/linked/python@3.12"),
+        )
+        .unwrap();
+
+        let metadata = HomebrewMetadata::new();
+        let keg = metadata.keg(&linked.join("bin/python3")).unwrap();
+        assert_eq!(keg.formula, "python@3.12");
+        assert_eq!(keg.version, "3.12.1_1");
+        assert_eq!(keg.prefix, prefix);
+        assert!(keg.linked);
+
+        assert!(!metadata.keg(&unlinked.join("bin/python3")).unwrap().linked);
+
+        // A Cellar path without an install receipt is not a keg
+        fs::create_dir_all(prefix.join("Cellar/stray/1.0/bin")).unwrap();
+        assert!(metadata
+            .keg(&prefix.join("Cellar/stray/1.0/bin/stray"))
+            .is_none());
+        assert!(metadata.keg(Path::new("/usr/bin/python3")).is_none());
+    }
+
+    #[test]
+    fn test_without_revision() {
+        assert_eq!(without_revision("3.12.1_1"), "3.12.1");
+        assert_eq!(without_revision("2024_beta"), "2024_beta");
+        assert_eq!(without_revision("1.0"), "1.0");
+    }
+}
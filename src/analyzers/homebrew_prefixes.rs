@@ -0,0 +1,184 @@
+use crate::output::types::{Conflict, Diagnostic, DiagnosticKind, Severity};
+use crate::platform::macos::{HOMEBREW_ARM_PREFIX, HOMEBREW_INTEL_PREFIX};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Formula names listed in the message before summarizing the rest
+const MAX_LISTED: usize = 10;
+
+/// Finds formulae installed by both the Intel (`/usr/local`) and Apple
+/// Silicon (`/opt/homebrew`) Homebrew, which typically happens after
+/// migrating to a new Mac and leaves two copies of every tool on PATH
+pub struct DualHomebrewAnalyzer;
+
+impl DualHomebrewAnalyzer {
+    pub fn new() -> Self {
+        DualHomebrewAnalyzer
+    }
+
+    pub fn analyze(
+        &self,
+        intel_formulae: &BTreeSet<String>,
+        arm_formulae: &BTreeSet<String>,
+        conflicts: &[Conflict],
+    ) -> Option<Diagnostic> {
+        let duplicated: Vec<&String> = intel_formulae.intersection(arm_formulae).collect();
+        if duplicated.is_empty() {
+            return None;
+        }
+
+        let binaries: Vec<&str> = conflicts
+            .iter()
+            .filter(|c| spans_both_prefixes(c))
+            .map(|c| c.binary_name.as_str())
+            .collect();
+
+        let mut listed: Vec<&str> = duplicated
+            .iter()
+            .take(MAX_LISTED)
+            .map(|f| f.as_str())
+            .collect();
+        if duplicated.len() > MAX_LISTED {
+            listed.push("...");
+        }
+
+        let message = format!(
+            "{} formula(e) are installed under both {} and {} ({}), duplicating {} binar{} on \
+             PATH. Migrate to {}: `{intel}/bin/brew bundle dump --file=~/Brewfile`, then \
+             `{arm}/bin/brew bundle install --file=~/Brewfile`, then uninstall the {} prefix",
+            duplicated.len(),
+            HOMEBREW_INTEL_PREFIX,
+            HOMEBREW_ARM_PREFIX,
+            listed.join(", "),
+            binaries.len(),
+            if binaries.len() == 1 { "y" } else { "ies" },
+            HOMEBREW_ARM_PREFIX,
+            HOMEBREW_INTEL_PREFIX,
+            intel = HOMEBREW_INTEL_PREFIX,
+            arm = HOMEBREW_ARM_PREFIX,
+        );
+
+        Some(Diagnostic {
+            kind: DiagnosticKind::DualHomebrew,
+            severity: if binaries.is_empty() {
+                Severity::Low
+            } else {
+                Severity::Medium
+            },
+            message,
+            paths: vec![
+                Path::new(HOMEBREW_INTEL_PREFIX).join("Cellar"),
+                Path::new(HOMEBREW_ARM_PREFIX).join("Cellar"),
+            ],
+        })
+    }
+}
+
+impl Default for DualHomebrewAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Both Homebrew installs provide a copy; the Intel one is recognized by
+/// resolving into its Cellar, since plain `/usr/local/bin` files may not be
+/// Homebrew's
+fn spans_both_prefixes(conflict: &Conflict) -> bool {
+    let intel_cellar = Path::new(HOMEBREW_INTEL_PREFIX).join("Cellar");
+    let in_intel = conflict
+        .instances
+        .iter()
+        .any(|i| i.resolved_path.starts_with(&intel_cellar));
+    let in_arm = conflict
+        .instances
+        .iter()
+        .any(|i| i.full_path.starts_with(HOMEBREW_ARM_PREFIX));
+
+    in_intel && in_arm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::types::{ConflictCategory, ExecutableInfo};
+    use std::path::PathBuf;
+
+    fn instance(path: &str, resolved: &str) -> ExecutableInfo {
+        ExecutableInfo {
+            name: "git".to_string(),
+            full_path: PathBuf::from(path),
+            size: 1000,
+            is_symlink: true,
+            resolved_path: PathBuf::from(resolved),
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    fn conflict(name: &str, instances: Vec<ExecutableInfo>) -> Conflict {
+        Conflict {
+            binary_name: name.to_string(),
+            active_instance: instances[0].clone(),
+            instances,
+            category: ConflictCategory::PackageManagerVsSystem,
+            severity: Severity::Medium,
+            severity_reason: String::new(),
+            description: String::new(),
+            recommendation: None,
+            commands: Vec::new(),
+            same_content: false,
+            risk_score: 0.0,
+        }
+    }
+
+    fn formulae(names: &[&str]) -> BTreeSet<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_duplicate_formulae() {
+        let conflicts = vec![
+            conflict(
+                "git",
+                vec![
+                    instance(
+                        "/opt/homebrew/bin/git",
+                        "/opt/homebrew/Cellar/git/2.44.0/bin/git",
+                    ),
+                    instance("/usr/local/bin/git", "/usr/local/Cellar/git/2.39.0/bin/git"),
+                ],
+            ),
+            conflict(
+                "make",
+                vec![
+                    instance("/usr/local/bin/make", "/usr/local/bin/make"),
+                    instance("/usr/bin/make", "/usr/bin/make"),
+                ],
+            ),
+        ];
+
+        let diagnostic = DualHomebrewAnalyzer::new()
+            .analyze(
+                &formulae(&["git", "wget", "openssl@3"]),
+                &formulae(&["git", "openssl@3", "ripgrep"]),
+                &conflicts,
+            )
+            .unwrap();
+
+        assert_eq!(diagnostic.severity, Severity::Medium);
+        assert!(diagnostic.message.starts_with("2 formula(e)"));
+        assert!(diagnostic.message.contains("(git, openssl@3)"));
+        assert!(diagnostic.message.contains("duplicating 1 binary"));
+        assert!(diagnostic.message.contains("brew bundle dump"));
+    }
+
+    #[test]
+    fn test_single_prefix() {
+        let analyzer = DualHomebrewAnalyzer::new();
+        assert!(analyzer
+            .analyze(&formulae(&["git"]), &BTreeSet::new(), &[])
+            .is_none());
+        assert!(analyzer
+            .analyze(&formulae(&["git"]), &formulae(&["wget"]), &[])
+            .is_none());
+    }
+}
@@ -0,0 +1,294 @@
+use crate::output::types::{Conflict, Diagnostic, DiagnosticKind, PathEntry, Severity};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Scripts named in a message before summarizing the rest
+const MAX_LISTED: usize = 5;
+
+/// What is wrong with a script's interpreter
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Problem {
+    /// The absolute interpreter path does not exist
+    Missing(PathBuf),
+    /// `env` finds no such command on PATH
+    NotOnPath(String),
+    /// `env` picks one of several conflicting copies
+    Conflicted(String),
+    /// The absolute interpreter is a copy PATH lookup does not pick
+    Shadowed(PathBuf),
+}
+
+/// Checks the `#!` line of scripts on PATH: the interpreter must exist, and
+/// should not be a name whose meaning depends on PATH order
+pub struct InterpreterAnalyzer;
+
+impl InterpreterAnalyzer {
+    pub fn new() -> Self {
+        InterpreterAnalyzer
+    }
+
+    /// Relies on `binary_format` from binary inspection for the `#!` line;
+    /// scripts without it are skipped
+    pub fn analyze(&self, path_entries: &[PathEntry], conflicts: &[Conflict]) -> Vec<Diagnostic> {
+        let mut on_path: HashMap<&str, &Path> = HashMap::new();
+        for exec in path_entries.iter().flat_map(|e| &e.executables) {
+            on_path.entry(exec.name.as_str()).or_insert(&exec.full_path);
+        }
+        let conflicts: HashMap<&str, &Conflict> = conflicts
+            .iter()
+            .map(|c| (c.binary_name.as_str(), c))
+            .collect();
+
+        let mut affected: BTreeMap<Problem, Vec<&Path>> = BTreeMap::new();
+        for exec in path_entries.iter().flat_map(|e| &e.executables) {
+            let Some(shebang) = exec
+                .binary_format
+                .as_ref()
+                .and_then(|format| format.interpreter.as_deref())
+            else {
+                continue;
+            };
+
+            let problem = match parse_shebang(shebang) {
+                Some(Interpreter::Env(name)) => match (on_path.get(name), conflicts.get(name)) {
+                    (None, _) => Some(Problem::NotOnPath(name.to_string())),
+                    (Some(_), Some(_)) => Some(Problem::Conflicted(name.to_string())),
+                    _ => None,
+                },
+                Some(Interpreter::Absolute(path)) if !path.is_file() => {
+                    Some(Problem::Missing(path.to_path_buf()))
+                }
+                Some(Interpreter::Absolute(path)) => path
+                    .file_name()
+                    .and_then(|name| conflicts.get(name.to_str()?))
+                    .filter(|c| c.active_instance.full_path != path)
+                    .filter(|c| c.instances.iter().any(|i| i.full_path == path))
+                    .map(|_| Problem::Shadowed(path.to_path_buf())),
+                None => None,
+            };
+            if let Some(problem) = problem {
+                let scripts = affected.entry(problem).or_default();
+                if !scripts.contains(&exec.full_path.as_path()) {
+                    scripts.push(&exec.full_path);
+                }
+            }
+        }
+
+        affected
+            .into_iter()
+            .map(|(problem, scripts)| diagnostic(&problem, &scripts, &conflicts))
+            .collect()
+    }
+}
+
+impl Default for InterpreterAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Interpreter<'a> {
+    /// `#!/usr/bin/env NAME`: looked up on PATH when the script runs
+    Env(&'a str),
+    Absolute(&'a Path),
+}
+
+/// The interpreter of a `#!` line, without arguments. For `env`, options
+/// (`-S`) and variable assignments before the command are skipped.
+fn parse_shebang(shebang: &str) -> Option<Interpreter<'_>> {
+    let mut words = shebang.split_whitespace();
+    let program = Path::new(words.next()?);
+    if !program.is_absolute() {
+        return None;
+    }
+    if program.file_name()? != "env" {
+        return Some(Interpreter::Absolute(program));
+    }
+    words
+        .find(|word| !word.starts_with('-') && !word.contains('='))
+        .map(Interpreter::Env)
+}
+
+fn diagnostic(
+    problem: &Problem,
+    scripts: &[&Path],
+    conflicts: &HashMap<&str, &Conflict>,
+) -> Diagnostic {
+    let mut listed: Vec<String> = scripts
+        .iter()
+        .take(MAX_LISTED)
+        .map(|s| s.display().to_string())
+        .collect();
+    if scripts.len() > MAX_LISTED {
+        listed.push(format!("and {} more", scripts.len() - MAX_LISTED));
+    }
+    let subject = format!(
+        "{} script{} ({})",
+        scripts.len(),
+        if scripts.len() == 1 { "" } else { "s" },
+        listed.join(", ")
+    );
+    let (name_verb, run_verb) = if scripts.len() == 1 {
+        ("names", "runs")
+    } else {
+        ("name", "run")
+    };
+
+    let (severity, message) = match problem {
+        Problem::Missing(interpreter) => (
+            Severity::Medium,
+            format!(
+                "{} {} the interpreter {}, which does not exist, so starting fails. \
+                 Install it or fix the #! line",
+                subject,
+                name_verb,
+                interpreter.display()
+            ),
+        ),
+        Problem::NotOnPath(name) => (
+            Severity::Medium,
+            format!(
+                "{} {} `{}` through env, but no {} is on PATH, so starting fails",
+                subject, run_verb, name, name
+            ),
+        ),
+        Problem::Conflicted(name) => {
+            let conflict = conflicts[name.as_str()];
+            (
+                Severity::Low,
+                format!(
+                    "{} {} `{}` through env, which picks {} out of {} copies on PATH; \
+                     reordering PATH changes which one is used",
+                    subject,
+                    run_verb,
+                    name,
+                    conflict.active_instance.full_path.display(),
+                    conflict.instances.len()
+                ),
+            )
+        }
+        Problem::Shadowed(interpreter) => {
+            let name = interpreter
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy();
+            let active = &conflicts[name.as_ref()].active_instance;
+            (
+                Severity::Info,
+                format!(
+                    "{} {} {}, while `{}` on PATH runs {}, so the version may differ from \
+                     the one the shell uses",
+                    subject,
+                    run_verb,
+                    interpreter.display(),
+                    name,
+                    active.full_path.display()
+                ),
+            )
+        }
+    };
+
+    Diagnostic {
+        kind: DiagnosticKind::ScriptInterpreter,
+        severity,
+        message,
+        paths: scripts.iter().map(|s| s.to_path_buf()).collect(),
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::output::types::{BinaryFormat, ConflictCategory, ExecutableInfo, FileFormat};
+
+    fn exec(path: &str, interpreter: Option<&str>) -> ExecutableInfo {
+        ExecutableInfo {
+            name: Path::new(path)
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .into(),
+            full_path: PathBuf::from(path),
+            size: 100,
+            resolved_path: PathBuf::from(path),
+            binary_format: interpreter.map(|interpreter| BinaryFormat {
+                format: FileFormat::Script,
+                architectures: Vec::new(),
+                interpreter: Some(interpreter.to_string()),
+            }),
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    fn entry(order: usize, executables: Vec<ExecutableInfo>) -> PathEntry {
+        PathEntry {
+            path: executables[0].full_path.parent().unwrap().to_path_buf(),
+            order,
+            exists: true,
+            is_accessible: true,
+            executables,
+            sources: Vec::new(),
+            scope: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_shebang() {
+        assert_eq!(
+            parse_shebang("/usr/bin/env -S python3 -u"),
+            Some(Interpreter::Env("python3"))
+        );
+        assert_eq!(
+            parse_shebang("/usr/bin/env LC_ALL=C perl"),
+            Some(Interpreter::Env("perl"))
+        );
+        assert_eq!(
+            parse_shebang("/bin/bash -e"),
+            Some(Interpreter::Absolute(Path::new("/bin/bash")))
+        );
+        assert_eq!(parse_shebang("python"), None);
+    }
+
+    #[test]
+    fn test_interpreter_problems() {
+        let python = |path: &str| exec(path, None);
+        let entries = vec![
+            entry(
+                0,
+                vec![
+                    python("/opt/py/bin/python3"),
+                    exec("/opt/py/bin/tool", Some("/usr/bin/env python3")),
+                    exec("/opt/py/bin/old", Some("/nonexistent/python2")),
+                    exec("/opt/py/bin/rb", Some("/usr/bin/env ruby-9")),
+                ],
+            ),
+            entry(1, vec![python("/usr/bin/python3")]),
+        ];
+        let conflict = Conflict {
+            binary_name: "python3".to_string(),
+            instances: vec![python("/opt/py/bin/python3"), python("/usr/bin/python3")],
+            active_instance: python("/opt/py/bin/python3"),
+            category: ConflictCategory::ShadowedBinary,
+            severity: Severity::Medium,
+            severity_reason: String::new(),
+            description: String::new(),
+            recommendation: None,
+            commands: Vec::new(),
+            same_content: false,
+            risk_score: 0.0,
+        };
+
+        let diagnostics = InterpreterAnalyzer::new().analyze(&entries, &[conflict]);
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(diagnostics[0].severity, Severity::Medium);
+        assert!(diagnostics[0]
+            .message
+            .contains("/nonexistent/python2, which does not exist"));
+        assert!(diagnostics[1].message.contains("no ruby-9 is on PATH"));
+        assert!(diagnostics[2]
+            .message
+            .starts_with("1 script (/opt/py/bin/tool) runs `python3` through env, which picks /opt/py/bin/python3 out of 2 copies"));
+        assert_eq!(diagnostics[2].severity, Severity::Low);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::core::binary_format;
+use crate::core::binary_info::same_file;
+use crate::core::search_path::SearchPath;
+use crate::output::types::{Diagnostic, DiagnosticKind, Severity};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The dynamic-linker analogue of PATH conflicts. Every program looks a
+/// library up by its soname in the LD_LIBRARY_PATH (DYLD_LIBRARY_PATH on
+/// macOS) directories before the system ones, so one copy there replaces
+/// the system library for everything run from that shell.
+pub struct LibraryPathAnalyzer;
+
+impl LibraryPathAnalyzer {
+    pub fn new() -> Self {
+        LibraryPathAnalyzer
+    }
+
+    /// `system_dirs` are searched after `library_path`
+    pub fn analyze(&self, library_path: &SearchPath, system_dirs: &[PathBuf]) -> Vec<Diagnostic> {
+        // Sonames provided by the variable's directories, first copy kept
+        let mut sonames = BTreeMap::new();
+        for dir in &library_path.dirs {
+            let Ok(entries) = std::fs::read_dir(dir) else {
+                continue;
+            };
+            let mut files: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| is_shared_library(path))
+                .collect();
+            files.sort();
+            for file in files {
+                // An unversioned libfoo.so without a soname is a link-time
+                // name (or a linker script) that is never loaded by name
+                let soname = binary_format::elf_soname(&file)
+                    .or_else(|| file_name(&file).filter(|name| !name.ends_with(".so")));
+                if let Some(soname) = soname {
+                    sonames.entry(soname).or_insert(file);
+                }
+            }
+        }
+
+        let mut diagnostics = Vec::new();
+        for soname in sonames.keys() {
+            // What the linker finds in each directory, in search order
+            let mut copies: Vec<(PathBuf, bool)> = Vec::new();
+            let dirs = library_path
+                .dirs
+                .iter()
+                .map(|dir| (dir, false))
+                .chain(system_dirs.iter().map(|dir| (dir, true)));
+            for (dir, system) in dirs {
+                let candidate = dir.join(soname);
+                if !candidate.is_file() || copies.iter().any(|(c, _)| same_file(c, &candidate)) {
+                    continue;
+                }
+                copies.push((candidate, system));
+            }
+            if copies.len() < 2 {
+                continue;
+            }
+
+            let (loaded, _) = &copies[0];
+            let shadowed: Vec<&PathBuf> = copies[1..].iter().map(|(copy, _)| copy).collect();
+            let system = copies[1..].iter().any(|(_, system)| *system);
+            let (severity, consequence) = if system {
+                (
+                    Severity::Medium,
+                    "instead of the system copy for every program",
+                )
+            } else {
+                (Severity::Low, "for programs expecting the later copies")
+            };
+            diagnostics.push(Diagnostic {
+                kind: DiagnosticKind::LibraryPath,
+                severity,
+                message: format!(
+                    "{} is loaded from {} ({}) {}; shadowed: {}",
+                    soname,
+                    loaded.display(),
+                    library_path.variable,
+                    consequence,
+                    shadowed
+                        .iter()
+                        .map(|path| path.display().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                paths: copies.into_iter().map(|(copy, _)| copy).collect(),
+            });
+        }
+
+        diagnostics
+    }
+}
+
+impl Default for LibraryPathAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `libfoo.so`, `libfoo.so.1.2` or `libfoo.1.dylib`
+fn is_shared_library(path: &Path) -> bool {
+    let Some(name) = file_name(path) else {
+        return false;
+    };
+    path.is_file() && (name.ends_with(".so") || name.contains(".so.") || name.ends_with(".dylib"))
+}
+
+fn file_name(path: &Path) -> Option<String> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_library_shadowing_system_copy() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = |name: &str| {
+            let dir = root.path().join(name);
+            std::fs::create_dir(&dir).unwrap();
+            dir
+        };
+        let (conda, cuda, system) = (dir("conda"), dir("cuda"), dir("system"));
+        std::fs::write(conda.join("libz.so.1"), b"conda").unwrap();
+        std::fs::write(conda.join("libonly.so.2"), b"conda").unwrap();
+        std::fs::write(cuda.join("libcudart.so.12"), b"cuda").unwrap();
+        std::fs::write(system.join("libz.so.1"), b"system").unwrap();
+        // A link back to conda's copy is not a second copy
+        std::os::unix::fs::symlink(conda.join("libonly.so.2"), system.join("libonly.so.2"))
+            .unwrap();
+
+        let value = format!("{}:{}", conda.display(), cuda.display());
+        let library_path = SearchPath::parse("LD_LIBRARY_PATH", &value);
+        let diagnostics =
+            LibraryPathAnalyzer::new().analyze(&library_path, std::slice::from_ref(&system));
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::LibraryPath);
+        assert_eq!(diagnostics[0].severity, Severity::Medium);
+        assert_eq!(
+            diagnostics[0].paths,
+            vec![conda.join("libz.so.1"), system.join("libz.so.1")]
+        );
+        assert!(diagnostics[0].message.starts_with(&format!(
+            "libz.so.1 is loaded from {}",
+            conda.join("libz.so.1").display()
+        )));
+    }
+}
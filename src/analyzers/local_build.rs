@@ -0,0 +1,205 @@
+use crate::output::types::{Conflict, Diagnostic, DiagnosticKind, ExecutableInfo, Severity};
+use std::path::Path;
+
+/// Package databases that install into `/usr`
+const DISTRO_SOURCES: &[&str] = &["dpkg", "rpm", "pacman"];
+
+/// Spots the classic "compiled from source into /usr/local over the distro
+/// package" setup: one copy under `/usr/local` that no package owns, one
+/// under `/usr` (or `/bin`, `/sbin`) from the distribution
+pub struct LocalBuildAnalyzer;
+
+impl LocalBuildAnalyzer {
+    pub fn new() -> Self {
+        LocalBuildAnalyzer
+    }
+
+    /// Without `origins_checked` (`--origins`), `/usr` copies are assumed to
+    /// come from the distribution, which is where the FHS puts them
+    pub fn analyze(&self, conflicts: &[Conflict], origins_checked: bool) -> Vec<Diagnostic> {
+        conflicts
+            .iter()
+            .filter_map(|conflict| {
+                let local = conflict.instances.iter().find(|i| is_local_build(i))?;
+                let system = conflict
+                    .instances
+                    .iter()
+                    .find(|i| is_distro_copy(i, origins_checked))?;
+                Some(self.diagnostic(local, system))
+            })
+            .collect()
+    }
+
+    fn diagnostic(&self, local: &ExecutableInfo, system: &ExecutableInfo) -> Diagnostic {
+        let package = match &system.origin {
+            Some(origin) => format!(
+                "{} package {}{}",
+                origin.source,
+                origin.package,
+                origin
+                    .version
+                    .as_ref()
+                    .map(|v| format!(" {}", v))
+                    .unwrap_or_default()
+            ),
+            None => "the distribution".to_string(),
+        };
+
+        let versions = match (version(local), version(system)) {
+            (Some(a), Some(b)) if a != b => Some(format!(" (local {}, packaged {})", a, b)),
+            _ => None,
+        };
+
+        let local_wins = local.path_order < system.path_order;
+        let relation = if local_wins {
+            "shadows"
+        } else {
+            "is shadowed by"
+        };
+
+        Diagnostic {
+            kind: DiagnosticKind::LocalBuild,
+            severity: if local_wins && versions.is_some() {
+                Severity::Medium
+            } else {
+                Severity::Low
+            },
+            message: format!(
+                "{} is not from any package and {} {} from {}{}, so it was likely built from \
+                 source. Remove the local build (`sudo make uninstall` in its source tree, or \
+                 delete the file) or upgrade it to match the package",
+                local.full_path.display(),
+                relation,
+                system.full_path.display(),
+                package,
+                versions.unwrap_or_default()
+            ),
+            paths: vec![local.full_path.clone(), system.full_path.clone()],
+        }
+    }
+}
+
+impl Default for LocalBuildAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Under `/usr/local`, with no package owner. Homebrew's Intel prefix is
+/// also `/usr/local`, so files resolving into its Cellar are excluded.
+fn is_local_build(instance: &ExecutableInfo) -> bool {
+    instance.full_path.starts_with("/usr/local")
+        && instance.origin.is_none()
+        && !instance.resolved_path.starts_with("/usr/local/Cellar")
+}
+
+fn is_distro_copy(instance: &ExecutableInfo, origins_checked: bool) -> bool {
+    let path: &Path = &instance.full_path;
+    let in_system_dir = !path.starts_with("/usr/local")
+        && ["/usr", "/bin", "/sbin"]
+            .iter()
+            .any(|root| path.starts_with(root));
+
+    let packaged = match &instance.origin {
+        Some(origin) => DISTRO_SOURCES.contains(&origin.source.as_str()),
+        None => !origins_checked,
+    };
+
+    in_system_dir && packaged
+}
+
+fn version(instance: &ExecutableInfo) -> Option<&str> {
+    let version = instance.version.as_ref()?;
+    Some(version.parsed.as_deref().unwrap_or(&version.raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::types::{ConflictCategory, PackageOrigin, VersionInfo};
+    use std::path::PathBuf;
+
+    fn instance(path: &str, order: usize, version: &str) -> ExecutableInfo {
+        ExecutableInfo {
+            name: "git".to_string(),
+            full_path: PathBuf::from(path),
+            size: 1000,
+            resolved_path: PathBuf::from(path),
+            version: Some(VersionInfo {
+                raw: version.to_string(),
+                parsed: Some(version.to_string()),
+                extraction_method: "--version".to_string(),
+            }),
+            path_order: order,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    fn conflict(instances: Vec<ExecutableInfo>) -> Conflict {
+        Conflict {
+            binary_name: "git".to_string(),
+            active_instance: instances[0].clone(),
+            instances,
+            category: ConflictCategory::DuplicateVersions,
+            severity: Severity::Medium,
+            severity_reason: String::new(),
+            description: String::new(),
+            recommendation: None,
+            commands: Vec::new(),
+            same_content: false,
+            risk_score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_local_build_over_package() {
+        let mut system = instance("/usr/bin/git", 1, "2.43.0");
+        system.origin = Some(PackageOrigin {
+            source: "dpkg".to_string(),
+            package: "git".to_string(),
+            version: Some("1:2.43.0-1".to_string()),
+            linked: None,
+        });
+        let conflicts = vec![conflict(vec![
+            instance("/usr/local/bin/git", 0, "2.30.0"),
+            system,
+        ])];
+
+        let diagnostics = LocalBuildAnalyzer::new().analyze(&conflicts, true);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Medium);
+        assert!(diagnostics[0]
+            .message
+            .contains("dpkg package git 1:2.43.0-1"));
+        assert!(diagnostics[0]
+            .message
+            .contains("(local 2.30.0, packaged 2.43.0)"));
+    }
+
+    #[test]
+    fn test_requires_package_owner_when_checked() {
+        let conflicts = vec![conflict(vec![
+            instance("/usr/local/bin/git", 0, "2.43.0"),
+            instance("/usr/bin/git", 1, "2.43.0"),
+        ])];
+
+        assert!(LocalBuildAnalyzer::new()
+            .analyze(&conflicts, true)
+            .is_empty());
+
+        let diagnostics = LocalBuildAnalyzer::new().analyze(&conflicts, false);
+        assert_eq!(diagnostics[0].severity, Severity::Low);
+        assert!(diagnostics[0].message.contains("from the distribution"));
+    }
+
+    #[test]
+    fn test_homebrew_is_not_a_local_build() {
+        let mut brew = instance("/usr/local/bin/git", 0, "2.44.0");
+        brew.resolved_path = PathBuf::from("/usr/local/Cellar/git/2.44.0/bin/git");
+        let conflicts = vec![conflict(vec![brew, instance("/usr/bin/git", 1, "2.39.0")])];
+
+        assert!(LocalBuildAnalyzer::new()
+            .analyze(&conflicts, false)
+            .is_empty());
+    }
+}
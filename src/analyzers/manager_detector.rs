@@ -1,3 +1,6 @@
+use crate::analyzers::env_modules::ModuleEnvironment;
+use crate::config::ManagerConfig;
+use crate::error::{Error, Result};
 use crate::output::types::{ExecutableInfo, ManagerInfo, ManagerType};
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -10,6 +13,10 @@ struct ManagerPattern {
     path_patterns: Vec<&'static str>,
 }
 
+/// A Spack install prefix: `<package>-<version>-<32 character hash>`
+static SPACK_PREFIX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(.+)-([0-9][^-]*)-[a-z0-9]{32}$").unwrap());
+
 static MANAGER_PATTERNS: Lazy<Vec<ManagerPattern>> = Lazy::new(|| {
     vec![
         // Version Managers
@@ -68,6 +75,16 @@ static MANAGER_PATTERNS: Lazy<Vec<ManagerPattern>> = Lazy::new(|| {
             description: "Package Manager for Windows",
             path_patterns: vec![r"\\scoop\\", r"/scoop/"],
         },
+        ManagerPattern {
+            manager_type: ManagerType::PackageManager,
+            name: "Spack",
+            description: "Spack Package Manager",
+            path_patterns: vec![
+                r"/opt/spack/",
+                r"/\.spack-env/view/",
+                r"/spack/var/spack/environments/",
+            ],
+        },
         // System paths
         ManagerPattern {
             manager_type: ManagerType::SystemInstall,
@@ -86,31 +103,116 @@ static MANAGER_PATTERNS: Lazy<Vec<ManagerPattern>> = Lazy::new(|| {
     ]
 });
 
-pub struct ManagerDetector {}
+/// A manager defined in the config file (`[[managers]]`)
+#[derive(Debug, Clone)]
+pub struct CustomManager {
+    info: ManagerInfo,
+    patterns: Vec<Regex>,
+}
+
+impl CustomManager {
+    pub fn from_config(config: &ManagerConfig) -> Result<Self> {
+        let invalid = |pattern: &str, reason: String| Error::InvalidPattern {
+            pattern: pattern.to_string(),
+            reason,
+        };
+        if config.name.is_empty() {
+            return Err(invalid("", "manager name is required".to_string()));
+        }
+        if config.patterns.is_empty() {
+            return Err(invalid(
+                &config.name,
+                "at least one path pattern is required".to_string(),
+            ));
+        }
+
+        let patterns = config
+            .patterns
+            .iter()
+            .map(|p| Regex::new(p).map_err(|e| invalid(p, e.to_string())))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(CustomManager {
+            info: ManagerInfo {
+                manager_type: config.kind.into(),
+                name: config.name.clone(),
+                description: config
+                    .description
+                    .clone()
+                    .unwrap_or_else(|| config.name.clone()),
+            },
+            patterns,
+        })
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        self.patterns.iter().any(|p| p.is_match(path))
+    }
+}
+
+pub struct ManagerDetector {
+    modules: ModuleEnvironment,
+    custom: Vec<CustomManager>,
+}
 
 impl ManagerDetector {
     pub fn new() -> Self {
-        ManagerDetector {}
+        ManagerDetector {
+            modules: ModuleEnvironment::from_env(),
+            custom: Vec::new(),
+        }
+    }
+
+    /// Check these managers, in order, before the built-in patterns
+    pub fn with_custom_managers(mut self, custom: Vec<CustomManager>) -> Self {
+        self.custom = custom;
+        self
+    }
+
+    /// Attribute executables to these loaded modules instead of the ones
+    /// in the environment
+    pub fn with_modules(mut self, modules: ModuleEnvironment) -> Self {
+        self.modules = modules;
+        self
     }
 
     pub fn detect_managers(&self, executables: &mut [ExecutableInfo]) {
         for executable in executables.iter_mut() {
-            executable.manager = self.detect(&executable.resolved_path);
+            // Modules put their directory on PATH, so match where the
+            // executable was found before following symlinks out of it
+            executable.manager = self
+                .detect_module(&executable.full_path)
+                .or_else(|| self.detect_spack_view(executable))
+                .or_else(|| self.detect(&executable.resolved_path));
         }
     }
 
     pub fn detect(&self, path: &Path) -> Option<ManagerInfo> {
+        if let Some(info) = self.detect_module(path) {
+            return Some(info);
+        }
+
         let path_str = path.to_string_lossy();
 
+        if let Some(custom) = self.custom.iter().find(|c| c.matches(&path_str)) {
+            return Some(custom.info.clone());
+        }
+
         // Check each pattern
         for pattern in MANAGER_PATTERNS.iter() {
             for path_pattern in &pattern.path_patterns {
                 if let Ok(regex) = Regex::new(path_pattern) {
                     if regex.is_match(&path_str) {
+                        let description = match pattern.name {
+                            "Spack" => spack_package(path)
+                                .map(|package| format!("Spack package {}", package)),
+                            _ => None,
+                        };
                         return Some(ManagerInfo {
                             manager_type: pattern.manager_type,
                             name: pattern.name.to_string(),
-                            description: pattern.description.to_string(),
+                            description: description
+                                .unwrap_or_else(|| pattern.description.to_string()),
                         });
                     }
                 }
@@ -134,6 +236,22 @@ impl ManagerDetector {
         None
     }
 
+    fn detect_module(&self, path: &Path) -> Option<ManagerInfo> {
+        let module = self.modules.module_for(path)?;
+        Some(ManagerInfo {
+            manager_type: ManagerType::EnvironmentModule,
+            name: module.name.clone(),
+            description: format!("{} module", self.modules.system),
+        })
+    }
+
+    /// Spack environment views can live anywhere, but their executables
+    /// are symlinks into the install tree
+    fn detect_spack_view(&self, executable: &ExecutableInfo) -> Option<ManagerInfo> {
+        let info = self.detect(executable.symlink_target.as_ref()?)?;
+        (info.name == "Spack").then_some(info)
+    }
+
     fn check_env_vars(&self, _path: &Path) -> bool {
         // Check for manager-specific environment variables
         if std::env::var("NVM_DIR").is_ok() {
@@ -156,6 +274,15 @@ impl ManagerDetector {
     }
 }
 
+/// `name@version` of the Spack install prefix holding `path`. Views link
+/// into the install tree, so this needs the resolved path.
+fn spack_package(path: &Path) -> Option<String> {
+    path.components().find_map(|component| {
+        let captures = SPACK_PREFIX.captures(component.as_os_str().to_str()?)?;
+        Some(format!("{}@{}", &captures[1], &captures[2]))
+    })
+}
+
 impl Default for ManagerDetector {
     fn default() -> Self {
         Self::new()
@@ -179,6 +306,31 @@ mod tests {
         assert_eq!(info.manager_type, ManagerType::VersionManager);
     }
 
+    #[test]
+    fn test_custom_manager_wins() {
+        let config = ManagerConfig {
+            name: "corp-tools".to_string(),
+            kind: crate::config::ManagerKind::VersionManager,
+            description: None,
+            patterns: vec![r"^/usr/local/corp/".to_string()],
+        };
+        let detector = ManagerDetector::new()
+            .with_custom_managers(vec![CustomManager::from_config(&config).unwrap()]);
+
+        let info = detector
+            .detect(&PathBuf::from("/usr/local/corp/bin/python"))
+            .unwrap();
+        assert_eq!(info.name, "corp-tools");
+        assert_eq!(info.manager_type, ManagerType::VersionManager);
+        assert_eq!(
+            detector
+                .detect(&PathBuf::from("/usr/bin/python"))
+                .unwrap()
+                .name,
+            "System"
+        );
+    }
+
     #[test]
     fn test_detect_system() {
         let detector = ManagerDetector::new();
@@ -191,6 +343,40 @@ mod tests {
         assert_eq!(info.manager_type, ManagerType::SystemInstall);
     }
 
+    #[test]
+    fn test_detect_loaded_module() {
+        let modules =
+            ModuleEnvironment::from_vars("gcc/12.2.0", "/apps/modulefiles/gcc.lua", |_| {
+                Some("prepend_path(\"PATH\", \"/apps/gcc/12.2.0/bin\")\n".to_string())
+            });
+        let detector = ManagerDetector::new().with_modules(modules);
+
+        let info = detector
+            .detect(&PathBuf::from("/apps/gcc/12.2.0/bin/gcc"))
+            .unwrap();
+        assert_eq!(info.name, "gcc/12.2.0");
+        assert_eq!(info.manager_type, ManagerType::EnvironmentModule);
+        assert_eq!(info.description, "Lmod module");
+    }
+
+    #[test]
+    fn test_detect_spack() {
+        let detector = ManagerDetector::new();
+        let path = PathBuf::from(
+            "/home/user/spack/opt/spack/linux-ubuntu22.04-x86_64/gcc-11.4.0/\
+             cmake-3.27.7-q2ctgpbb6qb5bvkaruzgvvx5ct6cvlwx/bin/cmake",
+        );
+        let info = detector.detect(&path).unwrap();
+        assert_eq!(info.name, "Spack");
+        assert_eq!(info.manager_type, ManagerType::PackageManager);
+        assert_eq!(info.description, "Spack package cmake@3.27.7");
+
+        let view = PathBuf::from("/home/user/project/.spack-env/view/bin/cmake");
+        let info = detector.detect(&view).unwrap();
+        assert_eq!(info.name, "Spack");
+        assert_eq!(info.description, "Spack Package Manager");
+    }
+
     #[test]
     fn test_detect_homebrew() {
         let detector = ManagerDetector::new();
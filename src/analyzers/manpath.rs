@@ -0,0 +1,195 @@
+use crate::core::search_path::SearchPath;
+use crate::output::types::{Conflict, Diagnostic, DiagnosticKind, ExecutableInfo, Severity};
+use std::path::{Path, PathBuf};
+
+/// Manual sections searched for commands, in the order `man` tries them
+const SECTIONS: &[&str] = &["1", "8"];
+
+/// Checks that `man <tool>` documents the copy of a conflicting tool that
+/// runs. With MANPATH ordered differently from PATH, `man git` can show the
+/// distribution's page while Homebrew's git is the one on PATH.
+pub struct ManPathAnalyzer;
+
+impl ManPathAnalyzer {
+    pub fn new() -> Self {
+        ManPathAnalyzer
+    }
+
+    pub fn analyze(&self, manpath: &SearchPath, conflicts: &[Conflict]) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for conflict in conflicts.iter().filter(|c| !c.same_content) {
+            let pages = man_pages(manpath, &conflict.binary_name);
+            let Some(shown) = pages.first() else {
+                continue;
+            };
+            let prefix_of = |page: &PathBuf| manpath.dir_of(page).map(man_prefix);
+            let shown_prefix = prefix_of(shown);
+            let belongs = |exec: &ExecutableInfo| {
+                shown_prefix.is_some()
+                    && [&exec.full_path, &exec.resolved_path]
+                        .iter()
+                        .any(|path| bin_prefix(path) == shown_prefix)
+            };
+            let active = &conflict.active_instance;
+            if belongs(active) {
+                continue;
+            }
+            let Some(documented) = conflict
+                .instances
+                .iter()
+                .filter(|exec| exec.full_path != active.full_path)
+                .find(|exec| belongs(exec))
+            else {
+                continue;
+            };
+
+            let active_page = pages.iter().find(|page| {
+                let prefix = prefix_of(page);
+                [&active.full_path, &active.resolved_path]
+                    .iter()
+                    .any(|path| prefix.is_some() && bin_prefix(path) == prefix)
+            });
+            let advice = match active_page {
+                Some(page) => format!(
+                    "its page is {}; list {} earlier in {}",
+                    page.display(),
+                    manpath.dir_of(page).unwrap_or(page).display(),
+                    manpath.variable
+                ),
+                None => format!("{} has no page for it", manpath.variable),
+            };
+
+            let mut paths = vec![shown.clone(), active.full_path.clone()];
+            paths.extend(active_page.cloned());
+            diagnostics.push(Diagnostic {
+                kind: DiagnosticKind::ManPage,
+                severity: Severity::Low,
+                message: format!(
+                    "man {} shows {}, which documents the shadowed {}, but {} runs; {}",
+                    conflict.binary_name,
+                    shown.display(),
+                    documented.full_path.display(),
+                    active.full_path.display(),
+                    advice
+                ),
+                paths,
+            });
+        }
+
+        diagnostics
+    }
+}
+
+impl Default for ManPathAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pages for `name` (plain or compressed), in the order `man` considers them
+fn man_pages(manpath: &SearchPath, name: &str) -> Vec<PathBuf> {
+    SECTIONS
+        .iter()
+        .flat_map(|section| {
+            let page = format!("{}.{}", name, section);
+            manpath.find_all(&format!("man{}", section), move |file| {
+                file == page
+                    || file
+                        .strip_prefix(page.as_str())
+                        .is_some_and(|rest| rest.starts_with('.'))
+            })
+        })
+        .collect()
+}
+
+/// The install prefix of a manual directory: `/usr` for `/usr/share/man`
+/// and `/opt/tool` for `/opt/tool/man`
+fn man_prefix(dir: &Path) -> PathBuf {
+    if dir.ends_with("share/man") {
+        dir.parent().and_then(Path::parent).unwrap_or(dir)
+    } else if dir.ends_with("man") {
+        dir.parent().unwrap_or(dir)
+    } else {
+        dir
+    }
+    .to_path_buf()
+}
+
+/// The install prefix of an executable: `/usr` for `/usr/bin/git`
+fn bin_prefix(path: &Path) -> Option<PathBuf> {
+    path.parent()?.parent().map(Path::to_path_buf)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::output::types::ConflictCategory;
+
+    fn instance(path: &Path, order: usize) -> ExecutableInfo {
+        ExecutableInfo {
+            name: "git".to_string(),
+            full_path: path.to_path_buf(),
+            size: 1000,
+            resolved_path: path.to_path_buf(),
+            path_order: order,
+            is_active: order == 0,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    fn conflict(paths: &[PathBuf]) -> Conflict {
+        let instances: Vec<_> = paths
+            .iter()
+            .enumerate()
+            .map(|(order, path)| instance(path, order))
+            .collect();
+        Conflict {
+            binary_name: "git".to_string(),
+            active_instance: instances[0].clone(),
+            instances,
+            category: ConflictCategory::PackageManagerVsSystem,
+            severity: Severity::Medium,
+            severity_reason: String::new(),
+            description: String::new(),
+            recommendation: None,
+            commands: Vec::new(),
+            same_content: false,
+            risk_score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_man_page_of_shadowed_copy() {
+        let root = tempfile::tempdir().unwrap();
+        let brew = root.path().join("homebrew");
+        let usr = root.path().join("usr");
+        let mut man_dirs = Vec::new();
+        for prefix in [&usr, &brew] {
+            let man1 = prefix.join("share/man/man1");
+            std::fs::create_dir_all(&man1).unwrap();
+            std::fs::write(man1.join("git.1.gz"), b"").unwrap();
+            man_dirs.push(prefix.join("share/man").display().to_string());
+        }
+        let manpath = SearchPath::parse("MANPATH", &man_dirs.join(":"));
+
+        let active = brew.join("bin/git");
+        let shadowed = usr.join("bin/git");
+        let diagnostics =
+            ManPathAnalyzer::new().analyze(&manpath, &[conflict(&[active, shadowed.clone()])]);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::ManPage);
+        let message = &diagnostics[0].message;
+        assert!(message.contains(&format!("documents the shadowed {}", shadowed.display())));
+        assert!(message.ends_with(&format!(
+            "list {} earlier in MANPATH",
+            brew.join("share/man").display()
+        )));
+
+        // MANPATH in PATH order documents the active copy
+        let manpath = SearchPath::parse("MANPATH", &format!("{}:{}", man_dirs[1], man_dirs[0]));
+        let diagnostics = ManPathAnalyzer::new()
+            .analyze(&manpath, &[conflict(&[brew.join("bin/git"), shadowed])]);
+        assert!(diagnostics.is_empty());
+    }
+}
@@ -1,9 +1,69 @@
+pub mod analyzer;
+pub mod app_paths;
 pub mod categorizer;
+pub mod cmd_internals;
+pub mod env_modules;
+pub mod fix_commands;
+pub mod gui_path;
+pub mod homebrew_metadata;
+pub mod homebrew_prefixes;
+pub mod interpreters;
+pub mod library_path;
+pub mod local_build;
 pub mod manager_detector;
+pub mod manpath;
+pub mod origin_detector;
+pub mod path_optimizer;
+pub mod path_security;
+pub mod pathext;
+pub mod pip_mismatch;
+pub mod plugins;
+pub mod python_path;
+pub mod registry_path;
+pub mod requirement_checker;
+pub mod risk_scorer;
+pub mod root_cause;
+pub mod rules;
+pub mod setuid;
+pub mod shims;
+pub mod simulated_entry;
 pub mod symlink_resolver;
 pub mod version_extractor;
+pub mod windows_apps;
+pub mod writable_shadow;
+pub mod wslenv;
 
+pub use analyzer::Analyzer;
+pub use app_paths::AppPathsAnalyzer;
 pub use categorizer::ConflictCategorizer;
-pub use manager_detector::ManagerDetector;
+pub use cmd_internals::CmdInternalAnalyzer;
+pub use env_modules::ModuleEnvironment;
+pub use fix_commands::FixCommandBuilder;
+pub use gui_path::GuiPathAnalyzer;
+pub use homebrew_metadata::HomebrewMetadata;
+pub use homebrew_prefixes::DualHomebrewAnalyzer;
+pub use interpreters::InterpreterAnalyzer;
+pub use library_path::LibraryPathAnalyzer;
+pub use local_build::LocalBuildAnalyzer;
+pub use manager_detector::{CustomManager, ManagerDetector};
+pub use manpath::ManPathAnalyzer;
+pub use origin_detector::OriginDetector;
+pub use path_optimizer::{PathOptimizer, Preference};
+pub use path_security::PathSecurityAnalyzer;
+pub use pathext::PathextAnalyzer;
+pub use pip_mismatch::PipMismatchAnalyzer;
+pub use plugins::AnalyzerPlugin;
+pub use python_path::{PythonInstall, PythonPathAnalyzer};
+pub use registry_path::RegistryPathAnalyzer;
+pub use requirement_checker::{RequirementChecker, VersionRequirement};
+pub use risk_scorer::RiskScorer;
+pub use root_cause::RootCauseAnalyzer;
+pub use rules::ConflictRule;
+pub use setuid::SetuidAnalyzer;
+pub use shims::ShimAnalyzer;
+pub use simulated_entry::SimulatedEntryAnalyzer;
 pub use symlink_resolver::SymlinkResolver;
-pub use version_extractor::VersionExtractor;
+pub use version_extractor::{VersionExtractor, VersionProbe};
+pub use windows_apps::WindowsAppsAnalyzer;
+pub use writable_shadow::WritableShadowAnalyzer;
+pub use wslenv::WslEnvAnalyzer;
@@ -0,0 +1,513 @@
+use crate::output::types::{ExecutableInfo, PackageOrigin, VersionInfo};
+use crate::platform::macos;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// dpkg's database: `info/<package>.list` and `status`
+const DPKG_ADMIN_DIR: &str = "/var/lib/dpkg";
+/// pacman's database: one `<name>-<version>` directory per package
+const PACMAN_LOCAL_DIR: &str = "/var/lib/pacman/local";
+/// Homebrew on Linux
+const LINUXBREW_PREFIX: &str = "/home/linuxbrew/.linuxbrew";
+
+/// Native package database that can be asked who owns a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PackageDatabase {
+    Dpkg,
+    Rpm,
+    Pacman,
+}
+
+pub struct OriginDetector {
+    databases: Vec<PackageDatabase>,
+    homebrew_prefixes: Vec<PathBuf>,
+    /// Read database files instead of running the package tools
+    exec: bool,
+}
+
+impl OriginDetector {
+    pub fn new() -> Self {
+        let mut databases = Vec::new();
+        if which::which("dpkg-query").is_ok() {
+            databases.push(PackageDatabase::Dpkg);
+        }
+        if which::which("rpm").is_ok() {
+            databases.push(PackageDatabase::Rpm);
+        }
+        if which::which("pacman").is_ok() {
+            databases.push(PackageDatabase::Pacman);
+        }
+
+        OriginDetector {
+            databases,
+            homebrew_prefixes: detect_homebrew_prefix().into_iter().collect(),
+            exec: true,
+        }
+    }
+
+    /// A detector that never runs a program: dpkg and pacman databases are
+    /// read from disk and Homebrew kegs are recognized by path. rpm keeps
+    /// its database in a binary format, so RPM systems get no origins.
+    pub fn without_exec() -> Self {
+        let mut databases = Vec::new();
+        if Path::new(DPKG_ADMIN_DIR).join("status").is_file() {
+            databases.push(PackageDatabase::Dpkg);
+        }
+        if Path::new(PACMAN_LOCAL_DIR).is_dir() {
+            databases.push(PackageDatabase::Pacman);
+        }
+
+        let homebrew_prefixes = std::env::var_os("HOMEBREW_PREFIX")
+            .map(PathBuf::from)
+            .into_iter()
+            .chain(
+                [
+                    macos::HOMEBREW_ARM_PREFIX,
+                    macos::HOMEBREW_INTEL_PREFIX,
+                    LINUXBREW_PREFIX,
+                ]
+                .map(PathBuf::from),
+            )
+            .filter(|prefix| prefix.join("Cellar").is_dir())
+            .collect();
+
+        OriginDetector {
+            databases,
+            homebrew_prefixes,
+            exec: false,
+        }
+    }
+
+    /// Record which package owns each executable, when the OS knows
+    pub fn detect_origins(&self, executables: &mut [ExecutableInfo]) {
+        let paths: Vec<&Path> = executables
+            .iter()
+            .flat_map(|e| [e.full_path.as_path(), e.resolved_path.as_path()])
+            .collect();
+        let mut database_origins = HashMap::new();
+        for database in &self.databases {
+            let origins = match (database, self.exec) {
+                // dpkg-query is slow to start, so ask about every file at once
+                (PackageDatabase::Dpkg, true) => query_dpkg(&paths),
+                (PackageDatabase::Dpkg, false) => {
+                    read_dpkg_database(Path::new(DPKG_ADMIN_DIR), &paths)
+                }
+                (PackageDatabase::Pacman, false) => {
+                    read_pacman_database(Path::new(PACMAN_LOCAL_DIR), &paths)
+                }
+                // Asked one file at a time below
+                _ => HashMap::new(),
+            };
+            for (path, origin) in origins {
+                database_origins.entry(path).or_insert(origin);
+            }
+        }
+
+        for executable in executables.iter_mut() {
+            // Keep what Homebrew metadata already found, with its link state
+            executable.origin = executable
+                .origin
+                .take()
+                .or_else(|| self.homebrew_origin(&executable.resolved_path))
+                .or_else(|| {
+                    database_origins
+                        .get(&executable.full_path)
+                        .or_else(|| database_origins.get(&executable.resolved_path))
+                        .cloned()
+                })
+                .or_else(|| self.query_per_file(executable));
+        }
+    }
+
+    fn query_per_file(&self, executable: &ExecutableInfo) -> Option<PackageOrigin> {
+        if !self.exec {
+            return None;
+        }
+        for database in &self.databases {
+            for path in [&executable.full_path, &executable.resolved_path] {
+                let origin = match database {
+                    PackageDatabase::Rpm => query_rpm(path),
+                    PackageDatabase::Pacman => query_pacman(path),
+                    PackageDatabase::Dpkg => None, // Already queried in bulk
+                };
+                if origin.is_some() {
+                    return origin;
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Homebrew formulae live in `<prefix>/Cellar/<formula>/<version>/`, so
+    /// the resolved path alone identifies the owner without running brew
+    fn homebrew_origin(&self, resolved_path: &Path) -> Option<PackageOrigin> {
+        self.homebrew_prefixes
+            .iter()
+            .find_map(|prefix| parse_cellar_path(&prefix.join("Cellar"), resolved_path))
+    }
+}
+
+impl Default for OriginDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn detect_homebrew_prefix() -> Option<PathBuf> {
+    if let Ok(prefix) = std::env::var("HOMEBREW_PREFIX") {
+        return Some(PathBuf::from(prefix));
+    }
+
+    which::which("brew").ok()?;
+    run_query("brew", &["--prefix"]).map(|out| PathBuf::from(out.trim()))
+}
+
+fn parse_cellar_path(cellar: &Path, path: &Path) -> Option<PackageOrigin> {
+    let mut components = path.strip_prefix(cellar).ok()?.components();
+    let package = components.next()?.as_os_str().to_string_lossy().to_string();
+    let version = components.next()?.as_os_str().to_string_lossy().to_string();
+
+    Some(PackageOrigin {
+        source: "brew".to_string(),
+        package,
+        version: Some(version),
+        linked: None,
+    })
+}
+
+/// The version of the package that installed a binary, without the
+/// packaging parts: `1:9.4-2` becomes `9.4` and a Homebrew `3.12.1_1`
+/// becomes `3.12.1`
+pub fn package_version(origin: &PackageOrigin) -> Option<VersionInfo> {
+    let raw = origin.version.as_deref()?.trim();
+    let upstream = raw.split_once(':').map_or(raw, |(_, rest)| rest);
+    let upstream = match origin.source.as_str() {
+        "brew" => upstream.split('_').next(),
+        _ => upstream.rsplit_once('-').map(|(version, _)| version),
+    }
+    .filter(|version| !version.is_empty())
+    .unwrap_or(upstream);
+
+    Some(VersionInfo {
+        raw: raw.to_string(),
+        parsed: Some(upstream.to_string()),
+        extraction_method: format!("{} database", origin.source),
+    })
+}
+
+/// Owners of `paths` from dpkg's `info/*.list` files, versions from `status`
+fn read_dpkg_database(admin_dir: &Path, paths: &[&Path]) -> HashMap<PathBuf, PackageOrigin> {
+    let wanted: std::collections::HashSet<&Path> = paths.iter().copied().collect();
+    let mut owners: HashMap<PathBuf, String> = HashMap::new();
+    let Ok(entries) = std::fs::read_dir(admin_dir.join("info")) else {
+        return HashMap::new();
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        let Some(package) = file_name.strip_suffix(".list") else {
+            continue;
+        };
+        // `python3.11-minimal:amd64.list` on multiarch systems
+        let package = package.split(':').next().unwrap_or(package);
+        let Ok(list) = std::fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        for line in list.lines() {
+            if wanted.contains(Path::new(line)) {
+                owners
+                    .entry(PathBuf::from(line))
+                    .or_insert_with(|| package.to_string());
+            }
+        }
+    }
+    if owners.is_empty() {
+        return HashMap::new();
+    }
+
+    let versions = std::fs::read_to_string(admin_dir.join("status"))
+        .map(|status| parse_dpkg_status(&status))
+        .unwrap_or_default();
+    owners
+        .into_iter()
+        .map(|(path, package)| {
+            let origin = PackageOrigin {
+                source: "dpkg".to_string(),
+                version: versions.get(&package).cloned(),
+                package,
+                linked: None,
+            };
+            (path, origin)
+        })
+        .collect()
+}
+
+/// Installed package versions from dpkg's `status` file
+fn parse_dpkg_status(status: &str) -> HashMap<String, String> {
+    let mut versions = HashMap::new();
+    for stanza in status.split("\n\n") {
+        let field = |name: &str| {
+            stanza
+                .lines()
+                .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+                .map(str::trim)
+        };
+        if let (Some(package), Some(version)) = (field("Package"), field("Version")) {
+            versions.insert(package.to_string(), version.to_string());
+        }
+    }
+    versions
+}
+
+/// Owners of `paths` from pacman's local database, where each package
+/// directory holds a `desc` (name and version) and a `files` list
+fn read_pacman_database(local_dir: &Path, paths: &[&Path]) -> HashMap<PathBuf, PackageOrigin> {
+    let wanted: std::collections::HashSet<&Path> = paths.iter().copied().collect();
+    let mut origins = HashMap::new();
+    let Ok(entries) = std::fs::read_dir(local_dir) else {
+        return origins;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let Ok(files) = std::fs::read_to_string(entry.path().join("files")) else {
+            continue;
+        };
+        // Paths are listed relative to the root
+        let owned: Vec<PathBuf> = files
+            .lines()
+            .map(|line| Path::new("/").join(line))
+            .filter(|path| wanted.contains(path.as_path()))
+            .collect();
+        if owned.is_empty() {
+            continue;
+        }
+
+        let desc = std::fs::read_to_string(entry.path().join("desc")).unwrap_or_default();
+        let Some(package) = pacman_desc_field(&desc, "NAME") else {
+            continue;
+        };
+        for path in owned {
+            origins.entry(path).or_insert_with(|| PackageOrigin {
+                source: "pacman".to_string(),
+                package: package.to_string(),
+                version: pacman_desc_field(&desc, "VERSION").map(str::to_string),
+                linked: None,
+            });
+        }
+    }
+    origins
+}
+
+/// The line after `%FIELD%` in a pacman `desc` file
+fn pacman_desc_field<'a>(desc: &'a str, field: &str) -> Option<&'a str> {
+    let header = format!("%{}%", field);
+    let mut lines = desc.lines();
+    lines.find(|line| *line == header)?;
+    lines
+        .next()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn query_dpkg(paths: &[&Path]) -> HashMap<PathBuf, PackageOrigin> {
+    let mut origins = HashMap::new();
+    if paths.is_empty() {
+        return origins;
+    }
+
+    let mut args = vec!["-S".to_string()];
+    args.extend(paths.iter().map(|p| p.to_string_lossy().to_string()));
+    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    // dpkg-query exits non-zero when any path is unowned, but still prints
+    // the ones it found
+    let owners = match run_query("dpkg-query", &arg_refs) {
+        Some(output) => parse_dpkg_search_output(&output),
+        None => return origins,
+    };
+
+    let mut packages: Vec<&str> = owners.values().map(String::as_str).collect();
+    packages.sort_unstable();
+    packages.dedup();
+
+    let mut version_args = vec!["-W", "-f=${Package} ${Version}\\n"];
+    version_args.extend(packages.iter().copied());
+    let versions: HashMap<String, String> = run_query("dpkg-query", &version_args)
+        .map(|output| {
+            output
+                .lines()
+                .filter_map(|line| line.split_once(' '))
+                .map(|(pkg, ver)| (pkg.to_string(), ver.trim().to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for (path, package) in owners {
+        origins.insert(
+            path,
+            PackageOrigin {
+                source: "dpkg".to_string(),
+                version: versions.get(&package).cloned(),
+                package,
+                linked: None,
+            },
+        );
+    }
+
+    origins
+}
+
+/// Parse `dpkg-query -S` lines like `python3.11-minimal:amd64: /usr/bin/python3.11`
+fn parse_dpkg_search_output(output: &str) -> HashMap<PathBuf, String> {
+    output
+        .lines()
+        .filter(|line| !line.starts_with("diversion by"))
+        .filter_map(|line| line.split_once(": "))
+        .filter_map(|(packages, path)| {
+            // Several packages can share a directory; take the first owner
+            let package = packages.split(", ").next()?;
+            let package = package.split(':').next()?.trim();
+            (!package.is_empty()).then(|| (PathBuf::from(path.trim()), package.to_string()))
+        })
+        .collect()
+}
+
+fn query_rpm(path: &Path) -> Option<PackageOrigin> {
+    let path = path.to_string_lossy();
+    let output = run_query(
+        "rpm",
+        &[
+            "-qf",
+            "--queryformat",
+            "%{NAME} %{VERSION}-%{RELEASE}",
+            &path,
+        ],
+    )?;
+    if output.contains("not owned") {
+        return None;
+    }
+
+    let (package, version) = output.trim().split_once(' ')?;
+    Some(PackageOrigin {
+        source: "rpm".to_string(),
+        package: package.to_string(),
+        version: Some(version.to_string()),
+        linked: None,
+    })
+}
+
+fn query_pacman(path: &Path) -> Option<PackageOrigin> {
+    let path = path.to_string_lossy();
+    parse_pacman_output(&run_query("pacman", &["-Qo", &path])?)
+}
+
+/// Parse `pacman -Qo` output like `/usr/bin/ls is owned by coreutils 9.1-1`
+fn parse_pacman_output(output: &str) -> Option<PackageOrigin> {
+    let (_, owner) = output.trim().split_once(" is owned by ")?;
+    let (package, version) = owner.split_once(' ')?;
+
+    Some(PackageOrigin {
+        source: "pacman".to_string(),
+        package: package.to_string(),
+        version: Some(version.to_string()),
+        linked: None,
+    })
+}
+
+fn run_query(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    String::from_utf8(output.stdout)
+        .ok()
+        .filter(|out| !out.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dpkg_search_output() {
+        let output = "python3.11-minimal:amd64: /usr/bin/python3.11\n\
+                      diversion by foo from: /usr/bin/bar\n\
+                      git, git-man: /usr/bin/git\n";
+        let owners = parse_dpkg_search_output(output);
+
+        assert_eq!(
+            owners.get(Path::new("/usr/bin/python3.11")).unwrap(),
+            "python3.11-minimal"
+        );
+        assert_eq!(owners.get(Path::new("/usr/bin/git")).unwrap(), "git");
+        assert_eq!(owners.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_pacman_output() {
+        let origin = parse_pacman_output("/usr/bin/ls is owned by coreutils 9.1-1\n").unwrap();
+        assert_eq!(origin.package, "coreutils");
+        assert_eq!(origin.version.as_deref(), Some("9.1-1"));
+        assert!(parse_pacman_output("error: No package owns /usr/local/bin/x").is_none());
+    }
+
+    #[test]
+    fn test_read_databases_without_exec() {
+        let root = tempfile::tempdir().unwrap();
+        let dpkg = root.path().join("dpkg");
+        std::fs::create_dir_all(dpkg.join("info")).unwrap();
+        std::fs::write(dpkg.join("info/git.list"), "/.\n/usr/bin\n/usr/bin/git\n").unwrap();
+        std::fs::write(
+            dpkg.join("status"),
+            "Package: git\nStatus: install ok installed\nVersion: 1:2.39.5-0+deb12u3\n\n\
+             Package: vim\nVersion: 2:9.0.1378-2\n",
+        )
+        .unwrap();
+
+        let git = Path::new("/usr/bin/git");
+        let origins = read_dpkg_database(&dpkg, &[git, Path::new("/usr/local/bin/git")]);
+        assert_eq!(origins.len(), 1);
+        let version = package_version(&origins[git]).unwrap();
+        assert_eq!(version.raw, "1:2.39.5-0+deb12u3");
+        assert_eq!(version.parsed.as_deref(), Some("2.39.5"));
+        assert_eq!(version.extraction_method, "dpkg database");
+
+        let pacman = root.path().join("pacman/coreutils-9.1-1");
+        std::fs::create_dir_all(&pacman).unwrap();
+        std::fs::write(pacman.join("files"), "%FILES%\nusr/\nusr/bin/ls\n").unwrap();
+        std::fs::write(
+            pacman.join("desc"),
+            "%NAME%\ncoreutils\n\n%VERSION%\n9.1-1\n",
+        )
+        .unwrap();
+
+        let ls = Path::new("/usr/bin/ls");
+        let origins = read_pacman_database(&root.path().join("pacman"), &[ls]);
+        assert_eq!(origins[ls].package, "coreutils");
+        assert_eq!(
+            package_version(&origins[ls]).unwrap().parsed.as_deref(),
+            Some("9.1")
+        );
+    }
+
+    #[test]
+    fn test_parse_cellar_path() {
+        let origin = parse_cellar_path(
+            Path::new("/opt/homebrew/Cellar"),
+            Path::new("/opt/homebrew/Cellar/python@3.12/3.12.1/bin/python3.12"),
+        )
+        .unwrap();
+        assert_eq!(origin.source, "brew");
+        assert_eq!(origin.package, "python@3.12");
+        assert_eq!(origin.version.as_deref(), Some("3.12.1"));
+
+        assert!(parse_cellar_path(
+            Path::new("/opt/homebrew/Cellar"),
+            Path::new("/usr/bin/python3")
+        )
+        .is_none());
+    }
+}
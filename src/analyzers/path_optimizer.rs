@@ -0,0 +1,327 @@
+use crate::output::types::{Conflict, ExecutableInfo, ManagerType, PathEntry};
+use crate::platform::wsl;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+
+/// Which copy of a conflicting binary should win
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Preference {
+    /// pyenv, nvm, asdf and the like over everything else
+    VersionManagers,
+    /// Homebrew, Nix, Scoop and the like over system installs
+    PackageManagers,
+    /// In WSL, Linux copies over the Windows ones under /mnt
+    Linux,
+}
+
+impl Preference {
+    pub fn all() -> Vec<Preference> {
+        vec![
+            Preference::VersionManagers,
+            Preference::PackageManagers,
+            Preference::Linux,
+        ]
+    }
+
+    fn matches(self, instance: &ExecutableInfo) -> bool {
+        let managed_by = |manager_type| {
+            instance
+                .manager
+                .as_ref()
+                .map(|m| m.manager_type == manager_type)
+                .unwrap_or(false)
+        };
+        match self {
+            Preference::VersionManagers => managed_by(ManagerType::VersionManager),
+            Preference::PackageManagers => managed_by(ManagerType::PackageManager),
+            Preference::Linux => !wsl::is_windows_path_in_wsl(&instance.resolved_path),
+        }
+    }
+}
+
+/// A PATH order proposed by `PathOptimizer`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProposedOrder {
+    /// Indices into the analyzed PATH entries, in their proposed order
+    pub order: Vec<usize>,
+    /// Binaries whose preferred copy cannot come first without overriding a
+    /// preference shared by more binaries
+    pub unresolved: Vec<String>,
+}
+
+impl ProposedOrder {
+    /// The entries rearranged into the proposed order
+    pub fn apply(&self, path_entries: &[PathEntry]) -> Vec<PathEntry> {
+        self.order
+            .iter()
+            .map(|&i| path_entries[i].clone())
+            .collect()
+    }
+}
+
+/// Proposes a PATH order in which the preferred copy of each conflicting
+/// binary runs, moving as few directories as possible.
+///
+/// Each conflict asks for its preferred copy's directory to come before the
+/// directories of the other copies. Requests shared by the most binaries are
+/// granted first; one that would contradict those already granted is left
+/// unresolved. Conflicts no preference decides ask to keep their current
+/// winner, at lower priority. Directories then keep their original relative
+/// order wherever the granted requests allow.
+pub struct PathOptimizer {
+    preferences: Vec<Preference>,
+}
+
+impl PathOptimizer {
+    /// Earlier preferences decide first, so `[Linux, VersionManagers]`
+    /// prefers a Linux system copy over a Windows nvm one
+    pub fn new(preferences: Vec<Preference>) -> Self {
+        PathOptimizer { preferences }
+    }
+
+    pub fn optimize(&self, path_entries: &[PathEntry], conflicts: &[Conflict]) -> ProposedOrder {
+        let index: HashMap<usize, usize> = path_entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| (entry.order, i))
+            .collect();
+
+        // Binaries asking for the same set of orderings are granted together
+        let mut preferred: BTreeMap<Vec<(usize, usize)>, Vec<String>> = BTreeMap::new();
+        let mut kept: BTreeMap<Vec<(usize, usize)>, Vec<String>> = BTreeMap::new();
+        for conflict in conflicts {
+            let instances: Vec<&ExecutableInfo> =
+                conflict.instances.iter().filter(|i| !i.broken).collect();
+            let (winner, groups) = match self.preferred_instance(&instances) {
+                Some(winner) => (winner, &mut preferred),
+                None => match instances.first() {
+                    Some(&winner) => (winner, &mut kept),
+                    None => continue,
+                },
+            };
+            let Some(&from) = index.get(&winner.path_order) else {
+                continue;
+            };
+
+            let mut edges: Vec<(usize, usize)> = instances
+                .iter()
+                .filter(|other| !other.is_same_file(winner))
+                .filter_map(|other| index.get(&other.path_order).copied())
+                .filter(|&to| to != from)
+                .map(|to| (from, to))
+                .collect();
+            edges.sort_unstable();
+            edges.dedup();
+            if !edges.is_empty() {
+                groups
+                    .entry(edges)
+                    .or_default()
+                    .push(conflict.binary_name.clone());
+            }
+        }
+
+        let mut graph = vec![Vec::new(); path_entries.len()];
+        let mut unresolved = Vec::new();
+        for (groups, required) in [(preferred, true), (kept, false)] {
+            let mut groups: Vec<_> = groups.into_iter().collect();
+            groups.sort_by_key(|(edges, binaries)| (Reverse(binaries.len()), edges[0]));
+            for (edges, binaries) in groups {
+                if !add_edges(&mut graph, &edges) && required {
+                    unresolved.extend(binaries);
+                }
+            }
+        }
+        unresolved.sort();
+
+        ProposedOrder {
+            order: stable_order(&graph),
+            unresolved,
+        }
+    }
+
+    /// The first copy matching the highest preference that tells the copies
+    /// apart
+    fn preferred_instance<'a>(
+        &self,
+        instances: &[&'a ExecutableInfo],
+    ) -> Option<&'a ExecutableInfo> {
+        self.preferences.iter().find_map(|preference| {
+            let matching: Vec<&ExecutableInfo> = instances
+                .iter()
+                .copied()
+                .filter(|i| preference.matches(i))
+                .collect();
+            (matching.len() < instances.len())
+                .then(|| matching.first().copied())
+                .flatten()
+        })
+    }
+}
+
+/// Add every edge or none: false when one would close a cycle
+fn add_edges(graph: &mut [Vec<usize>], edges: &[(usize, usize)]) -> bool {
+    let before = graph.to_vec();
+    for &(from, to) in edges {
+        if reaches(graph, to, from) {
+            graph.clone_from_slice(&before);
+            return false;
+        }
+        if !graph[from].contains(&to) {
+            graph[from].push(to);
+        }
+    }
+    true
+}
+
+fn reaches(graph: &[Vec<usize>], from: usize, to: usize) -> bool {
+    let mut seen = vec![false; graph.len()];
+    let mut stack = vec![from];
+    while let Some(node) = stack.pop() {
+        if node == to {
+            return true;
+        }
+        if !std::mem::replace(&mut seen[node], true) {
+            stack.extend(&graph[node]);
+        }
+    }
+    false
+}
+
+/// Topological order that takes the earliest original entry whenever
+/// several are free to go next
+fn stable_order(graph: &[Vec<usize>]) -> Vec<usize> {
+    let mut incoming = vec![0usize; graph.len()];
+    for &to in graph.iter().flatten() {
+        incoming[to] += 1;
+    }
+
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..graph.len())
+        .filter(|&node| incoming[node] == 0)
+        .map(Reverse)
+        .collect();
+    let mut order = Vec::with_capacity(graph.len());
+    while let Some(Reverse(node)) = ready.pop() {
+        order.push(node);
+        for &to in &graph[node] {
+            incoming[to] -= 1;
+            if incoming[to] == 0 {
+                ready.push(Reverse(to));
+            }
+        }
+    }
+    order
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::output::types::{ConflictCategory, ManagerInfo, Severity};
+    use std::path::PathBuf;
+
+    fn instance(dir: &str, order: usize, manager_type: ManagerType) -> ExecutableInfo {
+        let path = PathBuf::from(dir).join("tool");
+        ExecutableInfo {
+            name: "tool".to_string(),
+            full_path: path.clone(),
+            resolved_path: path,
+            manager: Some(ManagerInfo {
+                manager_type,
+                name: String::new(),
+                description: String::new(),
+            }),
+            path_order: order,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    fn conflict(name: &str, instances: Vec<ExecutableInfo>) -> Conflict {
+        Conflict {
+            binary_name: name.to_string(),
+            active_instance: instances[0].clone(),
+            instances,
+            category: ConflictCategory::VersionManagerVsSystem,
+            severity: Severity::Medium,
+            severity_reason: String::new(),
+            description: String::new(),
+            recommendation: None,
+            commands: Vec::new(),
+            same_content: false,
+            risk_score: 0.0,
+        }
+    }
+
+    fn entries(dirs: &[&str]) -> Vec<PathEntry> {
+        dirs.iter()
+            .enumerate()
+            .map(|(order, dir)| PathEntry {
+                path: PathBuf::from(dir),
+                order,
+                exists: true,
+                is_accessible: true,
+                executables: Vec::new(),
+                sources: Vec::new(),
+                scope: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_moves_managed_directories_ahead() {
+        use ManagerType::*;
+        let entries = entries(&[
+            "/usr/local/bin",
+            "/usr/bin",
+            "/home/me/.nvm/bin",
+            "/opt/brew/bin",
+        ]);
+        let conflicts = vec![
+            conflict(
+                "node",
+                vec![
+                    instance("/usr/bin", 1, SystemInstall),
+                    instance("/home/me/.nvm/bin", 2, VersionManager),
+                ],
+            ),
+            conflict(
+                "git",
+                vec![
+                    instance("/usr/bin", 1, SystemInstall),
+                    instance("/opt/brew/bin", 3, PackageManager),
+                ],
+            ),
+            // Two binaries want brew first, one wants /usr/bin first: the
+            // majority wins
+            conflict(
+                "curl",
+                vec![
+                    instance("/usr/bin", 1, SystemInstall),
+                    instance("/opt/brew/bin", 3, PackageManager),
+                ],
+            ),
+            conflict(
+                "make",
+                vec![
+                    instance("/usr/bin", 1, VersionManager),
+                    instance("/opt/brew/bin", 3, PackageManager),
+                ],
+            ),
+            // Nothing to prefer; /usr/local/bin keeps winning
+            conflict(
+                "vim",
+                vec![
+                    instance("/usr/local/bin", 0, ManualInstall),
+                    instance("/usr/bin", 1, ManualInstall),
+                ],
+            ),
+        ];
+
+        let proposed = PathOptimizer::new(Preference::all()).optimize(&entries, &conflicts);
+        assert_eq!(proposed.order, vec![0, 2, 3, 1]);
+        assert_eq!(proposed.unresolved, vec!["make"]);
+
+        let reordered = proposed.apply(&entries);
+        assert_eq!(reordered[1].path, PathBuf::from("/home/me/.nvm/bin"));
+    }
+}
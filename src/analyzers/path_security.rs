@@ -0,0 +1,120 @@
+use crate::output::types::{SecurityFinding, SecurityKind, Severity};
+use crate::platform;
+use std::path::{Path, PathBuf};
+
+/// Flags PATH entries that make command lookup depend on the current
+/// directory: empty segments, `.` and relative directories. Whoever controls
+/// a directory you `cd` into can then plant an `ls` or `git` that runs in
+/// place of the real one.
+pub struct PathSecurityAnalyzer {
+    separator: char,
+}
+
+impl PathSecurityAnalyzer {
+    pub fn new() -> Self {
+        PathSecurityAnalyzer {
+            separator: platform::get_path_separator(),
+        }
+    }
+
+    /// `path_var` is the raw PATH value, before empty segments are dropped
+    /// and relative entries made absolute
+    pub fn analyze(&self, path_var: &str) -> Vec<SecurityFinding> {
+        let mut findings = Vec::new();
+
+        for (order, segment) in path_var.split(self.separator).enumerate() {
+            let position = order + 1;
+            let segment = segment.trim();
+
+            // Windows skips empty segments; POSIX shells search the current
+            // directory for them
+            if segment.is_empty() {
+                if cfg!(unix) {
+                    findings.push(finding(
+                        SecurityKind::EmptyEntry,
+                        format!(
+                            "PATH entry {} is empty, which means the current directory; \
+                             remove the stray '{}'",
+                            position, self.separator
+                        ),
+                        segment,
+                    ));
+                }
+                continue;
+            }
+
+            let expanded = platform::expand_env_vars(segment);
+            if is_current_directory(&expanded) {
+                findings.push(finding(
+                    SecurityKind::CurrentDirectory,
+                    format!(
+                        "PATH entry {} is '{}', so programs in whatever directory you are in \
+                         can run in place of system commands; remove it",
+                        position, segment
+                    ),
+                    segment,
+                ));
+            } else if !Path::new(&expanded).has_root() {
+                findings.push(finding(
+                    SecurityKind::RelativeEntry,
+                    format!(
+                        "PATH entry {} '{}' is relative, so it points somewhere different in \
+                         every directory; use an absolute path",
+                        position, segment
+                    ),
+                    segment,
+                ));
+            }
+        }
+
+        findings
+    }
+}
+
+impl Default for PathSecurityAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `.`, `./`, `.\` and the like
+fn is_current_directory(entry: &str) -> bool {
+    entry.trim_end_matches(['/', '\\']) == "."
+}
+
+fn finding(kind: SecurityKind, message: String, entry: &str) -> SecurityFinding {
+    SecurityFinding {
+        kind,
+        severity: Severity::Critical,
+        message,
+        paths: vec![PathBuf::from(entry)],
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_directory_and_relative_entries() {
+        let findings = PathSecurityAnalyzer::new().analyze(":/usr/bin:.:bin::/opt/bin/");
+
+        let kinds: Vec<SecurityKind> = findings.iter().map(|f| f.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                SecurityKind::EmptyEntry,
+                SecurityKind::CurrentDirectory,
+                SecurityKind::RelativeEntry,
+                SecurityKind::EmptyEntry,
+            ]
+        );
+        assert!(findings.iter().all(|f| f.severity == Severity::Critical));
+        assert!(findings[0].message.starts_with("PATH entry 1 is empty"));
+        assert!(findings[2].message.contains("'bin' is relative"));
+
+        assert!(PathSecurityAnalyzer::new()
+            .analyze("/usr/local/bin:/usr/bin")
+            .is_empty());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::output::types::{Diagnostic, DiagnosticKind, Severity};
+use crate::platform::windows::{self, DEFAULT_PATHEXT};
+use std::collections::HashSet;
+
+/// Extensions cmd.exe needs to start ordinary programs and scripts
+const ESSENTIAL: &[&str] = &["com", "exe", "bat", "cmd"];
+
+/// Checks PATHEXT, which decides which files run without typing their
+/// extension, for entries that break command lookup
+pub struct PathextAnalyzer;
+
+impl PathextAnalyzer {
+    pub fn new() -> Self {
+        PathextAnalyzer
+    }
+
+    /// `value` is PATHEXT, or `None` when it is unset
+    pub fn analyze(&self, value: Option<&str>) -> Vec<Diagnostic> {
+        let Some(value) = value else {
+            return vec![diagnostic(
+                Severity::Medium,
+                format!(
+                    "PATHEXT is not set, so programs that resolve commands themselves may not \
+                     find .exe files; restore it to {}",
+                    DEFAULT_PATHEXT
+                ),
+            )];
+        };
+
+        let mut diagnostics = Vec::new();
+        let extensions = windows::parse_pathext(value);
+
+        let malformed: Vec<&str> = value
+            .split(';')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter(|entry| {
+                !entry.starts_with('.') || entry.len() == 1 || entry.contains(['*', '?', ' '])
+            })
+            .collect();
+        if !malformed.is_empty() {
+            diagnostics.push(diagnostic(
+                Severity::Medium,
+                format!(
+                    "PATHEXT entries must look like .EXT; {} never match",
+                    malformed.join(", ")
+                ),
+            ));
+        }
+
+        if !extensions.iter().any(|e| e == "exe") {
+            diagnostics.push(diagnostic(
+                Severity::High,
+                format!(
+                    "PATHEXT lacks .EXE, so programs only run when typed with their extension; \
+                     restore it to {}",
+                    DEFAULT_PATHEXT
+                ),
+            ));
+        } else {
+            let missing: Vec<String> = ESSENTIAL
+                .iter()
+                .filter(|ext| !extensions.iter().any(|e| e == *ext))
+                .map(|ext| format!(".{}", ext.to_uppercase()))
+                .collect();
+            if !missing.is_empty() {
+                diagnostics.push(diagnostic(
+                    Severity::Low,
+                    format!(
+                        "PATHEXT lacks {}, so such files need their extension to run",
+                        missing.join(", ")
+                    ),
+                ));
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let duplicates: Vec<String> = extensions
+            .iter()
+            .filter(|ext| !seen.insert(ext.as_str()))
+            .map(|ext| format!(".{}", ext.to_uppercase()))
+            .collect();
+        if !duplicates.is_empty() {
+            diagnostics.push(diagnostic(
+                Severity::Info,
+                format!("PATHEXT repeats {}", duplicates.join(", ")),
+            ));
+        }
+
+        diagnostics
+    }
+}
+
+impl Default for PathextAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn diagnostic(severity: Severity, message: String) -> Diagnostic {
+    Diagnostic {
+        kind: DiagnosticKind::Pathext,
+        severity,
+        message,
+        paths: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pathext() {
+        assert_eq!(
+            windows::parse_pathext(".COM;.EXE;;.Py; RB;.rb"),
+            vec!["com", "exe", "py", "rb"]
+        );
+    }
+
+    #[test]
+    fn test_default_and_scripting_additions_are_fine() {
+        let analyzer = PathextAnalyzer::new();
+        assert!(analyzer.analyze(Some(DEFAULT_PATHEXT)).is_empty());
+        assert!(analyzer
+            .analyze(Some(&format!("{};.PY;.RB", DEFAULT_PATHEXT)))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_misconfigured() {
+        let analyzer = PathextAnalyzer::new();
+
+        let diagnostics = analyzer.analyze(Some(".COM;.BAT;.CMD;PY"));
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics[0].message.contains("PY never match"));
+        assert_eq!(diagnostics[1].severity, Severity::High);
+
+        let diagnostics = analyzer.analyze(Some(".EXE;.exe"));
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics[0].message.contains(".COM, .BAT, .CMD"));
+        assert_eq!(diagnostics[1].severity, Severity::Info);
+
+        assert_eq!(analyzer.analyze(None)[0].severity, Severity::Medium);
+    }
+}
@@ -0,0 +1,259 @@
+use crate::analyzers::categorizer::ConflictCategorizer;
+use crate::analyzers::python_path::{self, PythonInstall};
+use crate::analyzers::risk_scorer::RiskScorer;
+use crate::core::binary_format;
+use crate::core::binary_info::same_file;
+use crate::output::types::{Conflict, ConflictCategory, ExecutableInfo, PathEntry, PlatformInfo};
+use std::path::Path;
+
+/// The pip and python names that are expected to belong together
+const PAIRS: &[(&str, &str)] = &[("pip", "python"), ("pip3", "python3")];
+
+/// Checks that the `pip` that runs installs for the `python` that runs.
+/// A pip from one installation ahead of a python from another (Homebrew's
+/// pip3 with pyenv's python3, say) installs packages that python cannot
+/// import, which looks like a name conflict to nobody.
+pub struct PipMismatchAnalyzer {
+    categorizer: ConflictCategorizer,
+    risk_scorer: RiskScorer,
+}
+
+impl PipMismatchAnalyzer {
+    pub fn new(platform: PlatformInfo) -> Self {
+        PipMismatchAnalyzer {
+            categorizer: ConflictCategorizer::new(platform),
+            risk_scorer: RiskScorer::new(),
+        }
+    }
+
+    pub fn analyze(&self, path_entries: &[PathEntry]) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+
+        for (pip_name, python_name) in PAIRS {
+            let (Some(pip), Some(python)) = (
+                active(path_entries, pip_name),
+                active(path_entries, python_name),
+            ) else {
+                continue;
+            };
+            let Some(owner) = owner(pip, python) else {
+                continue;
+            };
+
+            let instances = vec![pip.clone(), python.clone()];
+            let category = ConflictCategory::PipMismatch;
+            let (severity, severity_reason) = self
+                .categorizer
+                .assess_severity_with_reason(category, &instances);
+            let recommendation = self
+                .categorizer
+                .generate_recommendation(category, &pip.name, &instances);
+            let risk_score = self.risk_scorer.score(category, &instances, false);
+
+            conflicts.push(Conflict {
+                binary_name: pip.name.clone(),
+                description: format!(
+                    "{} ({}) installs packages for {}, but {} runs {}",
+                    pip.name,
+                    pip.full_path.display(),
+                    owner,
+                    python.name,
+                    python.full_path.display()
+                ),
+                active_instance: pip.clone(),
+                instances,
+                category,
+                severity,
+                severity_reason,
+                recommendation,
+                commands: Vec::new(),
+                same_content: false,
+                risk_score,
+            });
+        }
+
+        conflicts
+    }
+}
+
+/// Whether `pip` and `python` are a pair this analyzer checks and the
+/// copies of them that run
+pub(crate) fn is_active_pair(
+    path_entries: &[PathEntry],
+    pip: &ExecutableInfo,
+    python: &ExecutableInfo,
+) -> bool {
+    PAIRS.iter().any(|(pip_name, python_name)| {
+        active(path_entries, pip_name).is_some_and(|a| a.full_path == pip.full_path)
+            && active(path_entries, python_name).is_some_and(|a| a.full_path == python.full_path)
+    })
+}
+
+/// The first working copy of `name` in PATH order, ignoring the `.exe`
+/// on Windows
+fn active<'a>(path_entries: &'a [PathEntry], name: &str) -> Option<&'a ExecutableInfo> {
+    path_entries
+        .iter()
+        .flat_map(|entry| &entry.executables)
+        .filter(|exec| !exec.broken)
+        .find(|exec| {
+            if cfg!(windows) {
+                Path::new(&exec.name)
+                    .file_stem()
+                    .is_some_and(|stem| stem.eq_ignore_ascii_case(name))
+            } else {
+                exec.name == name
+            }
+        })
+}
+
+/// The Python that `pip` installs for, described for the report, when it
+/// is not `python`. The `#!` line names it for a pip script; a binary
+/// launcher such as `pip.exe` belongs to the installation whose
+/// site-packages holds pip's dist-info.
+fn owner(pip: &ExecutableInfo, python: &ExecutableInfo) -> Option<String> {
+    if let Some(interpreter) = python_path::pip_interpreter(pip) {
+        let runs_python = [&python.full_path, &python.resolved_path]
+            .iter()
+            .any(|path| same_file(&interpreter, path));
+        return (!runs_python).then(|| interpreter.display().to_string());
+    }
+
+    // `#!/usr/bin/env python3` runs whichever python comes first
+    let format = match &pip.binary_format {
+        Some(format) => Some(format.clone()),
+        None => binary_format::detect(&pip.full_path),
+    };
+    if format.is_some_and(|format| format.interpreter.is_some()) {
+        return None;
+    }
+
+    let pip_prefix = pip.resolved_path.parent()?.parent()?;
+    let install = PythonInstall::locate(python)?;
+    let python_prefix = if cfg!(windows) {
+        install.stdlib.parent()?
+    } else {
+        install.stdlib.parent()?.parent()?
+    };
+    if same_file(pip_prefix, python_prefix) || !has_pip_dist_info(pip_prefix) {
+        return None;
+    }
+    Some(format!("the Python in {}", pip_prefix.display()))
+}
+
+/// Whether an installation prefix has pip in one of its site-packages
+fn has_pip_dist_info(prefix: &Path) -> bool {
+    let lib = if cfg!(windows) {
+        vec![prefix.join("Lib")]
+    } else {
+        std::fs::read_dir(prefix.join("lib"))
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    lib.iter().any(|dir| {
+        std::fs::read_dir(dir.join("site-packages"))
+            .map(|entries| {
+                entries.filter_map(|entry| entry.ok()).any(|entry| {
+                    let name = entry.file_name();
+                    let name = name.to_string_lossy();
+                    name.starts_with("pip-") && name.ends_with(".dist-info")
+                })
+            })
+            .unwrap_or(false)
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::output::types::Severity;
+
+    fn exec(path: &Path, order: usize) -> ExecutableInfo {
+        ExecutableInfo {
+            name: path.file_name().unwrap().to_string_lossy().into_owned(),
+            full_path: path.to_path_buf(),
+            resolved_path: path.to_path_buf(),
+            path_order: order,
+            is_active: true,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    fn entry(dir: &Path, order: usize, executables: Vec<ExecutableInfo>) -> PathEntry {
+        PathEntry {
+            path: dir.to_path_buf(),
+            order,
+            exists: true,
+            is_accessible: true,
+            executables,
+            sources: Vec::new(),
+            scope: None,
+        }
+    }
+
+    fn analyzer() -> PipMismatchAnalyzer {
+        PipMismatchAnalyzer::new(PlatformInfo {
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            is_wsl: false,
+            wsl_version: None,
+            wsl_distro: None,
+            wsl_interop: None,
+        })
+    }
+
+    #[test]
+    fn test_pip_of_another_python_first() {
+        let root = tempfile::tempdir().unwrap();
+        let brew = root.path().join("homebrew/bin");
+        let pyenv = root.path().join("pyenv/bin");
+        std::fs::create_dir_all(&brew).unwrap();
+        std::fs::create_dir_all(&pyenv).unwrap();
+        let brew_python = brew.join("python3");
+        let pyenv_python = pyenv.join("python3");
+        std::fs::write(&brew_python, "").unwrap();
+        std::fs::write(&pyenv_python, "").unwrap();
+        let pip = brew.join("pip3");
+        std::fs::write(&pip, format!("#!{}\n", brew_python.display())).unwrap();
+
+        // pyenv's python3 first, Homebrew's pip3 the only one
+        let entries = vec![
+            entry(&pyenv, 0, vec![exec(&pyenv_python, 0)]),
+            entry(&brew, 1, vec![exec(&pip, 1), exec(&brew_python, 1)]),
+        ];
+        let conflicts = analyzer().analyze(&entries);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].binary_name, "pip3");
+        assert_eq!(conflicts[0].category, ConflictCategory::PipMismatch);
+        assert_eq!(conflicts[0].severity, Severity::High);
+        assert_eq!(conflicts[0].instances[1].full_path, pyenv_python);
+        assert!(conflicts[0]
+            .description
+            .contains(&format!("installs packages for {}", brew_python.display())));
+        assert!(conflicts[0]
+            .recommendation
+            .as_ref()
+            .unwrap()
+            .contains("`python3 -m pip`"));
+
+        // Homebrew first: pip3 and python3 agree
+        let entries = vec![
+            entry(&brew, 0, vec![exec(&pip, 0), exec(&brew_python, 0)]),
+            entry(&pyenv, 1, vec![exec(&pyenv_python, 1)]),
+        ];
+        assert!(analyzer().analyze(&entries).is_empty());
+
+        // An env shebang follows PATH to whichever python3 runs
+        std::fs::write(&pip, "#!/usr/bin/env python3\n").unwrap();
+        let entries = vec![
+            entry(&pyenv, 0, vec![exec(&pyenv_python, 0)]),
+            entry(&brew, 1, vec![exec(&pip, 1), exec(&brew_python, 1)]),
+        ];
+        assert!(analyzer().analyze(&entries).is_empty());
+    }
+}
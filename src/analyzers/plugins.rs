@@ -0,0 +1,225 @@
+//! Third-party analyzers loaded from shared libraries (`plugins` feature).
+//!
+//! A plugin exports four C functions. Data crosses the boundary as UTF-8
+//! JSON, so plugins can be written in any language that builds a shared
+//! library:
+//!
+//! ```c
+//! uint32_t pcd_plugin_abi_version(void);            /* must return 1 */
+//! const char *pcd_plugin_name(void);                 /* static string */
+//! char *pcd_plugin_analyze(const char *result_json); /* NULL on failure */
+//! void pcd_plugin_free(char *findings_json);
+//! ```
+//!
+//! `pcd_plugin_analyze` receives the full JSON report and returns a JSON
+//! array of findings, `[{"severity": "medium", "message": "...", "paths":
+//! ["/opt/x/bin/cc"]}]`, which the host releases with `pcd_plugin_free`.
+
+use crate::error::{Error, Result};
+use crate::output::types::{Diagnostic, DiagnosticKind, Severity};
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// The plugin ABI this build speaks
+pub const ABI_VERSION: u32 = 1;
+
+#[derive(Debug, Deserialize)]
+struct Finding {
+    severity: String,
+    message: String,
+    #[serde(default)]
+    paths: Vec<PathBuf>,
+}
+
+/// A loaded analyzer plugin
+pub struct AnalyzerPlugin {
+    pub name: String,
+    pub path: PathBuf,
+    #[cfg(feature = "plugins")]
+    library: libloading::Library,
+}
+
+#[cfg(feature = "plugins")]
+impl AnalyzerPlugin {
+    /// Turn a plugin's findings into diagnostics tagged with its name
+    fn findings(&self, json: &str) -> Result<Vec<Diagnostic>> {
+        parse_findings(&self.name, json).map_err(|reason| self.error(reason))
+    }
+
+    fn error(&self, reason: impl Into<String>) -> Error {
+        Error::PluginError {
+            plugin: self.path.display().to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+#[cfg(feature = "plugins")]
+mod ffi {
+    use super::*;
+    use crate::output::types::AnalysisResult;
+    use std::ffi::{c_char, CStr, CString};
+    use std::path::Path;
+
+    type AbiVersionFn = unsafe extern "C" fn() -> u32;
+    type NameFn = unsafe extern "C" fn() -> *const c_char;
+    type AnalyzeFn = unsafe extern "C" fn(*const c_char) -> *mut c_char;
+    type FreeFn = unsafe extern "C" fn(*mut c_char);
+
+    impl AnalyzerPlugin {
+        /// Load the shared library at `path` and check its ABI version
+        pub fn load(path: &Path) -> Result<Self> {
+            let error = |reason: String| Error::PluginError {
+                plugin: path.display().to_string(),
+                reason,
+            };
+
+            // SAFETY: loading runs the library's initializers; plugins are
+            // trusted code the user configured explicitly
+            let library =
+                unsafe { libloading::Library::new(path) }.map_err(|e| error(e.to_string()))?;
+
+            // SAFETY: the symbol types are the documented plugin ABI
+            let (version, name) = unsafe {
+                let version = library
+                    .get::<AbiVersionFn>(b"pcd_plugin_abi_version\0")
+                    .map_err(|e| error(e.to_string()))?;
+                let name = library
+                    .get::<NameFn>(b"pcd_plugin_name\0")
+                    .map_err(|e| error(e.to_string()))?;
+                let name = name();
+                let name = if name.is_null() {
+                    String::new()
+                } else {
+                    CStr::from_ptr(name).to_string_lossy().into_owned()
+                };
+                (version(), name)
+            };
+            if version != ABI_VERSION {
+                return Err(error(format!(
+                    "plugin ABI version {} is not supported (expected {})",
+                    version, ABI_VERSION
+                )));
+            }
+
+            Ok(AnalyzerPlugin {
+                name: if name.is_empty() {
+                    path.file_stem()
+                        .map(|s| s.to_string_lossy().into_owned())
+                        .unwrap_or_default()
+                } else {
+                    name
+                },
+                path: path.to_path_buf(),
+                library,
+            })
+        }
+
+        /// Run the plugin over a finished analysis
+        pub fn analyze(&self, result: &AnalysisResult) -> Result<Vec<Diagnostic>> {
+            let input = CString::new(serde_json::to_string(result)?)
+                .map_err(|e| self.error(e.to_string()))?;
+
+            // SAFETY: the symbol types are the documented plugin ABI, and the
+            // returned string is released with the plugin's own free function
+            let output = unsafe {
+                let analyze = self
+                    .library
+                    .get::<AnalyzeFn>(b"pcd_plugin_analyze\0")
+                    .map_err(|e| self.error(e.to_string()))?;
+                let free = self
+                    .library
+                    .get::<FreeFn>(b"pcd_plugin_free\0")
+                    .map_err(|e| self.error(e.to_string()))?;
+
+                let findings = analyze(input.as_ptr());
+                if findings.is_null() {
+                    return Err(self.error("analysis failed"));
+                }
+                let output = CStr::from_ptr(findings).to_string_lossy().into_owned();
+                free(findings);
+                output
+            };
+
+            self.findings(&output)
+        }
+    }
+}
+
+#[cfg(not(feature = "plugins"))]
+impl AnalyzerPlugin {
+    pub fn load(_path: &std::path::Path) -> Result<Self> {
+        Err(Error::FeatureDisabled {
+            feature: "plugins".to_string(),
+        })
+    }
+
+    pub fn analyze(&self, _result: &crate::AnalysisResult) -> Result<Vec<Diagnostic>> {
+        Ok(Vec::new())
+    }
+}
+
+impl super::Analyzer for AnalyzerPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn analyze(&self, result: &crate::AnalysisResult) -> Result<Vec<Diagnostic>> {
+        AnalyzerPlugin::analyze(self, result)
+    }
+}
+
+#[cfg_attr(not(feature = "plugins"), allow(dead_code))]
+fn parse_findings(plugin: &str, json: &str) -> std::result::Result<Vec<Diagnostic>, String> {
+    let findings: Vec<Finding> =
+        serde_json::from_str(json).map_err(|e| format!("invalid findings: {}", e))?;
+    findings
+        .into_iter()
+        .map(|finding| {
+            let severity = finding.severity.parse::<Severity>()?;
+            Ok(Diagnostic {
+                kind: DiagnosticKind::Plugin,
+                severity,
+                message: format!("[{}] {}", plugin, finding.message),
+                paths: finding.paths,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_findings() {
+        let diagnostics = parse_findings(
+            "acme-toolchain",
+            r#"[{"severity": "high", "message": "cc is not the approved compiler",
+                 "paths": ["/usr/bin/cc"]},
+                {"severity": "info", "message": "checked 12 tools"}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].severity, Severity::High);
+        assert_eq!(
+            diagnostics[0].message,
+            "[acme-toolchain] cc is not the approved compiler"
+        );
+        assert_eq!(diagnostics[0].paths, vec![PathBuf::from("/usr/bin/cc")]);
+        assert!(diagnostics[1].paths.is_empty());
+
+        assert!(parse_findings("p", r#"[{"severity": "urgent", "message": "x"}]"#).is_err());
+        assert!(parse_findings("p", "not json").is_err());
+    }
+
+    #[cfg(feature = "plugins")]
+    #[test]
+    fn test_load_missing_library() {
+        let err = AnalyzerPlugin::load(std::path::Path::new("/nonexistent/libplugin.so"))
+            .err()
+            .unwrap();
+        assert_eq!(err.code(), "plugin");
+    }
+}
@@ -0,0 +1,468 @@
+use crate::analyzers::pip_mismatch;
+use crate::core::binary_format;
+use crate::core::binary_info::same_file;
+use crate::core::search_path::SearchPath;
+use crate::output::types::{Diagnostic, DiagnosticKind, ExecutableInfo, PathEntry, Severity};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// `python`, `python3` or `python3.12`, with the version suffix captured
+static PYTHON_NAME: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^python([0-9]+(?:\.[0-9]+)?)?$").unwrap());
+/// `pip`, `pip3` or `pip3.12`
+static PIP_NAME: Lazy<Regex> = Lazy::new(|| Regex::new(r"^pip([0-9]+(?:\.[0-9]+)?)?$").unwrap());
+/// A `pythonX.Y/site-packages` or `dist-packages` directory
+static PACKAGES_DIR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"python([0-9]+\.[0-9]+)[/\\](?:site|dist)-packages$").unwrap());
+
+/// A Python installation on PATH, located from its files without running it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonInstall {
+    /// The interpreter as found on PATH
+    pub executable: PathBuf,
+    /// `lib/python3.12`, or `Lib` on Windows
+    pub stdlib: PathBuf,
+    pub site_packages: Vec<PathBuf>,
+}
+
+impl PythonInstall {
+    /// Find the standard library next to `exec`: `<prefix>/lib/pythonX.Y`
+    /// for `<prefix>/bin/python3`, or `Lib` beside `python.exe`
+    pub fn locate(exec: &ExecutableInfo) -> Option<Self> {
+        let bin = exec.resolved_path.parent()?;
+        let candidates = if cfg!(windows) {
+            vec![bin.join("Lib")]
+        } else {
+            let lib = bin.parent()?.join("lib");
+            let version = exec
+                .resolved_path
+                .file_name()
+                .and_then(|name| PYTHON_NAME.captures(name.to_str()?))
+                .and_then(|caps| caps.get(1))
+                .map(|version| version.as_str().to_string())
+                .filter(|version| version.contains('.'));
+            match version {
+                Some(version) => vec![lib.join(format!("python{}", version))],
+                None => {
+                    let mut dirs: Vec<PathBuf> = std::fs::read_dir(&lib)
+                        .map(|entries| {
+                            entries
+                                .filter_map(|entry| entry.ok())
+                                .map(|entry| entry.path())
+                                .filter(|path| {
+                                    path.file_name()
+                                        .and_then(|name| name.to_str())
+                                        .and_then(|name| PYTHON_NAME.captures(name))
+                                        .is_some_and(|caps| {
+                                            caps.get(1).is_some_and(|v| v.as_str().contains('.'))
+                                        })
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    // Newest first, so python3 with 3.11 and 3.12 libs
+                    // picks 3.12
+                    dirs.sort_by_key(|dir| std::cmp::Reverse(version_key(dir)));
+                    dirs
+                }
+            }
+        };
+        let stdlib = candidates
+            .into_iter()
+            .find(|dir| dir.join("os.py").is_file())?;
+
+        let mut site_packages = vec![stdlib.join("site-packages")];
+        // Debian and Ubuntu install distribution packages here
+        if let Some(lib) = stdlib.parent().filter(|_| !cfg!(windows)) {
+            site_packages.push(lib.join("python3").join("dist-packages"));
+        }
+        site_packages.retain(|dir| dir.is_dir());
+
+        Some(PythonInstall {
+            executable: exec.full_path.clone(),
+            stdlib,
+            site_packages,
+        })
+    }
+
+    /// `3.12`, from the standard library directory name
+    pub fn version(&self) -> Option<&str> {
+        self.stdlib.file_name()?.to_str()?.strip_prefix("python")
+    }
+}
+
+/// Looks for Python imports that go somewhere unexpected: modules in
+/// PYTHONPATH directories that hide the standard library or installed
+/// packages of a python on PATH, and pip scripts that install for a
+/// different interpreter than the python next to them
+pub struct PythonPathAnalyzer;
+
+impl PythonPathAnalyzer {
+    pub fn new() -> Self {
+        PythonPathAnalyzer
+    }
+
+    pub fn analyze(
+        &self,
+        pythonpath: Option<&SearchPath>,
+        path_entries: &[PathEntry],
+    ) -> Vec<Diagnostic> {
+        let installs = python_installs(path_entries);
+        let mut diagnostics = Vec::new();
+        if let Some(pythonpath) = pythonpath {
+            for dir in &pythonpath.dirs {
+                diagnostics.extend(shadowed_modules(dir, &installs));
+            }
+        }
+        for entry in path_entries {
+            diagnostics.extend(mismatched_pips(entry, path_entries));
+        }
+        diagnostics
+    }
+}
+
+impl Default for PythonPathAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One install per interpreter file, however many names link to it
+fn python_installs(path_entries: &[PathEntry]) -> Vec<PythonInstall> {
+    let mut installs: Vec<PythonInstall> = Vec::new();
+    for exec in path_entries
+        .iter()
+        .flat_map(|entry| &entry.executables)
+        .filter(|exec| !exec.broken && PYTHON_NAME.is_match(&exec.name))
+    {
+        if let Some(install) = PythonInstall::locate(exec) {
+            if !installs.iter().any(|i| i.stdlib == install.stdlib) {
+                installs.push(install);
+            }
+        }
+    }
+    installs
+}
+
+/// Modules in one PYTHONPATH directory that every python on PATH imports
+/// instead of its own
+fn shadowed_modules(dir: &Path, installs: &[PythonInstall]) -> Vec<Diagnostic> {
+    // Another version's packages: its compiled modules will not load
+    let dir_text = dir.to_string_lossy();
+    if let Some(version) = PACKAGES_DIR
+        .captures(dir_text.trim_end_matches(['/', '\\']))
+        .map(|caps| caps[1].to_string())
+    {
+        let others: Vec<&PythonInstall> = installs
+            .iter()
+            .filter(|install| install.version().is_some_and(|v| v != version))
+            .collect();
+        if others.is_empty() {
+            return Vec::new();
+        }
+        return vec![Diagnostic {
+            kind: DiagnosticKind::PythonPath,
+            severity: Severity::Medium,
+            message: format!(
+                "PYTHONPATH includes {}, packages installed for Python {}, which {} also \
+                 import{}; their compiled modules do not load in another version, so drop it \
+                 and install what you need for each python",
+                dir.display(),
+                version,
+                list(others.iter().map(|install| &install.executable)),
+                if others.len() == 1 { "s" } else { "" }
+            ),
+            paths: std::iter::once(dir.to_path_buf())
+                .chain(others.iter().map(|install| install.executable.clone()))
+                .collect(),
+        }];
+    }
+
+    let mut diagnostics = Vec::new();
+    for (module, file) in top_level_modules(dir) {
+        let stdlib: Vec<&PythonInstall> = installs
+            .iter()
+            .filter(|install| {
+                provides(&install.stdlib, &module)
+                    || provides(&install.stdlib.join("lib-dynload"), &module)
+            })
+            .collect();
+        let (severity, hidden, affected) = if !stdlib.is_empty() {
+            (
+                Severity::Medium,
+                "the standard library module".to_string(),
+                stdlib,
+            )
+        } else {
+            let installed: Vec<(&PythonInstall, &PathBuf)> = installs
+                .iter()
+                .filter_map(|install| {
+                    install
+                        .site_packages
+                        .iter()
+                        .find(|site| provides(site, &module))
+                        .map(|site| (install, site))
+                })
+                .collect();
+            let Some((_, site)) = installed.first() else {
+                continue;
+            };
+            (
+                Severity::Low,
+                format!("the package installed in {}", site.display()),
+                installed.iter().map(|(install, _)| *install).collect(),
+            )
+        };
+
+        diagnostics.push(Diagnostic {
+            kind: DiagnosticKind::PythonPath,
+            severity,
+            message: format!(
+                "{} in PYTHONPATH entry {} hides {} {} for {}; rename it or remove {} from \
+                 PYTHONPATH",
+                file.file_name().unwrap_or_default().to_string_lossy(),
+                dir.display(),
+                hidden,
+                module,
+                list(affected.iter().map(|install| &install.executable)),
+                dir.display()
+            ),
+            paths: std::iter::once(file)
+                .chain(affected.iter().map(|install| install.executable.clone()))
+                .collect(),
+        });
+    }
+    diagnostics
+}
+
+/// Importable names at the top of `dir`: `name.py`, packages with an
+/// `__init__.py`, and extension modules such as `name.cpython-312-x86_64-linux-gnu.so`
+fn top_level_modules(dir: &Path) -> BTreeMap<String, PathBuf> {
+    let mut modules = BTreeMap::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return modules;
+    };
+    for path in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let module = if path.is_dir() {
+            path.join("__init__.py").is_file().then_some(name)
+        } else if let Some(stem) = name.strip_suffix(".py") {
+            Some(stem)
+        } else if name.ends_with(".so") || name.ends_with(".pyd") {
+            name.split('.').next()
+        } else {
+            None
+        };
+        if let Some(module) = module.filter(|m| is_identifier(m) && !m.starts_with("__")) {
+            modules.entry(module.to_string()).or_insert(path);
+        }
+    }
+    modules
+}
+
+/// Whether `dir` has a module or package called `module`
+fn provides(dir: &Path, module: &str) -> bool {
+    if dir.join(format!("{}.py", module)).is_file() || dir.join(module).is_dir() {
+        return true;
+    }
+    let prefix = format!("{}.", module);
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries.filter_map(|entry| entry.ok()).any(|entry| {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                name.starts_with(&prefix) && (name.ends_with(".so") || name.ends_with(".pyd"))
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// pip scripts in one PATH directory whose `#!` interpreter is not the
+/// python of the same name there (`pip3` and `python3`, `pip3.12` and
+/// `python3.12`). The pair that runs is left to the pip/python Mismatch
+/// conflict.
+fn mismatched_pips(entry: &PathEntry, path_entries: &[PathEntry]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for pip in entry
+        .executables
+        .iter()
+        .filter(|exec| !exec.broken && PIP_NAME.is_match(&exec.name))
+    {
+        let python_name = format!("python{}", &pip.name[3..]);
+        let Some(python) = entry
+            .executables
+            .iter()
+            .find(|exec| !exec.broken && exec.name == python_name)
+        else {
+            continue;
+        };
+        let Some(interpreter) = pip_interpreter(pip) else {
+            continue;
+        };
+        if same_file(&interpreter, &python.full_path)
+            || pip_mismatch::is_active_pair(path_entries, pip, python)
+        {
+            continue;
+        }
+
+        diagnostics.push(Diagnostic {
+            kind: DiagnosticKind::PipInterpreter,
+            severity: Severity::Medium,
+            message: format!(
+                "{} runs {}, not {} beside it, so it installs packages that {} cannot import; \
+                 use `{} -m pip`",
+                pip.full_path.display(),
+                interpreter.display(),
+                python.full_path.display(),
+                python_name,
+                python_name
+            ),
+            paths: vec![pip.full_path.clone(), interpreter, python.full_path.clone()],
+        });
+    }
+    diagnostics
+}
+
+/// The absolute interpreter on a pip script's `#!` line; `None` for
+/// `/usr/bin/env python3`, which follows PATH, and for binary launchers
+pub fn pip_interpreter(pip: &ExecutableInfo) -> Option<PathBuf> {
+    let format = match &pip.binary_format {
+        Some(format) => Some(format.clone()),
+        None => binary_format::detect(&pip.full_path),
+    }?;
+    let interpreter = PathBuf::from(format.interpreter?.split_whitespace().next()?);
+    (interpreter.is_absolute() && interpreter.file_name()? != "env").then_some(interpreter)
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// `(3, 12)` for `python3.12`
+fn version_key(dir: &Path) -> (u32, u32) {
+    let version = dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_prefix("python"))
+        .unwrap_or_default();
+    let mut parts = version.split('.').map(|part| part.parse().unwrap_or(0));
+    (parts.next().unwrap_or(0), parts.next().unwrap_or(0))
+}
+
+fn list<'a>(paths: impl Iterator<Item = &'a PathBuf>) -> String {
+    paths
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn exec(path: &Path) -> ExecutableInfo {
+        ExecutableInfo {
+            name: path.file_name().unwrap().to_string_lossy().into_owned(),
+            full_path: path.to_path_buf(),
+            resolved_path: path.to_path_buf(),
+            is_active: true,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    /// `<root>/<prefix>/bin/python3.12` with a stdlib and site-packages
+    fn install(root: &Path, prefix: &str) -> PathEntry {
+        let prefix = root.join(prefix);
+        let bin = prefix.join("bin");
+        let stdlib = prefix.join("lib/python3.12");
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::create_dir_all(stdlib.join("site-packages/requests")).unwrap();
+        std::fs::create_dir_all(stdlib.join("json")).unwrap();
+        std::fs::write(stdlib.join("os.py"), "").unwrap();
+        std::fs::write(stdlib.join("random.py"), "").unwrap();
+        let python = bin.join("python3.12");
+        std::fs::write(&python, "").unwrap();
+        std::fs::set_permissions(&python, std::fs::Permissions::from_mode(0o755)).unwrap();
+        PathEntry {
+            path: bin,
+            order: 0,
+            exists: true,
+            is_accessible: true,
+            executables: vec![exec(&python)],
+            sources: Vec::new(),
+            scope: None,
+        }
+    }
+
+    #[test]
+    fn test_pythonpath_shadows_stdlib_and_packages() {
+        let root = tempfile::tempdir().unwrap();
+        let entries = vec![install(root.path(), "usr")];
+        let project = root.path().join("project");
+        std::fs::create_dir_all(project.join("requests")).unwrap();
+        std::fs::write(project.join("requests/__init__.py"), "").unwrap();
+        std::fs::write(project.join("random.py"), "").unwrap();
+        std::fs::write(project.join("mytool.py"), "").unwrap();
+        let old = root.path().join("lib/python2.7/site-packages");
+        std::fs::create_dir_all(&old).unwrap();
+
+        let value = format!("{}:{}", project.display(), old.display());
+        let pythonpath = SearchPath::parse("PYTHONPATH", &value);
+        let diagnostics = PythonPathAnalyzer::new().analyze(Some(&pythonpath), &entries);
+
+        assert_eq!(diagnostics.len(), 3);
+        assert!(diagnostics[0]
+            .message
+            .starts_with("random.py in PYTHONPATH entry"));
+        assert!(diagnostics[0]
+            .message
+            .contains("hides the standard library module random for"));
+        assert_eq!(diagnostics[0].severity, Severity::Medium);
+        assert!(diagnostics[1].message.starts_with("requests in PYTHONPATH"));
+        assert_eq!(diagnostics[1].severity, Severity::Low);
+        assert!(diagnostics[2]
+            .message
+            .contains("packages installed for Python 2.7"));
+    }
+
+    #[test]
+    fn test_pip_for_another_python() {
+        let root = tempfile::tempdir().unwrap();
+        let mut entry = install(root.path(), "usr/local");
+        let old_python = root.path().join("opt/python3.9/bin/python3.9");
+        std::fs::create_dir_all(old_python.parent().unwrap()).unwrap();
+        std::fs::write(&old_python, "").unwrap();
+
+        let pip = entry.path.join("pip3.12");
+        std::fs::write(&pip, format!("#!{}\n", old_python.display())).unwrap();
+        entry.executables.push(exec(&pip));
+        let diagnostics = PythonPathAnalyzer::new().analyze(None, std::slice::from_ref(&entry));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::PipInterpreter);
+        assert!(diagnostics[0].message.ends_with("use `python3.12 -m pip`"));
+
+        // The pip3 and python3 that run are a pip/python Mismatch conflict
+        let python3 = entry.path.join("python3");
+        let pip3 = entry.path.join("pip3");
+        std::fs::write(&python3, "").unwrap();
+        std::fs::write(&pip3, format!("#!{}\n", old_python.display())).unwrap();
+        entry.executables.extend([exec(&python3), exec(&pip3)]);
+        let diagnostics = PythonPathAnalyzer::new().analyze(None, std::slice::from_ref(&entry));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].paths[0], pip);
+
+        let python = entry.path.join("python3.12");
+        std::fs::write(&pip, format!("#!{}\n", python.display())).unwrap();
+        assert!(PythonPathAnalyzer::new().analyze(None, &[entry]).is_empty());
+    }
+}
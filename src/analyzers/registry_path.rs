@@ -0,0 +1,292 @@
+use crate::output::types::{Conflict, Diagnostic, DiagnosticKind, PathEntry, PathScope, Severity};
+use crate::platform::windows::{self, RegistryPath};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Profile variables marking a directory that belongs to one user
+const PROFILE_VARS: &[&str] = &["%userprofile%", "%localappdata%", "%appdata%"];
+
+/// Finds directories listed in both the user (HKCU) and machine (HKLM) PATH
+/// values. Windows concatenates the two, so a shared directory is searched
+/// twice and uses up the PATH length limit for nothing.
+///
+/// Also records which of the two values each PATH entry comes from, and
+/// flags user entries that run ahead of machine-wide tools.
+pub struct RegistryPathAnalyzer;
+
+impl RegistryPathAnalyzer {
+    pub fn new() -> Self {
+        RegistryPathAnalyzer
+    }
+
+    pub fn analyze(&self, registry: &RegistryPath) -> Vec<Diagnostic> {
+        let (Some(user), Some(machine)) = (&registry.user, &registry.machine) else {
+            return Vec::new();
+        };
+
+        let machine_entries: Vec<String> = entries(machine).map(normalize).collect();
+        let mut seen = Vec::new();
+        let mut diagnostics = Vec::new();
+
+        for raw in entries(user) {
+            let normalized = normalize(raw);
+            if !machine_entries.contains(&normalized) || seen.contains(&normalized) {
+                continue;
+            }
+
+            let remove_from = if is_per_user(raw, &normalized) {
+                "machine PATH (HKLM), since it lives in your profile and other users cannot \
+                 use it"
+            } else {
+                "user PATH (HKCU\\Environment); the machine copy is searched first, so the \
+                 user copy never takes effect"
+            };
+
+            diagnostics.push(Diagnostic {
+                kind: DiagnosticKind::DuplicatePathEntry,
+                severity: Severity::Low,
+                message: format!(
+                    "{} is in both the user and machine PATH; remove it from the {} \
+                     ({} characters saved)",
+                    raw,
+                    remove_from,
+                    raw.len() + 1
+                ),
+                paths: vec![PathBuf::from(windows::expand_windows_env_vars(raw))],
+            });
+            seen.push(normalized);
+        }
+
+        diagnostics
+    }
+
+    /// Set each entry's `scope`. Windows puts the machine value first, so an
+    /// entry listed in both is a machine entry.
+    pub fn assign_scopes(&self, registry: &RegistryPath, path_entries: &mut [PathEntry]) {
+        let scoped = |value: &Option<String>| -> Vec<String> {
+            value
+                .as_deref()
+                .map(|value| entries(value).map(normalize).collect())
+                .unwrap_or_default()
+        };
+        let (machine, user) = (scoped(&registry.machine), scoped(&registry.user));
+
+        for entry in path_entries {
+            let path = normalize(&entry.path.to_string_lossy());
+            entry.scope = if machine.contains(&path) {
+                Some(PathScope::Machine)
+            } else if user.contains(&path) {
+                Some(PathScope::User)
+            } else {
+                None
+            };
+        }
+    }
+
+    /// Conflicts won by a user PATH copy over a machine PATH one. The process
+    /// PATH starts with the machine value, so this only happens when
+    /// something moved the user entry ahead, and it changes the tool for this
+    /// user alone.
+    pub fn user_shadowing(
+        &self,
+        conflicts: &[Conflict],
+        path_entries: &[PathEntry],
+    ) -> Vec<Diagnostic> {
+        let scoped = |order: usize, scope: PathScope| {
+            path_entries
+                .iter()
+                .find(|entry| entry.order == order && entry.scope == Some(scope))
+        };
+
+        // One finding per pair of directories, listing every binary
+        let mut shadowed: BTreeMap<(PathBuf, PathBuf), Vec<&str>> = BTreeMap::new();
+        for conflict in conflicts.iter().filter(|c| !c.same_content) {
+            let active = &conflict.active_instance;
+            let Some(user) = scoped(active.path_order, PathScope::User) else {
+                continue;
+            };
+            let machine = conflict
+                .instances
+                .iter()
+                .filter(|instance| !instance.broken && !instance.is_same_file(active))
+                .find_map(|instance| scoped(instance.path_order, PathScope::Machine));
+            if let Some(machine) = machine {
+                shadowed
+                    .entry((user.path.clone(), machine.path.clone()))
+                    .or_default()
+                    .push(&conflict.binary_name);
+            }
+        }
+
+        shadowed
+            .into_iter()
+            .map(|((user_dir, machine_dir), binaries)| Diagnostic {
+                kind: DiagnosticKind::UserShadowsMachine,
+                severity: Severity::Medium,
+                message: format!(
+                    "{} from the user PATH runs {} instead of the machine-wide copies in {}; \
+                     Windows puts the machine PATH first, so something moved it ahead",
+                    user_dir.display(),
+                    binaries.join(", "),
+                    machine_dir.display()
+                ),
+                paths: vec![user_dir, machine_dir],
+            })
+            .collect()
+    }
+}
+
+impl Default for RegistryPathAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn entries(value: &str) -> impl Iterator<Item = &str> {
+    value.split(';').map(str::trim).filter(|e| !e.is_empty())
+}
+
+/// Compare entries the way Windows resolves them: variables expanded,
+/// either slash, no trailing separator, case-insensitive
+pub(crate) fn normalize(entry: &str) -> String {
+    let expanded = windows::expand_windows_env_vars(entry).replace('/', "\\");
+    let trimmed = expanded.trim_end_matches('\\');
+    // Keep the separator of a drive root such as `C:\`
+    let trimmed = if trimmed.ends_with(':') {
+        &expanded[..trimmed.len() + 1]
+    } else {
+        trimmed
+    };
+    trimmed.to_lowercase()
+}
+
+fn is_per_user(raw: &str, normalized: &str) -> bool {
+    let raw = raw.to_lowercase();
+    if PROFILE_VARS.iter().any(|var| raw.contains(var)) {
+        return true;
+    }
+
+    std::env::var("USERPROFILE")
+        .map(|profile| normalized.starts_with(&normalize(&profile)))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::types::{ConflictCategory, ExecutableInfo};
+
+    fn registry(user: &str, machine: &str) -> RegistryPath {
+        RegistryPath {
+            user: Some(user.to_string()),
+            machine: Some(machine.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_duplicate_entries() {
+        let diagnostics = RegistryPathAnalyzer::new().analyze(&registry(
+            r"C:\Tools\bin\;C:\Python312;c:/tools/bin",
+            r"C:\Windows\system32;c:\tools\BIN",
+        ));
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::DuplicatePathEntry);
+        assert!(diagnostics[0]
+            .message
+            .contains("remove it from the user PATH"));
+    }
+
+    #[test]
+    fn test_profile_entry_kept_in_user_path() {
+        let dir = r"%LOCALAPPDATA%\Programs\Microsoft VS Code\bin";
+        let diagnostics =
+            RegistryPathAnalyzer::new().analyze(&registry(dir, &format!(r"C:\Windows;{}", dir)));
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0]
+            .message
+            .contains("remove it from the machine PATH"));
+    }
+
+    #[test]
+    fn test_missing_values() {
+        let analyzer = RegistryPathAnalyzer::new();
+        assert!(analyzer.analyze(&RegistryPath::default()).is_empty());
+        assert!(analyzer
+            .analyze(&registry(r"C:\Tools", r"C:\Windows"))
+            .is_empty());
+    }
+
+    fn executable(dir: &str, order: usize) -> ExecutableInfo {
+        let path = PathBuf::from(format!(r"{}\git.exe", dir));
+        ExecutableInfo {
+            name: "git".to_string(),
+            full_path: path.clone(),
+            resolved_path: path,
+            path_order: order,
+            is_active: order == 0,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    #[test]
+    fn test_user_entry_shadowing_machine_tool() {
+        let mut path_entries: Vec<PathEntry> = [r"C:\Users\me\bin\", r"C:\Program Files\Git\cmd"]
+            .into_iter()
+            .enumerate()
+            .map(|(order, dir)| PathEntry {
+                path: PathBuf::from(dir),
+                order,
+                exists: true,
+                is_accessible: true,
+                executables: Vec::new(),
+                sources: Vec::new(),
+                scope: None,
+            })
+            .collect();
+        let analyzer = RegistryPathAnalyzer::new();
+        analyzer.assign_scopes(
+            &registry(
+                r"c:\users\me\bin;C:\Program Files\Git\cmd",
+                r"C:\Program Files\Git\cmd",
+            ),
+            &mut path_entries,
+        );
+        let scopes: Vec<_> = path_entries.iter().map(|e| e.scope).collect();
+        assert_eq!(
+            scopes,
+            vec![Some(PathScope::User), Some(PathScope::Machine)]
+        );
+
+        let instances = vec![
+            executable(r"C:\Users\me\bin", 0),
+            executable(r"C:\Program Files\Git\cmd", 1),
+        ];
+        let conflict = Conflict {
+            binary_name: "git".to_string(),
+            active_instance: instances[0].clone(),
+            instances,
+            category: ConflictCategory::ShadowedBinary,
+            severity: Severity::Medium,
+            severity_reason: String::new(),
+            description: String::new(),
+            recommendation: None,
+            commands: Vec::new(),
+            same_content: false,
+            risk_score: 0.0,
+        };
+        let diagnostics = analyzer.user_shadowing(&[conflict], &path_entries);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::UserShadowsMachine);
+        assert!(diagnostics[0]
+            .message
+            .starts_with(r"C:\Users\me\bin\ from the user PATH runs git instead of"));
+    }
+
+    #[test]
+    fn test_normalize_drive_root() {
+        assert_eq!(normalize(r"C:\"), r"c:\");
+        assert_eq!(normalize("D:/Tools/"), r"d:\tools");
+    }
+}
@@ -0,0 +1,363 @@
+use crate::analyzers::VersionExtractor;
+use crate::error::{Error, Result};
+use crate::output::types::{ExecutableInfo, PathEntry, RequirementCheck};
+use std::cmp::Ordering;
+
+/// Comparison used by a version requirement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintOp {
+    Eq,
+    NotEq,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    /// Same leftmost non-zero component, and at least this version
+    Caret,
+    /// Same major (and minor, when given), and at least this version
+    Tilde,
+}
+
+impl ConstraintOp {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ConstraintOp::Eq => "=",
+            ConstraintOp::NotEq => "!=",
+            ConstraintOp::Greater => ">",
+            ConstraintOp::GreaterEq => ">=",
+            ConstraintOp::Less => "<",
+            ConstraintOp::LessEq => "<=",
+            ConstraintOp::Caret => "^",
+            ConstraintOp::Tilde => "~",
+        }
+    }
+}
+
+/// A constraint like `python>=3.10` or `node^20`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRequirement {
+    pub binary: String,
+    pub op: ConstraintOp,
+    pub version: String,
+}
+
+impl VersionRequirement {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let invalid = |reason: &str| Error::InvalidRequirement {
+            requirement: spec.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let op_start = spec
+            .find(|c| "<>=!^~".contains(c))
+            .ok_or_else(|| invalid("expected an operator such as >=, ^ or ~"))?;
+        let binary = spec[..op_start].trim();
+        if binary.is_empty() {
+            return Err(invalid("missing binary name"));
+        }
+
+        let rest = &spec[op_start..];
+        let operators = [
+            (">=", ConstraintOp::GreaterEq),
+            ("<=", ConstraintOp::LessEq),
+            ("==", ConstraintOp::Eq),
+            ("!=", ConstraintOp::NotEq),
+            (">", ConstraintOp::Greater),
+            ("<", ConstraintOp::Less),
+            ("=", ConstraintOp::Eq),
+            ("^", ConstraintOp::Caret),
+            ("~", ConstraintOp::Tilde),
+        ];
+        let (symbol, op) = operators
+            .iter()
+            .find(|(symbol, _)| rest.starts_with(symbol))
+            .ok_or_else(|| invalid("unknown operator"))?;
+
+        let version = rest[symbol.len()..].trim();
+        if version_components(version).is_empty() {
+            return Err(invalid("expected a numeric version"));
+        }
+
+        Ok(VersionRequirement {
+            binary: binary.to_string(),
+            op: *op,
+            version: version.to_string(),
+        })
+    }
+
+    /// A requirement from a separate binary name and constraint, as written
+    /// in config tables like `python = ">=3.10"`
+    pub fn for_binary(binary: &str, constraint: &str) -> Result<Self> {
+        Self::parse(&format!("{}{}", binary, constraint))
+    }
+
+    /// Whether `version` satisfies this requirement
+    pub fn matches(&self, version: &str) -> bool {
+        let actual = version_components(version);
+        let required = version_components(&self.version);
+        if actual.is_empty() {
+            return false;
+        }
+
+        let ordering = compare_components(&actual, &required);
+        // Components the requirement pins, e.g. `=3.11` accepts 3.11.4
+        let prefix_equal = |len: usize| {
+            actual
+                .iter()
+                .chain(std::iter::repeat(&0))
+                .take(len)
+                .eq(required.iter().take(len))
+        };
+
+        match self.op {
+            ConstraintOp::Eq => prefix_equal(required.len()),
+            ConstraintOp::NotEq => !prefix_equal(required.len()),
+            ConstraintOp::Greater => ordering == Ordering::Greater,
+            ConstraintOp::GreaterEq => ordering != Ordering::Less,
+            ConstraintOp::Less => ordering == Ordering::Less,
+            ConstraintOp::LessEq => ordering != Ordering::Greater,
+            ConstraintOp::Caret => {
+                let pinned = required
+                    .iter()
+                    .position(|&c| c != 0)
+                    .unwrap_or(required.len() - 1);
+                ordering != Ordering::Less && prefix_equal(pinned + 1)
+            }
+            ConstraintOp::Tilde => {
+                ordering != Ordering::Less && prefix_equal(required.len().min(2))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for VersionRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.binary, self.op.as_str(), self.version)
+    }
+}
+
+/// Leading numeric components of a version, e.g. `v17.0.2+8` -> [17, 0, 2]
+fn version_components(version: &str) -> Vec<u64> {
+    let mut components = Vec::new();
+    for part in version.trim().trim_start_matches('v').split('.') {
+        let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+        match digits.parse() {
+            Ok(n) => components.push(n),
+            Err(_) => break,
+        }
+        if digits.len() < part.len() {
+            break;
+        }
+    }
+    components
+}
+
+fn compare_components(a: &[u64], b: &[u64]) -> Ordering {
+    let len = a.len().max(b.len());
+    let padded = |v: &[u64], i: usize| v.get(i).copied().unwrap_or(0);
+    (0..len)
+        .map(|i| padded(a, i).cmp(&padded(b, i)))
+        .find(|o| *o != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+pub struct RequirementChecker {
+    version_extractor: VersionExtractor,
+}
+
+impl RequirementChecker {
+    pub fn new(version_extractor: VersionExtractor) -> Self {
+        RequirementChecker { version_extractor }
+    }
+
+    /// Check each requirement against the active instance of its binary,
+    /// extracting versions for the instances that lack one
+    pub fn check(
+        &self,
+        requirements: &[VersionRequirement],
+        path_entries: &mut [PathEntry],
+    ) -> Vec<RequirementCheck> {
+        requirements
+            .iter()
+            .map(|requirement| self.check_one(requirement, path_entries))
+            .collect()
+    }
+
+    fn check_one(
+        &self,
+        requirement: &VersionRequirement,
+        path_entries: &mut [PathEntry],
+    ) -> RequirementCheck {
+        let mut instances: Vec<&mut ExecutableInfo> = path_entries
+            .iter_mut()
+            .flat_map(|entry| entry.executables.iter_mut())
+            .filter(|exec| exec.name == requirement.binary)
+            .collect();
+        instances.sort_by_key(|exec| exec.path_order);
+
+        for exec in instances.iter_mut() {
+            if exec.version.is_none() {
+                self.version_extractor.extract_into(exec);
+            }
+        }
+
+        let version_of = |exec: &ExecutableInfo| {
+            exec.version
+                .as_ref()
+                .and_then(|v| v.parsed.clone().or_else(|| Some(v.raw.clone())))
+        };
+        let satisfies = |exec: &ExecutableInfo| {
+            version_of(exec).is_some_and(|version| requirement.matches(&version))
+        };
+
+        let mut check = RequirementCheck {
+            requirement: requirement.to_string(),
+            binary_name: requirement.binary.clone(),
+            satisfied: false,
+            active_path: None,
+            active_version: None,
+            satisfying_paths: Vec::new(),
+            message: String::new(),
+        };
+
+        let active = match instances.first() {
+            Some(active) => active,
+            None => {
+                check.message =
+                    format!("{}: {} not found in PATH", requirement, requirement.binary);
+                return check;
+            }
+        };
+
+        check.active_path = Some(active.full_path.clone());
+        check.active_version = version_of(active);
+        check.satisfied = satisfies(active);
+        check.satisfying_paths = instances
+            .iter()
+            .skip(1)
+            .filter(|exec| satisfies(exec))
+            .map(|exec| exec.full_path.clone())
+            .collect();
+
+        check.message = match (&check.active_version, check.satisfied) {
+            (Some(version), true) => format!(
+                "{}: {} is {}",
+                requirement,
+                active.full_path.display(),
+                version
+            ),
+            (Some(version), false) => format!(
+                "{}: active {} is {}",
+                requirement,
+                active.full_path.display(),
+                version
+            ),
+            (None, _) => format!(
+                "{}: could not determine the version of {}",
+                requirement,
+                active.full_path.display()
+            ),
+        };
+
+        check
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::types::VersionInfo;
+    use std::path::PathBuf;
+
+    fn entry_with_version(dir: &str, order: usize, version: &str) -> PathEntry {
+        let full_path = PathBuf::from(dir).join("python");
+        PathEntry {
+            path: PathBuf::from(dir),
+            order,
+            exists: true,
+            is_accessible: true,
+            sources: Vec::new(),
+            scope: None,
+            executables: vec![ExecutableInfo {
+                name: "python".to_string(),
+                full_path: full_path.clone(),
+                size: 1000,
+                resolved_path: full_path,
+                version: Some(VersionInfo {
+                    raw: version.to_string(),
+                    parsed: Some(version.to_string()),
+                    extraction_method: "command execution".to_string(),
+                }),
+                path_order: order,
+                ..ExecutableInfo::fixture()
+            }],
+        }
+    }
+
+    #[test]
+    fn test_check_reports_satisfying_instances() {
+        let checker = RequirementChecker::new(VersionExtractor::new());
+        let mut entries = vec![
+            entry_with_version("/usr/bin", 0, "3.8.10"),
+            entry_with_version("/opt/py/bin", 1, "3.12.1"),
+        ];
+        let requirements = vec![
+            VersionRequirement::parse("python>=3.10").unwrap(),
+            VersionRequirement::parse("ruby>=3").unwrap(),
+        ];
+
+        let checks = checker.check(&requirements, &mut entries);
+
+        assert!(!checks[0].satisfied);
+        assert_eq!(
+            checks[0].active_path,
+            Some(PathBuf::from("/usr/bin/python"))
+        );
+        assert_eq!(checks[0].active_version.as_deref(), Some("3.8.10"));
+        assert_eq!(
+            checks[0].satisfying_paths,
+            vec![PathBuf::from("/opt/py/bin/python")]
+        );
+
+        assert!(!checks[1].satisfied);
+        assert!(checks[1].active_path.is_none());
+        assert!(checks[1].message.contains("not found"));
+    }
+
+    #[test]
+    fn test_parse_requirement() {
+        let req = VersionRequirement::parse("python>=3.10").unwrap();
+        assert_eq!(req.binary, "python");
+        assert_eq!(req.op, ConstraintOp::GreaterEq);
+        assert_eq!(req.version, "3.10");
+        assert_eq!(req.to_string(), "python>=3.10");
+
+        assert_eq!(
+            VersionRequirement::parse("node^20").unwrap().op,
+            ConstraintOp::Caret
+        );
+        assert!(VersionRequirement::parse("python").is_err());
+        assert!(VersionRequirement::parse(">=3").is_err());
+        assert!(VersionRequirement::parse("go>=latest").is_err());
+    }
+
+    #[test]
+    fn test_requirement_matches() {
+        let check =
+            |spec: &str, version: &str| VersionRequirement::parse(spec).unwrap().matches(version);
+
+        assert!(check("python>=3.10", "3.12.1"));
+        assert!(!check("python>=3.10", "3.9.18"));
+        assert!(check("python>3.10", "3.10.1"));
+        assert!(!check("python<3.10", "3.10"));
+        assert!(check("python=3.11", "3.11.4"));
+        assert!(!check("python!=3.11", "3.11.4"));
+        assert!(check("node^20", "v20.11.0"));
+        assert!(!check("node^20", "21.0.0"));
+        assert!(!check("tool^0.4", "0.5.0"));
+        assert!(check("tool~1.2", "1.2.9"));
+        assert!(!check("tool~1.2", "1.3.0"));
+        assert!(check("java>=17", "17.0.2+8"));
+        assert!(!check("java>=17", "unknown"));
+    }
+}
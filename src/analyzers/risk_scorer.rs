@@ -0,0 +1,222 @@
+use crate::output::types::{Conflict, ConflictCategory, ExecutableInfo};
+use std::collections::HashSet;
+
+/// Largest product of the multiplicative factors, used to scale scores
+/// into 0-100
+const MAX_FACTOR: f64 = 1.5 * 1.4 * 1.5;
+
+/// Scores conflicts on a 0-100 scale so they can be tracked over time
+///
+/// The score is `category weight × version delta × manager mix × path
+/// position`, scaled so the worst possible conflict scores 100.
+pub struct RiskScorer;
+
+impl RiskScorer {
+    pub fn new() -> Self {
+        RiskScorer
+    }
+
+    pub fn score(
+        &self,
+        category: ConflictCategory,
+        instances: &[ExecutableInfo],
+        same_content: bool,
+    ) -> f64 {
+        let active = match instances.iter().find(|i| !i.broken).or(instances.first()) {
+            Some(active) => active,
+            None => return 0.0,
+        };
+
+        let raw = self.category_weight(category)
+            * self.version_delta(instances)
+            * self.manager_mix(instances)
+            * self.path_position(active);
+        let mut score = (raw / MAX_FACTOR * 100.0).min(100.0);
+
+        // Identical copies cannot change behaviour
+        if same_content {
+            score *= 0.1;
+        }
+
+        round1(score)
+    }
+
+    /// Overall PATH score: combines conflict scores like independent
+    /// probabilities, so it grows with each conflict but never exceeds 100
+    pub fn path_score(&self, conflicts: &[Conflict]) -> f64 {
+        let safe = conflicts
+            .iter()
+            .map(|c| 1.0 - c.risk_score / 100.0)
+            .product::<f64>();
+        round1((1.0 - safe) * 100.0)
+    }
+
+    fn category_weight(&self, category: ConflictCategory) -> f64 {
+        match category {
+            ConflictCategory::WslVsWindows => 1.0,
+            ConflictCategory::VersionManagerVsSystem => 0.8,
+            ConflictCategory::MultipleVersionManagers => 0.7,
+            ConflictCategory::DuplicateVersions => 0.6,
+            ConflictCategory::PackageManagerVsSystem => 0.4,
+            ConflictCategory::ShadowedBinary => 0.3,
+            ConflictCategory::PathextShadowing => 0.5,
+            ConflictCategory::PipMismatch => 0.7,
+            ConflictCategory::BrokenSymlink => 0.2,
+            ConflictCategory::Other => 0.2,
+        }
+    }
+
+    /// 1.5 for a major version difference, 1.2 for a minor one, 0.5 when
+    /// every known version matches, 1.0 when versions are unknown
+    fn version_delta(&self, instances: &[ExecutableInfo]) -> f64 {
+        let versions: Vec<Vec<u64>> = instances
+            .iter()
+            .filter_map(|i| i.version.as_ref())
+            .map(|v| numeric_components(v.parsed.as_deref().unwrap_or(&v.raw)))
+            .filter(|c| !c.is_empty())
+            .collect();
+
+        if versions.len() < 2 {
+            return 1.0;
+        }
+
+        let differs_at = |len: usize| {
+            versions
+                .iter()
+                .any(|v| v.iter().take(len).ne(versions[0].iter().take(len)))
+        };
+
+        if differs_at(1) {
+            1.5
+        } else if differs_at(2) {
+            1.2
+        } else if differs_at(usize::MAX) {
+            1.0
+        } else {
+            0.5
+        }
+    }
+
+    /// More distinct owners means more ways for the active one to change
+    fn manager_mix(&self, instances: &[ExecutableInfo]) -> f64 {
+        let managers: HashSet<Option<&str>> = instances
+            .iter()
+            .map(|i| i.manager.as_ref().map(|m| m.name.as_str()))
+            .collect();
+
+        match managers.len() {
+            0 | 1 => 1.0,
+            2 => 1.2,
+            _ => 1.4,
+        }
+    }
+
+    /// Entries early in PATH are usually prepended by shell startup files
+    /// and override everything after them
+    fn path_position(&self, active: &ExecutableInfo) -> f64 {
+        1.0 + 0.5 / (1.0 + active.path_order as f64)
+    }
+}
+
+impl Default for RiskScorer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn numeric_components(version: &str) -> Vec<u64> {
+    version
+        .trim_start_matches('v')
+        .split('.')
+        .map_while(|part| {
+            let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+            digits.parse().ok()
+        })
+        .collect()
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::types::{ManagerInfo, ManagerType, VersionInfo};
+    use std::path::PathBuf;
+
+    fn instance(order: usize, version: Option<&str>, manager: Option<&str>) -> ExecutableInfo {
+        let full_path = PathBuf::from(format!("/opt/{}/bin/python", order));
+        ExecutableInfo {
+            name: "python".to_string(),
+            full_path: full_path.clone(),
+            size: 1000,
+            resolved_path: full_path,
+            version: version.map(|v| VersionInfo {
+                raw: v.to_string(),
+                parsed: Some(v.to_string()),
+                extraction_method: "command execution".to_string(),
+            }),
+            manager: manager.map(|name| ManagerInfo {
+                manager_type: ManagerType::VersionManager,
+                name: name.to_string(),
+                description: String::new(),
+            }),
+            path_order: order,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    #[test]
+    fn test_worst_case_scores_100() {
+        let scorer = RiskScorer::new();
+        let instances = vec![
+            instance(0, Some("3.12.1"), Some("pyenv")),
+            instance(1, Some("2.7.18"), Some("asdf")),
+            instance(2, None, None),
+        ];
+        assert_eq!(
+            scorer.score(ConflictCategory::WslVsWindows, &instances, false),
+            100.0
+        );
+    }
+
+    #[test]
+    fn test_factors_lower_the_score() {
+        let scorer = RiskScorer::new();
+        let major = vec![
+            instance(0, Some("3.12"), None),
+            instance(1, Some("2.7"), None),
+        ];
+        let same = vec![
+            instance(0, Some("3.12"), None),
+            instance(1, Some("3.12"), None),
+        ];
+        let late = vec![
+            instance(5, Some("3.12"), None),
+            instance(6, Some("3.12"), None),
+        ];
+
+        let category = ConflictCategory::DuplicateVersions;
+        assert!(scorer.score(category, &major, false) > scorer.score(category, &same, false));
+        assert!(scorer.score(category, &same, false) > scorer.score(category, &late, false));
+        assert!(scorer.score(category, &major, true) < scorer.score(category, &major, false));
+        assert!(
+            scorer.score(ConflictCategory::ShadowedBinary, &major, false)
+                < scorer.score(category, &major, false)
+        );
+    }
+
+    #[test]
+    fn test_version_delta() {
+        let scorer = RiskScorer::new();
+        let delta = |a: &str, b: &str| {
+            scorer.version_delta(&[instance(0, Some(a), None), instance(1, Some(b), None)])
+        };
+        assert_eq!(delta("3.12.1", "2.7.18"), 1.5);
+        assert_eq!(delta("3.12.1", "3.11.0"), 1.2);
+        assert_eq!(delta("3.12.1", "3.12.2"), 1.0);
+        assert_eq!(delta("3.12.1", "3.12.1"), 0.5);
+        assert_eq!(scorer.version_delta(&[instance(0, None, None)]), 1.0);
+    }
+}
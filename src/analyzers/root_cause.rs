@@ -0,0 +1,177 @@
+use crate::output::types::{Conflict, RootCause, RootCauseAction};
+use crate::platform::wsl;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Fewest conflicts a pair of directories must account for to be reported
+const MIN_CONFLICTS: usize = 3;
+
+/// Clusters conflicts caused by the same pair of PATH entries, so one
+/// mis-ordered directory shows up as a single fix rather than dozens of
+/// conflicts
+pub struct RootCauseAnalyzer;
+
+impl RootCauseAnalyzer {
+    pub fn new() -> Self {
+        RootCauseAnalyzer
+    }
+
+    /// Only conflicts between exactly two directories are clustered; with
+    /// more, no single entry change resolves them
+    pub fn analyze(&self, conflicts: &[Conflict]) -> Vec<RootCause> {
+        let mut by_pair: BTreeMap<(&Path, &Path), Vec<&Conflict>> = BTreeMap::new();
+        for conflict in conflicts {
+            if let Some(pair) = directory_pair(conflict) {
+                by_pair.entry(pair).or_default().push(conflict);
+            }
+        }
+
+        let mut root_causes: Vec<RootCause> = by_pair
+            .into_iter()
+            .filter(|(_, conflicts)| conflicts.len() >= MIN_CONFLICTS)
+            .map(|((active, shadowed), conflicts)| self.root_cause(active, shadowed, &conflicts))
+            .collect();
+
+        root_causes.sort_by_key(|c| std::cmp::Reverse(c.binaries.len()));
+        root_causes
+    }
+
+    fn root_cause(&self, active: &Path, shadowed: &Path, conflicts: &[&Conflict]) -> RootCause {
+        let binaries: Vec<String> = conflicts.iter().map(|c| c.binary_name.clone()).collect();
+
+        // Windows directories appended to a WSL PATH are the usual culprit;
+        // otherwise blame the earlier entry, which does the shadowing
+        let (directory, other_directory, action) =
+            if wsl::is_windows_path_in_wsl(shadowed) && !wsl::is_windows_path_in_wsl(active) {
+                (shadowed, active, RootCauseAction::Remove)
+            } else {
+                (active, shadowed, RootCauseAction::MoveAfter)
+            };
+
+        let message = match action {
+            RootCauseAction::Remove => format!(
+                "Remove {} from PATH to fix {} conflicts",
+                directory.display(),
+                binaries.len()
+            ),
+            RootCauseAction::MoveAfter => format!(
+                "{} shadows {} for {} binaries: move it later in PATH, or remove one of the two",
+                directory.display(),
+                other_directory.display(),
+                binaries.len()
+            ),
+        };
+
+        RootCause {
+            directory: directory.to_path_buf(),
+            other_directory: other_directory.to_path_buf(),
+            action,
+            binaries,
+            message,
+        }
+    }
+}
+
+impl Default for RootCauseAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// (active directory, shadowed directory) when the conflict spans exactly two
+fn directory_pair(conflict: &Conflict) -> Option<(&Path, &Path)> {
+    let active = conflict.active_instance.full_path.parent()?;
+    let mut shadowed = conflict
+        .instances
+        .iter()
+        .filter_map(|i| i.full_path.parent())
+        .filter(|dir| *dir != active);
+
+    let first = shadowed.next()?;
+    if shadowed.all(|dir| dir == first) {
+        Some((active, first))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::types::{ConflictCategory, ExecutableInfo, Severity};
+    use std::path::PathBuf;
+
+    fn instance(dir: &str, name: &str, order: usize) -> ExecutableInfo {
+        let full_path = PathBuf::from(dir).join(name);
+        ExecutableInfo {
+            name: name.to_string(),
+            full_path: full_path.clone(),
+            size: 1000,
+            resolved_path: full_path,
+            path_order: order,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    fn conflict(name: &str, dirs: &[&str]) -> Conflict {
+        let instances: Vec<_> = dirs
+            .iter()
+            .enumerate()
+            .map(|(order, dir)| instance(dir, name, order))
+            .collect();
+        Conflict {
+            binary_name: name.to_string(),
+            active_instance: instances[0].clone(),
+            instances,
+            category: ConflictCategory::ShadowedBinary,
+            severity: Severity::Medium,
+            severity_reason: String::new(),
+            description: String::new(),
+            recommendation: None,
+            commands: Vec::new(),
+            same_content: false,
+            risk_score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_windows_directory_in_wsl_is_removed() {
+        let windows = "/mnt/c/Windows/System32";
+        let conflicts = vec![
+            conflict("ssh", &["/usr/bin", windows]),
+            conflict("curl", &["/usr/bin", windows]),
+            conflict("tar", &["/usr/bin", windows]),
+            conflict("python", &["/usr/local/bin", "/usr/bin"]),
+        ];
+
+        let causes = RootCauseAnalyzer::new().analyze(&conflicts);
+
+        assert_eq!(causes.len(), 1);
+        assert_eq!(causes[0].directory, PathBuf::from(windows));
+        assert_eq!(causes[0].action, RootCauseAction::Remove);
+        assert_eq!(causes[0].binaries, vec!["ssh", "curl", "tar"]);
+    }
+
+    #[test]
+    fn test_prepended_directory_is_moved() {
+        let conflicts: Vec<_> = ["python", "pip", "openssl"]
+            .iter()
+            .map(|name| conflict(name, &["/opt/anaconda3/bin", "/usr/bin"]))
+            .collect();
+
+        let causes = RootCauseAnalyzer::new().analyze(&conflicts);
+
+        assert_eq!(causes[0].directory, PathBuf::from("/opt/anaconda3/bin"));
+        assert_eq!(causes[0].other_directory, PathBuf::from("/usr/bin"));
+        assert_eq!(causes[0].action, RootCauseAction::MoveAfter);
+    }
+
+    #[test]
+    fn test_three_way_conflicts_have_no_single_cause() {
+        let conflicts: Vec<_> = ["a", "b", "c"]
+            .iter()
+            .map(|name| conflict(name, &["/x", "/y", "/z"]))
+            .collect();
+        assert!(RootCauseAnalyzer::new().analyze(&conflicts).is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+use crate::analyzers::VersionRequirement;
+use crate::config::RuleConfig;
+use crate::error::{Error, Result};
+use crate::output::types::{ConflictCategory, ExecutableInfo, Severity};
+use globset::{Glob, GlobMatcher};
+
+/// A user-defined policy from the config file's `[[rules]]` array. Every
+/// condition that is set must hold; matching rules then override the
+/// conflict's category, severity and recommendation (later rules win).
+#[derive(Debug, Clone)]
+pub struct ConflictRule {
+    pub name: String,
+    binary: Option<GlobMatcher>,
+    active_path: Option<GlobMatcher>,
+    shadowed_path: Option<GlobMatcher>,
+    manager: Option<String>,
+    active_version: Option<VersionRequirement>,
+    pub category: Option<ConflictCategory>,
+    pub severity: Option<Severity>,
+    pub recommendation: Option<String>,
+}
+
+impl ConflictRule {
+    pub fn from_config(config: &RuleConfig) -> Result<Self> {
+        let name = config
+            .name
+            .clone()
+            .or_else(|| config.binary.clone())
+            .unwrap_or_else(|| "unnamed".to_string());
+        let invalid = |reason: String| Error::InvalidRule {
+            rule: name.clone(),
+            reason,
+        };
+
+        let glob = |pattern: &Option<String>| -> Result<Option<GlobMatcher>> {
+            pattern
+                .as_ref()
+                .map(|p| {
+                    Glob::new(p)
+                        .map(|g| g.compile_matcher())
+                        .map_err(|e| invalid(e.to_string()))
+                })
+                .transpose()
+        };
+
+        let active_version = match &config.active_version {
+            Some(constraint) => Some(
+                VersionRequirement::for_binary(config.binary.as_deref().unwrap_or("*"), constraint)
+                    .map_err(|e| invalid(e.to_string()))?,
+            ),
+            None => None,
+        };
+
+        Ok(ConflictRule {
+            binary: glob(&config.binary)?,
+            active_path: glob(&config.active_path)?,
+            shadowed_path: glob(&config.shadowed_path)?,
+            manager: config.manager.clone(),
+            active_version,
+            category: config
+                .category
+                .as_deref()
+                .map(str::parse)
+                .transpose()
+                .map_err(invalid)?,
+            severity: config
+                .severity
+                .as_deref()
+                .map(str::parse)
+                .transpose()
+                .map_err(invalid)?,
+            recommendation: config.recommendation.clone(),
+            name,
+        })
+    }
+
+    /// `instances` are sorted by PATH order, active first
+    pub fn matches(&self, binary_name: &str, instances: &[ExecutableInfo]) -> bool {
+        let (active, shadowed) = match instances.split_first() {
+            Some(split) => split,
+            None => return false,
+        };
+
+        if let Some(binary) = &self.binary {
+            if !binary.is_match(binary_name) {
+                return false;
+            }
+        }
+
+        if let Some(active_path) = &self.active_path {
+            if !active_path.is_match(&active.full_path) {
+                return false;
+            }
+        }
+
+        if let Some(shadowed_path) = &self.shadowed_path {
+            if !shadowed
+                .iter()
+                .any(|i| shadowed_path.is_match(&i.full_path))
+            {
+                return false;
+            }
+        }
+
+        if let Some(manager) = &self.manager {
+            let managed = instances.iter().any(|i| {
+                i.manager
+                    .as_ref()
+                    .is_some_and(|m| m.name.eq_ignore_ascii_case(manager))
+            });
+            if !managed {
+                return false;
+            }
+        }
+
+        if let Some(requirement) = &self.active_version {
+            let version = active
+                .version
+                .as_ref()
+                .map(|v| v.parsed.as_deref().unwrap_or(&v.raw));
+            if !version.is_some_and(|v| requirement.matches(v)) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn instance(path: &str, order: usize) -> ExecutableInfo {
+        ExecutableInfo {
+            name: "docker".to_string(),
+            full_path: PathBuf::from(path),
+            size: 1000,
+            resolved_path: PathBuf::from(path),
+            path_order: order,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    fn docker_desktop_rule() -> ConflictRule {
+        ConflictRule::from_config(&RuleConfig {
+            name: Some("docker-desktop".to_string()),
+            binary: Some("docker".to_string()),
+            active_path: Some("/usr/local/bin/*".to_string()),
+            shadowed_path: Some("/Applications/Docker.app/**".to_string()),
+            severity: Some("critical".to_string()),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rule_matches_policy() {
+        let rule = docker_desktop_rule();
+        assert_eq!(rule.severity, Some(Severity::Critical));
+
+        let shadowing = vec![
+            instance("/usr/local/bin/docker", 0),
+            instance("/Applications/Docker.app/Contents/Resources/bin/docker", 1),
+        ];
+        assert!(rule.matches("docker", &shadowing));
+
+        let reversed: Vec<_> = shadowing.into_iter().rev().collect();
+        assert!(!rule.matches("docker", &reversed));
+        assert!(!rule.matches("podman", &reversed));
+    }
+
+    #[test]
+    fn test_invalid_rule_rejected() {
+        let config = RuleConfig {
+            name: Some("bad".to_string()),
+            severity: Some("urgent".to_string()),
+            ..Default::default()
+        };
+        let err = ConflictRule::from_config(&config).unwrap_err();
+        assert!(err.to_string().contains("unknown severity"));
+    }
+}
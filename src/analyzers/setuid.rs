@@ -0,0 +1,100 @@
+use crate::output::types::{ExecutableInfo, PathEntry, SecurityFinding, SecurityKind, Severity};
+use crate::platform;
+
+/// Flags setuid and setgid executables outside /bin, /usr/bin and their sbin
+/// siblings. The OS ships its privileged helpers there; one anywhere else
+/// was installed by hand and is worth an audit.
+pub struct SetuidAnalyzer;
+
+impl SetuidAnalyzer {
+    pub fn new() -> Self {
+        SetuidAnalyzer
+    }
+
+    pub fn analyze(&self, path_entries: &[PathEntry]) -> Vec<SecurityFinding> {
+        path_entries
+            .iter()
+            .flat_map(|entry| &entry.executables)
+            .filter_map(|exec| Some((exec, exec.ownership.as_ref()?)))
+            .filter(|(exec, ownership)| {
+                (ownership.setuid() || ownership.setgid()) && !in_system_dir(exec)
+            })
+            .map(|(exec, ownership)| {
+                let (severity, bit, owner) = if ownership.setuid() {
+                    (Severity::High, "setuid", ownership.user.clone())
+                } else {
+                    (Severity::Medium, "setgid", ownership.group.clone())
+                };
+                let owner = owner.map(|o| format!(" {}", o)).unwrap_or_default();
+                SecurityFinding {
+                    kind: SecurityKind::SetuidBinary,
+                    severity,
+                    message: format!(
+                        "{} is {}{} outside the system directories; check where it came from",
+                        exec.full_path.display(),
+                        bit,
+                        owner
+                    ),
+                    paths: vec![exec.full_path.clone()],
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for SetuidAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A link into /usr/bin is the system's own binary
+fn in_system_dir(exec: &ExecutableInfo) -> bool {
+    [&exec.full_path, &exec.resolved_path].iter().any(|path| {
+        path.parent()
+            .map(platform::is_system_bin_dir)
+            .unwrap_or(false)
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::core::ExecutableScanner;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_setuid_outside_system_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, mode) in [("su-helper", 0o4755), ("mailer", 0o2755), ("plain", 0o755)] {
+            let path = dir.path().join(name);
+            std::fs::write(&path, "").unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+        }
+
+        let mut executables = ExecutableScanner::new()
+            .scan_directory(&dir.path().to_path_buf(), 0)
+            .unwrap();
+        executables.sort_by(|a, b| a.name.cmp(&b.name));
+        let flags: Vec<(bool, bool)> = executables
+            .iter()
+            .map(|e| e.ownership.as_ref().unwrap())
+            .map(|o| (o.setuid(), o.setgid()))
+            .collect();
+        assert_eq!(flags, vec![(false, true), (false, false), (true, false)]);
+
+        let entries = vec![PathEntry {
+            path: dir.path().to_path_buf(),
+            order: 0,
+            exists: true,
+            is_accessible: true,
+            executables,
+            sources: Vec::new(),
+            scope: None,
+        }];
+        let findings = SetuidAnalyzer::new().analyze(&entries);
+        let severities: Vec<Severity> = findings.iter().map(|f| f.severity).collect();
+        assert_eq!(severities, vec![Severity::Medium, Severity::High]);
+        assert!(findings[1].message.contains("su-helper is setuid"));
+    }
+}
@@ -0,0 +1,272 @@
+use crate::output::types::{Diagnostic, DiagnosticKind, ExecutableInfo, PathEntry, Severity};
+use std::path::{Path, PathBuf};
+
+/// Version managers whose shim directories can be checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ShimManager {
+    Pyenv,
+    Rbenv,
+    Asdf,
+    Scoop,
+}
+
+impl ShimManager {
+    /// Recognize `<root>/shims` by the name of the root directory
+    fn for_shims_dir(dir: &Path) -> Option<Self> {
+        if dir.file_name()? != "shims" {
+            return None;
+        }
+        match dir.parent()?.file_name()?.to_str()? {
+            ".pyenv" | "pyenv" => Some(ShimManager::Pyenv),
+            ".rbenv" | "rbenv" => Some(ShimManager::Rbenv),
+            ".asdf" | "asdf" => Some(ShimManager::Asdf),
+            "scoop" => Some(ShimManager::Scoop),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ShimManager::Pyenv => "pyenv",
+            ShimManager::Rbenv => "rbenv",
+            ShimManager::Asdf => "asdf",
+            ShimManager::Scoop => "Scoop",
+        }
+    }
+}
+
+/// Finds version-manager shims whose target was uninstalled. Such a shim
+/// still wins PATH lookup and only fails once it runs.
+pub struct ShimAnalyzer;
+
+impl ShimAnalyzer {
+    pub fn new() -> Self {
+        ShimAnalyzer
+    }
+
+    pub fn analyze(&self, path_entries: &[PathEntry]) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for (idx, entry) in path_entries.iter().enumerate() {
+            let Some(manager) = ShimManager::for_shims_dir(&entry.path) else {
+                continue;
+            };
+            let root = entry.path.parent().unwrap_or(&entry.path);
+
+            for shim in &entry.executables {
+                let Some((reason, fix)) = orphaned(manager, root, shim) else {
+                    continue;
+                };
+
+                // Only a shim that wins lookup breaks the command
+                let active = !path_entries[..idx]
+                    .iter()
+                    .any(|e| e.executables.iter().any(|x| x.name == shim.name));
+                let consequence = if active {
+                    format!("running `{}` will fail", shim.name)
+                } else {
+                    "it is shadowed by an earlier entry for now".to_string()
+                };
+
+                diagnostics.push(Diagnostic {
+                    kind: DiagnosticKind::OrphanedShim,
+                    severity: if active {
+                        Severity::Medium
+                    } else {
+                        Severity::Low
+                    },
+                    message: format!(
+                        "{} is a {} shim but {}, so {}. {}",
+                        shim.full_path.display(),
+                        manager.name(),
+                        reason,
+                        consequence,
+                        fix
+                    ),
+                    paths: vec![shim.full_path.clone()],
+                });
+            }
+        }
+
+        diagnostics
+    }
+}
+
+impl Default for ShimAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Why the shim has nothing left to run, and how to clean it up; `None`
+/// when its target still exists or cannot be determined
+fn orphaned(manager: ShimManager, root: &Path, shim: &ExecutableInfo) -> Option<(String, String)> {
+    match manager {
+        ShimManager::Pyenv | ShimManager::Rbenv => {
+            let versions = root.join("versions");
+            let provided = std::fs::read_dir(&versions)
+                .map(|entries| {
+                    entries
+                        .filter_map(|entry| entry.ok())
+                        .any(|entry| entry.path().join("bin").join(&shim.name).exists())
+                })
+                .unwrap_or(false);
+            (!provided).then(|| {
+                (
+                    format!(
+                        "no installed version under {} provides {}",
+                        versions.display(),
+                        shim.name
+                    ),
+                    format!(
+                        "Run `{} rehash` to drop stale shims, or reinstall the version that \
+                         provided it",
+                        manager.name()
+                    ),
+                )
+            })
+        }
+        ShimManager::Asdf => {
+            // Shims list the installs they dispatch to as
+            // `# asdf-plugin: <plugin> <version>`
+            let contents = std::fs::read_to_string(&shim.full_path).ok()?;
+            let installs: Vec<(&str, &str)> = contents
+                .lines()
+                .filter_map(|line| line.strip_prefix("# asdf-plugin: "))
+                .filter_map(|rest| rest.trim().split_once(' '))
+                .collect();
+            let (plugin, _) = *installs.first()?;
+            let installed = installs
+                .iter()
+                .any(|(plugin, version)| root.join("installs").join(plugin).join(version).is_dir());
+            (!installed).then(|| {
+                let listed: Vec<String> = installs
+                    .iter()
+                    .map(|(plugin, version)| format!("{} {}", plugin, version))
+                    .collect();
+                (
+                    format!("{} is no longer installed", listed.join(", ")),
+                    format!(
+                        "Run `asdf reshim {}` to regenerate its shims, or `asdf install {}` \
+                         to restore the version",
+                        plugin, plugin
+                    ),
+                )
+            })
+        }
+        ShimManager::Scoop => {
+            // shims/<name>.exe reads its target from shims/<name>.shim
+            let shim_file = shim.full_path.with_extension("shim");
+            let contents = std::fs::read_to_string(&shim_file).ok()?;
+            let target = contents.lines().find_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                (key.trim() == "path").then(|| PathBuf::from(value.trim().trim_matches('"')))
+            })?;
+            if target.exists() {
+                return None;
+            }
+            let fix = match scoop_app(&target) {
+                Some(app) => format!(
+                    "Run `scoop reset {}` to relink it, or `scoop uninstall {}`",
+                    app, app
+                ),
+                None => format!(
+                    "Delete {} and {}",
+                    shim.full_path.display(),
+                    shim_file.display()
+                ),
+            };
+            Some((
+                format!("its target {} no longer exists", target.display()),
+                fix,
+            ))
+        }
+    }
+}
+
+/// The app in a Scoop path like `...\scoop\apps\<app>\current\bin\x.exe`
+fn scoop_app(target: &Path) -> Option<String> {
+    let text = target.to_string_lossy().replace('\\', "/");
+    let mut parts = text.split('/').skip_while(|part| *part != "apps");
+    parts.next()?;
+    parts.next().map(str::to_string)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn shim(path: &Path) -> ExecutableInfo {
+        ExecutableInfo {
+            name: path.file_name().unwrap().to_string_lossy().into(),
+            full_path: path.to_path_buf(),
+            size: 100,
+            resolved_path: path.to_path_buf(),
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    fn entry(path: &Path, order: usize, shims: &[&str]) -> PathEntry {
+        fs::create_dir_all(path).unwrap();
+        PathEntry {
+            path: path.to_path_buf(),
+            order,
+            exists: true,
+            is_accessible: true,
+            executables: shims.iter().map(|name| shim(&path.join(name))).collect(),
+            sources: Vec::new(),
+            scope: None,
+        }
+    }
+
+    #[test]
+    fn test_pyenv_and_asdf_shims() {
+        let dir = tempfile::tempdir().unwrap();
+        let pyenv = dir.path().join(".pyenv");
+        fs::create_dir_all(pyenv.join("versions/3.12.1/bin")).unwrap();
+        fs::write(pyenv.join("versions/3.12.1/bin/python3"), "").unwrap();
+
+        let asdf = dir.path().join(".asdf");
+        fs::create_dir_all(asdf.join("shims")).unwrap();
+        fs::create_dir_all(asdf.join("installs/nodejs/20.11.0")).unwrap();
+        fs::write(
+            asdf.join("shims/node"),
+            "#!/usr/bin/env bash\n# asdf-plugin: nodejs 20.11.0\nexec asdf exec node\n",
+        )
+        .unwrap();
+        fs::write(
+            asdf.join("shims/deno"),
+            "#!/usr/bin/env bash\n# asdf-plugin: deno 1.40.0\nexec asdf exec deno\n",
+        )
+        .unwrap();
+
+        let entries = vec![
+            entry(&dir.path().join("bin"), 0, &["python2.7"]),
+            entry(&pyenv.join("shims"), 1, &["python3", "python2.7"]),
+            entry(&asdf.join("shims"), 2, &["node", "deno"]),
+        ];
+        let diagnostics = ShimAnalyzer::new().analyze(&entries);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics[0].message.contains("pyenv rehash"));
+        // An earlier python2.7 still wins, so the stale shim is harmless
+        assert_eq!(diagnostics[0].severity, Severity::Low);
+        assert!(diagnostics[1]
+            .message
+            .contains("deno 1.40.0 is no longer installed"));
+        assert!(diagnostics[1].message.contains("asdf reshim deno"));
+        assert_eq!(diagnostics[1].severity, Severity::Medium);
+    }
+
+    #[test]
+    fn test_scoop_app() {
+        assert_eq!(
+            scoop_app(Path::new(
+                "C:\\Users\\me\\scoop\\apps\\nodejs\\current\\node.exe"
+            )),
+            Some("nodejs".to_string())
+        );
+        assert_eq!(scoop_app(Path::new("C:\\tools\\node.exe")), None);
+    }
+}
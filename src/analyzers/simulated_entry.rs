@@ -0,0 +1,168 @@
+use crate::output::types::{Conflict, Diagnostic, DiagnosticKind, Severity};
+use std::path::Path;
+
+/// Reports what a directory added with `--simulate-prepend` or
+/// `--simulate-append` would change: the commands it takes over and the
+/// ones of its own that earlier entries would still hide
+pub struct SimulatedEntryAnalyzer;
+
+impl SimulatedEntryAnalyzer {
+    pub fn new() -> Self {
+        SimulatedEntryAnalyzer
+    }
+
+    /// `action` describes the edit, e.g. "prepend ~/.local/bin"; `dir` is
+    /// the directory as the scanner saw it
+    pub fn analyze(&self, action: &str, dir: &Path, conflicts: &[Conflict]) -> Diagnostic {
+        let mut shadows = Vec::new();
+        let mut hidden = Vec::new();
+        for conflict in conflicts {
+            let Some(own) = conflict
+                .instances
+                .iter()
+                .find(|i| i.full_path.parent() == Some(dir))
+            else {
+                continue;
+            };
+
+            if conflict.active_instance.full_path == own.full_path {
+                // The copy that used to run is the next one outside `dir`
+                if let Some(previous) = conflict
+                    .instances
+                    .iter()
+                    .find(|i| i.full_path.parent() != Some(dir))
+                {
+                    shadows.push((conflict.binary_name.as_str(), previous.full_path.as_path()));
+                }
+            } else {
+                hidden.push((
+                    conflict.binary_name.as_str(),
+                    conflict.active_instance.full_path.as_path(),
+                ));
+            }
+        }
+
+        let list = |entries: &[(&str, &Path)]| {
+            entries
+                .iter()
+                .map(|(name, path)| format!("{} ({})", name, path.display()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let mut message = if shadows.is_empty() {
+            format!("Simulated {} would shadow no existing command", action)
+        } else {
+            format!(
+                "Simulated {} would shadow {} existing command(s): {}",
+                action,
+                shadows.len(),
+                list(&shadows)
+            )
+        };
+        if !hidden.is_empty() {
+            message.push_str(&format!(
+                "; {} of its command(s) would stay hidden behind earlier entries: {}",
+                hidden.len(),
+                list(&hidden)
+            ));
+        }
+
+        Diagnostic {
+            kind: DiagnosticKind::SimulatedEntry,
+            severity: if shadows.is_empty() {
+                Severity::Info
+            } else {
+                Severity::Low
+            },
+            message,
+            paths: shadows.iter().map(|(_, path)| path.to_path_buf()).collect(),
+        }
+    }
+}
+
+impl Default for SimulatedEntryAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::output::types::{ConflictCategory, ExecutableInfo};
+    use std::path::PathBuf;
+
+    fn instance(path: &str, order: usize) -> ExecutableInfo {
+        ExecutableInfo {
+            name: Path::new(path)
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .into(),
+            full_path: PathBuf::from(path),
+            size: 1000,
+            resolved_path: PathBuf::from(path),
+            path_order: order,
+            is_active: order == 0,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    fn conflict(name: &str, instances: Vec<ExecutableInfo>) -> Conflict {
+        Conflict {
+            binary_name: name.to_string(),
+            active_instance: instances[0].clone(),
+            instances,
+            category: ConflictCategory::ShadowedBinary,
+            severity: Severity::Low,
+            severity_reason: String::new(),
+            description: String::new(),
+            recommendation: None,
+            commands: Vec::new(),
+            same_content: false,
+            risk_score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_shadowed_and_hidden() {
+        let conflicts = vec![
+            conflict(
+                "python",
+                vec![
+                    instance("/home/me/.local/bin/python", 0),
+                    instance("/usr/bin/python", 1),
+                ],
+            ),
+            conflict(
+                "node",
+                vec![
+                    instance("/opt/node/bin/node", 0),
+                    instance("/home/me/.local/bin/node", 2),
+                ],
+            ),
+        ];
+
+        let diagnostic = SimulatedEntryAnalyzer::new().analyze(
+            "prepend /home/me/.local/bin",
+            Path::new("/home/me/.local/bin"),
+            &conflicts,
+        );
+        assert_eq!(diagnostic.severity, Severity::Low);
+        assert!(diagnostic
+            .message
+            .contains("would shadow 1 existing command(s): python (/usr/bin/python)"));
+        assert!(diagnostic
+            .message
+            .contains("hidden behind earlier entries: node (/opt/node/bin/node)"));
+        assert_eq!(diagnostic.paths, vec![PathBuf::from("/usr/bin/python")]);
+
+        let untouched = SimulatedEntryAnalyzer::new().analyze(
+            "append /srv/bin",
+            Path::new("/srv/bin"),
+            &conflicts,
+        );
+        assert_eq!(untouched.severity, Severity::Info);
+    }
+}
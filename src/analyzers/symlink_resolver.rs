@@ -1,9 +1,10 @@
 use crate::error::{Error, Result};
-use crate::output::types::ExecutableInfo;
+use crate::output::types::{Diagnostic, DiagnosticKind, ExecutableInfo, Severity};
 use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 
+#[derive(Debug, Clone)]
 pub struct SymlinkResolver {
     max_depth: usize,
 }
@@ -17,18 +18,30 @@ impl SymlinkResolver {
         SymlinkResolver { max_depth }
     }
 
-    pub fn resolve_executables(&self, executables: &mut [ExecutableInfo]) -> Result<()> {
+    /// Resolve every symlinked executable, returning diagnostics for links
+    /// that could not be followed because they loop back on themselves.
+    pub fn resolve_executables(
+        &self,
+        executables: &mut [ExecutableInfo],
+    ) -> Result<Vec<Diagnostic>> {
+        let mut diagnostics = Vec::new();
+
         for executable in executables.iter_mut() {
             if executable.is_symlink {
-                match self.resolve(&executable.full_path) {
-                    Ok(resolved) => {
-                        executable.resolved_path = resolved;
+                match self.walk(&executable.full_path) {
+                    Ok((chain, last)) => {
+                        executable.resolved_path = Self::final_target(&last);
+                        executable.symlink_chain = chain;
+                    }
+                    Err(Error::CircularSymlink { cycle, .. }) => {
+                        diagnostics.push(circular_symlink_diagnostic(&executable.full_path, cycle));
+                        executable.resolved_path = executable.full_path.clone();
                     }
                     Err(e) => {
-                        eprintln!(
-                            "Warning: Failed to resolve symlink {}: {}",
-                            executable.full_path.display(),
-                            e
+                        tracing::warn!(
+                            path = %executable.full_path.display(),
+                            error = %e,
+                            "failed to resolve symlink"
                         );
                         // Keep the original path as resolved_path
                         executable.resolved_path = executable.full_path.clone();
@@ -40,23 +53,53 @@ impl SymlinkResolver {
             }
         }
 
-        Ok(())
+        Ok(diagnostics)
+    }
+
+    pub fn resolve(&self, path: &Path) -> Result<PathBuf> {
+        let (_, last) = self.walk(path)?;
+        Ok(Self::final_target(&last))
     }
 
-    pub fn resolve(&self, path: &std::path::Path) -> Result<PathBuf> {
+    /// Follow a symlink hop by hop, returning every target visited.
+    ///
+    /// The original path is not included, so non-symlinks yield an empty chain.
+    pub fn resolve_chain(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        self.walk(path).map(|(chain, _)| chain)
+    }
+
+    /// The chain as displayed, with `..` collapsed, and the last hop as the
+    /// kernel would reach it. Relative targets are joined without collapsing
+    /// `..`, since a symlinked parent (`/bin -> usr/bin`) makes `bin/..`
+    /// something other than the directory containing `bin`.
+    fn walk(&self, path: &Path) -> Result<(Vec<PathBuf>, PathBuf)> {
         let mut current = path.to_path_buf();
         let mut seen = HashSet::new();
+        let mut chain = Vec::new();
         let mut depth = 0;
 
         while current.is_symlink() && depth < self.max_depth {
-            // Check for circular symlinks
-            if seen.contains(&current) {
+            // Check for circular symlinks. Unnormalized hops such as
+            // `dir/./a` grow on every pass, so compare the collapsed form.
+            let key = normalize_lexically(&current);
+            if seen.contains(&key) {
+                let visited: Vec<PathBuf> = std::iter::once(path.to_path_buf())
+                    .chain(chain.iter().cloned())
+                    .collect();
+                // `current` was pushed as the last hop, so the loop runs from its
+                // first occurrence up to (but not including) that last hop
+                let loop_start = visited
+                    .iter()
+                    .position(|p| normalize_lexically(p) == key)
+                    .unwrap_or(0);
+                let cycle = visited[loop_start..visited.len() - 1].to_vec();
                 return Err(Error::CircularSymlink {
-                    path: current.to_string_lossy().to_string(),
+                    path: path.to_string_lossy().to_string(),
+                    cycle,
                 });
             }
 
-            seen.insert(current.clone());
+            seen.insert(key);
 
             // Read the symlink target
             let target = fs::read_link(&current).map_err(|_| Error::SymlinkError {
@@ -64,28 +107,30 @@ impl SymlinkResolver {
             })?;
 
             // If target is relative, resolve it relative to the symlink's directory
-            current = if target.is_relative() {
-                if let Some(parent) = current.parent() {
-                    parent.join(target)
-                } else {
-                    target
-                }
-            } else {
-                target
+            let relative = target.is_relative();
+            current = match current.parent() {
+                Some(parent) if relative => parent.join(target),
+                _ => target,
             };
 
+            chain.push(if relative {
+                normalize_lexically(&current)
+            } else {
+                current.clone()
+            });
             depth += 1;
         }
 
+        Ok((chain, current))
+    }
+
+    fn final_target(last: &Path) -> PathBuf {
         // Try to canonicalize the final path
-        if let Ok(canonical) = current.canonicalize() {
-            Ok(canonical)
-        } else {
-            Ok(current)
-        }
+        last.canonicalize()
+            .unwrap_or_else(|_| normalize_lexically(last))
     }
 
-    pub fn are_same_binary(&self, path1: &std::path::Path, path2: &std::path::Path) -> bool {
+    pub fn are_same_binary(&self, path1: &Path, path2: &Path) -> bool {
         let resolved1 = self.resolve(path1).ok();
         let resolved2 = self.resolve(path2).ok();
 
@@ -97,6 +142,47 @@ impl SymlinkResolver {
     }
 }
 
+/// Build the finding reported for a symlink whose chain loops back on itself
+pub fn circular_symlink_diagnostic(path: &Path, cycle: Vec<PathBuf>) -> Diagnostic {
+    // Close the loop so the output reads `a → b → a`
+    let loop_display: Vec<String> = cycle
+        .iter()
+        .chain(cycle.first())
+        .map(|p| p.display().to_string())
+        .collect();
+    let mut paths = vec![path.to_path_buf()];
+    paths.extend(cycle.into_iter().filter(|p| p != path));
+
+    Diagnostic {
+        kind: DiagnosticKind::CircularSymlink,
+        severity: Severity::Medium,
+        message: format!(
+            "{} is a circular symlink and can never be executed: {}",
+            path.display(),
+            loop_display.join(" → ")
+        ),
+        paths,
+    }
+}
+
+/// Collapse `.` and `..` components without touching the filesystem, so hops
+/// like `/usr/local/bin/../Cellar/...` are displayed as `/usr/local/Cellar/...`
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
 impl Default for SymlinkResolver {
     fn default() -> Self {
         Self::new()
@@ -115,4 +201,49 @@ mod tests {
         let resolver_custom = SymlinkResolver::with_max_depth(5);
         assert_eq!(resolver_custom.max_depth, 5);
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_chain_records_every_hop() {
+        let dir = tempfile::tempdir().unwrap();
+        let real = dir.path().join("python3.11");
+        std::fs::write(&real, b"").unwrap();
+        std::os::unix::fs::symlink("python3.11", dir.path().join("python3")).unwrap();
+        std::os::unix::fs::symlink("python3", dir.path().join("python")).unwrap();
+
+        let resolver = SymlinkResolver::new();
+        let chain = resolver.resolve_chain(&dir.path().join("python")).unwrap();
+
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain[0], dir.path().join("python3"));
+        assert_eq!(chain[1].file_name().unwrap(), "python3.11");
+        assert!(resolver.resolve_chain(&real).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_relative_target_under_symlinked_parent() {
+        // Merged /usr: bin -> usr/bin, so bin/.. is usr, not the root
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join("usr/bin")).unwrap();
+        std::fs::create_dir_all(root.path().join("usr/lib/tool")).unwrap();
+        std::fs::write(root.path().join("usr/lib/tool/tool-1.0"), b"").unwrap();
+        std::os::unix::fs::symlink("tool-1.0", root.path().join("usr/lib/tool/current")).unwrap();
+        std::os::unix::fs::symlink("../lib/tool/current", root.path().join("usr/bin/tool"))
+            .unwrap();
+        std::os::unix::fs::symlink("usr/bin", root.path().join("bin")).unwrap();
+
+        let resolver = SymlinkResolver::new();
+        let resolved = resolver.resolve(&root.path().join("bin/tool")).unwrap();
+        assert_eq!(
+            resolved,
+            root.path()
+                .join("usr/lib/tool/tool-1.0")
+                .canonicalize()
+                .unwrap()
+        );
+        let chain = resolver
+            .resolve_chain(&root.path().join("bin/tool"))
+            .unwrap();
+        assert_eq!(chain.len(), 2);
+    }
 }
@@ -1,38 +1,439 @@
-use crate::output::types::{ExecutableInfo, VersionInfo};
+use crate::config::{OutputStream, VersionCommand};
+use crate::core::binary_format;
+use crate::core::deadline::{self, Deadline};
+use crate::core::parallel::for_each_bounded;
+use crate::core::progress::{ProgressReporter, Stage};
+use crate::error::{Error, Result};
+use crate::output::types::{Diagnostic, ExecutableInfo, ProbeAttempt, VersionInfo};
+use crate::platform;
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use regex::Regex;
-use std::process::{Command, Stdio};
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::process::{Command, Output, Stdio};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// How long one version command may run
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Binaries known to print their version and exit when run with one of the
+/// generic version flags
+const DEFAULT_ALLOWLIST: &[&str] = &[
+    "bun", "cargo", "clang", "cmake", "deno", "dotnet", "gcc", "gem", "git", "go", "gradle",
+    "make", "mvn", "node", "npm", "npx", "perl", "php", "pip", "pip3", "pnpm", "python", "python3",
+    "ruby", "rustc", "rustup", "uv", "yarn",
+];
+
+/// Which binaries may be executed to read their version
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VersionProbe {
+    /// Run every binary not on the blacklist
+    #[default]
+    All,
+    /// Run only allowlisted binaries; everything else uses static extraction
+    Allowlist,
+    /// Never run anything: versions come from paths, file metadata and
+    /// package databases (`--no-exec`)
+    Never,
+}
+
+/// The built-in allowlist used by `VersionProbe::Allowlist`
+pub fn default_allowlist() -> Vec<String> {
+    DEFAULT_ALLOWLIST.iter().map(|s| s.to_string()).collect()
+}
+
+/// Bytes of each binary searched by the static version scan
+const STATIC_SCAN_LIMIT: u64 = 64 * 1024 * 1024;
+/// The static scan reads this much at a time, so memory stays flat however
+/// large the binary is
+const STATIC_SCAN_CHUNK: usize = 1024 * 1024;
+/// Bytes carried from one chunk to the next, so a version string split
+/// across the boundary is still found
+const STATIC_SCAN_OVERLAP: usize = 4096;
+
+/// Executables that should not be executed to read their version. These
+/// are known to open GUI windows, hang, or cause issues. Config entries
+/// (`[version.skip] names`) extend this list.
+const DEFAULT_SKIP_LIST: &[&str] = &[
+    // Git GUI tools
+    "git-gui",
+    "gitk",
+    "git-citool",
+    // Office applications
+    "winword",
+    "excel",
+    "powerpnt",
+    // Windows GUI applications
+    "mspaint",
+    "notepad",
+    "calc",
+    "explorer",
+    "write",
+    "wordpad",
+    // Scripting hosts
+    "cmd",
+    "powershell",
+    "pwsh",
+    "wscript",
+    "cscript",
+    "mshta",
+    "rundll32",
+    // Windows system tools that output usage instead of version
+    "apphostnameregistrationverifier",
+    "appidpolicyconverter",
+    "appverifier",
+    "aspnet_regiis",
+    "bcdedit",
+    "bitsadmin",
+    "certreq",
+    "certutil",
+    "chkdsk",
+    "choice",
+    "cipher",
+    "clip",
+    "cmdkey",
+    "cmstp",
+    "comp",
+    "compact",
+    "computerdefaults",
+    "control",
+    "convert",
+    "cscript",
+    "dism",
+    "diskpart",
+    "doskey",
+    "driverquery",
+    "eventcreate",
+    "expand",
+    "extrac32",
+    "fc",
+    "find",
+    "findstr",
+    "fontview",
+    "forfiles",
+    "fsutil",
+    "ftp",
+    "getmac",
+    "gpresult",
+    "gpupdate",
+    "help",
+    "hostname",
+    "icacls",
+    "iexpress",
+    "label",
+    "logoff",
+    "makecab",
+    "manage-bde",
+    "mkdir",
+    "mklink",
+    "mode",
+    "more",
+    "mountvol",
+    "move",
+    "msiexec",
+    "msinfo32",
+    "mstsc",
+    "net",
+    "netsh",
+    "netstat",
+    "nslookup",
+    "openfiles",
+    "path",
+    "pathping",
+    "pause",
+    "ping",
+    "pnputil",
+    "print",
+    "prompt",
+    "pushd",
+    "qprocess",
+    "query",
+    "quser",
+    "qwinsta",
+    "rasdial",
+    "rcp",
+    "recover",
+    "reg",
+    "regini",
+    "regsvr32",
+    "relog",
+    "rem",
+    "ren",
+    "rename",
+    "repair-bde",
+    "replace",
+    "reset",
+    "rmdir",
+    "robocopy",
+    "route",
+    "rpcping",
+    "runas",
+    "sc",
+    "schtasks",
+    "secedit",
+    "set",
+    "setlocal",
+    "setx",
+    "sfc",
+    "shutdown",
+    "sort",
+    "start",
+    "subst",
+    "systeminfo",
+    "takeown",
+    "taskkill",
+    "tasklist",
+    "telnet",
+    "time",
+    "timeout",
+    "title",
+    "tracert",
+    "tree",
+    "type",
+    "typeperf",
+    "tzutil",
+    "ver",
+    "verify",
+    "vol",
+    "vssadmin",
+    "w32tm",
+    "waitfor",
+    "wbadmin",
+    "wevtutil",
+    "where",
+    "whoami",
+    "winmgmt",
+    "winrm",
+    "winrs",
+    "wmic",
+    "wscript",
+    "xcopy",
+];
+
+/// The built-in list of binaries never executed for their version
+pub fn default_skip_list() -> Vec<String> {
+    DEFAULT_SKIP_LIST.iter().map(|s| s.to_string()).collect()
+}
+
+/// Built-in commands for tools the generic version flags get wrong
+pub fn default_commands() -> HashMap<String, VersionCommand> {
+    let command =
+        |args: &[&str], stream, pattern: Option<&str>, json_field: Option<&str>| VersionCommand {
+            args: args.iter().map(|s| s.to_string()).collect(),
+            stream,
+            pattern: pattern.map(str::to_string),
+            json_field: json_field.map(str::to_string),
+        };
+
+    HashMap::from([
+        (
+            "java".to_string(),
+            command(
+                &["-version"],
+                OutputStream::Stderr,
+                Some(r#"version "([^"]+)""#),
+                None,
+            ),
+        ),
+        (
+            "go".to_string(),
+            command(
+                &["version"],
+                OutputStream::Stdout,
+                Some(r"go(\d+\.\d+(?:\.\d+)?)"),
+                None,
+            ),
+        ),
+        (
+            "terraform".to_string(),
+            command(
+                &["version", "-json"],
+                OutputStream::Stdout,
+                None,
+                Some("terraform_version"),
+            ),
+        ),
+    ])
+}
 
 pub struct VersionExtractor {
-    _timeout_secs: u64,
+    /// Per-command limit; a probe still running after it is killed
+    timeout: Duration,
+    /// Worker threads for `extract_versions` (0 = one per CPU)
+    jobs: usize,
+    probe: VersionProbe,
+    allowlist: Vec<String>,
+    commands: HashMap<String, VersionCommand>,
+    /// Keep a record of every command run, for debugging odd results
+    record_probes: bool,
+    /// Lowercased names never executed (with or without a `_suffix`)
+    skip_list: Vec<String>,
+    /// Skip entries containing glob characters, e.g. `*-gui`
+    skip_globs: GlobSet,
+    progress: ProgressReporter,
 }
 
 impl VersionExtractor {
     pub fn new() -> Self {
-        VersionExtractor { _timeout_secs: 5 }
+        Self::with_timeout(DEFAULT_TIMEOUT)
     }
 
-    pub fn with_timeout(timeout_secs: u64) -> Self {
+    pub fn with_timeout(timeout: Duration) -> Self {
         VersionExtractor {
-            _timeout_secs: timeout_secs,
+            timeout,
+            jobs: 0,
+            probe: VersionProbe::default(),
+            allowlist: default_allowlist(),
+            commands: default_commands(),
+            record_probes: false,
+            skip_list: default_skip_list(),
+            skip_globs: GlobSet::empty(),
+            progress: ProgressReporter::none(),
         }
     }
 
-    pub fn extract_versions(&self, executables: &mut [ExecutableInfo]) {
-        for executable in executables.iter_mut() {
-            if let Some(version) = self.extract(&executable.full_path, &executable.name) {
-                executable.version = Some(version);
+    /// Restrict which binaries are executed
+    pub fn with_probe(mut self, probe: VersionProbe, allowlist: Vec<String>) -> Self {
+        self.probe = probe;
+        self.allowlist = allowlist;
+        self
+    }
+
+    /// Probe with at most `jobs` threads (0 = one per CPU)
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    /// Report each binary `extract_versions` handles
+    pub fn with_progress(mut self, progress: ProgressReporter) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Binaries never executed. Entries containing `*`, `?` or `[` are
+    /// matched as globs against the lowercased binary name.
+    pub fn with_skip_list(mut self, names: Vec<String>) -> Result<Self> {
+        let (globs, literals): (Vec<String>, Vec<String>) = names
+            .into_iter()
+            .map(|name| name.to_lowercase())
+            .partition(|name| is_glob(name));
+
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &globs {
+            builder.add(Glob::new(pattern).map_err(|e| Error::InvalidPattern {
+                pattern: pattern.clone(),
+                reason: e.to_string(),
+            })?);
+        }
+
+        self.skip_globs = builder.build().map_err(|e| Error::InvalidPattern {
+            pattern: globs.join(", "),
+            reason: e.to_string(),
+        })?;
+        self.skip_list = literals;
+        Ok(self)
+    }
+
+    /// Record each command attempted in `ExecutableInfo::version_probes`
+    pub fn with_probe_recording(mut self, record_probes: bool) -> Self {
+        self.record_probes = record_probes;
+        self
+    }
+
+    /// Per-binary version commands, tried before the generic flags
+    pub fn with_commands(mut self, commands: HashMap<String, VersionCommand>) -> Self {
+        self.commands = commands;
+        self
+    }
+
+    /// Extract every version on up to `jobs` threads, stopping once
+    /// `deadline` passes; returns a diagnostic listing the binaries left
+    /// unprobed
+    pub fn extract_versions(
+        &self,
+        executables: &mut [ExecutableInfo],
+        deadline: Deadline,
+    ) -> Vec<Diagnostic> {
+        let skipped = Mutex::new(HashSet::new());
+        let progress = self
+            .progress
+            .start(Stage::ExtractingVersions, executables.len());
+        for_each_bounded(executables, self.jobs, |executable| {
+            // Already known, e.g. from Homebrew metadata, or nothing to run
+            if executable.version.is_none() && !executable.broken {
+                if deadline.expired() {
+                    if let Ok(mut skipped) = skipped.lock() {
+                        skipped.insert(executable.full_path.clone());
+                    }
+                } else {
+                    self.extract_into(executable);
+                }
             }
+            progress.tick();
+        });
+        progress.finish();
+
+        // Report in PATH order, whichever worker got there
+        let mut skipped = skipped.into_inner().unwrap_or_default();
+        if skipped.is_empty() {
+            return Vec::new();
+        }
+        let ordered = executables
+            .iter()
+            .filter(|e| skipped.remove(&e.full_path))
+            .map(|e| e.full_path.clone())
+            .collect();
+        vec![deadline::skipped_diagnostic("version extraction", ordered)]
+    }
+
+    /// Fill in one executable's version, and its probe attempts when
+    /// recording is enabled
+    pub fn extract_into(&self, executable: &mut ExecutableInfo) {
+        let mut probes = Vec::new();
+        if let Some(version) =
+            self.extract_with_probes(&executable.full_path, &executable.name, &mut probes)
+        {
+            executable.version = Some(version);
         }
+        executable.version_probes = probes;
     }
 
     pub fn extract(&self, path: &std::path::Path, binary_name: &str) -> Option<VersionInfo> {
-        // Skip known problematic executables
-        if self.should_skip_binary(binary_name) {
-            return self.try_path_parsing(path, binary_name);
+        self.extract_with_probes(path, binary_name, &mut Vec::new())
+    }
+
+    fn extract_with_probes(
+        &self,
+        path: &std::path::Path,
+        binary_name: &str,
+        probes: &mut Vec<ProbeAttempt>,
+    ) -> Option<VersionInfo> {
+        // Tools shipped inside a macOS app carry the app's version
+        if let Some(version) = platform::macos::get_macos_bundle_version(path) {
+            return Some(VersionInfo {
+                raw: version.clone(),
+                parsed: Some(version),
+                extraction_method: "app bundle".to_string(),
+            });
+        }
+
+        // Skip known problematic executables, and any GUI-subsystem Windows
+        // binary, which would open a window instead of printing a version
+        if !self.may_execute(binary_name)
+            || self.should_skip_binary(binary_name)
+            || binary_format::is_gui_subsystem(path)
+        {
+            return self.try_static_extraction(path, binary_name);
+        }
+
+        if let Some(version) = self.try_custom_command(path, binary_name, probes) {
+            return Some(version);
         }
 
         // Try different version extraction methods
-        if let Some(version) = self.try_execution_methods(path) {
+        if let Some(version) = self.try_execution_methods(path, probes) {
             return Some(version);
         }
 
@@ -43,197 +444,117 @@ impl VersionExtractor {
         None
     }
 
-    fn should_skip_binary(&self, binary_name: &str) -> bool {
-        // Blacklist of executables that should not be executed
-        // These are known to open GUI windows, hang, or cause issues
-        let blacklist = [
-            // Git GUI tools
-            "git-gui",
-            "gitk",
-            "git-citool",
-            // Office applications
-            "winword",
-            "excel",
-            "powerpnt",
-            // Windows GUI applications
-            "mspaint",
-            "notepad",
-            "calc",
-            "explorer",
-            "write",
-            "wordpad",
-            // Scripting hosts
-            "cmd",
-            "powershell",
-            "pwsh",
-            "wscript",
-            "cscript",
-            "mshta",
-            "rundll32",
-            // Windows system tools that output usage instead of version
-            "apphostnameregistrationverifier",
-            "appidpolicyconverter",
-            "appverifier",
-            "aspnet_regiis",
-            "bcdedit",
-            "bitsadmin",
-            "certreq",
-            "certutil",
-            "chkdsk",
-            "choice",
-            "cipher",
-            "clip",
-            "cmdkey",
-            "cmstp",
-            "comp",
-            "compact",
-            "computerdefaults",
-            "control",
-            "convert",
-            "cscript",
-            "dism",
-            "diskpart",
-            "doskey",
-            "driverquery",
-            "eventcreate",
-            "expand",
-            "extrac32",
-            "fc",
-            "find",
-            "findstr",
-            "fontview",
-            "forfiles",
-            "fsutil",
-            "ftp",
-            "getmac",
-            "gpresult",
-            "gpupdate",
-            "help",
-            "hostname",
-            "icacls",
-            "iexpress",
-            "label",
-            "logoff",
-            "makecab",
-            "manage-bde",
-            "mkdir",
-            "mklink",
-            "mode",
-            "more",
-            "mountvol",
-            "move",
-            "msiexec",
-            "msinfo32",
-            "mstsc",
-            "net",
-            "netsh",
-            "netstat",
-            "nslookup",
-            "openfiles",
-            "path",
-            "pathping",
-            "pause",
-            "ping",
-            "pnputil",
-            "print",
-            "prompt",
-            "pushd",
-            "qprocess",
-            "query",
-            "quser",
-            "qwinsta",
-            "rasdial",
-            "rcp",
-            "recover",
-            "reg",
-            "regini",
-            "regsvr32",
-            "relog",
-            "rem",
-            "ren",
-            "rename",
-            "repair-bde",
-            "replace",
-            "reset",
-            "rmdir",
-            "robocopy",
-            "route",
-            "rpcping",
-            "runas",
-            "sc",
-            "schtasks",
-            "secedit",
-            "set",
-            "setlocal",
-            "setx",
-            "sfc",
-            "shutdown",
-            "sort",
-            "start",
-            "subst",
-            "systeminfo",
-            "takeown",
-            "taskkill",
-            "tasklist",
-            "telnet",
-            "time",
-            "timeout",
-            "title",
-            "tracert",
-            "tree",
-            "type",
-            "typeperf",
-            "tzutil",
-            "ver",
-            "verify",
-            "vol",
-            "vssadmin",
-            "w32tm",
-            "waitfor",
-            "wbadmin",
-            "wevtutil",
-            "where",
-            "whoami",
-            "winmgmt",
-            "winrm",
-            "winrs",
-            "wmic",
-            "wscript",
-            "xcopy",
-        ];
+    fn may_execute(&self, binary_name: &str) -> bool {
+        match self.probe {
+            VersionProbe::All => true,
+            VersionProbe::Never => false,
+            VersionProbe::Allowlist => self
+                .allowlist
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(binary_name)),
+        }
+    }
 
+    fn should_skip_binary(&self, binary_name: &str) -> bool {
         let name_lower = binary_name.to_lowercase();
-        blacklist.iter().any(|&blocked| {
-            name_lower == blocked || name_lower.starts_with(&format!("{}_", blocked))
+        if self.skip_globs.is_match(&name_lower) {
+            return true;
+        }
+
+        self.skip_list.iter().any(|blocked| {
+            name_lower == *blocked || name_lower.starts_with(&format!("{}_", blocked))
         })
     }
 
-    fn try_execution_methods(&self, path: &std::path::Path) -> Option<VersionInfo> {
+    fn try_execution_methods(
+        &self,
+        path: &std::path::Path,
+        probes: &mut Vec<ProbeAttempt>,
+    ) -> Option<VersionInfo> {
         let version_args = vec![vec!["--version"], vec!["-v"], vec!["version"], vec!["-V"]];
 
         for args in version_args {
-            if let Some(output) = self.execute_with_timeout(path, &args) {
-                if let Some(version) = self.parse_version_output(&output) {
-                    return Some(VersionInfo {
-                        raw: version.clone(),
-                        parsed: Some(version),
-                        extraction_method: "command execution".to_string(),
-                    });
+            match self.execute_with_timeout(path, &args, probes) {
+                Ok(Some(output)) => {
+                    if let Some(version) = self.parse_version_output(&output) {
+                        return Some(VersionInfo {
+                            raw: version.clone(),
+                            parsed: Some(version),
+                            extraction_method: "command execution".to_string(),
+                        });
+                    }
                 }
+                // A binary that hangs on one flag would hang on the others
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut => return None,
+                _ => {}
             }
         }
 
         None
     }
 
-    fn execute_with_timeout(&self, path: &std::path::Path, args: &[&str]) -> Option<String> {
+    fn try_custom_command(
+        &self,
+        path: &std::path::Path,
+        binary_name: &str,
+        probes: &mut Vec<ProbeAttempt>,
+    ) -> Option<VersionInfo> {
+        let command = self
+            .commands
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(binary_name))
+            .map(|(_, command)| command)?;
+
+        let args: Vec<&str> = command.args.iter().map(String::as_str).collect();
+        let output = self.run(path, &args, probes).ok()?;
+        let text = match command.stream {
+            OutputStream::Any => stream_text(&output)?,
+            OutputStream::Stdout => String::from_utf8_lossy(&output.stdout).to_string(),
+            OutputStream::Stderr => String::from_utf8_lossy(&output.stderr).to_string(),
+        };
+
+        self.parse_custom_output(command, &text)
+    }
+
+    fn parse_custom_output(&self, command: &VersionCommand, output: &str) -> Option<VersionInfo> {
+        let version = if let Some(field) = &command.json_field {
+            let json: serde_json::Value = serde_json::from_str(output.trim()).ok()?;
+            let value = field
+                .split('.')
+                .try_fold(&json, |value, key| value.get(key))?;
+            value.as_str()?.trim_start_matches('v').to_string()
+        } else if let Some(pattern) = &command.pattern {
+            let caps = Regex::new(pattern).ok()?.captures(output)?;
+            caps.get(1).or_else(|| caps.get(0))?.as_str().to_string()
+        } else {
+            self.parse_version_output(output.trim())?
+        };
+
+        Some(VersionInfo {
+            raw: output.trim().to_string(),
+            parsed: Some(version),
+            extraction_method: "custom command".to_string(),
+        })
+    }
+
+    fn execute_with_timeout(
+        &self,
+        path: &std::path::Path,
+        args: &[&str],
+        probes: &mut Vec<ProbeAttempt>,
+    ) -> std::io::Result<Option<String>> {
+        Ok(stream_text(&self.run(path, args, probes)?))
+    }
+
+    fn run(
+        &self,
+        path: &std::path::Path,
+        args: &[&str],
+        probes: &mut Vec<ProbeAttempt>,
+    ) -> std::io::Result<Output> {
         // Create command with proper configuration to prevent GUI windows
         let mut command = Command::new(path);
-        command
-            .args(args)
-            .stdin(Stdio::null()) // Close stdin to prevent hanging
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+        command.args(args).stdin(Stdio::null()); // Close stdin to prevent hanging
 
         // On Windows, use CREATE_NO_WINDOW flag to prevent GUI windows
         #[cfg(windows)]
@@ -244,26 +565,35 @@ impl VersionExtractor {
         }
 
         // Try to execute the binary with the given arguments
-        match command.output() {
-            Ok(output) => {
-                // Try stdout first
-                if let Ok(stdout) = String::from_utf8(output.stdout) {
-                    if !stdout.trim().is_empty() {
-                        return Some(stdout.trim().to_string());
-                    }
-                }
+        tracing::debug!(binary = %path.display(), ?args, "running version probe");
+        let result = platform::process::output_with_timeout(&mut command, self.timeout);
+        if let Err(e) = &result {
+            tracing::debug!(binary = %path.display(), error = %e, "version probe failed");
+        }
 
-                // Try stderr as fallback (some tools output version to stderr)
-                if let Ok(stderr) = String::from_utf8(output.stderr) {
-                    if !stderr.trim().is_empty() {
-                        return Some(stderr.trim().to_string());
-                    }
+        if self.record_probes {
+            let mut attempt = ProbeAttempt {
+                command: std::iter::once(path.display().to_string())
+                    .chain(args.iter().map(|a| a.to_string()))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                exit_code: None,
+                error: None,
+                stdout: String::new(),
+                stderr: String::new(),
+            };
+            match &result {
+                Ok(output) => {
+                    attempt.exit_code = output.status.code();
+                    attempt.stdout = output_sample(&output.stdout);
+                    attempt.stderr = output_sample(&output.stderr);
                 }
-
-                None
+                Err(e) => attempt.error = Some(e.to_string()),
             }
-            Err(_) => None,
+            probes.push(attempt);
         }
+
+        result
     }
 
     fn parse_version_output(&self, output: &str) -> Option<String> {
@@ -306,6 +636,76 @@ impl VersionExtractor {
         None
     }
 
+    /// Version sources that never run the binary
+    fn try_static_extraction(
+        &self,
+        path: &std::path::Path,
+        binary_name: &str,
+    ) -> Option<VersionInfo> {
+        if let Some(version) = platform::windows::get_file_version_windows(path) {
+            return Some(VersionInfo {
+                raw: version.clone(),
+                parsed: Some(version),
+                extraction_method: "file metadata".to_string(),
+            });
+        }
+
+        self.try_path_parsing(path, binary_name)
+            .or_else(|| self.try_static_scan(path, binary_name))
+    }
+
+    /// Look for a version string embedded next to the tool's name in the
+    /// binary itself, e.g. `ripgrep 14.1.0` in the rodata section
+    fn try_static_scan(&self, path: &std::path::Path, binary_name: &str) -> Option<VersionInfo> {
+        let pattern = format!(
+            r"(?i-u)\b{}[ /_-]{{0,3}}(?:version[ :]*)?v?(\d+\.\d+\.\d+[0-9A-Za-z.+-]*)",
+            regex::escape(binary_name)
+        );
+        let re = regex::bytes::Regex::new(&pattern).ok()?;
+
+        let mut file = std::fs::File::open(path).ok()?.take(STATIC_SCAN_LIMIT);
+        let mut window = Vec::with_capacity(STATIC_SCAN_CHUNK + STATIC_SCAN_OVERLAP);
+        // Once the front of the file is dropped, the first byte of the window
+        // is only there as context for `\b`
+        let mut start = 0;
+        let at = loop {
+            let read = (&mut file)
+                .take(STATIC_SCAN_CHUNK as u64)
+                .read_to_end(&mut window)
+                .ok()?;
+            let eof = read < STATIC_SCAN_CHUNK;
+
+            let found = re.find_at(&window, start);
+            match found {
+                // A match running into the end of the chunk may continue in
+                // the next one; read on unless it is already implausibly long
+                Some(m) if !eof && m.end() == window.len() && m.len() < STATIC_SCAN_OVERLAP => {}
+                Some(m) => break Some(m.start()),
+                None if eof => break None,
+                None => {}
+            }
+
+            let carry_from = found
+                .map_or(window.len(), |m| m.start())
+                .min(window.len().saturating_sub(STATIC_SCAN_OVERLAP))
+                .saturating_sub(1);
+            if carry_from > 0 {
+                window.drain(..carry_from);
+                start = 1;
+            }
+        }?;
+        let caps = re.captures_at(&window, at)?;
+        let version = String::from_utf8_lossy(caps.get(1)?.as_bytes())
+            .trim_end_matches(['.', '-', '+'])
+            .to_string();
+
+        Some(VersionInfo {
+            raw: String::from_utf8_lossy(caps.get(0)?.as_bytes()).to_string(),
+            parsed: Some(version),
+            extraction_method: "static-scan".to_string(),
+        })
+    }
+
     fn try_path_parsing(&self, path: &std::path::Path, binary_name: &str) -> Option<VersionInfo> {
         let path_str = path.to_string_lossy();
 
@@ -340,6 +740,32 @@ impl VersionExtractor {
     }
 }
 
+/// Characters of probe output kept per stream
+const PROBE_SAMPLE_CHARS: usize = 200;
+
+fn output_sample(bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    let text = text.trim();
+    match text.char_indices().nth(PROBE_SAMPLE_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
+}
+
+/// Trimmed stdout, or stderr when stdout is empty (some tools print their
+/// version to stderr)
+fn stream_text(output: &Output) -> Option<String> {
+    [&output.stdout, &output.stderr]
+        .into_iter()
+        .filter_map(|bytes| String::from_utf8(bytes.clone()).ok())
+        .map(|text| text.trim().to_string())
+        .find(|text| !text.is_empty())
+}
+
 impl Default for VersionExtractor {
     fn default() -> Self {
         Self::new()
@@ -375,6 +801,200 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_allowlist_probe() {
+        let extractor = VersionExtractor::new();
+        assert!(extractor.may_execute("anything"));
+
+        let extractor =
+            VersionExtractor::new().with_probe(VersionProbe::Allowlist, vec!["Python".to_string()]);
+        assert!(extractor.may_execute("python"));
+        assert!(!extractor.may_execute("node"));
+
+        // Falls back to path parsing instead of running the binary
+        let version = extractor
+            .extract(std::path::Path::new("/opt/tools/v1.2.3/bin/tool"), "tool")
+            .unwrap();
+        assert_eq!(version.extraction_method, "path parsing");
+        assert_eq!(version.parsed.as_deref(), Some("1.2.3"));
+    }
+
+    #[test]
+    fn test_parse_custom_output() {
+        let extractor = VersionExtractor::new();
+        let commands = default_commands();
+
+        let java = extractor
+            .parse_custom_output(
+                &commands["java"],
+                "openjdk version \"17.0.2\" 2022-01-18\nOpenJDK Runtime Environment",
+            )
+            .unwrap();
+        assert_eq!(java.parsed.as_deref(), Some("17.0.2"));
+        assert_eq!(java.extraction_method, "custom command");
+
+        let go = extractor
+            .parse_custom_output(&commands["go"], "go version go1.22.1 linux/amd64")
+            .unwrap();
+        assert_eq!(go.parsed.as_deref(), Some("1.22.1"));
+
+        let terraform = extractor
+            .parse_custom_output(
+                &commands["terraform"],
+                r#"{"terraform_version": "1.7.4", "platform": "linux_amd64"}"#,
+            )
+            .unwrap();
+        assert_eq!(terraform.parsed.as_deref(), Some("1.7.4"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_custom_command_reads_configured_stream() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let tool = dir.path().join("tool");
+        std::fs::write(
+            &tool,
+            "#!/bin/sh\n[ \"$1\" = \"-V\" ] && echo 'tool 1.2' >&2 || echo 'tool 9.9'\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let extractor = VersionExtractor::new().with_commands(HashMap::from([(
+            "tool".to_string(),
+            VersionCommand {
+                args: vec!["-V".to_string()],
+                stream: OutputStream::Stderr,
+                ..Default::default()
+            },
+        )]));
+
+        let version = extractor.extract(&tool, "tool").unwrap();
+        assert_eq!(version.parsed.as_deref(), Some("1.2"));
+        assert_eq!(version.extraction_method, "custom command");
+    }
+
+    #[test]
+    fn test_static_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = dir.path().join("mytool");
+        let mut contents = vec![0x7f, b'E', b'L', b'F', 0, 1, 2];
+        contents.extend_from_slice(b"\0libfoo 9.9.9\0MyTool version 2.4.1-rc1.\0");
+        std::fs::write(&binary, &contents).unwrap();
+
+        let extractor = VersionExtractor::new();
+        let version = extractor.try_static_scan(&binary, "mytool").unwrap();
+        assert_eq!(version.parsed.as_deref(), Some("2.4.1-rc1"));
+        assert_eq!(version.extraction_method, "static-scan");
+
+        // Version strings not next to the tool's name are ignored
+        assert!(extractor.try_static_scan(&binary, "other").is_none());
+
+        // Found across the boundary between two chunks, but not as part of
+        // a longer word
+        let mut contents = vec![0u8; STATIC_SCAN_CHUNK - 10];
+        contents.extend_from_slice(b"MyTool version 2.4.1\0");
+        contents.resize(3 * STATIC_SCAN_CHUNK - 1, 0);
+        contents.extend_from_slice(b"xmytool 3.0.0\0");
+        std::fs::write(&binary, &contents).unwrap();
+        let version = extractor.try_static_scan(&binary, "mytool").unwrap();
+        assert_eq!(version.parsed.as_deref(), Some("2.4.1"));
+        contents[STATIC_SCAN_CHUNK - 10] = 0;
+        std::fs::write(&binary, &contents).unwrap();
+        assert!(extractor.try_static_scan(&binary, "mytool").is_none());
+    }
+
+    #[test]
+    fn test_skip_list() {
+        let extractor = VersionExtractor::new();
+        assert!(extractor.should_skip_binary("notepad"));
+        assert!(extractor.should_skip_binary("Cmd"));
+        assert!(extractor.should_skip_binary("net_helper"));
+        assert!(!extractor.should_skip_binary("python"));
+
+        let extractor = VersionExtractor::new()
+            .with_skip_list(vec!["code".to_string(), "*-GUI".to_string()])
+            .unwrap();
+        assert!(extractor.should_skip_binary("code"));
+        assert!(extractor.should_skip_binary("meld-gui"));
+        assert!(!extractor.should_skip_binary("notepad"));
+    }
+
+    #[test]
+    fn test_output_sample_truncates() {
+        assert_eq!(output_sample(b"  v1.0\n"), "v1.0");
+        let long = "x".repeat(PROBE_SAMPLE_CHARS + 10);
+        let sample = output_sample(long.as_bytes());
+        assert_eq!(sample.chars().count(), PROBE_SAMPLE_CHARS + 1);
+        assert!(sample.ends_with('…'));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_probe_recording() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let tool = dir.path().join("tool");
+        std::fs::write(&tool, "#!/bin/sh\necho \"bad flag $1\" >&2\nexit 2\n").unwrap();
+        std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut probes = Vec::new();
+        let extractor = VersionExtractor::new().with_probe_recording(true);
+        assert!(extractor
+            .extract_with_probes(&tool, "tool", &mut probes)
+            .is_none());
+
+        assert_eq!(probes.len(), 4);
+        assert!(probes[0].command.ends_with("tool --version"));
+        assert_eq!(probes[0].exit_code, Some(2));
+        assert_eq!(probes[0].stderr, "bad flag --version");
+
+        // Nothing is kept unless recording is enabled
+        let mut probes = Vec::new();
+        VersionExtractor::new().extract_with_probes(&tool, "tool", &mut probes);
+        assert!(probes.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_extract_versions_in_parallel() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut executables: Vec<ExecutableInfo> = (0..4)
+            .map(|i| {
+                let tool = dir.path().join(format!("tool{}", i));
+                let script = format!("#!/bin/sh\nsleep 0.5\necho 'tool 1.{}.0'\n", i);
+                std::fs::write(&tool, script).unwrap();
+                std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755)).unwrap();
+                ExecutableInfo {
+                    name: format!("tool{}", i),
+                    full_path: tool.clone(),
+                    resolved_path: tool,
+                    ..ExecutableInfo::fixture()
+                }
+            })
+            .collect();
+
+        let started = std::time::Instant::now();
+        let diagnostics = VersionExtractor::new()
+            .with_jobs(4)
+            .extract_versions(&mut executables, Deadline::none());
+
+        // One at a time would take at least two seconds
+        assert!(started.elapsed() < Duration::from_millis(1900));
+        assert!(diagnostics.is_empty());
+        for (i, exec) in executables.iter().enumerate() {
+            let version = exec.version.as_ref().unwrap();
+            assert_eq!(
+                version.parsed.as_deref(),
+                Some(format!("1.{}.0", i).as_str())
+            );
+        }
+    }
+
     #[test]
     fn test_try_path_parsing() {
         let extractor = VersionExtractor::new();
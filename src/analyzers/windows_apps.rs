@@ -0,0 +1,217 @@
+use crate::output::types::{Diagnostic, DiagnosticKind, ExecutableInfo, PathEntry, Severity};
+use std::path::{Path, PathBuf};
+
+/// App Execution Aliases that open the Microsoft Store unless the Store
+/// build of Python is installed
+const PYTHON_STUBS: &[&str] = &["python.exe", "python3.exe"];
+
+/// Checks where `%LOCALAPPDATA%\Microsoft\WindowsApps` sits in PATH. Its
+/// App Execution Aliases (python, winget, wt, ...) win or lose against real
+/// toolchains purely by position.
+pub struct WindowsAppsAnalyzer;
+
+impl WindowsAppsAnalyzer {
+    pub fn new() -> Self {
+        WindowsAppsAnalyzer
+    }
+
+    pub fn analyze(&self, path_entries: &[PathEntry]) -> Vec<Diagnostic> {
+        let Some(apps_idx) = path_entries.iter().position(is_windows_apps) else {
+            return Vec::new();
+        };
+        let apps = &path_entries[apps_idx];
+        let store_python = apps
+            .executables
+            .iter()
+            .any(|e| is_versioned_python(&file_name(e)));
+        let mut diagnostics = Vec::new();
+
+        for alias in &apps.executables {
+            let name = file_name(alias);
+            let is_stub = !store_python && PYTHON_STUBS.contains(&name.as_str());
+
+            // Aliases before WindowsApps beat it; stubs only matter if they
+            // beat something real
+            let before = path_entries[..apps_idx].iter().find_map(|e| find(e, &name));
+            let after = path_entries[apps_idx + 1..]
+                .iter()
+                .find_map(|e| find(e, &name));
+
+            if is_stub {
+                let Some(real) = after else {
+                    if before.is_none() {
+                        diagnostics.push(diagnostic(
+                            Severity::Low,
+                            format!(
+                                "{} is only the Microsoft Store installer stub; install Python \
+                                 or turn the alias off in Settings > Apps > Advanced app \
+                                 settings > App execution aliases",
+                                alias.name
+                            ),
+                            vec![alias.full_path.clone()],
+                        ));
+                    }
+                    continue;
+                };
+                if before.is_none() {
+                    diagnostics.push(diagnostic(
+                        Severity::High,
+                        format!(
+                            "{} runs the Microsoft Store stub in WindowsApps instead of {}; move \
+                             {} after {} in PATH, or turn the alias off in Settings > Apps > \
+                             Advanced app settings > App execution aliases",
+                            alias.name,
+                            real.display(),
+                            apps.path.display(),
+                            real.parent().unwrap_or(real).display()
+                        ),
+                        vec![alias.full_path.clone(), real.to_path_buf()],
+                    ));
+                }
+            } else if let Some(shadowing) = before {
+                diagnostics.push(diagnostic(
+                    Severity::Medium,
+                    format!(
+                        "{} shadows the Store app alias {}; move {} earlier in PATH if the \
+                         Store version is the one you want",
+                        shadowing.display(),
+                        alias.full_path.display(),
+                        apps.path.display()
+                    ),
+                    vec![shadowing.to_path_buf(), alias.full_path.clone()],
+                ));
+            }
+        }
+
+        diagnostics
+    }
+}
+
+impl Default for WindowsAppsAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_windows_apps(entry: &PathEntry) -> bool {
+    let path = entry
+        .path
+        .to_string_lossy()
+        .to_lowercase()
+        .replace('\\', "/");
+    path.trim_end_matches('/')
+        .ends_with("/microsoft/windowsapps")
+}
+
+/// `python3.12.exe` and friends exist only when Store Python is installed
+fn is_versioned_python(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.strip_prefix("python3.")
+        .and_then(|rest| rest.strip_suffix(".exe"))
+        .is_some_and(|minor| !minor.is_empty() && minor.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn find<'a>(entry: &'a PathEntry, name: &str) -> Option<&'a Path> {
+    entry
+        .executables
+        .iter()
+        .find(|e| file_name(e) == name)
+        .map(|e| e.full_path.as_path())
+}
+
+/// Lowercase file name with extension; `name` has it stripped on Windows
+fn file_name(exec: &ExecutableInfo) -> String {
+    exec.full_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+fn diagnostic(severity: Severity, message: String, paths: Vec<PathBuf>) -> Diagnostic {
+    Diagnostic {
+        kind: DiagnosticKind::WindowsApps,
+        severity,
+        message,
+        paths,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const APPS: &str = r"C:\Users\me\AppData\Local\Microsoft\WindowsApps";
+
+    fn executable(dir: &str, file_name: &str) -> ExecutableInfo {
+        let full_path = PathBuf::from(dir).join(file_name);
+        ExecutableInfo {
+            // Stripped of its extension, as the scanner does on Windows
+            name: file_name.rsplit_once('.').unwrap().0.to_string(),
+            full_path: full_path.clone(),
+            resolved_path: full_path,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    fn entry(dir: &str, names: &[&str]) -> PathEntry {
+        PathEntry {
+            path: PathBuf::from(dir),
+            order: 0,
+            exists: true,
+            is_accessible: true,
+            executables: names.iter().map(|name| executable(dir, name)).collect(),
+            sources: Vec::new(),
+            scope: None,
+        }
+    }
+
+    #[test]
+    fn test_stub_before_real_python() {
+        let entries = vec![
+            entry(APPS, &["python.exe", "winget.exe"]),
+            entry(r"C:\Python312", &["python.exe"]),
+        ];
+
+        let diagnostics = WindowsAppsAnalyzer::new().analyze(&entries);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::High);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::WindowsApps);
+    }
+
+    #[test]
+    fn test_store_python_is_not_a_stub() {
+        let entries = vec![
+            entry(APPS, &["python.exe", "python3.12.exe"]),
+            entry(r"C:\Python312", &["python.exe"]),
+        ];
+
+        assert!(WindowsAppsAnalyzer::new().analyze(&entries).is_empty());
+    }
+
+    #[test]
+    fn test_real_python_first() {
+        let entries = vec![
+            entry(r"C:\Python312", &["python.exe"]),
+            entry(APPS, &["python.exe"]),
+        ];
+
+        assert!(WindowsAppsAnalyzer::new().analyze(&entries).is_empty());
+    }
+
+    #[test]
+    fn test_shadowed_store_app() {
+        let entries = vec![entry(r"C:\tools", &["wt.exe"]), entry(APPS, &["wt.exe"])];
+
+        let diagnostics = WindowsAppsAnalyzer::new().analyze(&entries);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Medium);
+    }
+
+    #[test]
+    fn test_lone_stub() {
+        let entries = vec![entry(APPS, &["python3.exe"])];
+
+        let diagnostics = WindowsAppsAnalyzer::new().analyze(&entries);
+        assert_eq!(diagnostics[0].severity, Severity::Low);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::output::types::{
+    Conflict, ExecutableInfo, ManagerType, SecurityFinding, SecurityKind, Severity,
+};
+use crate::platform::{self, DirWriters};
+use std::path::Path;
+
+/// Flags copies in user-writable directories that run instead of a system
+/// command, the classic PATH hijack: whoever can write there decides what
+/// `ls` or `sudo` does.
+///
+/// A directory others can write to is Critical. One only its owner can write
+/// to is High, but only for manually installed copies; Homebrew, nvm and the
+/// like own their directories by design.
+pub struct WritableShadowAnalyzer;
+
+impl WritableShadowAnalyzer {
+    pub fn new() -> Self {
+        WritableShadowAnalyzer
+    }
+
+    pub fn analyze(&self, conflicts: &[Conflict]) -> Vec<SecurityFinding> {
+        self.analyze_with(conflicts, platform::dir_writers)
+    }
+
+    fn analyze_with(
+        &self,
+        conflicts: &[Conflict],
+        writers: impl Fn(&Path) -> DirWriters,
+    ) -> Vec<SecurityFinding> {
+        let mut findings = Vec::new();
+
+        for conflict in conflicts {
+            for (idx, instance) in conflict.instances.iter().enumerate() {
+                let Some(dir) = instance.full_path.parent() else {
+                    continue;
+                };
+                if platform::is_system_bin_dir(dir) {
+                    break;
+                }
+
+                let Some(system) = conflict.instances[idx + 1..]
+                    .iter()
+                    .find(|other| is_system_copy(other) && !other.is_same_file(instance))
+                else {
+                    continue;
+                };
+
+                let severity = match writers(dir) {
+                    DirWriters::Others => Severity::Critical,
+                    DirWriters::Owner if is_manual(instance) => Severity::High,
+                    _ => continue,
+                };
+                let who = if severity == Severity::Critical {
+                    "other users"
+                } else {
+                    "any program running as you"
+                };
+                findings.push(SecurityFinding {
+                    kind: SecurityKind::WritableShadow,
+                    severity,
+                    message: format!(
+                        "{} runs instead of {}, and {} can write to {}",
+                        instance.full_path.display(),
+                        system.full_path.display(),
+                        who,
+                        dir.display()
+                    ),
+                    paths: vec![instance.full_path.clone(), system.full_path.clone()],
+                });
+                break;
+            }
+        }
+
+        findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+        findings
+    }
+}
+
+impl Default for WritableShadowAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_system_copy(instance: &ExecutableInfo) -> bool {
+    instance
+        .full_path
+        .parent()
+        .map(platform::is_system_bin_dir)
+        .unwrap_or(false)
+}
+
+fn is_manual(instance: &ExecutableInfo) -> bool {
+    !matches!(
+        instance.manager.as_ref().map(|m| m.manager_type),
+        Some(
+            ManagerType::VersionManager
+                | ManagerType::PackageManager
+                | ManagerType::EnvironmentModule
+        )
+    )
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::output::types::{ConflictCategory, ManagerInfo};
+    use std::path::PathBuf;
+
+    fn instance(path: &str, manager_type: ManagerType) -> ExecutableInfo {
+        let path = PathBuf::from(path);
+        ExecutableInfo {
+            name: path.file_name().unwrap().to_string_lossy().into(),
+            full_path: path.clone(),
+            size: 100,
+            resolved_path: path,
+            manager: Some(ManagerInfo {
+                manager_type,
+                name: String::new(),
+                description: String::new(),
+            }),
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    fn conflict(instances: Vec<ExecutableInfo>) -> Conflict {
+        Conflict {
+            binary_name: instances[0].name.clone(),
+            active_instance: instances[0].clone(),
+            instances,
+            category: ConflictCategory::ShadowedBinary,
+            severity: Severity::Medium,
+            severity_reason: String::new(),
+            description: String::new(),
+            recommendation: None,
+            commands: Vec::new(),
+            same_content: false,
+            risk_score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_writable_copies_shadowing_system_commands() {
+        let conflicts = vec![
+            conflict(vec![
+                instance("/home/me/bin/ls", ManagerType::ManualInstall),
+                instance("/usr/bin/ls", ManagerType::SystemInstall),
+            ]),
+            conflict(vec![
+                instance("/tmp/shared/sudo", ManagerType::Unknown),
+                instance("/usr/bin/sudo", ManagerType::SystemInstall),
+            ]),
+            // Package managers own their prefix by design
+            conflict(vec![
+                instance("/opt/homebrew/bin/git", ManagerType::PackageManager),
+                instance("/usr/bin/git", ManagerType::SystemInstall),
+            ]),
+            // Nothing from the system is shadowed
+            conflict(vec![
+                instance("/home/me/bin/node", ManagerType::ManualInstall),
+                instance("/home/me/.nvm/bin/node", ManagerType::VersionManager),
+            ]),
+        ];
+        let writers = |dir: &Path| {
+            if dir.starts_with("/tmp") {
+                DirWriters::Others
+            } else if dir.starts_with("/usr") {
+                DirWriters::System
+            } else {
+                DirWriters::Owner
+            }
+        };
+
+        let findings = WritableShadowAnalyzer::new().analyze_with(&conflicts, writers);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].severity, Severity::Critical);
+        assert_eq!(
+            findings[0].message,
+            "/tmp/shared/sudo runs instead of /usr/bin/sudo, and other users can write to /tmp/shared"
+        );
+        assert_eq!(findings[1].severity, Severity::High);
+        assert!(findings[1]
+            .message
+            .starts_with("/home/me/bin/ls runs instead of /usr/bin/ls"));
+    }
+}
@@ -0,0 +1,323 @@
+use crate::output::types::{
+    Conflict, ConflictCategory, Diagnostic, DiagnosticKind, PathEntry, Severity, WslInterop,
+};
+use crate::platform::wsl::{self, WslEnvEntry};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Variables WSL-side toolchains read to locate themselves; a Windows value
+/// makes them pick up the Windows installation
+const TOOLCHAIN_VARS: &[&str] = &[
+    "ANDROID_HOME",
+    "CARGO_HOME",
+    "GOPATH",
+    "GOROOT",
+    "GRADLE_HOME",
+    "JAVA_HOME",
+    "M2_HOME",
+    "NODE_PATH",
+    "NVM_HOME",
+    "PYTHONHOME",
+    "PYTHONPATH",
+    "RUSTUP_HOME",
+];
+
+/// Reviews `WSLENV`, which decides which variables cross the Windows/WSL
+/// boundary, for entries that leak Windows toolchains into WSL
+pub struct WslEnvAnalyzer;
+
+impl WslEnvAnalyzer {
+    pub fn new() -> Self {
+        WslEnvAnalyzer
+    }
+
+    /// `lookup` returns the WSL-side value of a variable, i.e. after WSLENV
+    /// translation
+    pub fn analyze(
+        &self,
+        wslenv: &str,
+        lookup: impl Fn(&str) -> Option<String>,
+        path_entries: &[PathEntry],
+    ) -> Vec<Diagnostic> {
+        let entries = wsl::parse_wslenv(wslenv);
+        let mut diagnostics = Vec::new();
+
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for entry in &entries {
+            *counts.entry(entry.name.as_str()).or_default() += 1;
+        }
+
+        for (idx, entry) in entries.iter().enumerate() {
+            let first = entries[..idx].iter().all(|e| e.name != entry.name);
+            if first && counts[entry.name.as_str()] > 1 {
+                diagnostics.push(diagnostic(
+                    Severity::Low,
+                    format!(
+                        "WSLENV lists {} {} times; keep a single entry",
+                        entry.name,
+                        counts[entry.name.as_str()]
+                    ),
+                    Vec::new(),
+                ));
+            }
+
+            let unknown: String = entry
+                .flags
+                .chars()
+                .filter(|flag| !"pluw".contains(*flag))
+                .collect();
+            if !unknown.is_empty() {
+                diagnostics.push(diagnostic(
+                    Severity::Low,
+                    format!(
+                        "WSLENV entry {}/{} has unknown flag(s) '{}'; valid flags are p, l, u and w",
+                        entry.name, entry.flags, unknown
+                    ),
+                    Vec::new(),
+                ));
+            }
+
+            if entry.name == "PATH" {
+                if entry.reaches_wsl() {
+                    diagnostics.push(shared_path(entry, path_entries));
+                }
+            } else if entry.translates_paths() && entry.reaches_wsl() {
+                diagnostics.extend(translated_toolchain(entry, &lookup, path_entries));
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Recommend turning off `appendWindowsPath` when most conflicts are
+    /// between WSL tools and the Windows copies it appends
+    pub fn interop(&self, interop: &WslInterop, conflicts: &[Conflict]) -> Option<Diagnostic> {
+        if !interop.enabled || !interop.append_windows_path {
+            return None;
+        }
+        let windows = conflicts
+            .iter()
+            .filter(|c| c.category == ConflictCategory::WslVsWindows)
+            .count();
+        if windows == 0 || windows * 2 <= conflicts.len() {
+            return None;
+        }
+
+        Some(Diagnostic {
+            kind: DiagnosticKind::WslInterop,
+            severity: Severity::Medium,
+            message: format!(
+                "{} of {} conflicts are between WSL and Windows copies from the appended \
+                 Windows PATH; set appendWindowsPath = false under [interop] in {} and add back \
+                 only the Windows directories you use (such as VS Code's bin), then run \
+                 `wsl --shutdown` from Windows",
+                windows,
+                conflicts.len(),
+                wsl::WSL_CONF
+            ),
+            paths: vec![PathBuf::from(wsl::WSL_CONF)],
+        })
+    }
+}
+
+impl Default for WslEnvAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// WSL already appends the Windows PATH on its own, so sharing PATH through
+/// WSLENV only adds the same directories a second time
+fn shared_path(entry: &WslEnvEntry, path_entries: &[PathEntry]) -> Diagnostic {
+    let windows_entries: Vec<PathBuf> = path_entries
+        .iter()
+        .filter(|e| wsl::is_windows_path_in_wsl(&e.path))
+        .map(|e| e.path.clone())
+        .collect();
+
+    diagnostic(
+        Severity::Medium,
+        format!(
+            "WSLENV shares {}, but WSL already appends the Windows PATH (appendWindowsPath); \
+             drop it from WSLENV so Windows directories are not added twice, or set \
+             appendWindowsPath = false in /etc/wsl.conf to isolate WSL from Windows tools",
+            label(entry)
+        ),
+        windows_entries,
+    )
+}
+
+/// A translated variable pointing WSL at Windows directories, reported when
+/// those directories also supply PATH entries or the variable is one that
+/// toolchains read
+fn translated_toolchain(
+    entry: &WslEnvEntry,
+    lookup: &impl Fn(&str) -> Option<String>,
+    path_entries: &[PathEntry],
+) -> Option<Diagnostic> {
+    let value = lookup(&entry.name)?;
+    let windows_dirs: Vec<&Path> = value
+        .split(':')
+        .map(Path::new)
+        .filter(|dir| wsl::is_windows_path_in_wsl(dir))
+        .collect();
+    if windows_dirs.is_empty() {
+        return None;
+    }
+
+    let on_path: Vec<PathBuf> = path_entries
+        .iter()
+        .filter(|e| windows_dirs.iter().any(|dir| e.path.starts_with(dir)))
+        .map(|e| e.path.clone())
+        .collect();
+
+    if !on_path.is_empty() {
+        Some(diagnostic(
+            Severity::Medium,
+            format!(
+                "WSLENV translates {} into Windows directories that are also on PATH, so their \
+                 tools are found twice; drop the translation, or use /u to share it only from \
+                 WSL to Windows",
+                label(entry)
+            ),
+            on_path,
+        ))
+    } else if TOOLCHAIN_VARS.contains(&entry.name.as_str()) {
+        Some(diagnostic(
+            Severity::Low,
+            format!(
+                "WSLENV translates {}, so WSL tools that read it use the Windows installation; \
+                 use /u if only Windows needs the value",
+                label(entry)
+            ),
+            windows_dirs.iter().map(|dir| dir.to_path_buf()).collect(),
+        ))
+    } else {
+        None
+    }
+}
+
+fn label(entry: &WslEnvEntry) -> String {
+    if entry.flags.is_empty() {
+        entry.name.clone()
+    } else {
+        format!("{}/{}", entry.name, entry.flags)
+    }
+}
+
+fn diagnostic(severity: Severity, message: String, paths: Vec<PathBuf>) -> Diagnostic {
+    Diagnostic {
+        kind: DiagnosticKind::WslEnv,
+        severity,
+        message,
+        paths,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::types::ExecutableInfo;
+
+    fn entry(path: &str) -> PathEntry {
+        PathEntry {
+            path: PathBuf::from(path),
+            order: 0,
+            exists: true,
+            is_accessible: true,
+            executables: Vec::new(),
+            sources: Vec::new(),
+            scope: None,
+        }
+    }
+
+    fn analyze(wslenv: &str, vars: &[(&str, &str)], path: &[&str]) -> Vec<Diagnostic> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let path_entries: Vec<PathEntry> = path.iter().map(|p| entry(p)).collect();
+        WslEnvAnalyzer::new().analyze(wslenv, |name| vars.get(name).cloned(), &path_entries)
+    }
+
+    #[test]
+    fn test_shared_path() {
+        let diagnostics = analyze("PATH/l", &[], &["/usr/bin", "/mnt/c/Windows"]);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Medium);
+        assert_eq!(diagnostics[0].paths, vec![PathBuf::from("/mnt/c/Windows")]);
+
+        // Only exported from WSL to Windows
+        assert!(analyze("PATH/lu", &[], &["/mnt/c/Windows"]).is_empty());
+    }
+
+    #[test]
+    fn test_translated_toolchain() {
+        let vars = [("GOPATH", "/mnt/c/Users/me/go")];
+
+        let diagnostics = analyze("GOPATH/p", &vars, &["/usr/bin", "/mnt/c/Users/me/go/bin"]);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Medium);
+
+        let diagnostics = analyze("GOPATH/p", &vars, &["/usr/bin"]);
+        assert_eq!(diagnostics[0].severity, Severity::Low);
+
+        assert!(analyze("GOPATH/pu", &vars, &["/mnt/c/Users/me/go/bin"]).is_empty());
+        assert!(analyze("USERPROFILE/p", &[("USERPROFILE", "/mnt/c/Users/me")], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_duplicates_and_unknown_flags() {
+        let diagnostics = analyze("TERM:TERM/x", &[], &[]);
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Low));
+    }
+
+    fn conflict(category: ConflictCategory) -> Conflict {
+        let full_path = PathBuf::from("/usr/bin/git");
+        let instance = ExecutableInfo {
+            name: "git".to_string(),
+            full_path: full_path.clone(),
+            resolved_path: full_path,
+            is_active: true,
+            ..ExecutableInfo::fixture()
+        };
+        Conflict {
+            binary_name: "git".to_string(),
+            instances: vec![instance.clone()],
+            active_instance: instance,
+            category,
+            severity: Severity::Medium,
+            severity_reason: String::new(),
+            description: String::new(),
+            recommendation: None,
+            commands: Vec::new(),
+            same_content: false,
+            risk_score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_interop_recommendation() {
+        let mut interop = wsl::parse_wsl_conf("");
+        let conflicts = vec![
+            conflict(ConflictCategory::WslVsWindows),
+            conflict(ConflictCategory::WslVsWindows),
+            conflict(ConflictCategory::ShadowedBinary),
+        ];
+
+        let diagnostic = WslEnvAnalyzer::new().interop(&interop, &conflicts).unwrap();
+        assert_eq!(diagnostic.kind, DiagnosticKind::WslInterop);
+        assert!(diagnostic.message.starts_with("2 of 3 conflicts"));
+
+        // Not the majority
+        assert!(WslEnvAnalyzer::new()
+            .interop(&interop, &conflicts[1..])
+            .is_none());
+
+        interop.append_windows_path = false;
+        assert!(WslEnvAnalyzer::new()
+            .interop(&interop, &conflicts)
+            .is_none());
+    }
+}
@@ -0,0 +1,286 @@
+use crate::analyzers::{Analyzer, ConflictRule, CustomManager, VersionRequirement};
+use crate::config::VersionCommand;
+use crate::core::{ExecutableScanner, Progress, ProgressReporter};
+use crate::output::types::{HashAlgorithm, HashScope};
+use crate::{AnalysisOptions, PathAnalyzer, VersionProbe};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Fluent construction of a [`PathAnalyzer`]. Anything not set keeps the
+/// value from [`AnalysisOptions::default`].
+///
+/// ```no_run
+/// use path_conflict_detector::PathAnalyzer;
+/// use std::time::Duration;
+///
+/// let analyzer = PathAnalyzer::builder()
+///     .extract_versions(true)
+///     .custom_path("/usr/local/bin:/usr/bin")
+///     .ignore_binary("python*-config")
+///     .max_scan_time(Duration::from_secs(10))
+///     .build();
+/// let result = analyzer.analyze()?;
+/// # Ok::<(), path_conflict_detector::Error>(())
+/// ```
+#[derive(Default)]
+pub struct PathAnalyzerBuilder {
+    options: AnalysisOptions,
+    scanner: ExecutableScanner,
+    analyzers: Vec<Box<dyn Analyzer>>,
+    progress: ProgressReporter,
+}
+
+impl PathAnalyzerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from a complete set of options, replacing any set so far
+    pub fn options(mut self, options: AnalysisOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Run each binary to read its version
+    pub fn extract_versions(mut self, enabled: bool) -> Self {
+        self.options.extract_versions = enabled;
+        self
+    }
+
+    /// Read versions only for binaries involved in conflicts
+    pub fn lazy_versions(mut self, enabled: bool) -> Self {
+        self.options.lazy_versions = enabled;
+        self
+    }
+
+    /// Which binaries may be executed, and the allowlist used by
+    /// `VersionProbe::Allowlist`
+    pub fn version_probe(mut self, probe: VersionProbe, allowlist: Vec<String>) -> Self {
+        self.options.version_probe = probe;
+        self.options.version_allowlist = allowlist;
+        self
+    }
+
+    /// Never execute binaries found on PATH; see `VersionProbe::Never`
+    pub fn no_exec(mut self) -> Self {
+        self.options.version_probe = VersionProbe::Never;
+        self
+    }
+
+    /// Version command for `binary`, tried before the generic flags
+    pub fn version_command(mut self, binary: impl Into<String>, command: VersionCommand) -> Self {
+        self.options.version_commands.insert(binary.into(), command);
+        self
+    }
+
+    /// Kill a version command still running after `timeout`
+    pub fn version_timeout(mut self, timeout: Duration) -> Self {
+        self.options.version_timeout = timeout;
+        self
+    }
+
+    /// Run at most `jobs` version commands at once (0 = one per CPU)
+    pub fn version_jobs(mut self, jobs: usize) -> Self {
+        self.options.version_jobs = jobs;
+        self
+    }
+
+    /// Keep every version command attempted in `ExecutableInfo::version_probes`
+    pub fn record_version_probes(mut self, enabled: bool) -> Self {
+        self.options.record_version_probes = enabled;
+        self
+    }
+
+    /// Never execute `binary` (a name or glob) for its version
+    pub fn skip_version(mut self, binary: impl Into<String>) -> Self {
+        self.options.version_skip_list.push(binary.into());
+        self
+    }
+
+    pub fn resolve_symlinks(mut self, enabled: bool) -> Self {
+        self.options.resolve_symlinks = enabled;
+        self
+    }
+
+    pub fn categorize_managers(mut self, enabled: bool) -> Self {
+        self.options.categorize_managers = enabled;
+        self
+    }
+
+    /// Hash every executable with `algorithm`, over `scope` of each file
+    pub fn file_hashes(mut self, algorithm: HashAlgorithm, scope: HashScope) -> Self {
+        self.options.include_file_hashes = true;
+        self.options.hash_algorithm = algorithm;
+        self.options.hash_scope = scope;
+        self
+    }
+
+    /// Hash with at most `jobs` threads (0 = one per CPU)
+    pub fn hash_jobs(mut self, jobs: usize) -> Self {
+        self.options.hash_jobs = jobs;
+        self
+    }
+
+    /// Leave files larger than `bytes` unhashed
+    pub fn max_hash_file_size(mut self, bytes: u64) -> Self {
+        self.options.max_hash_file_size = Some(bytes);
+        self
+    }
+
+    pub fn detect_binary_kinds(mut self, enabled: bool) -> Self {
+        self.options.detect_binary_kinds = enabled;
+        self
+    }
+
+    /// Ask the OS package database which package owns each conflicting file
+    pub fn detect_origins(mut self, enabled: bool) -> Self {
+        self.options.detect_origins = enabled;
+        self
+    }
+
+    /// Read Homebrew formula versions from the Cellar
+    pub fn brew_metadata(mut self, enabled: bool) -> Self {
+        self.options.brew_metadata = enabled;
+        self
+    }
+
+    /// Compare PATH with the one apps launched from the desktop get; runs
+    /// `launchctl` or `systemctl` unless `no_exec` is set
+    pub fn compare_gui_path(mut self, enabled: bool) -> Self {
+        self.options.compare_gui_path = enabled;
+        self
+    }
+
+    /// Analyze this PATH value instead of the environment's
+    pub fn custom_path(mut self, path: impl Into<String>) -> Self {
+        self.options.custom_path = Some(path.into());
+        self
+    }
+
+    /// Skip stages still pending once `budget` has passed
+    pub fn max_scan_time(mut self, budget: Duration) -> Self {
+        self.options.max_scan_time = Some(budget);
+        self
+    }
+
+    /// Add a conflict rule; later rules win
+    pub fn rule(mut self, rule: ConflictRule) -> Self {
+        self.options.rules.push(rule);
+        self
+    }
+
+    /// Check a version constraint against the active binary
+    pub fn requirement(mut self, requirement: VersionRequirement) -> Self {
+        self.options.requirements.push(requirement);
+        self
+    }
+
+    /// Drop conflicts for binaries matching `pattern`, a name or glob
+    pub fn ignore_binary(mut self, pattern: impl Into<String>) -> Self {
+        self.options.ignored_binaries.push(pattern.into());
+        self
+    }
+
+    /// Drop conflicts whose instances are all the same file or identical copies
+    pub fn hide_identical(mut self, enabled: bool) -> Self {
+        self.options.hide_identical = enabled;
+        self
+    }
+
+    /// Leave this PATH entry unscanned
+    pub fn skip_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.options.skip_dirs.push(dir.into());
+        self
+    }
+
+    /// Check `manager` before the built-in manager patterns
+    pub fn custom_manager(mut self, manager: CustomManager) -> Self {
+        self.options.custom_managers.push(manager);
+        self
+    }
+
+    /// Scan PATH entries with `scanner`, e.g. one from
+    /// `ExecutableScanner::with_options` that descends into subdirectories.
+    /// Skipped directories, the deadline and progress are still applied.
+    pub fn scanner(mut self, scanner: ExecutableScanner) -> Self {
+        self.scanner = scanner;
+        self
+    }
+
+    /// Run `analyzer` over every finished analysis
+    pub fn analyzer(mut self, analyzer: impl Analyzer + 'static) -> Self {
+        self.analyzers.push(Box::new(analyzer));
+        self
+    }
+
+    /// Call `callback` as each stage starts, advances and ends; see
+    /// [`PathAnalyzer::with_progress`]
+    pub fn progress(mut self, callback: impl Fn(Progress) + Send + Sync + 'static) -> Self {
+        self.progress = ProgressReporter::new(callback);
+        self
+    }
+
+    pub fn build(self) -> PathAnalyzer {
+        PathAnalyzer {
+            options: self.options,
+            scanner: self.scanner,
+            analyzers: self.analyzers,
+            progress: self.progress,
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+    use crate::output::types::{AnalysisResult, Diagnostic, DiagnosticKind, Severity};
+    use std::os::unix::fs::PermissionsExt;
+
+    struct ConflictCount;
+
+    impl Analyzer for ConflictCount {
+        fn name(&self) -> &str {
+            "conflict-count"
+        }
+
+        fn analyze(&self, result: &AnalysisResult) -> Result<Vec<Diagnostic>> {
+            Ok(vec![Diagnostic {
+                kind: DiagnosticKind::Plugin,
+                severity: Severity::Info,
+                message: format!("{} conflict(s)", result.conflicts.len()),
+                paths: Vec::new(),
+            }])
+        }
+    }
+
+    #[test]
+    fn test_builder_runs_analyzers_and_ignores() {
+        let root = tempfile::tempdir().unwrap();
+        let mut dirs = Vec::new();
+        for name in ["a", "b"] {
+            let dir = root.path().join(name);
+            std::fs::create_dir(&dir).unwrap();
+            let tool = dir.join("tool");
+            std::fs::write(&tool, "#!/bin/sh\n").unwrap();
+            std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755)).unwrap();
+            dirs.push(dir.display().to_string());
+        }
+
+        let builder = || {
+            PathAnalyzer::builder()
+                .custom_path(dirs.join(":"))
+                .analyzer(ConflictCount)
+        };
+
+        let result = builder().build().analyze().unwrap();
+        assert_eq!(result.conflicts.len(), 1);
+        assert!(result
+            .diagnostics
+            .iter()
+            .any(|d| d.message == "1 conflict(s)"));
+
+        let result = builder().ignore_binary("to*").build().analyze().unwrap();
+        assert!(result.conflicts.is_empty());
+    }
+}
@@ -1,4 +1,7 @@
-use clap::{Parser, ValueEnum};
+use crate::analyzers::Preference;
+use crate::output::types::Severity;
+use crate::platform::shell::ShellKind;
+use clap::{Parser, Subcommand, ValueEnum};
 
 #[derive(Parser, Debug)]
 #[command(name = "path-conflict-detector")]
@@ -9,20 +12,45 @@ use clap::{Parser, ValueEnum};
     path-conflict-detector --binary python\n  \
     path-conflict-detector --severity high\n  \
     path-conflict-detector --category wsl-vs-windows\n  \
-    path-conflict-detector --conflicts-only --recommendations")]
+    path-conflict-detector --conflicts-only --recommendations\n  \
+    path-conflict-detector --require 'python>=3.10' --require 'node^20'\n  \
+    path-conflict-detector --severity medium tui\n  \
+    path-conflict-detector bundle")]
 pub struct Args {
-    /// Output format
-    #[arg(short, long, value_enum, default_value_t = OutputFormat::Human)]
-    pub output: OutputFormat,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Output format [default: human, or `output` in the config file]
+    #[arg(short, long, value_enum)]
+    pub output: Option<OutputFormat>,
 
     /// Use JSON output (shorthand for --output json)
     #[arg(long, conflicts_with = "output")]
     pub json: bool,
 
     /// Check specific binary name
-    #[arg(short, long)]
+    #[arg(short, long, value_hint = clap::ValueHint::CommandName)]
     pub binary: Option<String>,
 
+    /// Never report conflicts for binaries matching NAME, a glob such as
+    /// 'python*-config' (repeatable; added to the config file's list)
+    #[arg(long, value_name = "NAME")]
+    pub ignore: Vec<String>,
+
+    /// Hide conflicts whose copies are all the same file, or byte-identical
+    /// when --include-hashes is given
+    #[arg(long)]
+    pub hide_identical: bool,
+
+    /// Match binary names case-sensitively (true) or not (false); by default
+    /// names ignore case on Windows and macOS, whose filesystems do
+    #[arg(long, value_name = "BOOL")]
+    pub case_sensitive: Option<bool>,
+
+    /// Exit with code 1 when PATH has missing, unreadable or empty entries
+    #[arg(long)]
+    pub path_issues: bool,
+
     /// Filter by conflict category
     #[arg(short, long, value_enum)]
     pub category: Option<CategoryFilter>,
@@ -31,6 +59,11 @@ pub struct Args {
     #[arg(short, long, value_enum)]
     pub severity: Option<SeverityFilter>,
 
+    /// With --output junit, conflicts at or above this severity fail their
+    /// test case
+    #[arg(long, value_enum, value_name = "SEVERITY", default_value = "medium")]
+    pub junit_fail_on: SeverityFilter,
+
     /// Show only conflicts (hide non-conflicting binaries)
     #[arg(long)]
     pub conflicts_only: bool,
@@ -39,36 +72,318 @@ pub struct Args {
     #[arg(long)]
     pub extract_versions: bool,
 
+    /// Extract versions only for binaries involved in conflicts (implies --extract-versions)
+    #[arg(long)]
+    pub lazy_versions: bool,
+
+    /// Which binaries may be executed to read their version
+    #[arg(long, value_enum, default_value_t = VersionProbeMode::All)]
+    pub version_probe: VersionProbeMode,
+
+    /// Never execute binaries found on PATH; read versions from paths, file
+    /// metadata and package databases only
+    #[arg(long)]
+    pub no_exec: bool,
+
+    /// Kill a version command still running after this many seconds
+    #[arg(
+        long,
+        value_name = "SECS",
+        default_value_t = 5,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub version_timeout: u64,
+
+    /// Number of version commands run concurrently (0 = one per CPU)
+    #[arg(long, default_value_t = 0, value_name = "N")]
+    pub version_jobs: usize,
+
+    /// Require a version of the active binary, e.g. 'python>=3.10' or 'node^20' (repeatable)
+    #[arg(long, value_name = "CONSTRAINT")]
+    pub require: Vec<String>,
+
+    /// Read settings from this TOML file instead of the default
+    /// ~/.config/path-conflict-detector/config.toml
+    /// (%APPDATA%\path-conflict-detector\config.toml on Windows)
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<std::path::PathBuf>,
+
+    /// Ignore the default config file
+    #[arg(long, conflicts_with = "config")]
+    pub no_config: bool,
+
     /// Resolve symbolic links
     #[arg(long, default_value_t = true)]
     pub resolve_symlinks: bool,
 
-    /// Include file hash calculations (slower)
+    /// Include full-file hashes (slower)
     #[arg(long)]
     pub include_hashes: bool,
 
+    /// Hash algorithm used with --include-hashes
+    #[arg(long, value_enum, default_value_t = HashAlgo::Sha256)]
+    pub hash_algo: HashAlgo,
+
+    /// How much of each file to hash with --include-hashes
+    #[arg(long, value_enum, default_value_t = HashBytes::Full)]
+    pub hash_bytes: HashBytes,
+
+    /// Number of files hashed concurrently (0 = one per CPU)
+    #[arg(long, default_value_t = 0, value_name = "N")]
+    pub hash_jobs: usize,
+
+    /// Skip hashing files larger than this many megabytes
+    #[arg(long, value_name = "MB")]
+    pub hash_max_size: Option<u64>,
+
+    /// Look up the package that owns each conflicting file (dpkg, rpm, pacman, brew)
+    #[arg(long)]
+    pub origins: bool,
+
+    /// Read each executable's header for its format, architectures and #!
+    /// interpreter, and check script interpreters (implied by --verbose and
+    /// JSON or SBOM output)
+    #[arg(long)]
+    pub binary_kinds: bool,
+
+    /// Read versions and link state of Homebrew formulae from the Cellar
+    /// instead of running the binaries
+    #[arg(long)]
+    pub brew_metadata: bool,
+
     /// Use custom PATH instead of system PATH
     #[arg(long)]
     pub custom_path: Option<String>,
 
-    /// Verbose output
-    #[arg(short, long)]
-    pub verbose: bool,
+    /// Compare PATH with the one apps launched from the desktop get (runs
+    /// launchctl on macOS, systemctl on Linux; ignored with --no-exec)
+    #[arg(long)]
+    pub gui_path: bool,
+
+    /// Don't search shell startup files and PowerShell profiles for the
+    /// lines that add each PATH entry
+    #[arg(long)]
+    pub no_trace_sources: bool,
+
+    /// Analyze as if DIR were first on PATH, reporting the commands it would
+    /// shadow (repeatable)
+    #[arg(long, value_name = "DIR")]
+    pub simulate_prepend: Vec<String>,
+
+    /// Analyze as if DIR were last on PATH (repeatable)
+    #[arg(long, value_name = "DIR")]
+    pub simulate_append: Vec<String>,
+
+    /// Verbose output; repeat for more log detail (-v info, -vv debug,
+    /// -vvv trace)
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Format of log lines written to stderr
+    #[arg(long, value_enum, default_value = "text")]
+    pub log_format: LogFormat,
 
     /// Quiet mode (minimal output)
     #[arg(short, long, conflicts_with = "verbose")]
     pub quiet: bool,
 
+    /// Don't draw a progress bar on stderr while analyzing
+    #[arg(long)]
+    pub no_progress: bool,
+
+    /// Stop after this many seconds and report what was scanned so far
+    #[arg(long, value_name = "SECS")]
+    pub max_scan_time: Option<u64>,
+
+    /// Scan one directory at a time and print shadowed copies as they are
+    /// found, keeping memory bounded on very large PATHs. Reports name
+    /// conflicts only: no versions, categories or severities.
+    #[arg(long)]
+    pub stream: bool,
+
+    /// Run the analyzer plugin in this shared library (repeatable; adds to
+    /// the config file's `plugins`)
+    #[arg(long, value_name = "FILE")]
+    pub plugin: Vec<std::path::PathBuf>,
+
+    /// Save each run's JSON result to DIR (overrides [snapshots] dir)
+    #[arg(long, value_name = "DIR")]
+    pub snapshot_dir: Option<std::path::PathBuf>,
+
+    /// Keep at most N snapshots (0 = no limit; default 50)
+    #[arg(long, value_name = "N")]
+    pub snapshot_keep: Option<usize>,
+
+    /// Delete snapshots older than DAYS (0 = no limit; default 30)
+    #[arg(long, value_name = "DAYS")]
+    pub snapshot_max_age: Option<u64>,
+
+    /// Report only conflicts added, removed, changed in severity or running
+    /// a different copy since the last snapshot; print nothing and exit 0
+    /// when none were
+    #[arg(long)]
+    pub changed_only: bool,
+
+    /// Sign JSON output with this ed25519 secret key (see `keygen`)
+    #[arg(long, value_name = "FILE")]
+    pub sign_key: Option<std::path::PathBuf>,
+
+    /// Never pipe human output through $PAGER
+    #[arg(long)]
+    pub no_pager: bool,
+
     /// Show recommendations for resolving conflicts
     #[arg(long)]
     pub recommendations: bool,
 }
 
+impl Args {
+    /// Output format after applying the `--json` shorthand
+    pub fn output_format(&self) -> OutputFormat {
+        if self.json {
+            OutputFormat::Json
+        } else {
+            self.output.unwrap_or(OutputFormat::Human)
+        }
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Browse the results interactively (analysis options still apply)
+    Tui,
+    /// Run the analysis, optionally saving the full result as a baseline
+    /// for `diff`
+    Scan {
+        /// Write the JSON result to FILE as well as printing the report
+        #[arg(long, value_name = "FILE")]
+        save: Option<std::path::PathBuf>,
+    },
+    /// Compare the current PATH against a baseline saved with `scan --save`:
+    /// new and resolved conflicts, and conflicts whose active copy changed
+    Diff {
+        /// Baseline JSON result (from `scan --save` or `--output json`)
+        baseline: std::path::PathBuf,
+    },
+    /// Replace this binary with the latest GitHub release
+    SelfUpdate {
+        /// Only report whether a newer release exists
+        #[arg(long)]
+        check: bool,
+    },
+    /// Re-run the analysis periodically, printing changes and notifying the
+    /// config file's [[webhooks]]
+    Watch {
+        /// Seconds between runs
+        #[arg(long, default_value_t = 300, value_name = "SECS")]
+        interval: u64,
+    },
+    /// Compare command resolution and conflicts of a hypothetical PATH with
+    /// the current ones. Edits apply to --path (default: the current PATH):
+    /// removals, then prepends, then moves.
+    Simulate {
+        /// Candidate PATH value
+        #[arg(long)]
+        path: Option<String>,
+        /// Drop a directory (repeatable)
+        #[arg(long, visible_alias = "remove", value_name = "DIR")]
+        remove_entry: Vec<String>,
+        /// Put a directory first (repeatable; the last one given ends up first)
+        #[arg(long, value_name = "DIR")]
+        prepend: Vec<String>,
+        /// Move a directory to a 1-based position, `first` or `last`
+        /// (repeatable)
+        #[arg(long, value_name = "DIR=POS")]
+        move_entry: Vec<String>,
+        /// Same as --move-entry with separate arguments (repeatable)
+        #[arg(long = "move", num_args = 2, value_names = ["DIR", "POS"])]
+        move_to: Vec<String>,
+    },
+    /// Write the JSON result, an HTML report, the redacted PATH and
+    /// environment details to one .tar.gz to attach to a support request
+    Bundle {
+        /// Archive to write (default: path-conflict-report-<time>.tar.gz)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Create an ed25519 key pair for --sign-key (FILE and FILE.pub)
+    Keygen {
+        #[arg(default_value = "path-conflict-detector.key", value_name = "FILE")]
+        path: std::path::PathBuf,
+    },
+    /// Check the signature of a report written with --sign-key
+    Verify {
+        /// Signed JSON report
+        report: std::path::PathBuf,
+        /// Also require the report to be signed by this public key
+        #[arg(long, value_name = "FILE")]
+        public_key: Option<std::path::PathBuf>,
+    },
+    /// Write a script of PATH changes that resolve the conflicts, for review.
+    /// Nothing is changed until you run it yourself.
+    Fix {
+        /// Shell to write the script for (default: the current shell)
+        #[arg(long, value_enum)]
+        shell: Option<ShellChoice>,
+        /// Write the script to FILE instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Propose a PATH order in which the preferred copy of each conflicting
+    /// binary runs, and show which commands it would change
+    Optimize {
+        /// Copies that should win, highest first (repeatable; default: the
+        /// config's [optimize] prefer, or all)
+        #[arg(long, value_enum)]
+        prefer: Vec<PreferChoice>,
+    },
+    /// Explain which copy of BINARY runs: every PATH directory searched, in
+    /// order, with the symlink chain, manager and version of each match
+    Why { binary: String },
+    /// Print a completion script for SHELL to stdout
+    Completions {
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// Print cached binary names starting with PREFIX (used by shell
+    /// completion for --binary)
+    #[command(hide = true)]
+    CompleteBinary {
+        #[arg(default_value = "")]
+        prefix: String,
+    },
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum OutputFormat {
     Human,
     Json,
     JsonPretty,
+    /// CycloneDX 1.5 SBOM of every executable on PATH
+    #[value(name = "cyclonedx")]
+    CycloneDx,
+    /// SPDX 2.3 SBOM of every executable on PATH
+    Spdx,
+    /// Standalone HTML page
+    Html,
+    /// Slack Block Kit message (summary and top conflicts)
+    Slack,
+    /// Microsoft Teams Adaptive Card message (summary and top conflicts)
+    Teams,
+    /// SARIF 2.1.0 log for code scanning (one result per conflict)
+    Sarif,
+    /// JUnit XML report, one test case per binary name on PATH
+    Junit,
+    /// One comma-separated row per copy of each conflicting binary
+    Csv,
+    /// Like csv, tab-separated
+    Tsv,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum LogFormat {
+    Text,
+    Json,
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -79,6 +394,9 @@ pub enum CategoryFilter {
     PackageManagerVsSystem,
     DuplicateVersions,
     ShadowedBinary,
+    BrokenSymlink,
+    PathextShadowing,
+    PipMismatch,
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -89,3 +407,83 @@ pub enum SeverityFilter {
     High,
     Critical,
 }
+
+impl From<SeverityFilter> for Severity {
+    fn from(filter: SeverityFilter) -> Self {
+        match filter {
+            SeverityFilter::Info => Severity::Info,
+            SeverityFilter::Low => Severity::Low,
+            SeverityFilter::Medium => Severity::Medium,
+            SeverityFilter::High => Severity::High,
+            SeverityFilter::Critical => Severity::Critical,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ShellChoice {
+    Bash,
+    Zsh,
+    Fish,
+    Sh,
+    #[value(name = "powershell")]
+    PowerShell,
+    Cmd,
+}
+
+impl From<ShellChoice> for ShellKind {
+    fn from(choice: ShellChoice) -> Self {
+        match choice {
+            ShellChoice::Bash => ShellKind::Bash,
+            ShellChoice::Zsh => ShellKind::Zsh,
+            ShellChoice::Fish => ShellKind::Fish,
+            ShellChoice::Sh => ShellKind::Sh,
+            ShellChoice::PowerShell => ShellKind::PowerShell,
+            ShellChoice::Cmd => ShellKind::Cmd,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum PreferChoice {
+    /// Version manager copies (pyenv, nvm, asdf, ...)
+    VersionManagers,
+    /// Package manager copies over system installs
+    PackageManagers,
+    /// Linux copies over Windows ones in WSL
+    Linux,
+}
+
+impl From<PreferChoice> for Preference {
+    fn from(choice: PreferChoice) -> Self {
+        match choice {
+            PreferChoice::VersionManagers => Preference::VersionManagers,
+            PreferChoice::PackageManagers => Preference::PackageManagers,
+            PreferChoice::Linux => Preference::Linux,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum VersionProbeMode {
+    /// Run every binary not known to misbehave
+    All,
+    /// Only run binaries on the allowlist; use static extraction for the rest
+    Allowlist,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum HashAlgo {
+    Sha256,
+    Blake3,
+    Xxh3,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum HashBytes {
+    Full,
+    #[value(name = "8k")]
+    Prefix8K,
+    #[value(name = "1m")]
+    Prefix1M,
+}
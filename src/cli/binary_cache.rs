@@ -0,0 +1,87 @@
+use crate::output::types::AnalysisResult;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Executable names seen by the last analysis of the real PATH, one per line.
+/// Shell completion for `--binary` reads it through the hidden
+/// `complete-binary` subcommand, so completing never rescans PATH.
+pub fn cache_file() -> Option<PathBuf> {
+    let dir = if cfg!(windows) {
+        std::env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else {
+        std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+    }?;
+
+    Some(dir.join("path-conflict-detector").join("binaries"))
+}
+
+/// Refresh the cache from `result`. Failures only cost completions, so they
+/// are logged rather than reported.
+pub fn store(result: &AnalysisResult) {
+    let Some(path) = cache_file() else {
+        return;
+    };
+
+    let names: BTreeSet<&str> = result
+        .path_entries
+        .iter()
+        .flat_map(|entry| entry.executables.iter())
+        .map(|exec| exec.name.as_str())
+        .collect();
+
+    if let Err(e) = write_names(&path, names) {
+        tracing::debug!(path = %path.display(), error = %e, "could not update binary cache");
+    }
+}
+
+/// Cached names starting with `prefix`
+pub fn matching(prefix: &str) -> Vec<String> {
+    cache_file()
+        .map(|path| read_matching(&path, prefix))
+        .unwrap_or_default()
+}
+
+fn write_names<'a>(path: &Path, names: impl IntoIterator<Item = &'a str>) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let mut contents = String::new();
+    for name in names {
+        contents.push_str(name);
+        contents.push('\n');
+    }
+    std::fs::write(path, contents)
+}
+
+fn read_matching(path: &Path, prefix: &str) -> Vec<String> {
+    std::fs::read_to_string(path)
+        .map(|contents| {
+            contents
+                .lines()
+                .filter(|name| name.starts_with(prefix))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache").join("binaries");
+
+        write_names(&path, ["node", "npm", "python3"]).unwrap();
+
+        assert_eq!(read_matching(&path, "n"), vec!["node", "npm"]);
+        assert_eq!(read_matching(&path, ""), vec!["node", "npm", "python3"]);
+        assert!(read_matching(&path, "ruby").is_empty());
+        assert!(read_matching(&dir.path().join("missing"), "").is_empty());
+    }
+}
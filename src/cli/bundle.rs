@@ -0,0 +1,107 @@
+use crate::error::Result;
+use crate::output::types::AnalysisResult;
+use crate::output::{html, json_output};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+/// Default archive name, e.g. `path-conflict-report-20240131T083000Z.tar.gz`
+pub fn default_file_name(result: &AnalysisResult) -> PathBuf {
+    PathBuf::from(format!(
+        "path-conflict-report-{}.tar.gz",
+        result.scan_time.format("%Y%m%dT%H%M%SZ")
+    ))
+}
+
+/// Write a `.tar.gz` with the JSON result, the HTML report, the PATH value
+/// with the home directory and user name redacted, and environment details
+pub fn write(result: &AnalysisResult, path_value: &str, output: &Path) -> Result<()> {
+    let home = home_dir();
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok();
+    let redacted_path = redact(path_value, home.as_deref(), user.as_deref());
+
+    let files = [
+        ("result.json", json_output::format_json(result, true)?),
+        ("report.html", html::format_html(result)),
+        ("path.txt", format!("{}\n", redacted_path)),
+        (
+            "environment.json",
+            serde_json::to_string_pretty(&environment(result))?,
+        ),
+    ];
+
+    let encoder = GzEncoder::new(File::create(output)?, Compression::default());
+    let mut archive = tar::Builder::new(encoder);
+    let mtime = result.scan_time.timestamp().max(0) as u64;
+
+    for (name, contents) in &files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        archive.append_data(&mut header, name, contents.as_bytes())?;
+    }
+
+    archive.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// Replace the home directory with `~` and any other mention of the user
+/// name with `<user>`
+fn redact(value: &str, home: Option<&str>, user: Option<&str>) -> String {
+    let mut redacted = value.to_string();
+    if let Some(home) = home.filter(|h| h.len() > 1) {
+        redacted = redacted.replace(home, "~");
+    }
+    if let Some(user) = user.filter(|u| !u.is_empty()) {
+        redacted = redacted.replace(user, "<user>");
+    }
+    redacted
+}
+
+fn home_dir() -> Option<String> {
+    std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .ok()
+        .map(|home| home.trim_end_matches(['/', '\\']).to_string())
+}
+
+/// What a helper needs to know about the machine, without identifying it
+fn environment(result: &AnalysisResult) -> serde_json::Value {
+    let shell = std::env::var("SHELL")
+        .ok()
+        .or_else(|| std::env::var("ComSpec").ok());
+
+    serde_json::json!({
+        "tool_version": env!("CARGO_PKG_VERSION"),
+        "scan_time": result.scan_time.to_rfc3339(),
+        "os": result.platform.os,
+        "arch": result.platform.arch,
+        "is_wsl": result.platform.is_wsl,
+        "wsl_version": result.platform.wsl_version,
+        "wsl_distro": result.platform.wsl_distro,
+        "wsl_interop": result.platform.wsl_interop,
+        "shell": shell,
+        "path_entries": result.summary.total_path_entries,
+        "conflicts": result.summary.total_conflicts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        let path = "/home/alice/.cargo/bin:/usr/bin:/opt/alice-tools/bin";
+        assert_eq!(
+            redact(path, Some("/home/alice"), Some("alice")),
+            "~/.cargo/bin:/usr/bin:/opt/<user>-tools/bin"
+        );
+        assert_eq!(redact(path, Some("/"), None), path);
+    }
+}
@@ -0,0 +1,58 @@
+use crate::cli::args::Args;
+use crate::error::Result;
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::io::Write;
+
+const BIN_NAME: &str = "path-conflict-detector";
+
+/// Completes `--binary` from the names cached by the last analysis,
+/// deferring to the generated function for everything else
+const BASH_BINARY_HOOK: &str = r#"
+_path_conflict_detector_with_binaries() {
+    case "${COMP_WORDS[COMP_CWORD-1]}" in
+        -b|--binary)
+            COMPREPLY=($(path-conflict-detector complete-binary "${COMP_WORDS[COMP_CWORD]}" 2>/dev/null))
+            return 0
+            ;;
+    esac
+    _path__conflict__detector "$@"
+}
+if [[ "${BASH_VERSINFO[0]}" -eq 4 && "${BASH_VERSINFO[1]}" -ge 4 || "${BASH_VERSINFO[0]}" -gt 4 ]]; then
+    complete -F _path_conflict_detector_with_binaries -o nosort -o bashdefault -o default path-conflict-detector
+else
+    complete -F _path_conflict_detector_with_binaries -o bashdefault -o default path-conflict-detector
+fi
+"#;
+
+const FISH_BINARY_HOOK: &str = r#"
+complete -c path-conflict-detector -s b -l binary -x -a '(path-conflict-detector complete-binary (commandline -ct) 2>/dev/null)'
+"#;
+
+/// Write the completion script for `shell`. Bash and fish scripts also
+/// complete `--binary` through the hidden `complete-binary` subcommand.
+pub fn generate(shell: Shell, out: &mut dyn Write) -> Result<()> {
+    clap_complete::generate(shell, &mut Args::command(), BIN_NAME, out);
+    match shell {
+        Shell::Bash => out.write_all(BASH_BINARY_HOOK.as_bytes())?,
+        Shell::Fish => out.write_all(FISH_BINARY_HOOK.as_bytes())?,
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_lists_enum_values() {
+        let mut script = Vec::new();
+        generate(Shell::Bash, &mut script).unwrap();
+        let script = String::from_utf8(script).unwrap();
+
+        assert!(script.contains("--output"));
+        assert!(script.contains("json-pretty"));
+        assert!(script.contains("complete-binary"));
+    }
+}
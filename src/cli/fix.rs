@@ -0,0 +1,542 @@
+use crate::analyzers::fix_commands::fish_quote;
+use crate::analyzers::FixCommandBuilder;
+use crate::core::path_edit::{self, PathEdit};
+use crate::error::Result;
+use crate::output::types::{AnalysisResult, Conflict, PathSource, RootCauseAction};
+use crate::platform::shell::ShellKind;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+/// `setx` silently truncates longer values
+const SETX_MAX_LEN: usize = 1024;
+
+/// One PATH edit and why it is made
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedEdit {
+    pub edit: PathEdit,
+    pub reason: String,
+}
+
+/// PATH edits derived from the analysis, plus what needs a human decision
+#[derive(Debug, Default)]
+pub struct FixPlan {
+    pub edits: Vec<PlannedEdit>,
+    /// Conflicts and root causes with no unambiguous edit
+    pub review: Vec<String>,
+    /// PATH after every edit
+    pub path: String,
+    /// Startup file lines that add each PATH directory
+    pub sources: BTreeMap<String, Vec<PathSource>>,
+}
+
+impl FixPlan {
+    /// Plan edits against `current`, the PATH value that was analyzed.
+    /// Windows directories behind WSL root causes are removed, and the
+    /// preferred instance of each conflict is moved ahead of the active
+    /// one; an edit that would undo an earlier one goes to review instead.
+    pub fn new(result: &AnalysisResult, current: &str, builder: &FixCommandBuilder) -> Self {
+        let mut plan = FixPlan {
+            path: current.to_string(),
+            sources: result
+                .path_entries
+                .iter()
+                .filter(|entry| !entry.sources.is_empty())
+                .map(|entry| (display(&entry.path), entry.sources.clone()))
+                .collect(),
+            ..FixPlan::default()
+        };
+
+        for root_cause in &result.root_causes {
+            match root_cause.action {
+                RootCauseAction::Remove => plan.push(
+                    PathEdit::Remove(display(&root_cause.directory)),
+                    root_cause.message.clone(),
+                ),
+                RootCauseAction::MoveAfter => plan.review.push(root_cause.message.clone()),
+            }
+        }
+
+        let mut conflicts: Vec<&Conflict> = result.conflicts.iter().collect();
+        conflicts.sort_by(|a, b| a.binary_name.cmp(&b.binary_name));
+
+        // Binaries sharing the same pair of directories become one edit
+        let mut moves: Vec<(String, String, Vec<&Conflict>)> = Vec::new();
+        for conflict in conflicts {
+            let preferred = builder
+                .preferred_instance(conflict.category, &conflict.instances)
+                .filter(|p| p.full_path != conflict.active_instance.full_path);
+            let dirs = preferred.and_then(|p| {
+                Some((
+                    display(p.full_path.parent()?),
+                    display(conflict.active_instance.full_path.parent()?),
+                ))
+            });
+
+            match dirs {
+                Some((dir, before)) => {
+                    match moves.iter_mut().find(|(d, b, _)| *d == dir && *b == before) {
+                        Some((_, _, grouped)) => grouped.push(conflict),
+                        None => moves.push((dir, before, vec![conflict])),
+                    }
+                }
+                None => {
+                    let advice = conflict
+                        .recommendation
+                        .as_deref()
+                        .unwrap_or(&conflict.description);
+                    plan.review.push(format!(
+                        "{} ({}): {}",
+                        conflict.binary_name, conflict.category, advice
+                    ));
+                }
+            }
+        }
+
+        for (dir, before, grouped) in moves {
+            let names: Vec<&str> = grouped.iter().map(|c| c.binary_name.as_str()).collect();
+            let reason = format!(
+                "{} ({}): prefer {} over {}",
+                names.join(", "),
+                grouped[0].category,
+                dir,
+                before
+            );
+            plan.push(PathEdit::MoveBefore { dir, before }, reason);
+        }
+
+        plan
+    }
+
+    /// Apply `edit` if it keeps every earlier move in place
+    fn push(&mut self, edit: PathEdit, reason: String) {
+        let candidate = match path_edit::apply_edits(&self.path, std::slice::from_ref(&edit)) {
+            Ok(candidate) => candidate,
+            // The directory is already gone, e.g. removed by a root cause
+            Err(_) => return,
+        };
+
+        let kept = self.edits.iter().all(|planned| match &planned.edit {
+            PathEdit::MoveBefore { dir, before } => path_edit::is_before(&candidate, dir, before),
+            _ => true,
+        });
+        if kept {
+            self.path = candidate;
+            self.edits.push(PlannedEdit { edit, reason });
+        } else {
+            self.review.push(format!(
+                "{} (not applied: it undoes an earlier change)",
+                reason
+            ));
+        }
+    }
+
+    /// The script for `shell`: what changes and why as comments, the new
+    /// PATH for this session, and a commented-out line to keep it
+    pub fn script(&self, shell: ShellKind, builder: &FixCommandBuilder) -> String {
+        let comment = if shell == ShellKind::Cmd { "REM" } else { "#" };
+        let mut out = String::new();
+        let mut line = |text: &str| {
+            let _ = if text.is_empty() {
+                writeln!(out, "{}", comment)
+            } else {
+                writeln!(out, "{} {}", comment, comment_safe(text))
+            };
+        };
+
+        line(&format!(
+            "PATH fixes from {} {} for {}. Review before running; nothing has been changed.",
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            shell
+        ));
+
+        if self.edits.is_empty() {
+            line("");
+            line("No PATH change resolves the reported conflicts.");
+        } else {
+            line("");
+            line("Changes:");
+            for planned in &self.edits {
+                line(&format!("  {}", planned.edit));
+                line(&format!("    {}", planned.reason));
+                if let Some(sources) = self.sources.get(edited_dir(&planned.edit)) {
+                    let sources: Vec<String> = sources.iter().map(ToString::to_string).collect();
+                    line(&format!("    added by {}", sources.join(", ")));
+                }
+            }
+        }
+
+        if !self.review.is_empty() {
+            line("");
+            line("Needs a decision (not changed):");
+            for item in &self.review {
+                line(&format!("  {}", item));
+            }
+        }
+
+        if self.edits.is_empty() {
+            return out;
+        }
+
+        let _ = writeln!(out);
+        let _ = writeln!(out, "{}", builder.set_path(&self.path));
+        let _ = writeln!(out);
+
+        if shell == ShellKind::Fish {
+            let (commands, notes) = self.fish_keep(builder);
+            if !notes.is_empty() {
+                let _ = writeln!(
+                    out,
+                    "# Not in fish_user_paths; edit where they are added instead:"
+                );
+                for note in notes {
+                    let _ = writeln!(out, "#   {}", comment_safe(&note));
+                }
+            }
+            if !commands.is_empty() {
+                let _ = writeln!(
+                    out,
+                    "# To keep it, uncomment the next lines. They edit fish_user_paths, which \
+                     fish puts ahead of the inherited PATH."
+                );
+                for command in commands {
+                    let _ = writeln!(out, "# {}", comment_safe(&command));
+                }
+            }
+            return out;
+        }
+
+        let keep = match shell {
+            ShellKind::PowerShell | ShellKind::Cmd => {
+                "To keep it, uncomment the next line. It stores the whole PATH, machine \
+                 entries included, as your user PATH."
+            }
+            _ => "To keep it, uncomment the next line or copy the line above to your startup file.",
+        };
+        let _ = writeln!(out, "{} {}", comment, keep);
+        if shell == ShellKind::Cmd && self.path.len() > SETX_MAX_LEN {
+            let _ = writeln!(
+                out,
+                "{} setx truncates values over {} characters; edit PATH in System Properties \
+                 instead.",
+                comment, SETX_MAX_LEN
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{} {}",
+            comment,
+            comment_safe(&builder.persist_path(&self.path))
+        );
+
+        let notes = self.profile_notes();
+        if shell == ShellKind::PowerShell && !notes.is_empty() {
+            let _ = writeln!(out);
+            let _ = writeln!(
+                out,
+                "# Your profile sets these again in every new session; edit those lines too:"
+            );
+            for note in notes {
+                let _ = writeln!(out, "#   {}", comment_safe(&note));
+            }
+        }
+        out
+    }
+
+    /// The PowerShell profile lines that add an edited directory, which
+    /// would undo the edit when the next session starts
+    fn profile_notes(&self) -> Vec<String> {
+        self.edits
+            .iter()
+            .map(|planned| edited_dir(&planned.edit))
+            .flat_map(|dir| {
+                self.sources
+                    .get(dir)
+                    .into_iter()
+                    .flatten()
+                    .filter(|source| {
+                        source
+                            .file
+                            .extension()
+                            .map(|ext| ext.eq_ignore_ascii_case("ps1"))
+                            .unwrap_or(false)
+                    })
+                    .map(move |source| format!("{}: {}", dir, source))
+            })
+            .collect()
+    }
+
+    /// fish commands that make the edits last, `fish_add_path` for moves
+    /// and removals from the universal `fish_user_paths`, plus notes for
+    /// removed directories that some other file or the login environment
+    /// adds
+    fn fish_keep(&self, builder: &FixCommandBuilder) -> (Vec<String>, Vec<String>) {
+        let (mut commands, mut notes) = (Vec::new(), Vec::new());
+        for planned in &self.edits {
+            let dir = edited_dir(&planned.edit);
+            let sources = self.sources.get(dir).map(Vec::as_slice).unwrap_or_default();
+            let in_user_paths = sources.iter().any(|source| {
+                source
+                    .file
+                    .file_name()
+                    .map(|name| name == "fish_variables")
+                    .unwrap_or(false)
+            });
+
+            match &planned.edit {
+                PathEdit::Remove(_) if in_user_paths => commands.push(format!(
+                    "set -U fish_user_paths (string match -v -- {} $fish_user_paths)",
+                    fish_quote(dir)
+                )),
+                PathEdit::Remove(_) => match sources.first() {
+                    Some(source) => notes.push(format!("{}: {}", dir, source)),
+                    None => notes.push(format!("{}: the login environment", dir)),
+                },
+                _ => commands.extend(builder.prepend_to_path(Path::new(dir))),
+            }
+        }
+        (commands, notes)
+    }
+}
+
+/// `text` escaped to stay on one comment line. Names of files and PATH
+/// directories can contain line breaks, which would end the comment and
+/// leave the rest to run as a command.
+fn comment_safe(text: &str) -> String {
+    text.replace('\r', "\\r").replace('\n', "\\n")
+}
+
+/// The directory an edit moves or removes
+fn edited_dir(edit: &PathEdit) -> &str {
+    match edit {
+        PathEdit::Remove(dir)
+        | PathEdit::Move { dir, .. }
+        | PathEdit::MoveBefore { dir, .. }
+        | PathEdit::Prepend(dir)
+        | PathEdit::Append(dir) => dir,
+    }
+}
+
+/// Write the fix script to `output`, or stdout
+pub fn run(
+    result: &AnalysisResult,
+    current: &str,
+    shell: ShellKind,
+    output: Option<&Path>,
+) -> Result<()> {
+    let builder = FixCommandBuilder::new(shell);
+    let script = FixPlan::new(result, current, &builder).script(shell, &builder);
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, script)?;
+            eprintln!("Wrote {}; review it before running it", path.display());
+        }
+        None => print!("{}", script),
+    }
+    Ok(())
+}
+
+fn display(path: &Path) -> String {
+    path.display().to_string()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::output::types::{
+        ConflictCategory, ExecutableInfo, ManagerInfo, ManagerType, PathEntry, PlatformInfo,
+        Severity, Summary,
+    };
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn instance(path: &str, manager_type: ManagerType) -> ExecutableInfo {
+        let path = PathBuf::from(path);
+        ExecutableInfo {
+            name: path.file_name().unwrap().to_string_lossy().into(),
+            full_path: path.clone(),
+            size: 100,
+            resolved_path: path,
+            manager: Some(ManagerInfo {
+                manager_type,
+                name: String::new(),
+                description: String::new(),
+            }),
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    fn conflict(
+        name: &str,
+        category: ConflictCategory,
+        instances: Vec<ExecutableInfo>,
+    ) -> Conflict {
+        Conflict {
+            binary_name: name.to_string(),
+            active_instance: instances[0].clone(),
+            instances,
+            category,
+            severity: Severity::Medium,
+            severity_reason: String::new(),
+            description: String::new(),
+            recommendation: Some(format!("Check {}", name)),
+            commands: Vec::new(),
+            same_content: false,
+            risk_score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_fix_plan_and_script() {
+        let system = ManagerType::SystemInstall;
+        let pyenv = ManagerType::VersionManager;
+        let result = AnalysisResult {
+            scan_time: Utc::now(),
+            platform: PlatformInfo {
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                is_wsl: false,
+                wsl_version: None,
+                wsl_distro: None,
+                wsl_interop: None,
+            },
+            path_entries: vec![PathEntry {
+                path: PathBuf::from("/home/me/.pyenv/shims"),
+                order: 2,
+                exists: true,
+                is_accessible: true,
+                executables: Vec::new(),
+                sources: vec![
+                    PathSource {
+                        file: PathBuf::from("/etc/fish/config.fish"),
+                        line: 7,
+                    },
+                    PathSource {
+                        file: PathBuf::from("/opt/ps/profile.ps1"),
+                        line: 3,
+                    },
+                ],
+                scope: None,
+            }],
+            conflicts: vec![
+                conflict(
+                    "python3",
+                    ConflictCategory::VersionManagerVsSystem,
+                    vec![
+                        instance("/usr/bin/python3", system),
+                        instance("/home/me/.pyenv/shims/python3", pyenv),
+                    ],
+                ),
+                conflict(
+                    "pip3",
+                    ConflictCategory::VersionManagerVsSystem,
+                    vec![
+                        instance("/usr/bin/pip3", system),
+                        instance("/home/me/.pyenv/shims/pip3", pyenv),
+                    ],
+                ),
+                conflict(
+                    "ls",
+                    ConflictCategory::ShadowedBinary,
+                    vec![instance("/usr/bin/ls", system), instance("/bin/ls", system)],
+                ),
+            ],
+            summary: Summary {
+                total_path_entries: 3,
+                total_executables: 6,
+                unique_executables: 3,
+                total_conflicts: 3,
+                conflicts_by_category: HashMap::new(),
+                conflicts_by_severity: HashMap::new(),
+                risk_score: 0.0,
+                conflicts_by_manager: vec![],
+            },
+            diagnostics: vec![],
+            requirements: vec![],
+            root_causes: vec![],
+            path_issues: vec![],
+            security: vec![],
+            signature: None,
+        };
+
+        let builder = FixCommandBuilder::new(ShellKind::Bash);
+        let plan = FixPlan::new(&result, "/usr/bin:/bin:/home/me/.pyenv/shims", &builder);
+        assert_eq!(plan.path, "/home/me/.pyenv/shims:/usr/bin:/bin");
+        assert_eq!(plan.edits.len(), 1);
+        assert_eq!(
+            plan.edits[0].reason,
+            "pip3, python3 (Version Manager vs System): prefer /home/me/.pyenv/shims over /usr/bin"
+        );
+        assert_eq!(plan.review, vec!["ls (Shadowed Binary): Check ls"]);
+
+        let script = plan.script(ShellKind::Bash, &builder);
+        assert!(script.contains("\nexport PATH=\"/home/me/.pyenv/shims:/usr/bin:/bin\"\n"));
+        assert!(
+            script.contains("\n#     added by /etc/fish/config.fish:7, /opt/ps/profile.ps1:3\n")
+        );
+        assert!(!script.contains("edit those lines too"));
+        assert!(script.contains("\n# echo 'export PATH="));
+
+        let fish = FixCommandBuilder::new(ShellKind::Fish);
+        let script = plan.script(ShellKind::Fish, &fish);
+        assert!(script.contains("\nset -gx PATH '/home/me/.pyenv/shims' '/usr/bin' '/bin'\n"));
+        assert!(script.ends_with("\n# fish_add_path --move --prepend '/home/me/.pyenv/shims'\n"));
+
+        let powershell = FixCommandBuilder::new(ShellKind::PowerShell);
+        let script = plan.script(ShellKind::PowerShell, &powershell);
+        assert!(script.ends_with(
+            "edit those lines too:\n#   /home/me/.pyenv/shims: /opt/ps/profile.ps1:3\n"
+        ));
+    }
+
+    #[test]
+    fn test_line_breaks_stay_in_comments() {
+        let name = "x\nrm -rf ~";
+        let system = ManagerType::SystemInstall;
+        let mut instances = vec![
+            instance("/usr/local/bin/x", system),
+            instance("/usr/bin/x", system),
+        ];
+        for instance in &mut instances {
+            instance.name = name.to_string();
+        }
+        let result = AnalysisResult {
+            scan_time: Utc::now(),
+            platform: PlatformInfo {
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                is_wsl: false,
+                wsl_version: None,
+                wsl_distro: None,
+                wsl_interop: None,
+            },
+            path_entries: vec![],
+            conflicts: vec![conflict(name, ConflictCategory::ShadowedBinary, instances)],
+            summary: Summary {
+                total_path_entries: 2,
+                total_executables: 2,
+                unique_executables: 1,
+                total_conflicts: 1,
+                conflicts_by_category: HashMap::new(),
+                conflicts_by_severity: HashMap::new(),
+                risk_score: 0.0,
+                conflicts_by_manager: vec![],
+            },
+            diagnostics: vec![],
+            requirements: vec![],
+            root_causes: vec![],
+            path_issues: vec![],
+            security: vec![],
+            signature: None,
+        };
+
+        let builder = FixCommandBuilder::new(ShellKind::Bash);
+        let plan = FixPlan::new(&result, "/usr/local/bin:/usr/bin", &builder);
+        let script = plan.script(ShellKind::Bash, &builder);
+        assert!(script.contains("#   x\\nrm -rf ~ (Shadowed Binary): Check x\\nrm -rf ~\n"));
+        assert!(script.lines().all(|line| line.starts_with('#')));
+    }
+}
@@ -1,5 +1,18 @@
 pub mod args;
+pub mod binary_cache;
+pub mod bundle;
+pub mod completions;
+pub mod fix;
+pub mod optimize;
+pub mod pager;
+pub mod progress;
 pub mod runner;
+#[cfg(feature = "self-update")]
+pub mod self_update;
+pub mod simulate;
+pub mod watch;
+pub mod webhooks;
+pub mod why;
 
 pub use args::Args;
 pub use runner::run;
@@ -0,0 +1,76 @@
+use crate::analyzers::{PathOptimizer, Preference};
+use crate::cli::args::{Args, OutputFormat};
+use crate::cli::simulate::{compare_resolutions, format_resolutions};
+use crate::error::Result;
+use crate::output::types::AnalysisResult;
+use colored::*;
+
+/// Print the PATH order `PathOptimizer` proposes for the analyzed conflicts
+/// and the commands that would run a different copy under it
+pub fn run(result: &AnalysisResult, preferences: Vec<Preference>, args: &Args) -> Result<()> {
+    let proposed =
+        PathOptimizer::new(preferences).optimize(&result.path_entries, &result.conflicts);
+    let reordered = proposed.apply(&result.path_entries);
+    let resolutions = compare_resolutions(
+        &result.path_entries,
+        &reordered,
+        crate::cli::runner::case_insensitive_names(args),
+    );
+    let separator = crate::platform::get_path_separator().to_string();
+    let path_value = reordered
+        .iter()
+        .map(|entry| entry.path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(&separator);
+
+    match args.output_format() {
+        OutputFormat::Json | OutputFormat::JsonPretty => {
+            let value = serde_json::json!({
+                "proposed_path": reordered.iter().map(|e| &e.path).collect::<Vec<_>>(),
+                "resolutions": resolutions,
+                "unresolved": proposed.unresolved,
+            });
+            if matches!(args.output_format(), OutputFormat::Json) {
+                println!("{}", value);
+            } else {
+                println!("{}", serde_json::to_string_pretty(&value)?);
+            }
+        }
+        _ => {
+            println!("{}", "Proposed PATH:".bold());
+            for (position, &original) in proposed.order.iter().enumerate() {
+                // Entries only shift down to make room for those moved up
+                let moved = if position < original {
+                    format!("  (moved up from {})", original + 1)
+                        .yellow()
+                        .to_string()
+                } else {
+                    String::new()
+                };
+                println!(
+                    "  {:>2}. {}{}",
+                    position + 1,
+                    result.path_entries[original].path.display(),
+                    moved
+                );
+            }
+            println!();
+            println!("{}", "Commands that would run a different copy:".bold());
+            print!("{}", format_resolutions(&resolutions));
+            if !proposed.unresolved.is_empty() {
+                println!();
+                println!(
+                    "{}",
+                    "Not resolved (their preferred copy conflicts with a more common one):".bold()
+                );
+                println!("  {}", proposed.unresolved.join(", "));
+            }
+            if !resolutions.is_empty() {
+                println!();
+                println!("Preview it with: simulate --path '{}'", path_value);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+/// Print `text`, piping it through `$PAGER` (or `less`) when stdout is a
+/// terminal and the text would not fit on one screen
+pub fn print_paged(text: &str, enabled: bool) {
+    if enabled && std::io::stdout().is_terminal() && exceeds_terminal(text) {
+        if let Some(pager) = pager_command() {
+            if run_pager(&pager, text).is_ok() {
+                return;
+            }
+        }
+    }
+
+    println!("{}", text);
+}
+
+fn exceeds_terminal(text: &str) -> bool {
+    let height = terminal_size::terminal_size()
+        .map(|(_, terminal_size::Height(h))| h as usize)
+        .or_else(|| std::env::var("LINES").ok()?.parse().ok())
+        .unwrap_or(24);
+
+    text.lines().count() >= height
+}
+
+/// `$PAGER` split into program and arguments; an empty `$PAGER` disables
+/// paging
+fn pager_command() -> Option<Vec<String>> {
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less".to_string());
+    let parts: Vec<String> = pager.split_whitespace().map(str::to_string).collect();
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts)
+    }
+}
+
+fn run_pager(pager: &[String], text: &str) -> std::io::Result<()> {
+    let mut command = Command::new(&pager[0]);
+    command.args(&pager[1..]).stdin(Stdio::piped());
+
+    // Keep colors, and quit right away if the text fits after all
+    if std::env::var_os("LESS").is_none() {
+        command.env("LESS", "FRX");
+    }
+
+    let mut child = command.spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The user quitting the pager early closes the pipe; not an error
+        let _ = writeln!(stdin, "{}", text);
+    }
+    child.wait()?;
+    Ok(())
+}
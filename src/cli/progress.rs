@@ -0,0 +1,47 @@
+use crate::core::{Progress, Stage};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::io::IsTerminal;
+use std::sync::Mutex;
+
+/// Progress bar on stderr that follows the stages of an analysis
+pub struct StageBar {
+    bar: ProgressBar,
+    stage: Mutex<Option<Stage>>,
+}
+
+impl StageBar {
+    /// A bar when stderr is a terminal, otherwise `None` so that logs and
+    /// redirected output stay clean
+    pub fn for_terminal() -> Option<Self> {
+        if !std::io::stderr().is_terminal() {
+            return None;
+        }
+
+        let bar = ProgressBar::with_draw_target(None, ProgressDrawTarget::stderr());
+        bar.set_style(
+            ProgressStyle::with_template("{spinner} {msg:<27} [{bar:30}] {pos}/{len}")
+                .unwrap_or_else(|_| ProgressStyle::default_bar())
+                .progress_chars("=> "),
+        );
+        Some(StageBar {
+            bar,
+            stage: Mutex::new(None),
+        })
+    }
+
+    pub fn update(&self, progress: Progress) {
+        if let Ok(mut stage) = self.stage.lock() {
+            if *stage != Some(progress.stage) {
+                *stage = Some(progress.stage);
+                self.bar.set_message(progress.stage.to_string());
+                self.bar.set_length(progress.total as u64);
+            }
+        }
+        self.bar.set_position(progress.done as u64);
+    }
+
+    /// Remove the bar before the report is printed
+    pub fn finish(&self) {
+        self.bar.finish_and_clear();
+    }
+}
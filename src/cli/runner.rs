@@ -1,36 +1,481 @@
-use crate::cli::args::{Args, OutputFormat};
+use crate::analyzers::{
+    AnalyzerPlugin, ConflictRule, RootCauseAnalyzer, SimulatedEntryAnalyzer, VersionRequirement,
+};
+use crate::cli::args::{Args, Command, LogFormat, OutputFormat};
+use crate::cli::progress::StageBar;
+use crate::cli::webhooks::Webhook;
+use crate::cli::{binary_cache, bundle, completions, fix, optimize, pager, simulate, watch, why};
+use crate::core::path_edit::{self, PathEdit};
 use crate::error::Result;
-use crate::output::{formatter::HumanFormatter, json_output};
-use crate::{AnalysisOptions, PathAnalyzer};
+use crate::output::changes::ChangeReport;
+use crate::output::csv::Delimiter;
+use crate::output::snapshots::{Retention, SnapshotStore};
+use crate::output::types::Conflict;
+use crate::output::{chat, csv, formatter::HumanFormatter, html, json_output, junit, sarif, sbom};
+use crate::{AnalysisOptions, Config, PathAnalyzer};
+
+pub fn run(mut args: Args) -> Result<()> {
+    init_logging(&args);
+
+    match &args.command {
+        Some(Command::SelfUpdate { check }) => return self_update(*check),
+        Some(Command::Keygen { path }) => return keygen(path),
+        Some(Command::Verify { report, public_key }) => {
+            return verify(report, public_key.as_deref())
+        }
+        Some(Command::Completions { shell }) => {
+            return completions::generate(*shell, &mut std::io::stdout().lock())
+        }
+        Some(Command::CompleteBinary { prefix }) => {
+            for name in binary_cache::matching(prefix) {
+                println!("{}", name);
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    // --config, or the default file when there is one
+    let config_path = match &args.config {
+        Some(path) => Some(path.clone()),
+        None if args.no_config => None,
+        None => Config::default_path().filter(|path| path.is_file()),
+    };
+    let config = match &config_path {
+        Some(path) => {
+            tracing::info!(path = %path.display(), "loading config");
+            Config::load(path)?
+        }
+        None => Config::default(),
+    };
+    if let Some(path) = &config_path {
+        apply_config_defaults(&mut args, &config, path)?;
+    }
 
-pub fn run(args: Args) -> Result<()> {
     // Determine output format
-    let output_format = if args.json {
-        OutputFormat::Json
+    let output_format = args.output_format();
+    if args.sign_key.is_some()
+        && !matches!(output_format, OutputFormat::Json | OutputFormat::JsonPretty)
+    {
+        return Err(crate::Error::MissingOption {
+            option: "--sign-key".to_string(),
+            requirement: "JSON output (--json or --output json-pretty)".to_string(),
+        });
+    }
+
+    let version_probe = match args.version_probe {
+        _ if args.no_exec => crate::VersionProbe::Never,
+        crate::cli::args::VersionProbeMode::All => crate::VersionProbe::All,
+        crate::cli::args::VersionProbeMode::Allowlist => crate::VersionProbe::Allowlist,
+    };
+    let version_allowlist = config
+        .version
+        .allowlist
+        .unwrap_or_else(crate::analyzers::version_extractor::default_allowlist);
+    let mut requirements = Vec::new();
+    for (binary, constraint) in &config.require {
+        requirements.push(VersionRequirement::for_binary(binary, constraint)?);
+    }
+    for spec in &args.require {
+        requirements.push(VersionRequirement::parse(spec)?);
+    }
+
+    let snapshots = snapshot_store(&args, &config.snapshots);
+    if args.changed_only && snapshots.is_none() {
+        return Err(crate::Error::MissingOption {
+            option: "--changed-only".to_string(),
+            requirement: "a snapshot directory (--snapshot-dir or [snapshots] dir)".to_string(),
+        });
+    }
+
+    // Load plugins up front so a bad one fails before the scan
+    let plugins = config
+        .plugins
+        .iter()
+        .chain(&args.plugin)
+        .map(|path| {
+            let path = crate::platform::expand_env_vars(&path.to_string_lossy());
+            AnalyzerPlugin::load(std::path::Path::new(&path))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let rules = config
+        .rules
+        .iter()
+        .map(ConflictRule::from_config)
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut version_commands = crate::analyzers::version_extractor::default_commands();
+    version_commands.extend(config.version.commands);
+    let mut version_skip_list = if config.version.skip.replace_defaults {
+        Vec::new()
     } else {
-        args.output
+        crate::analyzers::version_extractor::default_skip_list()
+    };
+    version_skip_list.extend(config.version.skip.names);
+
+    let hash_algorithm = match args.hash_algo {
+        crate::cli::args::HashAlgo::Sha256 => crate::output::types::HashAlgorithm::Sha256,
+        crate::cli::args::HashAlgo::Blake3 => crate::output::types::HashAlgorithm::Blake3,
+        crate::cli::args::HashAlgo::Xxh3 => crate::output::types::HashAlgorithm::Xxh3,
+    };
+
+    let hash_scope = match args.hash_bytes {
+        crate::cli::args::HashBytes::Full => crate::output::types::HashScope::Full,
+        crate::cli::args::HashBytes::Prefix8K => crate::output::types::HashScope::Prefix8K,
+        crate::cli::args::HashBytes::Prefix1M => crate::output::types::HashScope::Prefix1M,
+    };
+
+    // --simulate-prepend/--simulate-append analyze an edited copy of PATH
+    let simulated: Vec<PathEdit> = args
+        .simulate_prepend
+        .iter()
+        .cloned()
+        .map(PathEdit::Prepend)
+        .chain(args.simulate_append.iter().cloned().map(PathEdit::Append))
+        .collect();
+    let custom_path = if simulated.is_empty() {
+        args.custom_path.clone()
+    } else {
+        Some(path_edit::apply_edits(&current_path(&args)?, &simulated)?)
     };
 
     // Build analysis options from CLI args
     let options = AnalysisOptions {
         extract_versions: args.extract_versions,
+        lazy_versions: args.lazy_versions,
+        version_probe,
+        version_allowlist,
+        version_commands,
+        record_version_probes: args.verbose > 0 || !matches!(output_format, OutputFormat::Human),
+        version_skip_list,
+        version_timeout: std::time::Duration::from_secs(args.version_timeout),
+        version_jobs: args.version_jobs,
         resolve_symlinks: args.resolve_symlinks,
         categorize_managers: true,
         include_file_hashes: args.include_hashes,
-        custom_path: args.custom_path,
+        // Headers are read only where the result is shown
+        detect_binary_kinds: args.binary_kinds
+            || args.verbose > 0
+            || matches!(
+                output_format,
+                OutputFormat::Json
+                    | OutputFormat::JsonPretty
+                    | OutputFormat::CycloneDx
+                    | OutputFormat::Spdx
+            ),
+        detect_origins: args.origins,
+        brew_metadata: args.brew_metadata,
+        hash_algorithm,
+        hash_scope,
+        hash_jobs: args.hash_jobs,
+        max_hash_file_size: args.hash_max_size.map(|mb| mb.saturating_mul(1024 * 1024)),
+        custom_path,
+        requirements,
+        rules,
+        ignored_binaries: config.ignore.iter().chain(&args.ignore).cloned().collect(),
+        hide_identical: args.hide_identical,
+        case_insensitive_names: case_insensitive_names(&args),
+        compare_gui_path: args.gui_path,
+        trace_path_sources: !args.no_trace_sources,
+        skip_dirs: config
+            .skip_dirs
+            .iter()
+            .map(|dir| crate::platform::expand_env_vars(&dir.to_string_lossy()).into())
+            .collect(),
+        custom_managers: config
+            .managers
+            .iter()
+            .map(crate::analyzers::CustomManager::from_config)
+            .collect::<Result<Vec<_>>>()?,
+        max_scan_time: args.max_scan_time.map(std::time::Duration::from_secs),
     };
 
+    if let Some(Command::Simulate {
+        path,
+        remove_entry,
+        prepend,
+        move_entry,
+        move_to,
+    }) = &args.command
+    {
+        let current = current_path(&args)?;
+        let mut edits: Vec<PathEdit> = remove_entry.iter().cloned().map(PathEdit::Remove).collect();
+        edits.extend(prepend.iter().cloned().map(PathEdit::Prepend));
+        for spec in move_entry {
+            edits.push(PathEdit::parse_move(spec)?);
+        }
+        for pair in move_to.chunks(2) {
+            edits.push(PathEdit::move_to(&pair[0], &pair[1])?);
+        }
+        if path.is_none() && edits.is_empty() {
+            return Err(crate::Error::MissingOption {
+                option: "simulate".to_string(),
+                requirement: "--path, --remove, --prepend or --move".to_string(),
+            });
+        }
+
+        let candidate = path_edit::apply_edits(path.as_deref().unwrap_or(&current), &edits)?;
+        return simulate::run(&options, &current, &candidate, &args);
+    }
+
+    if let Some(Command::Why { binary }) = &args.command {
+        return why::run(&options, binary, &args);
+    }
+
     // Create analyzer and run analysis
-    let analyzer = PathAnalyzer::with_options(options);
-    let mut result = analyzer.analyze()?;
+    let analyzer = plugins
+        .into_iter()
+        .fold(
+            PathAnalyzer::builder().options(options),
+            |builder, plugin| builder.analyzer(plugin),
+        )
+        .build();
+
+    if args.stream {
+        return stream(&analyzer, &args);
+    }
+
+    if let Some(Command::Watch { interval }) = &args.command {
+        let webhooks = config
+            .webhooks
+            .iter()
+            .map(Webhook::from_config)
+            .collect::<Result<Vec<_>>>()?;
+        return watch::run(
+            &analyzer,
+            std::time::Duration::from_secs(*interval),
+            &args,
+            snapshots.as_ref(),
+            &webhooks,
+        );
+    }
+
+    // Logs from -v would tear through the bar
+    let bar = if args.quiet || args.no_progress || args.verbose > 0 {
+        None
+    } else {
+        StageBar::for_terminal().map(std::sync::Arc::new)
+    };
+    let analyzer = match &bar {
+        Some(bar) => {
+            let bar = std::sync::Arc::clone(bar);
+            analyzer.with_progress(move |progress| bar.update(progress))
+        }
+        None => analyzer,
+    };
+
+    let result = analyzer.analyze();
+    if let Some(bar) = &bar {
+        bar.finish();
+    }
+    let mut result = result?;
+
+    // Only the real PATH is worth completing from
+    if args.custom_path.is_none() && simulated.is_empty() {
+        binary_cache::store(&result);
+    }
+
+    for edit in &simulated {
+        let (PathEdit::Prepend(dir) | PathEdit::Append(dir)) = edit else {
+            continue;
+        };
+        // Match the directory the way the scanner recorded it
+        if let Some(entry) = crate::core::PathParser::new().parse_path(dir)?.first() {
+            let diagnostic = SimulatedEntryAnalyzer::new().analyze(
+                &edit.to_string(),
+                &entry.path,
+                &result.conflicts,
+            );
+            result.diagnostics.push(diagnostic);
+        }
+    }
+
+    // Read the previous snapshot before this run replaces it as the latest
+    let previous = match &snapshots {
+        Some(store) if args.changed_only => store.latest()?,
+        _ => None,
+    };
+
+    // Snapshots hold the unfiltered result of the real PATH so runs stay
+    // comparable
+    if let Some(store) = snapshots.as_ref().filter(|_| simulated.is_empty()) {
+        let path = store.save(&result)?;
+        tracing::info!(path = %path.display(), "saved snapshot");
+    }
+
+    if let Some(Command::Scan { save: Some(path) }) = &args.command {
+        std::fs::write(path, json_output::format_json(&result, true)?)?;
+        tracing::info!(path = %path.display(), "saved baseline");
+    }
+
+    retain_selected(&args, &mut result.conflicts);
+
+    if let Some(Command::Diff { baseline }) = &args.command {
+        let mut baseline: crate::AnalysisResult =
+            serde_json::from_str(&std::fs::read_to_string(baseline)?)?;
+        retain_selected(&args, &mut baseline.conflicts);
+        let report = ChangeReport::compare(Some(&baseline), &result.conflicts);
+        // Unlike --changed-only, an explicit diff says when nothing changed
+        if !report.has_changes() && !args.quiet && matches!(output_format, OutputFormat::Human) {
+            print!("{}", report.format_human());
+        }
+        return report_changes(&report, &args);
+    }
+
+    if args.changed_only {
+        let previous = previous.map(|mut previous| {
+            retain_selected(&args, &mut previous.conflicts);
+            previous
+        });
+        return report_changes(
+            &ChangeReport::compare(previous.as_ref(), &result.conflicts),
+            &args,
+        );
+    }
 
-    // Filter conflicts if needed
-    if let Some(binary_name) = &args.binary {
-        result.conflicts.retain(|c| c.binary_name == *binary_name);
+    // Update summary after filtering
+    result.summary.total_conflicts = result.conflicts.len();
+    result.root_causes = RootCauseAnalyzer::new().analyze(&result.conflicts);
+
+    if let Some(key) = &args.sign_key {
+        sign(&mut result, key)?;
+    }
+
+    match &args.command {
+        Some(Command::Tui) => return run_tui(&result),
+        Some(Command::Bundle { output }) => {
+            let path_value = current_path(&args)?;
+            let output = output
+                .clone()
+                .unwrap_or_else(|| bundle::default_file_name(&result));
+            bundle::write(&result, &path_value, &output)?;
+            println!("Wrote {}", output.display());
+            return Ok(());
+        }
+        Some(Command::Fix { shell, output }) => {
+            let shell = shell
+                .map(Into::into)
+                .unwrap_or_else(crate::platform::shell::detect_shell);
+            return fix::run(&result, &current_path(&args)?, shell, output.as_deref());
+        }
+        Some(Command::Optimize { prefer }) => {
+            let preferences = if prefer.is_empty() {
+                config
+                    .optimize
+                    .prefer
+                    .clone()
+                    .unwrap_or_else(crate::analyzers::Preference::all)
+            } else {
+                prefer.iter().copied().map(Into::into).collect()
+            };
+            return optimize::run(&result, preferences, &args);
+        }
+        _ => {}
+    }
+
+    // Format and output
+    match output_format {
+        OutputFormat::Human => {
+            let formatter = HumanFormatter::new(args.recommendations, args.verbose > 0);
+            let output = formatter.format(&result);
+            if !args.quiet {
+                pager::print_paged(&output, !args.no_pager);
+            }
+        }
+        OutputFormat::Json => {
+            let json = json_output::format_json(&result, false)?;
+            println!("{}", json);
+        }
+        OutputFormat::JsonPretty => {
+            let json = json_output::format_json(&result, true)?;
+            println!("{}", json);
+        }
+        OutputFormat::CycloneDx => println!("{}", sbom::format_cyclonedx(&result)?),
+        OutputFormat::Spdx => println!("{}", sbom::format_spdx(&result)?),
+        OutputFormat::Html => print!("{}", html::format_html(&result)),
+        OutputFormat::Slack => println!("{}", chat::format_slack(&result, None)),
+        OutputFormat::Teams => println!("{}", chat::format_teams(&result, None)),
+        OutputFormat::Sarif => println!("{}", sarif::format_sarif(&result)?),
+        OutputFormat::Junit => print!(
+            "{}",
+            junit::format_junit(&result, args.junit_fail_on.into())
+        ),
+        OutputFormat::Csv => print!("{}", csv::format_delimited(&result, Delimiter::Comma)),
+        OutputFormat::Tsv => print!("{}", csv::format_delimited(&result, Delimiter::Tab)),
+    }
+
+    // Exit with non-zero code if conflicts found, requirements are unmet or,
+    // with --path-issues, PATH has dead entries (unless quiet mode)
+    let unmet_requirements = result.requirements.iter().any(|r| !r.satisfied);
+    let path_issues = args.path_issues && !result.path_issues.is_empty();
+    if (!result.conflicts.is_empty() || unmet_requirements || path_issues) && !args.quiet {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Fill in options the command line left unset from the config file
+fn apply_config_defaults(args: &mut Args, config: &Config, path: &std::path::Path) -> Result<()> {
+    use clap::ValueEnum;
+
+    let invalid = |key: &str, value: &str| crate::Error::ConfigError {
+        path: path.display().to_string(),
+        reason: format!("{}: unknown value '{}'", key, value),
+    };
+
+    if args.severity.is_none() {
+        if let Some(severity) = &config.severity {
+            args.severity = Some(
+                crate::cli::args::SeverityFilter::from_str(severity, true)
+                    .map_err(|_| invalid("severity", severity))?,
+            );
+        }
+    }
+    if args.output.is_none() && !args.json {
+        if let Some(output) = &config.output {
+            args.output =
+                Some(OutputFormat::from_str(output, true).map_err(|_| invalid("output", output))?);
+        }
+    }
+    Ok(())
+}
+
+/// The PATH being analyzed: --custom-path, or the process PATH
+fn current_path(args: &Args) -> Result<String> {
+    match &args.custom_path {
+        Some(path) => Ok(path.clone()),
+        None => crate::platform::get_path_env_var(),
+    }
+}
+
+/// Whether binary names ignore case: --case-sensitive, or the platform default
+pub(crate) fn case_insensitive_names(args: &Args) -> bool {
+    args.case_sensitive
+        .map(|sensitive| !sensitive)
+        .unwrap_or_else(crate::platform::is_case_insensitive)
+}
+
+/// Whether `name` passes the --binary filter
+fn is_selected_binary(args: &Args, name: &str) -> bool {
+    match &args.binary {
+        Some(binary_name) if case_insensitive_names(args) => {
+            name.to_lowercase() == binary_name.to_lowercase()
+        }
+        Some(binary_name) => name == binary_name,
+        None => true,
+    }
+}
+
+/// Apply the --binary, --category and --severity filters
+pub(crate) fn retain_selected(args: &Args, conflicts: &mut Vec<Conflict>) {
+    if args.binary.is_some() {
+        conflicts.retain(|c| is_selected_binary(args, &c.binary_name));
     }
 
     if let Some(category_filter) = args.category {
-        result.conflicts.retain(|c| {
+        conflicts.retain(|c| {
             matches!(
                 (category_filter, c.category),
                 (
@@ -51,49 +496,231 @@ pub fn run(args: Args) -> Result<()> {
                 ) | (
                     crate::cli::args::CategoryFilter::ShadowedBinary,
                     crate::output::types::ConflictCategory::ShadowedBinary
+                ) | (
+                    crate::cli::args::CategoryFilter::BrokenSymlink,
+                    crate::output::types::ConflictCategory::BrokenSymlink
+                ) | (
+                    crate::cli::args::CategoryFilter::PathextShadowing,
+                    crate::output::types::ConflictCategory::PathextShadowing
+                ) | (
+                    crate::cli::args::CategoryFilter::PipMismatch,
+                    crate::output::types::ConflictCategory::PipMismatch
                 )
             )
         });
     }
 
     if let Some(severity_filter) = args.severity {
-        let min_severity = match severity_filter {
-            crate::cli::args::SeverityFilter::Info => crate::output::types::Severity::Info,
-            crate::cli::args::SeverityFilter::Low => crate::output::types::Severity::Low,
-            crate::cli::args::SeverityFilter::Medium => crate::output::types::Severity::Medium,
-            crate::cli::args::SeverityFilter::High => crate::output::types::Severity::High,
-            crate::cli::args::SeverityFilter::Critical => crate::output::types::Severity::Critical,
-        };
+        let min_severity = crate::output::types::Severity::from(severity_filter);
+        conflicts.retain(|c| c.severity >= min_severity);
+    }
+}
 
-        result.conflicts.retain(|c| c.severity >= min_severity);
+/// Print what changed since the last snapshot, exiting 1 if anything did.
+/// Silent when nothing changed, so scheduled runs only mail real news.
+fn report_changes(report: &ChangeReport, args: &Args) -> Result<()> {
+    if !report.has_changes() {
+        return Ok(());
     }
 
-    // Update summary after filtering
-    result.summary.total_conflicts = result.conflicts.len();
+    if !args.quiet {
+        match args.output_format() {
+            OutputFormat::Json => println!("{}", serde_json::to_string(report)?),
+            OutputFormat::JsonPretty => println!("{}", serde_json::to_string_pretty(report)?),
+            _ => print!("{}", report.format_human()),
+        }
+    }
+    std::process::exit(1);
+}
 
-    // Format and output
-    match output_format {
-        OutputFormat::Human => {
-            let formatter = HumanFormatter::new(args.recommendations, args.verbose);
-            let output = formatter.format(&result);
-            if !args.quiet {
-                println!("{}", output);
-            }
+/// Print each shadowed copy as soon as its directory is scanned: a line per
+/// copy, or one JSON object per line. Exits 1 if any name conflicts.
+fn stream(analyzer: &PathAnalyzer, args: &Args) -> Result<()> {
+    use crate::core::StreamEvent;
+
+    let json = match args.output_format() {
+        OutputFormat::Human => false,
+        OutputFormat::Json | OutputFormat::JsonPretty => true,
+        _ => {
+            return Err(crate::Error::MissingOption {
+                option: "--stream".to_string(),
+                requirement: "human or JSON output".to_string(),
+            })
         }
-        OutputFormat::Json => {
-            let json = json_output::format_json(&result, false)?;
-            println!("{}", json);
+    };
+
+    let mut reported = 0;
+    let summary = analyzer.analyze_streaming(|conflict| {
+        if !is_selected_binary(args, &conflict.binary_name) {
+            return;
         }
-        OutputFormat::JsonPretty => {
-            let json = json_output::format_json(&result, true)?;
-            println!("{}", json);
+        reported += 1;
+        if args.quiet {
+            return;
+        }
+        if json {
+            if let Ok(line) = serde_json::to_string(&StreamEvent::Conflict(conflict)) {
+                println!("{}", line);
+            }
+        } else {
+            println!(
+                "{}: {} is shadowed by {}{}",
+                conflict.binary_name,
+                conflict.shadowed.display(),
+                conflict.active.display(),
+                if conflict.same_file {
+                    " (same file)"
+                } else {
+                    ""
+                }
+            );
+        }
+    })?;
+
+    if !args.quiet {
+        if json {
+            println!("{}", serde_json::to_string(&StreamEvent::Summary(summary))?);
+        } else {
+            println!(
+                "Scanned {} of {} PATH entries: {} executables, {} conflicting names, {} \
+                 shadowed copies{}",
+                summary.scanned_directories,
+                summary.total_path_entries,
+                summary.total_executables,
+                summary.conflicting_names,
+                summary.shadowed_copies,
+                if summary.timed_out {
+                    " (stopped at --max-scan-time)"
+                } else {
+                    ""
+                }
+            );
         }
     }
 
-    // Exit with non-zero code if conflicts found (unless quiet mode)
-    if !result.conflicts.is_empty() && !args.quiet {
+    if reported > 0 && !args.quiet {
         std::process::exit(1);
     }
+    Ok(())
+}
+
+/// Snapshot settings from the CLI, falling back to the config file
+fn snapshot_store(args: &Args, config: &crate::config::SnapshotConfig) -> Option<SnapshotStore> {
+    let dir = args.snapshot_dir.as_ref().or(config.dir.as_ref())?;
+    let defaults = Retention::default();
+    let retention = Retention {
+        keep_last: args
+            .snapshot_keep
+            .or(config.keep)
+            .unwrap_or(defaults.keep_last),
+        max_age_days: args
+            .snapshot_max_age
+            .or(config.max_age_days)
+            .unwrap_or(defaults.max_age_days),
+    };
+
+    // Config values may use ~ and environment variables
+    let dir = crate::platform::expand_env_vars(&dir.to_string_lossy());
+    Some(SnapshotStore::new(dir).with_retention(retention))
+}
+
+#[cfg(feature = "self-update")]
+fn self_update(check_only: bool) -> Result<()> {
+    crate::cli::self_update::run(check_only)
+}
+
+#[cfg(not(feature = "self-update"))]
+fn self_update(_check_only: bool) -> Result<()> {
+    Err(crate::Error::FeatureDisabled {
+        feature: "self-update".to_string(),
+    })
+}
+
+#[cfg(feature = "signing")]
+fn keygen(path: &std::path::Path) -> Result<()> {
+    let public_key = crate::output::signing::generate_key(path)?;
+    println!(
+        "Wrote secret key {} and public key {}",
+        path.display(),
+        public_key.display()
+    );
+    Ok(())
+}
 
+#[cfg(feature = "signing")]
+fn sign(result: &mut crate::AnalysisResult, key: &std::path::Path) -> Result<()> {
+    crate::output::signing::sign(result, key)
+}
+
+#[cfg(feature = "signing")]
+fn verify(report: &std::path::Path, public_key: Option<&std::path::Path>) -> Result<()> {
+    let contents = std::fs::read_to_string(report)?;
+    let signature = crate::output::signing::verify(&contents, public_key)?;
+    println!(
+        "{}: signature OK ({}, key {})",
+        report.display(),
+        signature.digest,
+        signature.public_key
+    );
     Ok(())
 }
+
+#[cfg(not(feature = "signing"))]
+fn keygen(_path: &std::path::Path) -> Result<()> {
+    Err(signing_disabled())
+}
+
+#[cfg(not(feature = "signing"))]
+fn sign(_result: &mut crate::AnalysisResult, _key: &std::path::Path) -> Result<()> {
+    Err(signing_disabled())
+}
+
+#[cfg(not(feature = "signing"))]
+fn verify(_report: &std::path::Path, _public_key: Option<&std::path::Path>) -> Result<()> {
+    Err(signing_disabled())
+}
+
+#[cfg(not(feature = "signing"))]
+fn signing_disabled() -> crate::Error {
+    crate::Error::FeatureDisabled {
+        feature: "signing".to_string(),
+    }
+}
+
+#[cfg(feature = "tui")]
+fn run_tui(result: &crate::AnalysisResult) -> Result<()> {
+    crate::tui::run(result)
+}
+
+#[cfg(not(feature = "tui"))]
+fn run_tui(_result: &crate::AnalysisResult) -> Result<()> {
+    Err(crate::Error::FeatureDisabled {
+        feature: "tui".to_string(),
+    })
+}
+
+/// Send `tracing` events to stderr at the level picked by `-v`/`--quiet`
+fn init_logging(args: &Args) {
+    let level = if args.quiet {
+        tracing::Level::ERROR
+    } else {
+        match args.verbose {
+            0 => tracing::Level::WARN,
+            1 => tracing::Level::INFO,
+            2 => tracing::Level::DEBUG,
+            _ => tracing::Level::TRACE,
+        }
+    };
+
+    let builder = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::IsTerminal::is_terminal(&std::io::stderr()))
+        .with_target(false);
+
+    // A subscriber may already be installed when embedded; keep it
+    let _ = match args.log_format {
+        LogFormat::Text => builder.try_init(),
+        LogFormat::Json => builder.json().try_init(),
+    };
+}
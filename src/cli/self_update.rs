@@ -0,0 +1,192 @@
+use crate::analyzers::VersionRequirement;
+use crate::core::binary_info::to_hex;
+use crate::error::{Error, Result};
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/kasaiarashi/path-conflict-detector/releases/latest";
+
+/// Checksum manifest published with every release
+const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+/// Release binaries are well under this; anything larger is not ours
+const MAX_DOWNLOAD_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Replace the running binary with the latest GitHub release, after checking
+/// its SHA-256 against the release's `SHA256SUMS`. With `check_only`, just
+/// report whether an update exists.
+pub fn run(check_only: bool) -> Result<()> {
+    let current = env!("CARGO_PKG_VERSION");
+    let release: serde_json::Value = serde_json::from_slice(&download(LATEST_RELEASE_URL)?)?;
+
+    let tag = release["tag_name"]
+        .as_str()
+        .ok_or_else(|| update_error("release has no tag"))?;
+    let latest = tag.trim_start_matches('v');
+
+    if !is_newer(latest, current) {
+        println!("path-conflict-detector {} is up to date", current);
+        return Ok(());
+    }
+
+    println!("Update available: {} -> {}", current, latest);
+    if check_only {
+        return Ok(());
+    }
+
+    let asset = asset_name().ok_or_else(|| update_error("no release binary for this platform"))?;
+    let checksums = String::from_utf8(download(&asset_url(&release, CHECKSUMS_ASSET)?)?)?;
+    let expected = find_checksum(&checksums, asset)
+        .ok_or_else(|| update_error(&format!("{} is not listed in {}", asset, CHECKSUMS_ASSET)))?;
+
+    let binary = download(&asset_url(&release, asset)?)?;
+    let actual = to_hex(&Sha256::digest(&binary));
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(update_error(&format!(
+            "checksum mismatch for {}: expected {}, got {}",
+            asset, expected, actual
+        )));
+    }
+
+    let exe = std::env::current_exe()?;
+    replace_executable(&exe, &binary)?;
+    println!("Updated {} to {}", exe.display(), latest);
+    Ok(())
+}
+
+fn update_error(reason: &str) -> Error {
+    Error::UpdateError {
+        reason: reason.to_string(),
+    }
+}
+
+fn download(url: &str) -> Result<Vec<u8>> {
+    let response = ureq::get(url)
+        .set(
+            "User-Agent",
+            concat!("path-conflict-detector/", env!("CARGO_PKG_VERSION")),
+        )
+        .call()
+        .map_err(|e| update_error(&format!("{}: {}", url, e)))?;
+
+    let mut body = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_DOWNLOAD_BYTES)
+        .read_to_end(&mut body)?;
+    Ok(body)
+}
+
+fn asset_url(release: &serde_json::Value, name: &str) -> Result<String> {
+    release["assets"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|asset| asset["name"] == name)
+        .and_then(|asset| asset["browser_download_url"].as_str())
+        .map(str::to_string)
+        .ok_or_else(|| update_error(&format!("release has no {} asset", name)))
+}
+
+/// Asset names published by the release workflow
+fn asset_name() -> Option<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => Some("path-conflict-detector-linux-x64"),
+        ("windows", "x86_64") => Some("path-conflict-detector-windows-x64.exe"),
+        ("macos", "x86_64") => Some("path-conflict-detector-macos-x64"),
+        ("macos", "aarch64") => Some("path-conflict-detector-macos-arm64"),
+        _ => None,
+    }
+}
+
+fn is_newer(latest: &str, current: &str) -> bool {
+    VersionRequirement::for_binary("path-conflict-detector", &format!(">{}", current))
+        .map(|requirement| requirement.matches(latest))
+        .unwrap_or(false)
+}
+
+/// Look up `asset` in `sha256sum` output (`<hex>  <name>`, or `<hex> *<name>`
+/// for binary mode)
+fn find_checksum<'a>(checksums: &'a str, asset: &str) -> Option<&'a str> {
+    checksums.lines().find_map(|line| {
+        let (digest, name) = line.trim().split_once(char::is_whitespace)?;
+        let name = name.trim_start().trim_start_matches('*');
+        (name == asset).then_some(digest)
+    })
+}
+
+/// Write the new binary next to the old one and rename it into place, so a
+/// failed download never leaves a half-written executable
+fn replace_executable(exe: &Path, binary: &[u8]) -> Result<()> {
+    let staged = sibling(exe, "new");
+    std::fs::write(&staged, binary)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    // Windows cannot overwrite a running executable, but it can rename it
+    #[cfg(windows)]
+    {
+        let old = sibling(exe, "old");
+        let _ = std::fs::remove_file(&old);
+        std::fs::rename(exe, &old)?;
+    }
+
+    std::fs::rename(&staged, exe)?;
+    Ok(())
+}
+
+fn sibling(exe: &Path, suffix: &str) -> PathBuf {
+    let mut name = exe.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    exe.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_checksum() {
+        let sums = "abc123  path-conflict-detector-linux-x64\n\
+                    def456 *path-conflict-detector-windows-x64.exe\n";
+        assert_eq!(
+            find_checksum(sums, "path-conflict-detector-linux-x64"),
+            Some("abc123")
+        );
+        assert_eq!(
+            find_checksum(sums, "path-conflict-detector-windows-x64.exe"),
+            Some("def456")
+        );
+        assert_eq!(
+            find_checksum(sums, "path-conflict-detector-macos-x64"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("0.2.0", "0.1.0"));
+        assert!(is_newer("1.0.0", "0.9.9"));
+        assert!(!is_newer("0.1.0", "0.1.0"));
+        assert!(!is_newer("0.0.9", "0.1.0"));
+    }
+
+    #[test]
+    fn test_replace_executable() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("tool");
+        std::fs::write(&exe, b"old").unwrap();
+
+        replace_executable(&exe, b"new").unwrap();
+
+        assert_eq!(std::fs::read(&exe).unwrap(), b"new");
+        assert!(!sibling(&exe, "new").exists());
+    }
+}
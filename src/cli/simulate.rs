@@ -0,0 +1,291 @@
+use crate::cli::args::{Args, OutputFormat};
+use crate::cli::runner::retain_selected;
+use crate::error::Result;
+use crate::output::changes::ChangeReport;
+use crate::output::types::PathEntry;
+use crate::{platform, AnalysisOptions, PathAnalyzer};
+use colored::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// How running a command by name would change
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolutionKind {
+    /// Only found on the candidate PATH
+    Gained,
+    /// No longer found
+    Lost,
+    /// Found on both, but a different copy runs
+    Changed,
+}
+
+/// A command whose active copy differs between the current and candidate PATH
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct Resolution {
+    pub binary: String,
+    pub kind: ResolutionKind,
+    /// The copy that runs now
+    pub before: Option<PathBuf>,
+    /// The copy that would run
+    pub after: Option<PathBuf>,
+}
+
+/// Every command whose active copy differs between two scans, sorted by name.
+/// With `case_insensitive`, names differing only in case are one command.
+pub fn compare_resolutions(
+    before: &[PathEntry],
+    after: &[PathEntry],
+    case_insensitive: bool,
+) -> Vec<Resolution> {
+    let before = active_copies(before, case_insensitive);
+    let mut after = active_copies(after, case_insensitive);
+    let mut resolutions = Vec::new();
+
+    for (key, (binary, was)) in before {
+        let (kind, now) = match after.remove(&key).map(|(_, now)| now) {
+            None => (ResolutionKind::Lost, None),
+            // On a case-insensitive filesystem both spellings open one file
+            Some(now)
+                if crate::name_key(&now.to_string_lossy(), case_insensitive)
+                    == crate::name_key(&was.to_string_lossy(), case_insensitive) =>
+            {
+                continue
+            }
+            Some(now) => (ResolutionKind::Changed, Some(now)),
+        };
+        resolutions.push(Resolution {
+            binary,
+            kind,
+            before: Some(was),
+            after: now,
+        });
+    }
+    resolutions.extend(after.into_values().map(|(binary, now)| Resolution {
+        binary,
+        kind: ResolutionKind::Gained,
+        before: None,
+        after: Some(now),
+    }));
+
+    resolutions.sort_by(|a, b| a.binary.cmp(&b.binary));
+    resolutions
+}
+
+/// The name and first copy of each command in PATH order, keyed by
+/// `name_key`; shells skip broken links
+fn active_copies(
+    entries: &[PathEntry],
+    case_insensitive: bool,
+) -> BTreeMap<String, (String, PathBuf)> {
+    let mut active = BTreeMap::new();
+    for exec in entries
+        .iter()
+        .flat_map(|entry| &entry.executables)
+        .filter(|exec| !exec.broken)
+    {
+        active
+            .entry(crate::name_key(&exec.name, case_insensitive))
+            .or_insert_with(|| (exec.name.clone(), exec.full_path.clone()));
+    }
+    active
+}
+
+pub(crate) fn format_resolutions(resolutions: &[Resolution]) -> String {
+    if resolutions.is_empty() {
+        return "  Every command would run the same copy as now.\n".to_string();
+    }
+
+    let width = resolutions
+        .iter()
+        .map(|r| r.binary.len())
+        .max()
+        .unwrap_or(0);
+    let show = |path: &Option<PathBuf>| {
+        path.as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_default()
+    };
+    let mut output = String::new();
+    for resolution in resolutions {
+        let line = match resolution.kind {
+            ResolutionKind::Gained => format!(
+                "  {} {:<width$}  {}",
+                "+".green(),
+                resolution.binary,
+                show(&resolution.after),
+                width = width
+            ),
+            ResolutionKind::Lost => format!(
+                "  {} {:<width$}  no longer found (was {})",
+                "-".red(),
+                resolution.binary,
+                show(&resolution.before),
+                width = width
+            ),
+            ResolutionKind::Changed => format!(
+                "  {} {:<width$}  {} (was {})",
+                "~".yellow(),
+                resolution.binary,
+                show(&resolution.after),
+                show(&resolution.before),
+                width = width
+            ),
+        };
+        output.push_str(&line);
+        output.push('\n');
+    }
+    output
+}
+
+/// Analyze `candidate` and `current` the same way and print which commands
+/// would run a different copy, and how the conflicts would change, if PATH
+/// were `candidate`
+pub fn run(options: &AnalysisOptions, current: &str, candidate: &str, args: &Args) -> Result<()> {
+    let analyze = |path: &str| {
+        let mut options = options.clone();
+        options.custom_path = Some(path.to_string());
+        let mut result = PathAnalyzer::with_options(options).analyze()?;
+        retain_selected(args, &mut result.conflicts);
+        Ok::<_, crate::Error>(result)
+    };
+
+    let before = analyze(current)?;
+    let after = analyze(candidate)?;
+    let report = ChangeReport::compare(Some(&before), &after.conflicts);
+    let resolutions = compare_resolutions(
+        &before.path_entries,
+        &after.path_entries,
+        options.case_insensitive_names,
+    );
+
+    let entries: Vec<&str> = candidate
+        .split(platform::get_path_separator())
+        .filter(|entry| !entry.trim().is_empty())
+        .collect();
+
+    match args.output_format() {
+        OutputFormat::Json | OutputFormat::JsonPretty => {
+            let value = serde_json::json!({
+                "candidate_path": entries,
+                "resolutions": resolutions,
+                "changes": report,
+            });
+            if matches!(args.output_format(), OutputFormat::Json) {
+                println!("{}", value);
+            } else {
+                println!("{}", serde_json::to_string_pretty(&value)?);
+            }
+        }
+        _ => {
+            println!("{}", "Simulated PATH:".bold());
+            for (idx, entry) in entries.iter().enumerate() {
+                println!("  {:>2}. {}", idx + 1, entry);
+            }
+            println!();
+            println!("{}", "Commands that would run a different copy:".bold());
+            print!("{}", format_resolutions(&resolutions));
+            println!();
+            println!("{}", "Conflicts compared with the current PATH:".bold());
+            print!("{}", report.format_changes());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::output::types::ExecutableInfo;
+
+    fn entry(dir: &str, names: &[&str]) -> PathEntry {
+        let executables = names
+            .iter()
+            .map(|name| {
+                let path = PathBuf::from(dir).join(name);
+                ExecutableInfo {
+                    name: name.to_string(),
+                    full_path: path.clone(),
+                    resolved_path: path,
+                    ..ExecutableInfo::fixture()
+                }
+            })
+            .collect();
+        PathEntry {
+            path: PathBuf::from(dir),
+            order: 0,
+            exists: true,
+            is_accessible: true,
+            executables,
+            sources: Vec::new(),
+            scope: None,
+        }
+    }
+
+    #[test]
+    fn test_compare_resolutions() {
+        let usr = entry("/usr/bin", &["ls", "node", "python"]);
+        let local = entry("/usr/local/bin", &["node", "rg"]);
+        let cargo = entry("/home/me/.cargo/bin", &["rg", "cargo"]);
+
+        // Drop /usr/local/bin, prepend ~/.cargo/bin
+        let resolutions = compare_resolutions(&[local, usr.clone()], &[cargo, usr], false);
+        let summary: Vec<(&str, ResolutionKind, Option<&str>)> = resolutions
+            .iter()
+            .map(|r| {
+                (
+                    r.binary.as_str(),
+                    r.kind,
+                    r.after.as_deref().and_then(|p| p.to_str()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "cargo",
+                    ResolutionKind::Gained,
+                    Some("/home/me/.cargo/bin/cargo")
+                ),
+                ("node", ResolutionKind::Changed, Some("/usr/bin/node")),
+                (
+                    "rg",
+                    ResolutionKind::Changed,
+                    Some("/home/me/.cargo/bin/rg")
+                ),
+            ]
+        );
+        assert_eq!(
+            resolutions[1].before.as_deref(),
+            Some(std::path::Path::new("/usr/local/bin/node"))
+        );
+
+        let lost = compare_resolutions(&[entry("/opt/bin", &["tool"])], &[], false);
+        assert_eq!(lost[0].kind, ResolutionKind::Lost);
+        assert_eq!(lost[0].after, None);
+    }
+
+    #[test]
+    fn test_resolutions_ignore_name_case() {
+        let before = [entry("/opt/bin", &["Python.exe"])];
+        let after = [entry("/opt/bin", &["python.exe"])];
+
+        assert!(compare_resolutions(&before, &after, true).is_empty());
+
+        let renamed = compare_resolutions(&before, &after, false);
+        let kinds: Vec<ResolutionKind> = renamed.iter().map(|r| r.kind).collect();
+        assert_eq!(kinds, vec![ResolutionKind::Lost, ResolutionKind::Gained]);
+
+        let moved = compare_resolutions(
+            &before,
+            &[entry("/usr/bin", &["python.exe"]), after[0].clone()],
+            true,
+        );
+        assert_eq!(moved.len(), 1);
+        assert_eq!(moved[0].binary, "Python.exe");
+        assert_eq!(moved[0].kind, ResolutionKind::Changed);
+    }
+}
@@ -0,0 +1,63 @@
+use crate::cli::args::Args;
+use crate::cli::runner::retain_selected;
+use crate::cli::webhooks::Webhook;
+use crate::error::{Error, Result};
+use crate::output::changes::ChangeReport;
+use crate::output::snapshots::SnapshotStore;
+use crate::PathAnalyzer;
+use std::time::Duration;
+
+/// Re-run the analysis every `interval`, printing what changed and notifying
+/// webhooks. Runs until interrupted.
+pub fn run(
+    analyzer: &PathAnalyzer,
+    interval: Duration,
+    args: &Args,
+    snapshots: Option<&SnapshotStore>,
+    webhooks: &[Webhook],
+) -> Result<()> {
+    if cfg!(not(feature = "webhooks")) && !webhooks.is_empty() {
+        return Err(Error::FeatureDisabled {
+            feature: "webhooks".to_string(),
+        });
+    }
+
+    let mut previous = None;
+    loop {
+        let mut result = analyzer.analyze()?;
+        if let Some(store) = snapshots {
+            store.save(&result)?;
+        }
+        retain_selected(args, &mut result.conflicts);
+        result.summary.total_conflicts = result.conflicts.len();
+
+        match &previous {
+            None => {
+                if !args.quiet {
+                    println!(
+                        "Watching {} conflict(s); checking every {}s",
+                        result.conflicts.len(),
+                        interval.as_secs()
+                    );
+                }
+            }
+            Some(previous) => {
+                let report = ChangeReport::compare(Some(previous), &result.conflicts);
+                if report.has_changes() {
+                    if !args.quiet {
+                        print!("{}", report.format_human());
+                    }
+                    for webhook in webhooks {
+                        // One unreachable endpoint must not stop the watch
+                        if let Err(e) = webhook.notify(&report, &result) {
+                            tracing::warn!("{}", e);
+                        }
+                    }
+                }
+            }
+        }
+
+        previous = Some(result);
+        std::thread::sleep(interval);
+    }
+}
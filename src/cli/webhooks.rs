@@ -0,0 +1,199 @@
+use crate::config::WebhookConfig;
+use crate::error::{Error, Result};
+use crate::output::changes::{ChangeKind, ChangeReport, ConflictChange};
+use crate::output::chat;
+use crate::output::types::{AnalysisResult, Severity};
+use std::collections::BTreeMap;
+
+/// Body sent to a webhook
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookFormat {
+    /// Generic `{"event": "conflicts_changed", ...}` object
+    Json,
+    /// Slack Block Kit message
+    Slack,
+    /// Microsoft Teams Adaptive Card message
+    Teams,
+}
+
+/// A configured `[[webhooks]]` endpoint
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    pub url: String,
+    pub min_severity: Severity,
+    pub headers: BTreeMap<String, String>,
+    pub format: WebhookFormat,
+}
+
+impl Webhook {
+    pub fn from_config(config: &WebhookConfig) -> Result<Self> {
+        let min_severity = match &config.min_severity {
+            Some(severity) => severity.parse().map_err(|reason| Error::WebhookError {
+                url: config.url.clone(),
+                reason,
+            })?,
+            None => Severity::Info,
+        };
+
+        let format = match config.format.as_deref() {
+            None | Some("json") => WebhookFormat::Json,
+            Some("slack") => WebhookFormat::Slack,
+            Some("teams") => WebhookFormat::Teams,
+            Some(other) => {
+                return Err(Error::WebhookError {
+                    url: config.url.clone(),
+                    reason: format!("unknown format '{}'", other),
+                })
+            }
+        };
+
+        Ok(Webhook {
+            url: config.url.clone(),
+            min_severity,
+            headers: config.headers.clone(),
+            format,
+        })
+    }
+
+    /// Changes worth notifying about: new conflicts at or above the
+    /// threshold, and conflicts that rose to it
+    pub fn triggering<'a>(&self, report: &'a ChangeReport) -> Vec<&'a ConflictChange> {
+        report
+            .changes
+            .iter()
+            .filter(|change| match change.kind {
+                ChangeKind::Added => change.severity >= self.min_severity,
+                ChangeKind::SeverityChanged { from } => {
+                    from < self.min_severity && change.severity >= self.min_severity
+                }
+                ChangeKind::Resolved | ChangeKind::Unchanged => false,
+            })
+            .collect()
+    }
+
+    /// POST the changes that cross this hook's threshold; does nothing when
+    /// none do
+    pub fn notify(&self, report: &ChangeReport, result: &AnalysisResult) -> Result<()> {
+        let changes = self.triggering(report);
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let body = match self.format {
+            WebhookFormat::Json => serde_json::json!({
+                "event": "conflicts_changed",
+                "scan_time": result.scan_time.to_rfc3339(),
+                "min_severity": self.min_severity,
+                "changes": changes,
+                "total_conflicts": result.summary.total_conflicts,
+                "risk_score": result.summary.risk_score,
+            }),
+            WebhookFormat::Slack => chat::format_slack(result, Some(&headline(&changes))),
+            WebhookFormat::Teams => chat::format_teams(result, Some(&headline(&changes))),
+        };
+        self.post(&body.to_string())
+    }
+
+    #[cfg(feature = "webhooks")]
+    fn post(&self, body: &str) -> Result<()> {
+        let mut request = ureq::post(&self.url)
+            .set(
+                "User-Agent",
+                concat!("path-conflict-detector/", env!("CARGO_PKG_VERSION")),
+            )
+            .set("Content-Type", "application/json");
+        for (name, value) in &self.headers {
+            request = request.set(name, value);
+        }
+
+        request.send_string(body).map_err(|e| Error::WebhookError {
+            url: self.url.clone(),
+            reason: e.to_string(),
+        })?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "webhooks"))]
+    fn post(&self, _body: &str) -> Result<()> {
+        Err(Error::FeatureDisabled {
+            feature: "webhooks".to_string(),
+        })
+    }
+}
+
+/// One line naming the changes behind a chat notification
+fn headline(changes: &[&ConflictChange]) -> String {
+    let names: Vec<String> = changes
+        .iter()
+        .map(|change| match change.kind {
+            ChangeKind::SeverityChanged { from } => {
+                format!("{} ({} -> {})", change.binary_name, from, change.severity)
+            }
+            _ => format!("{} ({})", change.binary_name, change.severity),
+        })
+        .collect();
+    format!("New or escalated PATH conflicts: {}", names.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(name: &str, kind: ChangeKind, severity: Severity) -> ConflictChange {
+        ConflictChange {
+            binary_name: name.to_string(),
+            kind,
+            severity,
+            active: None,
+            previous_active: None,
+        }
+    }
+
+    #[test]
+    fn test_triggering() {
+        let report = ChangeReport {
+            since: None,
+            summary: Default::default(),
+            changes: vec![
+                change("git", ChangeKind::Added, Severity::Low),
+                change("node", ChangeKind::Added, Severity::High),
+                change(
+                    "python",
+                    ChangeKind::SeverityChanged {
+                        from: Severity::Medium,
+                    },
+                    Severity::Critical,
+                ),
+                change(
+                    "java",
+                    ChangeKind::SeverityChanged {
+                        from: Severity::High,
+                    },
+                    Severity::Critical,
+                ),
+                change("ruby", ChangeKind::Resolved, Severity::Critical),
+                change("go", ChangeKind::Unchanged, Severity::Critical),
+            ],
+        };
+
+        let any = Webhook::from_config(&WebhookConfig {
+            url: "http://localhost/hook".to_string(),
+            ..WebhookConfig::default()
+        })
+        .unwrap();
+        assert_eq!(any.triggering(&report).len(), 2);
+
+        let high = Webhook::from_config(&WebhookConfig {
+            url: "http://localhost/hook".to_string(),
+            min_severity: Some("high".to_string()),
+            ..WebhookConfig::default()
+        })
+        .unwrap();
+        let names: Vec<&str> = high
+            .triggering(&report)
+            .iter()
+            .map(|c| c.binary_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["node", "python"]);
+    }
+}
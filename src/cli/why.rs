@@ -0,0 +1,315 @@
+use crate::cli::args::{Args, OutputFormat};
+use crate::core::Deadline;
+use crate::error::Result;
+use crate::output::types::{ExecutableInfo, PathEntry};
+use crate::{AnalysisOptions, PathAnalyzer};
+use colored::*;
+use serde::Serialize;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// What the shell finds in one PATH directory while looking up a command
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Lookup {
+    /// The directory does not exist
+    Missing,
+    /// The directory exists but cannot be read
+    Unreadable,
+    /// Searched, no match
+    NoMatch,
+    /// The first match; this copy runs
+    Runs,
+    /// A later match that never runs
+    Shadowed,
+    /// A dangling symlink; shells skip it and keep searching
+    Broken,
+}
+
+/// One PATH directory, in search order
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchStep {
+    pub order: usize,
+    pub directory: PathBuf,
+    pub lookup: Lookup,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executable: Option<ExecutableInfo>,
+}
+
+/// An annotated `which -a`: every directory searched for `binary` and what
+/// was found there
+#[derive(Debug, Clone, Serialize)]
+pub struct Explanation {
+    pub binary: String,
+    pub steps: Vec<SearchStep>,
+}
+
+impl Explanation {
+    pub fn new(path_entries: &[PathEntry], binary: &str, case_insensitive: bool) -> Self {
+        let mut found = false;
+        let steps = path_entries
+            .iter()
+            .map(|entry| {
+                let executable = find(entry, binary, case_insensitive);
+                let lookup = match executable {
+                    _ if !entry.exists => Lookup::Missing,
+                    _ if !entry.is_accessible => Lookup::Unreadable,
+                    None => Lookup::NoMatch,
+                    Some(exec) if exec.broken => Lookup::Broken,
+                    Some(_) if found => Lookup::Shadowed,
+                    Some(_) => {
+                        found = true;
+                        Lookup::Runs
+                    }
+                };
+                SearchStep {
+                    order: entry.order,
+                    directory: entry.path.clone(),
+                    lookup,
+                    executable: executable.cloned(),
+                }
+            })
+            .collect();
+
+        Explanation {
+            binary: binary.to_string(),
+            steps,
+        }
+    }
+
+    /// The copy that runs
+    pub fn active(&self) -> Option<&SearchStep> {
+        self.steps.iter().find(|step| step.lookup == Lookup::Runs)
+    }
+
+    pub fn format_human(&self) -> String {
+        let mut out = String::new();
+        let width = self
+            .steps
+            .iter()
+            .map(|step| step.directory.display().to_string().len())
+            .max()
+            .unwrap_or(0);
+
+        let _ = writeln!(
+            out,
+            "{}",
+            format!("Searching PATH for '{}':", self.binary).bold()
+        );
+        for step in &self.steps {
+            let status = match step.lookup {
+                Lookup::Missing => "does not exist".dimmed(),
+                Lookup::Unreadable => "cannot be read".yellow(),
+                Lookup::NoMatch => "no match".dimmed(),
+                Lookup::Runs => "match, runs".green().bold(),
+                Lookup::Shadowed => "match, shadowed".yellow(),
+                Lookup::Broken => "broken symlink, skipped".red(),
+            };
+            let _ = writeln!(
+                out,
+                "  {:>2}. {:<width$}  {}",
+                step.order + 1,
+                step.directory.display().to_string(),
+                status,
+                width = width
+            );
+            if let Some(exec) = &step.executable {
+                write_details(&mut out, exec);
+            }
+        }
+        let _ = writeln!(out);
+
+        let _ = match self.active() {
+            Some(active) => {
+                let shadowed = self
+                    .steps
+                    .iter()
+                    .filter(|step| step.lookup == Lookup::Shadowed)
+                    .count();
+                let mut summary = format!(
+                    "'{}' runs {}: entry {} is the first PATH directory that has it",
+                    self.binary,
+                    active.directory.join(self.file_name(active)).display(),
+                    active.order + 1
+                );
+                if shadowed > 0 {
+                    let _ = write!(
+                        summary,
+                        ", so the {} later {} never {}",
+                        shadowed,
+                        if shadowed == 1 { "copy" } else { "copies" },
+                        if shadowed == 1 { "runs" } else { "run" }
+                    );
+                }
+                writeln!(out, "{}.", summary)
+            }
+            None => writeln!(
+                out,
+                "'{}' is not in any of the {} PATH directories.",
+                self.binary,
+                self.steps.len()
+            ),
+        };
+        out
+    }
+
+    fn file_name(&self, step: &SearchStep) -> PathBuf {
+        step.executable
+            .as_ref()
+            .and_then(|exec| exec.full_path.file_name())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(&self.binary))
+    }
+}
+
+/// Symlink chain, manager and version under a matching directory
+fn write_details(out: &mut String, exec: &ExecutableInfo) {
+    let indent = "        ";
+    for hop in &exec.symlink_chain {
+        let _ = writeln!(out, "{}-> {}", indent, hop.display());
+    }
+    if exec.symlink_chain.is_empty() {
+        if let Some(target) = &exec.symlink_target {
+            let _ = writeln!(out, "{}-> {}", indent, target.display());
+        }
+    }
+    if let Some(manager) = &exec.manager {
+        let _ = writeln!(out, "{}manager: {}", indent, manager.name);
+    }
+    if let Some(version) = &exec.version {
+        let version = version.parsed.as_deref().unwrap_or(&version.raw);
+        let _ = writeln!(out, "{}version: {}", indent, version);
+    }
+}
+
+/// On Windows `python` also names `PYTHON.EXE`
+fn find<'a>(
+    entry: &'a PathEntry,
+    binary: &str,
+    case_insensitive: bool,
+) -> Option<&'a ExecutableInfo> {
+    entry.executables.iter().find(|exec| {
+        if cfg!(windows) {
+            exec.name.eq_ignore_ascii_case(binary)
+                || exec
+                    .full_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().eq_ignore_ascii_case(binary))
+                    .unwrap_or(false)
+        } else if case_insensitive {
+            exec.name.to_lowercase() == binary.to_lowercase()
+        } else {
+            exec.name == binary
+        }
+    })
+}
+
+/// Explain which copy of `binary` runs, exiting 1 like `which` when PATH
+/// has none. Versions are read only for the matches, so this stays quick
+/// without --extract-versions.
+pub fn run(options: &AnalysisOptions, binary: &str, args: &Args) -> Result<()> {
+    let case_insensitive = options.case_insensitive_names;
+    let mut options = options.clone();
+    options.extract_versions = false;
+    options.lazy_versions = false;
+    let analyzer = PathAnalyzer::with_options(options);
+    let mut result = analyzer.analyze()?;
+
+    let mut matches: Vec<ExecutableInfo> = result
+        .path_entries
+        .iter()
+        .filter_map(|entry| find(entry, binary, case_insensitive))
+        .filter(|exec| !exec.broken && exec.version.is_none())
+        .cloned()
+        .collect();
+    analyzer
+        .version_extractor()?
+        .extract_versions(&mut matches, Deadline::none());
+    for exec in result
+        .path_entries
+        .iter_mut()
+        .flat_map(|entry| entry.executables.iter_mut())
+    {
+        if let Some(versioned) = matches.iter().find(|m| m.full_path == exec.full_path) {
+            exec.version = versioned.version.clone();
+        }
+    }
+
+    let explanation = Explanation::new(&result.path_entries, binary, case_insensitive);
+    match args.output_format() {
+        OutputFormat::Json => println!("{}", serde_json::to_string(&explanation)?),
+        OutputFormat::JsonPretty => println!("{}", serde_json::to_string_pretty(&explanation)?),
+        _ => print!("{}", explanation.format_human()),
+    }
+
+    if explanation.active().is_none() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::core::ExecutableScanner;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_explains_search_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let make_dir = |name: &str| {
+            let path = dir.path().join(name);
+            std::fs::create_dir(&path).unwrap();
+            path
+        };
+        let (empty, first, dangling, second) = (
+            make_dir("empty"),
+            make_dir("first"),
+            make_dir("dangling"),
+            make_dir("second"),
+        );
+        for bin in [&first, &second] {
+            let path = bin.join("tool");
+            std::fs::write(&path, "#!/bin/sh\n").unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        std::os::unix::fs::symlink(dir.path().join("gone"), dangling.join("tool")).unwrap();
+
+        let scanner = ExecutableScanner::new();
+        let entry = |path: PathBuf, order: usize| PathEntry {
+            executables: scanner.scan_directory(&path, order).unwrap_or_default(),
+            sources: Vec::new(),
+            scope: None,
+            exists: path.exists(),
+            is_accessible: path.exists(),
+            path,
+            order,
+        };
+        let entries = vec![
+            entry(dir.path().join("missing"), 0),
+            entry(empty, 1),
+            entry(dangling, 2),
+            entry(first.clone(), 3),
+            entry(second, 4),
+        ];
+
+        let explanation = Explanation::new(&entries, "tool", false);
+        let lookups: Vec<&Lookup> = explanation.steps.iter().map(|s| &s.lookup).collect();
+        assert_eq!(
+            lookups,
+            vec![
+                &Lookup::Missing,
+                &Lookup::NoMatch,
+                &Lookup::Broken,
+                &Lookup::Runs,
+                &Lookup::Shadowed,
+            ]
+        );
+        assert_eq!(explanation.active().unwrap().directory, first);
+
+        let text = explanation.format_human();
+        assert!(text.contains("broken symlink, skipped"));
+        assert!(text.contains("entry 4 is the first PATH directory that has it"));
+        assert!(text.ends_with("so the 1 later copy never runs.\n"));
+    }
+}
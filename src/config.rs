@@ -0,0 +1,411 @@
+use crate::analyzers::{ConflictRule, CustomManager, Preference};
+use crate::error::{Error, Result};
+use crate::output::types::{ManagerType, Severity};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Settings read from a TOML config file (`--config`, or the default
+/// location from `Config::default_path`)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Default minimum severity to report; `--severity` overrides it
+    pub severity: Option<String>,
+    /// Default output format, e.g. `json-pretty`; `--output` and `--json`
+    /// override it
+    pub output: Option<String>,
+    /// Binaries (names or globs) whose conflicts are never reported
+    pub ignore: Vec<String>,
+    /// PATH entries that are not scanned (`~` and variables expand)
+    pub skip_dirs: Vec<PathBuf>,
+    /// Extra manager patterns, checked before the built-in ones
+    pub managers: Vec<ManagerConfig>,
+    pub version: VersionConfig,
+    /// Version constraints keyed by binary name, e.g. `python = ">=3.10"`
+    pub require: BTreeMap<String, String>,
+    /// Conflict policies, applied in order
+    pub rules: Vec<RuleConfig>,
+    pub snapshots: SnapshotConfig,
+    pub optimize: OptimizeConfig,
+    /// Endpoints notified by `watch` when conflicts appear or escalate
+    pub webhooks: Vec<WebhookConfig>,
+    /// Analyzer plugins (shared libraries) run after every analysis;
+    /// needs the `plugins` feature
+    pub plugins: Vec<PathBuf>,
+}
+
+/// The `[snapshots]` table: save every run's result for later diffing
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SnapshotConfig {
+    /// Directory to save results in (`~` and variables expand); snapshots
+    /// are off when unset
+    pub dir: Option<PathBuf>,
+    /// Keep at most this many snapshots (0 = no limit)
+    pub keep: Option<usize>,
+    /// Delete snapshots older than this many days (0 = no limit)
+    pub max_age_days: Option<u64>,
+}
+
+/// The `[optimize]` table: which copies `optimize` should make win
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OptimizeConfig {
+    /// `version-managers`, `package-managers` and `linux`, highest first;
+    /// all three when unset. `--prefer` overrides it.
+    pub prefer: Option<Vec<Preference>>,
+}
+
+/// One `[[webhooks]]` entry:
+///
+/// ```toml
+/// [[webhooks]]
+/// url = "https://alerts.example.com/hooks/path"
+/// min_severity = "high"
+/// headers = { Authorization = "Bearer ..." }
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Only fire for conflicts at or above this severity; a conflict rising
+    /// to it counts too. Any new conflict fires when unset.
+    pub min_severity: Option<String>,
+    /// Extra request headers, e.g. for authentication
+    pub headers: BTreeMap<String, String>,
+    /// Payload shape: `json` (default), `slack` or `teams`
+    pub format: Option<String>,
+}
+
+/// One `[[rules]]` entry. Conditions that are set must all hold:
+///
+/// ```toml
+/// [[rules]]
+/// name = "docker-desktop"
+/// binary = "docker"
+/// active_path = "/usr/local/bin/*"
+/// shadowed_path = "/Applications/Docker.app/**"
+/// severity = "critical"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RuleConfig {
+    pub name: Option<String>,
+    /// Binary name or glob
+    pub binary: Option<String>,
+    /// Glob matched against the active instance's path
+    pub active_path: Option<String>,
+    /// Glob matched against any shadowed instance's path
+    pub shadowed_path: Option<String>,
+    /// Manager name of any instance, e.g. `pyenv`
+    pub manager: Option<String>,
+    /// Constraint on the active version, e.g. `<3.10`
+    pub active_version: Option<String>,
+    pub category: Option<String>,
+    pub severity: Option<String>,
+    pub recommendation: Option<String>,
+}
+
+/// One `[[managers]]` entry: executables whose resolved path matches any
+/// of the regex `patterns` are attributed to this manager
+///
+/// ```toml
+/// [[managers]]
+/// name = "corp-tools"
+/// type = "package-manager"
+/// patterns = ['^/opt/corp/']
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ManagerConfig {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: ManagerKind,
+    pub description: Option<String>,
+    pub patterns: Vec<String>,
+}
+
+/// What a custom manager counts as when categorizing conflicts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ManagerKind {
+    VersionManager,
+    #[default]
+    PackageManager,
+    System,
+    Module,
+    Manual,
+}
+
+impl From<ManagerKind> for ManagerType {
+    fn from(kind: ManagerKind) -> Self {
+        match kind {
+            ManagerKind::VersionManager => ManagerType::VersionManager,
+            ManagerKind::PackageManager => ManagerType::PackageManager,
+            ManagerKind::System => ManagerType::SystemInstall,
+            ManagerKind::Module => ManagerType::EnvironmentModule,
+            ManagerKind::Manual => ManagerType::ManualInstall,
+        }
+    }
+}
+
+/// The `[version]` table
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct VersionConfig {
+    /// Binaries that may be executed under `--version-probe allowlist`.
+    /// Replaces the built-in list when set.
+    pub allowlist: Option<Vec<String>>,
+    /// Custom version commands keyed by binary name, tried before the
+    /// generic version flags. Entries override the built-in ones.
+    pub commands: HashMap<String, VersionCommand>,
+    pub skip: SkipConfig,
+}
+
+/// The `[version.skip]` table: binaries never executed for their version
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SkipConfig {
+    /// Names or globs (`*-gui`) added to the built-in list
+    pub names: Vec<String>,
+    /// Use only `names`, dropping the built-in list
+    pub replace_defaults: bool,
+}
+
+/// How to ask one binary for its version, e.g.
+///
+/// ```toml
+/// [version.commands.java]
+/// args = ["-version"]
+/// stream = "stderr"
+/// pattern = 'version "([^"]+)"'
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VersionCommand {
+    pub args: Vec<String>,
+    /// Which output stream carries the version
+    #[serde(default)]
+    pub stream: OutputStream,
+    /// Regex whose first capture group (or whole match) is the version
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Dotted path to the version in JSON output, e.g. `terraform_version`
+    #[serde(default)]
+    pub json_field: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    /// stdout, or stderr when stdout is empty
+    #[default]
+    Any,
+    Stdout,
+    Stderr,
+}
+
+impl Config {
+    /// `path-conflict-detector/config.toml` under `$XDG_CONFIG_HOME`
+    /// (`~/.config` by default), or under `%APPDATA%` on Windows
+    pub fn default_path() -> Option<PathBuf> {
+        let dir = if cfg!(windows) {
+            std::env::var_os("APPDATA").map(PathBuf::from)
+        } else {
+            std::env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        }?;
+
+        Some(dir.join("path-conflict-detector").join("config.toml"))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        Self::parse(&contents).map_err(|reason| Error::ConfigError {
+            path: path.display().to_string(),
+            reason,
+        })
+    }
+
+    fn parse(contents: &str) -> std::result::Result<Self, String> {
+        let config: Config = toml::from_str(contents).map_err(|e| e.message().to_string())?;
+
+        if let Some(severity) = &config.severity {
+            severity
+                .parse::<Severity>()
+                .map_err(|e| format!("severity: {}", e))?;
+        }
+
+        for pattern in &config.ignore {
+            globset::Glob::new(pattern).map_err(|e| format!("ignore: {}", e))?;
+        }
+
+        for manager in &config.managers {
+            CustomManager::from_config(manager).map_err(|e| format!("managers: {}", e))?;
+        }
+
+        for (name, command) in &config.version.commands {
+            if let Some(pattern) = &command.pattern {
+                Regex::new(pattern)
+                    .map_err(|e| format!("version.commands.{}.pattern: {}", name, e))?;
+            }
+        }
+
+        for name in &config.version.skip.names {
+            globset::Glob::new(&name.to_lowercase())
+                .map_err(|e| format!("version.skip.names: {}", e))?;
+        }
+
+        for rule in &config.rules {
+            ConflictRule::from_config(rule).map_err(|e| e.to_string())?;
+        }
+
+        for webhook in &config.webhooks {
+            if webhook.url.is_empty() {
+                return Err("webhooks: url is required".to_string());
+            }
+            if let Some(severity) = &webhook.min_severity {
+                severity
+                    .parse::<Severity>()
+                    .map_err(|e| format!("webhooks.min_severity: {}", e))?;
+            }
+            if let Some(format) = &webhook.format {
+                if !["json", "slack", "teams"].contains(&format.as_str()) {
+                    return Err(format!(
+                        "webhooks.format: expected json, slack or teams, got '{}'",
+                        format
+                    ));
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version_allowlist() {
+        let config = Config::parse("[version]\nallowlist = [\"python\", \"node\"]\n").unwrap();
+        assert_eq!(
+            config.version.allowlist,
+            Some(vec!["python".to_string(), "node".to_string()])
+        );
+
+        let empty = Config::parse("").unwrap();
+        assert!(empty.version.allowlist.is_none());
+    }
+
+    #[test]
+    fn test_parse_version_commands() {
+        let config = Config::parse(
+            "[version.commands.java]\n\
+             args = [\"-version\"]\n\
+             stream = \"stderr\"\n\
+             pattern = 'version \"([^\"]+)\"'\n\
+             [version.commands.terraform]\n\
+             args = [\"version\", \"-json\"]\n\
+             json_field = \"terraform_version\"\n",
+        )
+        .unwrap();
+
+        let java = &config.version.commands["java"];
+        assert_eq!(java.args, vec!["-version"]);
+        assert_eq!(java.stream, OutputStream::Stderr);
+        assert!(java.pattern.is_some());
+
+        let terraform = &config.version.commands["terraform"];
+        assert_eq!(terraform.stream, OutputStream::Any);
+        assert_eq!(terraform.json_field.as_deref(), Some("terraform_version"));
+
+        let bad = "[version.commands.x]\nargs = []\npattern = \"(\"\n";
+        assert!(Config::parse(bad)
+            .unwrap_err()
+            .contains("version.commands.x.pattern"));
+    }
+
+    #[test]
+    fn test_parse_require_table() {
+        let config = Config::parse("[require]\npython = \">=3.10\"\nnode = \"^20\"\n").unwrap();
+        assert_eq!(config.require["python"], ">=3.10");
+        assert_eq!(config.require["node"], "^20");
+    }
+
+    #[test]
+    fn test_parse_skip_names() {
+        let config = Config::parse("[version.skip]\nnames = [\"code\", \"*-gui\"]\n").unwrap();
+        assert_eq!(config.version.skip.names, vec!["code", "*-gui"]);
+        assert!(!config.version.skip.replace_defaults);
+
+        assert!(Config::parse("[version.skip]\nnames = [\"[oops\"]\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        let config = Config::parse(
+            "[[rules]]\n\
+             binary = \"docker\"\n\
+             active_path = \"/usr/local/bin/*\"\n\
+             severity = \"critical\"\n\
+             [[rules]]\n\
+             manager = \"pyenv\"\n\
+             category = \"version-manager-vs-system\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.rules.len(), 2);
+        assert_eq!(config.rules[0].binary.as_deref(), Some("docker"));
+
+        assert!(Config::parse("[[rules]]\nseverity = \"urgent\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_webhooks() {
+        let config = Config::parse(
+            "[[webhooks]]\n\
+             url = \"https://example.com/hook\"\n\
+             min_severity = \"high\"\n\
+             headers = { Authorization = \"Bearer x\" }\n",
+        )
+        .unwrap();
+        assert_eq!(config.webhooks[0].url, "https://example.com/hook");
+        assert_eq!(config.webhooks[0].headers["Authorization"], "Bearer x");
+
+        assert!(Config::parse("[[webhooks]]\nmin_severity = \"high\"\n").is_err());
+        assert!(Config::parse("[[webhooks]]\nurl = \"x\"\nformat = \"irc\"\n").is_err());
+        assert!(Config::parse("[[webhooks]]\nurl = \"x\"\nmin_severity = \"loud\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_defaults_and_managers() {
+        let config = Config::parse(
+            "severity = \"medium\"\n\
+             output = \"json\"\n\
+             ignore = [\"cc\", \"python*-config\"]\n\
+             skip_dirs = [\"~/.local/share/junk/bin\"]\n\
+             [[managers]]\n\
+             name = \"corp-tools\"\n\
+             type = \"version-manager\"\n\
+             patterns = ['^/opt/corp/']\n",
+        )
+        .unwrap();
+        assert_eq!(config.severity.as_deref(), Some("medium"));
+        assert_eq!(config.ignore, vec!["cc", "python*-config"]);
+        assert_eq!(config.managers[0].kind, ManagerKind::VersionManager);
+
+        assert!(Config::parse("severity = \"loud\"\n").is_err());
+        assert!(Config::parse("[[managers]]\nname = \"x\"\ntype = \"cult\"\n").is_err());
+        assert!(Config::parse("[[managers]]\nname = \"x\"\npatterns = [\"(\"]\n").is_err());
+    }
+
+    #[test]
+    fn test_unknown_keys_rejected() {
+        assert!(Config::parse("[version]\nallow = []\n").is_err());
+    }
+}
@@ -0,0 +1,403 @@
+use crate::output::types::{BinaryFormat, FileFormat};
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Read an executable's header to tell its format, target architectures and
+/// whether it is a `#!` script, e.g. "ELF x86_64", "Mach-O universal
+/// (x86_64, arm64)", "PE x86" or "script (/usr/bin/env python3)". Nothing is
+/// executed. `None` for files in no recognised format.
+pub fn detect(path: &Path) -> Option<BinaryFormat> {
+    let mut file = fs::File::open(path).ok()?;
+    let mut header = [0u8; 512];
+    let len = read_up_to(&mut file, &mut header).ok()?;
+    let header = &header[..len];
+
+    if header.starts_with(b"#!") {
+        let line_end = header
+            .iter()
+            .position(|&b| b == b'\n')
+            .unwrap_or(header.len());
+        let interpreter = String::from_utf8_lossy(&header[2..line_end])
+            .trim()
+            .to_string();
+        return Some(BinaryFormat {
+            format: FileFormat::Script,
+            architectures: Vec::new(),
+            interpreter: (!interpreter.is_empty()).then_some(interpreter),
+        });
+    }
+
+    if header.starts_with(b"\x7fELF") && header.len() >= 20 {
+        let little_endian = header[5] == 1;
+        let machine = read_u16(&header[18..20], little_endian);
+        return Some(native(FileFormat::Elf, vec![elf_arch(machine)]));
+    }
+
+    if header.len() >= 8 {
+        let magic_be = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+        let magic_le = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+
+        // Thin Mach-O (32/64-bit, either byte order)
+        if matches!(magic_le, 0xfeedface | 0xfeedfacf)
+            || matches!(magic_be, 0xfeedface | 0xfeedfacf)
+        {
+            let little_endian = matches!(magic_le, 0xfeedface | 0xfeedfacf);
+            let cpu_type = read_u32(&header[4..8], little_endian);
+            return Some(native(FileFormat::MachO, vec![macho_arch(cpu_type)]));
+        }
+
+        // Universal binary. Java class files share the 0xcafebabe magic but
+        // have a large version number where the arch count would be.
+        if matches!(magic_be, 0xcafebabe | 0xcafebabf) {
+            let count = read_u32(&header[4..8], false) as usize;
+            if count > 0 && count < 20 {
+                let entry_size = if magic_be == 0xcafebabf { 32 } else { 20 };
+                let archs = (0..count)
+                    .filter_map(|i| {
+                        let start = 8 + i * entry_size;
+                        header
+                            .get(start..start + 4)
+                            .map(|cpu| macho_arch(read_u32(cpu, false)))
+                    })
+                    .collect();
+                return Some(native(FileFormat::MachOUniversal, archs));
+            }
+        }
+    }
+
+    if header.starts_with(b"MZ") && header.len() >= 0x40 {
+        let pe_offset = read_u32(&header[0x3c..0x40], true) as u64;
+        return Some(match read_pe_header(&mut file, pe_offset) {
+            Some(pe) => native(FileFormat::Pe, vec![pe_arch(pe.machine)]),
+            None => native(FileFormat::Dos, Vec::new()),
+        });
+    }
+
+    None
+}
+
+fn native(format: FileFormat, architectures: Vec<&str>) -> BinaryFormat {
+    BinaryFormat {
+        format,
+        architectures: architectures.into_iter().map(str::to_string).collect(),
+        interpreter: None,
+    }
+}
+
+/// True for PE images built for the Windows GUI subsystem. Running these
+/// with `--version` pops up a window instead of printing anything.
+pub fn is_gui_subsystem(path: &Path) -> bool {
+    let mut file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(_) => return false,
+    };
+
+    let mut dos_header = [0u8; 0x40];
+    if read_up_to(&mut file, &mut dos_header).ok() != Some(0x40) || !dos_header.starts_with(b"MZ") {
+        return false;
+    }
+
+    let pe_offset = read_u32(&dos_header[0x3c..0x40], true) as u64;
+    read_pe_header(&mut file, pe_offset).and_then(|pe| pe.subsystem)
+        == Some(IMAGE_SUBSYSTEM_WINDOWS_GUI)
+}
+
+const IMAGE_SUBSYSTEM_WINDOWS_GUI: u16 = 2;
+
+struct PeHeader {
+    machine: u16,
+    subsystem: Option<u16>,
+}
+
+fn read_pe_header(file: &mut fs::File, pe_offset: u64) -> Option<PeHeader> {
+    // "PE\0\0" signature, 20-byte COFF header, then the optional header whose
+    // Subsystem field sits at offset 68 for both PE32 and PE32+
+    let mut header = [0u8; 24 + 70];
+    file.seek(SeekFrom::Start(pe_offset)).ok()?;
+    let len = read_up_to(file, &mut header).ok()?;
+    if len < 6 || !header.starts_with(b"PE\0\0") {
+        return None;
+    }
+
+    let machine = read_u16(&header[4..6], true);
+    let subsystem = (len >= 24 + 70).then(|| read_u16(&header[24 + 68..24 + 70], true));
+    Some(PeHeader { machine, subsystem })
+}
+
+/// The `DT_SONAME` of an ELF shared library, the name the dynamic linker
+/// looks for in each library directory. Read from the dynamic segment through
+/// the program headers, so stripped libraries work too.
+pub fn elf_soname(path: &Path) -> Option<String> {
+    let mut file = fs::File::open(path).ok()?;
+    let mut header = [0u8; 64];
+    if read_up_to(&mut file, &mut header).ok()? < 52 || !header.starts_with(b"\x7fELF") {
+        return None;
+    }
+    let is_64 = header[4] == 2;
+    let le = header[5] == 1;
+    let word = |bytes: &[u8]| {
+        if is_64 {
+            read_u64(bytes, le)
+        } else {
+            read_u32(bytes, le) as u64
+        }
+    };
+
+    let (phoff, phentsize, phnum) = if is_64 {
+        (
+            word(&header[0x20..0x28]),
+            read_u16(&header[0x36..0x38], le),
+            read_u16(&header[0x38..0x3a], le),
+        )
+    } else {
+        (
+            word(&header[0x1c..0x20]),
+            read_u16(&header[0x2a..0x2c], le),
+            read_u16(&header[0x2c..0x2e], le),
+        )
+    };
+    let phdrs = read_at(&mut file, phoff, phentsize as usize * phnum as usize)?;
+
+    // (offset, vaddr, filesz) of PT_LOAD segments, and the PT_DYNAMIC one
+    let mut loads = Vec::new();
+    let mut dynamic = None;
+    for phdr in phdrs.chunks_exact(phentsize as usize) {
+        let segment = if is_64 {
+            (word(&phdr[8..16]), word(&phdr[16..24]), word(&phdr[32..40]))
+        } else {
+            (word(&phdr[4..8]), word(&phdr[8..12]), word(&phdr[16..20]))
+        };
+        match read_u32(&phdr[0..4], le) {
+            PT_LOAD => loads.push(segment),
+            PT_DYNAMIC => dynamic = Some(segment),
+            _ => {}
+        }
+    }
+
+    let (dyn_offset, _, dyn_size) = dynamic?;
+    let entries = read_at(&mut file, dyn_offset, dyn_size.min(1 << 20) as usize)?;
+    let entry_size = if is_64 { 16 } else { 8 };
+    let (mut soname, mut strtab) = (None, None);
+    for entry in entries.chunks_exact(entry_size) {
+        let (tag, value) = entry.split_at(entry_size / 2);
+        match word(tag) {
+            DT_NULL => break,
+            DT_STRTAB => strtab = Some(word(value)),
+            DT_SONAME => soname = Some(word(value)),
+            _ => {}
+        }
+    }
+
+    // DT_STRTAB is an address; find the file offset of the segment holding it
+    let strtab = strtab?;
+    let strtab_offset = loads
+        .iter()
+        .find(|(_, vaddr, filesz)| (*vaddr..vaddr + filesz).contains(&strtab))
+        .map(|(offset, vaddr, _)| strtab - vaddr + offset)?;
+    let name = read_at(&mut file, strtab_offset + soname?, 256)?;
+    let end = name.iter().position(|&b| b == 0)?;
+    String::from_utf8(name[..end].to_vec())
+        .ok()
+        .filter(|name| !name.is_empty())
+}
+
+const PT_LOAD: u32 = 1;
+const PT_DYNAMIC: u32 = 2;
+const DT_NULL: u64 = 0;
+const DT_STRTAB: u64 = 5;
+const DT_SONAME: u64 = 14;
+
+/// Up to `len` bytes at `offset`
+fn read_at(file: &mut fs::File, offset: u64, len: usize) -> Option<Vec<u8>> {
+    file.seek(SeekFrom::Start(offset)).ok()?;
+    let mut buffer = vec![0u8; len];
+    let read = read_up_to(file, &mut buffer).ok()?;
+    buffer.truncate(read);
+    Some(buffer)
+}
+
+fn read_up_to(file: &mut fs::File, buffer: &mut [u8]) -> std::io::Result<usize> {
+    let mut total = 0;
+    while total < buffer.len() {
+        match file.read(&mut buffer[total..])? {
+            0 => break,
+            read => total += read,
+        }
+    }
+    Ok(total)
+}
+
+fn read_u16(bytes: &[u8], little_endian: bool) -> u16 {
+    let bytes = [bytes[0], bytes[1]];
+    if little_endian {
+        u16::from_le_bytes(bytes)
+    } else {
+        u16::from_be_bytes(bytes)
+    }
+}
+
+fn read_u32(bytes: &[u8], little_endian: bool) -> u32 {
+    let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+    if little_endian {
+        u32::from_le_bytes(bytes)
+    } else {
+        u32::from_be_bytes(bytes)
+    }
+}
+
+fn read_u64(bytes: &[u8], little_endian: bool) -> u64 {
+    let bytes: [u8; 8] = bytes[..8].try_into().unwrap();
+    if little_endian {
+        u64::from_le_bytes(bytes)
+    } else {
+        u64::from_be_bytes(bytes)
+    }
+}
+
+fn elf_arch(machine: u16) -> &'static str {
+    match machine {
+        0x03 => "x86",
+        0x08 => "mips",
+        0x14 => "ppc",
+        0x15 => "ppc64",
+        0x16 => "s390x",
+        0x28 => "arm",
+        0x3e => "x86_64",
+        0xb7 => "aarch64",
+        0xf3 => "riscv",
+        _ => "unknown",
+    }
+}
+
+fn macho_arch(cpu_type: u32) -> &'static str {
+    match cpu_type {
+        0x0000_0007 => "x86",
+        0x0100_0007 => "x86_64",
+        0x0000_000c => "arm",
+        0x0100_000c => "arm64",
+        0x0000_0012 => "ppc",
+        0x0100_0012 => "ppc64",
+        _ => "unknown",
+    }
+}
+
+fn pe_arch(machine: u16) -> &'static str {
+    match machine {
+        0x014c => "x86",
+        0x8664 => "x86_64",
+        0x01c0 | 0x01c4 => "arm",
+        0xaa64 => "arm64",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, contents: &[u8]| {
+            let path = dir.path().join(name);
+            fs::write(&path, contents).unwrap();
+            path
+        };
+        let kind = |path: std::path::PathBuf| detect(&path).map(|f| f.to_string());
+
+        let script = detect(&write("script", b"#!/usr/bin/env python3\nprint()\n")).unwrap();
+        assert!(script.is_script());
+        assert_eq!(script.interpreter.as_deref(), Some("/usr/bin/env python3"));
+        assert_eq!(script.to_string(), "script (/usr/bin/env python3)");
+
+        let mut elf = vec![0u8; 64];
+        elf[..4].copy_from_slice(b"\x7fELF");
+        elf[5] = 1; // little endian
+        elf[18] = 0xb7; // aarch64
+        let elf = detect(&write("elf", &elf)).unwrap();
+        assert!(elf.is_native());
+        assert_eq!(elf.format, FileFormat::Elf);
+        assert_eq!(elf.architectures, vec!["aarch64"]);
+        assert_eq!(elf.to_string(), "ELF aarch64");
+
+        let mut fat = vec![0u8; 64];
+        fat[..4].copy_from_slice(&0xcafebabeu32.to_be_bytes());
+        fat[4..8].copy_from_slice(&2u32.to_be_bytes());
+        fat[8..12].copy_from_slice(&0x0100_0007u32.to_be_bytes());
+        fat[28..32].copy_from_slice(&0x0100_000cu32.to_be_bytes());
+        assert_eq!(
+            kind(write("fat", &fat)).as_deref(),
+            Some("Mach-O universal (x86_64, arm64)")
+        );
+
+        let mut pe = vec![0u8; 0x90];
+        pe[..2].copy_from_slice(b"MZ");
+        pe[0x3c] = 0x80;
+        pe[0x80..0x84].copy_from_slice(b"PE\0\0");
+        pe[0x84..0x86].copy_from_slice(&0x014cu16.to_le_bytes());
+        assert_eq!(kind(write("pe.exe", &pe)).as_deref(), Some("PE x86"));
+
+        assert_eq!(detect(&write("text", b"hello")), None);
+    }
+
+    #[test]
+    fn test_is_gui_subsystem() {
+        let dir = tempfile::tempdir().unwrap();
+        let pe_with_subsystem = |subsystem: u16| {
+            let mut pe = vec![0u8; 0x100];
+            pe[..2].copy_from_slice(b"MZ");
+            pe[0x3c] = 0x80;
+            pe[0x80..0x84].copy_from_slice(b"PE\0\0");
+            pe[0x84..0x86].copy_from_slice(&0x8664u16.to_le_bytes());
+            pe[0x80 + 24 + 68..0x80 + 24 + 70].copy_from_slice(&subsystem.to_le_bytes());
+            pe
+        };
+
+        let gui = dir.path().join("gui.exe");
+        let console = dir.path().join("console.exe");
+        fs::write(&gui, pe_with_subsystem(2)).unwrap();
+        fs::write(&console, pe_with_subsystem(3)).unwrap();
+
+        assert!(is_gui_subsystem(&gui));
+        assert!(!is_gui_subsystem(&console));
+        assert!(!is_gui_subsystem(&dir.path().join("missing.exe")));
+    }
+
+    #[test]
+    fn test_elf_soname() {
+        // 64-bit little-endian header, two program headers at 0x40 (PT_LOAD
+        // mapping the file at 0x1000, PT_DYNAMIC at 0x100) and the string
+        // table at 0x200
+        let mut elf = vec![0u8; 0x240];
+        elf[..4].copy_from_slice(b"\x7fELF");
+        elf[4] = 2;
+        elf[5] = 1;
+        elf[0x20..0x28].copy_from_slice(&0x40u64.to_le_bytes());
+        elf[0x36..0x38].copy_from_slice(&56u16.to_le_bytes());
+        elf[0x38..0x3a].copy_from_slice(&2u16.to_le_bytes());
+        let phdr = |elf: &mut Vec<u8>, at: usize, kind: u32, offset: u64, vaddr: u64, size: u64| {
+            elf[at..at + 4].copy_from_slice(&kind.to_le_bytes());
+            elf[at + 8..at + 16].copy_from_slice(&offset.to_le_bytes());
+            elf[at + 16..at + 24].copy_from_slice(&vaddr.to_le_bytes());
+            elf[at + 32..at + 40].copy_from_slice(&size.to_le_bytes());
+        };
+        phdr(&mut elf, 0x40, PT_LOAD, 0, 0x1000, 0x240);
+        phdr(&mut elf, 0x78, PT_DYNAMIC, 0x100, 0x1100, 48);
+        let dynamic = [(DT_STRTAB, 0x1200u64), (DT_SONAME, 1), (DT_NULL, 0)];
+        for (i, (tag, value)) in dynamic.iter().enumerate() {
+            let at = 0x100 + i * 16;
+            elf[at..at + 8].copy_from_slice(&tag.to_le_bytes());
+            elf[at + 8..at + 16].copy_from_slice(&value.to_le_bytes());
+        }
+        elf[0x201..0x201 + 11].copy_from_slice(b"libssl.so.3");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("libssl.so.3.0.2");
+        fs::write(&path, &elf).unwrap();
+        assert_eq!(elf_soname(&path).as_deref(), Some("libssl.so.3"));
+
+        fs::write(&path, b"#!/bin/sh\n").unwrap();
+        assert_eq!(elf_soname(&path), None);
+    }
+}
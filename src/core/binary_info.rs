@@ -1,50 +1,306 @@
+use crate::core::binary_format;
+use crate::core::parallel::for_each_bounded;
+use crate::core::progress::{ProgressReporter, Stage};
 use crate::error::Result;
-use crate::output::types::ExecutableInfo;
+use crate::output::types::{ExecutableInfo, HashAlgorithm, HashScope};
+use memmap2::Mmap;
+use sha2::{Digest, Sha256};
 use std::fs;
 use std::io::Read;
+use xxhash_rust::xxh3::Xxh3;
+
+/// Files at least this large are memory-mapped instead of read in chunks
+const MMAP_THRESHOLD: u64 = 16 * 1024 * 1024;
 
 pub struct BinaryInfoExtractor {
     compute_hashes: bool,
+    detect_kinds: bool,
+    algorithm: HashAlgorithm,
+    scope: HashScope,
+    jobs: usize,
+    max_file_size: Option<u64>,
+    mmap_threshold: u64,
+    progress: ProgressReporter,
 }
 
 impl BinaryInfoExtractor {
     pub fn new(compute_hashes: bool) -> Self {
-        BinaryInfoExtractor { compute_hashes }
+        Self::with_algorithm(
+            compute_hashes,
+            HashAlgorithm::default(),
+            HashScope::default(),
+        )
+    }
+
+    pub fn with_algorithm(
+        compute_hashes: bool,
+        algorithm: HashAlgorithm,
+        scope: HashScope,
+    ) -> Self {
+        BinaryInfoExtractor {
+            compute_hashes,
+            detect_kinds: true,
+            algorithm,
+            scope,
+            jobs: 0,
+            max_file_size: None,
+            mmap_threshold: MMAP_THRESHOLD,
+            progress: ProgressReporter::none(),
+        }
+    }
+
+    /// Hash with at most `jobs` threads (0 = one per CPU), skipping files
+    /// larger than `max_file_size` bytes
+    pub fn with_workers(mut self, jobs: usize, max_file_size: Option<u64>) -> Self {
+        self.jobs = jobs;
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Report each executable as it is inspected
+    pub fn with_progress(mut self, progress: ProgressReporter) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Enable or disable header parsing for `ExecutableInfo::binary_format`
+    pub fn with_kind_detection(mut self, detect_kinds: bool) -> Self {
+        self.detect_kinds = detect_kinds;
+        self
     }
 
     pub fn enrich_executables(&self, executables: &mut [ExecutableInfo]) -> Result<()> {
-        for executable in executables.iter_mut() {
+        if !self.compute_hashes && !self.detect_kinds {
+            return Ok(());
+        }
+
+        let progress = self
+            .progress
+            .start(Stage::InspectingBinaries, executables.len());
+        for_each_bounded(executables, self.jobs, |executable| {
+            if self.detect_kinds {
+                executable.binary_format = binary_format::detect(&executable.full_path);
+            }
+
             if self.compute_hashes {
                 executable.file_hash = self.compute_file_hash(&executable.full_path);
+                let hashed = executable.file_hash.is_some();
+                executable.hash_algorithm = hashed.then_some(self.algorithm);
+                executable.hash_scope = hashed.then_some(self.scope);
             }
-        }
+            progress.tick();
+        });
+        progress.finish();
 
         Ok(())
     }
 
     fn compute_file_hash(&self, path: &std::path::Path) -> Option<String> {
-        // Simple hash computation using a basic algorithm
-        // In production, you might want to use a proper hashing library like sha2
+        // Stream the file (by default all of it, so that binaries sharing a
+        // common prefix never hash identically)
         let file = fs::File::open(path).ok()?;
-        let mut buffer = Vec::new();
+        let file_size = file.metadata().ok()?.len();
 
-        // Read first 8KB for hash (to avoid reading huge files)
-        let mut handle = file.take(8192);
-        handle.read_to_end(&mut buffer).ok()?;
+        if let Some(max_file_size) = self.max_file_size {
+            if file_size > max_file_size {
+                return None;
+            }
+        }
 
-        // Simple FNV-1a hash
-        let mut hash: u64 = 0xcbf29ce484222325;
-        for byte in buffer {
-            hash ^= byte as u64;
-            hash = hash.wrapping_mul(0x100000001b3);
+        let mut hasher = FileHasher::new(self.algorithm);
+        let limit = self.scope.byte_limit();
+
+        // Large binaries (Electron apps, toolchains) are mapped rather than
+        // copied through a buffer, which is faster and keeps memory flat
+        let hashed_len = limit.map_or(file_size, |limit| limit.min(file_size));
+        if hashed_len >= self.mmap_threshold {
+            if let Some(map) = Self::map_file(&file) {
+                let end = (hashed_len as usize).min(map.len());
+                hasher.update(&map[..end]);
+                return Some(hasher.finalize());
+            }
+        }
+
+        let mut file: Box<dyn Read> = match limit {
+            Some(limit) => Box::new(file.take(limit)),
+            None => Box::new(file),
+        };
+        let mut buffer = [0u8; 64 * 1024];
+
+        loop {
+            let read = file.read(&mut buffer).ok()?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
         }
 
-        Some(format!("{:016x}", hash))
+        Some(hasher.finalize())
+    }
+
+    fn map_file(file: &fs::File) -> Option<Mmap> {
+        // SAFETY: the mapping is only read while hashing. If another process
+        // truncates the file meanwhile the digest is wrong, but that is no
+        // different from a buffered read racing with a writer.
+        unsafe { Mmap::map(file) }.ok()
     }
 }
 
+/// Incremental hasher dispatching to the selected algorithm
+enum FileHasher {
+    Sha256(Sha256),
+    Blake3(Box<blake3::Hasher>),
+    Xxh3(Box<Xxh3>),
+}
+
+impl FileHasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => FileHasher::Sha256(Sha256::new()),
+            HashAlgorithm::Blake3 => FileHasher::Blake3(Box::new(blake3::Hasher::new())),
+            HashAlgorithm::Xxh3 => FileHasher::Xxh3(Box::new(Xxh3::new())),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            FileHasher::Sha256(hasher) => hasher.update(data),
+            FileHasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+            FileHasher::Xxh3(hasher) => hasher.update(data),
+        }
+    }
+
+    fn finalize(self) -> String {
+        match self {
+            FileHasher::Sha256(hasher) => to_hex(&hasher.finalize()),
+            FileHasher::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+            FileHasher::Xxh3(hasher) => format!("{:016x}", hasher.digest()),
+        }
+    }
+}
+
+/// Both paths lead to one file, through symlinks or not; paths that cannot
+/// be resolved are compared as written
+pub(crate) fn same_file(a: &std::path::Path, b: &std::path::Path) -> bool {
+    match (std::fs::canonicalize(a), std::fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Lowercase hex encoding of a digest
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 impl Default for BinaryInfoExtractor {
     fn default() -> Self {
         Self::new(false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_file_hash_is_full_file_sha256() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("empty");
+        fs::write(&path, b"").unwrap();
+
+        let extractor = BinaryInfoExtractor::new(true);
+        assert_eq!(
+            extractor.compute_file_hash(&path).unwrap(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+
+        // Files that only differ after the first 8KB must not collide
+        let a = dir.path().join("a");
+        let b = dir.path().join("b");
+        let mut contents = vec![0u8; 16 * 1024];
+        fs::write(&a, &contents).unwrap();
+        contents[12 * 1024] = 1;
+        fs::write(&b, &contents).unwrap();
+        assert_ne!(
+            extractor.compute_file_hash(&a),
+            extractor.compute_file_hash(&b)
+        );
+    }
+
+    #[test]
+    fn test_selectable_hash_algorithms() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("empty");
+        fs::write(&path, b"").unwrap();
+
+        let blake3 =
+            BinaryInfoExtractor::with_algorithm(true, HashAlgorithm::Blake3, HashScope::Full);
+        assert_eq!(
+            blake3.compute_file_hash(&path).unwrap(),
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        );
+
+        let xxh3 = BinaryInfoExtractor::with_algorithm(true, HashAlgorithm::Xxh3, HashScope::Full);
+        assert_eq!(xxh3.compute_file_hash(&path).unwrap().len(), 16);
+    }
+
+    #[test]
+    fn test_prefix_hash_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a");
+        let b = dir.path().join("b");
+        let mut contents = vec![0u8; 16 * 1024];
+        fs::write(&a, &contents).unwrap();
+        contents[12 * 1024] = 1;
+        fs::write(&b, &contents).unwrap();
+
+        // Only the first 8KB are read, so the difference is invisible
+        let prefix =
+            BinaryInfoExtractor::with_algorithm(true, HashAlgorithm::Sha256, HashScope::Prefix8K);
+        assert_eq!(prefix.compute_file_hash(&a), prefix.compute_file_hash(&b));
+
+        let mut executables = vec![ExecutableInfo {
+            name: "a".to_string(),
+            full_path: a.clone(),
+            resolved_path: a,
+            ..ExecutableInfo::fixture()
+        }];
+        prefix.enrich_executables(&mut executables).unwrap();
+        assert_eq!(executables[0].hash_scope, Some(HashScope::Prefix8K));
+    }
+
+    #[test]
+    fn test_mapped_and_buffered_hashes_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("binary");
+        fs::write(&path, (0..200_000u32).map(|i| i as u8).collect::<Vec<_>>()).unwrap();
+
+        for scope in [HashScope::Full, HashScope::Prefix8K] {
+            let buffered = BinaryInfoExtractor::with_algorithm(true, HashAlgorithm::Sha256, scope);
+            let mut mapped =
+                BinaryInfoExtractor::with_algorithm(true, HashAlgorithm::Sha256, scope);
+            mapped.mmap_threshold = 0;
+
+            assert_eq!(
+                buffered.compute_file_hash(&path),
+                mapped.compute_file_hash(&path)
+            );
+        }
+    }
+
+    #[test]
+    fn test_files_above_size_threshold_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let small = dir.path().join("small");
+        let large = dir.path().join("large");
+        fs::write(&small, vec![0u8; 10]).unwrap();
+        fs::write(&large, vec![0u8; 1000]).unwrap();
+
+        let extractor = BinaryInfoExtractor::new(true).with_workers(2, Some(100));
+        assert!(extractor.compute_file_hash(&small).is_some());
+        assert!(extractor.compute_file_hash(&large).is_none());
+    }
+}
@@ -1,16 +1,48 @@
-use crate::analyzers::ConflictCategorizer;
+use crate::analyzers::{ConflictCategorizer, ConflictRule, FixCommandBuilder, RiskScorer};
 use crate::error::Result;
-use crate::output::types::{Conflict, ExecutableInfo, PathEntry, PlatformInfo};
+use crate::output::types::{
+    Conflict, ExecutableInfo, HashScope, PathEntry, PlatformInfo, Severity,
+};
 use std::collections::HashMap;
 
 pub struct ConflictDetector {
     categorizer: ConflictCategorizer,
+    risk_scorer: RiskScorer,
+    fix_commands: FixCommandBuilder,
+    rules: Vec<ConflictRule>,
+    case_insensitive: bool,
 }
 
 impl ConflictDetector {
     pub fn new(platform: PlatformInfo) -> Self {
         ConflictDetector {
             categorizer: ConflictCategorizer::new(platform),
+            risk_scorer: RiskScorer::new(),
+            fix_commands: FixCommandBuilder::new(crate::platform::shell::detect_shell()),
+            rules: Vec::new(),
+            case_insensitive: crate::platform::is_case_insensitive(),
+        }
+    }
+
+    /// User-defined rules that override category, severity and recommendation
+    pub fn with_rules(mut self, rules: Vec<ConflictRule>) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Treat names differing only in case as one binary. Defaults to the
+    /// platform's filesystem: on for Windows and macOS, off elsewhere.
+    pub fn with_case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// The name binaries are grouped by: lowercased when names ignore case
+    pub fn name_key(&self, name: &str) -> String {
+        if self.case_insensitive {
+            name.to_lowercase()
+        } else {
+            name.to_string()
         }
     }
 
@@ -21,7 +53,7 @@ impl ConflictDetector {
         for entry in path_entries {
             for executable in &entry.executables {
                 executable_index
-                    .entry(executable.name.clone())
+                    .entry(self.name_key(&executable.name))
                     .or_default()
                     .push(executable.clone());
             }
@@ -30,7 +62,7 @@ impl ConflictDetector {
         // Find all binaries with multiple instances (conflicts)
         let mut conflicts = Vec::new();
 
-        for (binary_name, mut instances) in executable_index {
+        for (_, mut instances) in executable_index {
             if instances.len() <= 1 {
                 // No conflict, skip
                 continue;
@@ -39,22 +71,72 @@ impl ConflictDetector {
             // Sort instances by PATH order (lower order = higher priority)
             instances.sort_by_key(|i| i.path_order);
 
-            // The first instance is the active one (what gets executed)
-            let active_instance = instances[0].clone();
+            // The first working instance is the active one (what gets
+            // executed): shells skip dangling links ahead of it. It names
+            // the conflict with its spelling.
+            let active = instances.iter().position(|i| !i.broken).unwrap_or(0);
+            for (idx, instance) in instances.iter_mut().enumerate() {
+                instance.is_active = idx == active;
+                instance.shadowed_by = (idx > active).then_some(active);
+            }
+            let active_instance = instances[active].clone();
+            let binary_name = active_instance.name.clone();
+
+            // User rules take precedence over the built-in assessment
+            let rules: Vec<&ConflictRule> = self
+                .rules
+                .iter()
+                .filter(|rule| rule.matches(&binary_name, &instances))
+                .collect();
 
             // Categorize the conflict
-            let category = self.categorizer.categorize(&binary_name, &instances);
+            let category = rules
+                .iter()
+                .rev()
+                .find_map(|rule| rule.category)
+                .unwrap_or_else(|| self.categorizer.categorize(&binary_name, &instances));
+
+            // Identical copies are noise rather than a real conflict
+            let identical = identical_reason(&instances);
+            let same_content = identical.is_some();
 
             // Assess severity
-            let severity = self.categorizer.assess_severity(category, &instances);
+            let rule_severity = rules
+                .iter()
+                .rev()
+                .find_map(|rule| rule.severity.map(|severity| (severity, &rule.name)));
+            let (severity, severity_reason) = if let Some((severity, rule)) = rule_severity {
+                (severity, format!("set by rule '{}'", rule))
+            } else if let Some(reason) = identical {
+                (Severity::Info, reason.to_string())
+            } else {
+                self.categorizer
+                    .assess_severity_with_reason(category, &instances)
+            };
+
+            let risk_score = self.risk_scorer.score(category, &instances, same_content);
 
             // Generate description
-            let description = self.generate_description(&binary_name, &instances, &active_instance);
+            let description = if same_content {
+                format!(
+                    "{}: identical copies installed in multiple locations. Active: {}",
+                    binary_name,
+                    active_instance.full_path.display()
+                )
+            } else {
+                self.generate_description(&binary_name, &instances, &active_instance)
+            };
 
             // Generate recommendation
-            let recommendation =
-                self.categorizer
-                    .generate_recommendation(category, &binary_name, &instances);
+            let recommendation = rules
+                .iter()
+                .rev()
+                .find_map(|rule| rule.recommendation.clone())
+                .or_else(|| {
+                    self.categorizer
+                        .generate_recommendation(category, &binary_name, &instances)
+                });
+            let commands = self.fix_commands.commands_for(category, &instances);
 
             conflicts.push(Conflict {
                 binary_name,
@@ -62,13 +144,17 @@ impl ConflictDetector {
                 active_instance,
                 category,
                 severity,
+                severity_reason,
                 description,
                 recommendation,
+                commands,
+                same_content,
+                risk_score,
             });
         }
 
         // Sort conflicts by severity (critical first)
-        conflicts.sort_by(|a, b| b.severity.cmp(&a.severity));
+        conflicts.sort_by_key(|c| std::cmp::Reverse(c.severity));
 
         Ok(conflicts)
     }
@@ -81,7 +167,7 @@ impl ConflictDetector {
         let all_conflicts = self.detect_conflicts(path_entries)?;
         Ok(all_conflicts
             .into_iter()
-            .find(|c| c.binary_name == binary_name))
+            .find(|c| self.name_key(&c.binary_name) == self.name_key(binary_name)))
     }
 
     fn generate_description(
@@ -114,6 +200,26 @@ impl ConflictDetector {
     }
 }
 
+/// Why every instance runs the same program, if they all do: they are one
+/// file (symlinks, hard links, a bind-mounted directory), or each
+/// carries a full-file digest from the same algorithm and all digests
+/// match. Prefix hashes are not trusted.
+fn identical_reason(instances: &[ExecutableInfo]) -> Option<&'static str> {
+    let (first, rest) = instances.split_first()?;
+
+    if rest.iter().all(|i| i.is_same_file(first)) {
+        return Some("every instance resolves to the same file");
+    }
+
+    let same_digest = |i: &ExecutableInfo| {
+        i.file_hash == first.file_hash
+            && i.hash_algorithm == first.hash_algorithm
+            && i.hash_scope == first.hash_scope
+    };
+    let full_hash = first.file_hash.is_some() && first.hash_scope == Some(HashScope::Full);
+    (full_hash && rest.iter().all(same_digest)).then_some("every instance has identical content")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,6 +233,7 @@ mod tests {
             is_wsl: false,
             wsl_version: None,
             wsl_distro: None,
+            wsl_interop: None,
         }
     }
 
@@ -138,18 +245,14 @@ mod tests {
             order: 0,
             exists: true,
             is_accessible: true,
+            sources: Vec::new(),
+            scope: None,
             executables: vec![ExecutableInfo {
                 name: "python".to_string(),
                 full_path: PathBuf::from("/usr/bin/python"),
                 size: 1000,
-                modified: 0,
-                is_symlink: false,
-                symlink_target: None,
                 resolved_path: PathBuf::from("/usr/bin/python"),
-                version: None,
-                manager: None,
-                file_hash: None,
-                path_order: 0,
+                ..ExecutableInfo::fixture()
             }],
         }];
 
@@ -166,18 +269,14 @@ mod tests {
                 order: 0,
                 exists: true,
                 is_accessible: true,
+                sources: Vec::new(),
+                scope: None,
                 executables: vec![ExecutableInfo {
                     name: "python".to_string(),
                     full_path: PathBuf::from("/usr/bin/python"),
                     size: 1000,
-                    modified: 0,
-                    is_symlink: false,
-                    symlink_target: None,
                     resolved_path: PathBuf::from("/usr/bin/python"),
-                    version: None,
-                    manager: None,
-                    file_hash: None,
-                    path_order: 0,
+                    ..ExecutableInfo::fixture()
                 }],
             },
             PathEntry {
@@ -185,18 +284,15 @@ mod tests {
                 order: 1,
                 exists: true,
                 is_accessible: true,
+                sources: Vec::new(),
+                scope: None,
                 executables: vec![ExecutableInfo {
                     name: "python".to_string(),
                     full_path: PathBuf::from("/usr/local/bin/python"),
                     size: 2000,
-                    modified: 0,
-                    is_symlink: false,
-                    symlink_target: None,
                     resolved_path: PathBuf::from("/usr/local/bin/python"),
-                    version: None,
-                    manager: None,
-                    file_hash: None,
                     path_order: 1,
+                    ..ExecutableInfo::fixture()
                 }],
             },
         ];
@@ -205,5 +301,100 @@ mod tests {
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].binary_name, "python");
         assert_eq!(result[0].instances.len(), 2);
+        assert!(!result[0].same_content);
+        assert!(result[0].active_instance.is_active);
+        assert!(result[0].instances[0].is_active);
+        assert!(!result[0].instances[1].is_active);
+        assert_eq!(result[0].instances[1].shadowed_by, Some(0));
+    }
+
+    fn hashed_entry(dir: &str, order: usize, hash: &str, scope: HashScope) -> PathEntry {
+        let full_path = PathBuf::from(dir).join("node");
+        PathEntry {
+            path: PathBuf::from(dir),
+            order,
+            exists: true,
+            is_accessible: true,
+            sources: Vec::new(),
+            scope: None,
+            executables: vec![ExecutableInfo {
+                name: "node".to_string(),
+                full_path: full_path.clone(),
+                size: 1000,
+                resolved_path: full_path,
+                file_hash: Some(hash.to_string()),
+                hash_algorithm: Some(crate::output::types::HashAlgorithm::Sha256),
+                hash_scope: Some(scope),
+                path_order: order,
+                ..ExecutableInfo::fixture()
+            }],
+        }
+    }
+
+    #[test]
+    fn test_identical_copies_are_collapsed() {
+        let detector = ConflictDetector::new(create_test_platform());
+        let path_entries = vec![
+            hashed_entry("/opt/a/bin", 0, "abc", HashScope::Full),
+            hashed_entry("/opt/b/bin", 1, "abc", HashScope::Full),
+        ];
+
+        let result = detector.detect_conflicts(&path_entries).unwrap();
+        assert!(result[0].same_content);
+        assert_eq!(result[0].severity, Severity::Info);
+        assert_eq!(
+            result[0].severity_reason,
+            "every instance has identical content"
+        );
+        assert!(result[0]
+            .description
+            .contains("identical copies installed in multiple locations"));
+
+        // Matching prefix hashes say nothing about the rest of the file
+        let path_entries = vec![
+            hashed_entry("/opt/a/bin", 0, "abc", HashScope::Prefix8K),
+            hashed_entry("/opt/b/bin", 1, "abc", HashScope::Prefix8K),
+        ];
+        let result = detector.detect_conflicts(&path_entries).unwrap();
+        assert!(!result[0].same_content);
+
+        // One file reached through two directories (a hard link or bind mount)
+        let mut path_entries = path_entries;
+        for entry in &mut path_entries {
+            entry.executables[0].file_hash = None;
+            entry.executables[0].dev = Some(1);
+            entry.executables[0].inode = Some(42);
+        }
+        let result = detector.detect_conflicts(&path_entries).unwrap();
+        assert!(result[0].same_content);
+        assert_eq!(
+            result[0].severity_reason,
+            "every instance resolves to the same file"
+        );
+    }
+
+    #[test]
+    fn test_names_differing_in_case() {
+        let mut path_entries = vec![
+            hashed_entry("/opt/a/bin", 0, "abc", HashScope::Full),
+            hashed_entry("/opt/b/bin", 1, "def", HashScope::Full),
+        ];
+        path_entries[1].executables[0].name = "Node".to_string();
+
+        let sensitive = ConflictDetector::new(create_test_platform()).with_case_insensitive(false);
+        assert!(sensitive
+            .detect_conflicts(&path_entries)
+            .unwrap()
+            .is_empty());
+
+        let insensitive = ConflictDetector::new(create_test_platform()).with_case_insensitive(true);
+        let result = insensitive.detect_conflicts(&path_entries).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].binary_name, "node");
+        assert_eq!(result[0].instances.len(), 2);
+        assert!(insensitive
+            .find_binary_conflicts(&path_entries, "NODE")
+            .unwrap()
+            .is_some());
     }
 }
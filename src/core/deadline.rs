@@ -0,0 +1,65 @@
+use crate::output::types::{Diagnostic, DiagnosticKind, Severity};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Wall-clock budget for one analysis run (`--max-scan-time`). Stages check
+/// it between units of work and report what they skipped once it passes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Deadline(Option<Instant>);
+
+impl Deadline {
+    /// No budget; never expires
+    pub fn none() -> Self {
+        Deadline(None)
+    }
+
+    pub fn after(budget: Duration) -> Self {
+        Deadline(Some(Instant::now() + budget))
+    }
+
+    pub fn expired(&self) -> bool {
+        self.0.is_some_and(|at| Instant::now() >= at)
+    }
+
+    /// Time left, or `None` without a budget
+    pub fn remaining(&self) -> Option<Duration> {
+        self.0
+            .map(|at| at.saturating_duration_since(Instant::now()))
+    }
+}
+
+/// Finding for work left undone because the budget ran out
+pub fn skipped_diagnostic(what: &str, paths: Vec<PathBuf>) -> Diagnostic {
+    let message = if paths.is_empty() {
+        format!("Scan time budget exceeded; skipped {}", what)
+    } else {
+        format!(
+            "Scan time budget exceeded; skipped {} for {} path(s)",
+            what,
+            paths.len()
+        )
+    };
+
+    Diagnostic {
+        kind: DiagnosticKind::ScanTimeout,
+        severity: Severity::Medium,
+        message,
+        paths,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadline() {
+        assert!(!Deadline::none().expired());
+        assert_eq!(Deadline::none().remaining(), None);
+
+        assert!(Deadline::after(Duration::ZERO).expired());
+        let later = Deadline::after(Duration::from_secs(3600));
+        assert!(!later.expired());
+        assert!(later.remaining().unwrap() > Duration::from_secs(3500));
+    }
+}
@@ -1,13 +1,21 @@
-use crate::error::Result;
-use crate::output::types::{ExecutableInfo, PathEntry};
+use crate::analyzers::symlink_resolver::{circular_symlink_diagnostic, SymlinkResolver};
+use crate::core::deadline::{self, Deadline};
+use crate::core::progress::{ProgressReporter, Stage};
+use crate::error::{Error, Result};
+use crate::output::types::{Diagnostic, ExecutableInfo, PathEntry};
 use crate::platform;
 use std::collections::HashSet;
 use std::path::PathBuf;
 use walkdir::WalkDir;
 
+#[derive(Debug, Clone)]
 pub struct ExecutableScanner {
     max_depth: usize,
     follow_symlinks: bool,
+    symlink_resolver: SymlinkResolver,
+    deadline: Deadline,
+    skip_dirs: Vec<PathBuf>,
+    progress: ProgressReporter,
 }
 
 impl ExecutableScanner {
@@ -15,6 +23,10 @@ impl ExecutableScanner {
         ExecutableScanner {
             max_depth: 1, // Only scan the directory itself, not subdirectories
             follow_symlinks: false,
+            symlink_resolver: SymlinkResolver::new(),
+            deadline: Deadline::none(),
+            skip_dirs: Vec::new(),
+            progress: ProgressReporter::none(),
         }
     }
 
@@ -22,43 +34,93 @@ impl ExecutableScanner {
         ExecutableScanner {
             max_depth,
             follow_symlinks,
+            symlink_resolver: SymlinkResolver::new(),
+            deadline: Deadline::none(),
+            skip_dirs: Vec::new(),
+            progress: ProgressReporter::none(),
         }
     }
 
-    pub fn scan_path_entries(&self, entries: &mut [PathEntry]) -> Result<()> {
+    /// Leave these PATH entries unscanned
+    pub fn with_skip_dirs(mut self, skip_dirs: Vec<PathBuf>) -> Self {
+        self.skip_dirs = skip_dirs;
+        self
+    }
+
+    /// Report each PATH entry as it is scanned
+    pub fn with_progress(mut self, progress: ProgressReporter) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Stop scanning further directories once `deadline` passes
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Scan every accessible PATH entry, returning diagnostics for entries
+    /// that could not be treated as executables (e.g. circular symlinks).
+    pub fn scan_path_entries(&self, entries: &mut [PathEntry]) -> Result<Vec<Diagnostic>> {
+        let mut diagnostics = Vec::new();
+        let mut skipped = Vec::new();
+        let progress = self
+            .progress
+            .start(Stage::ScanningDirectories, entries.len());
+
         for entry in entries.iter_mut() {
+            progress.tick();
             if !entry.exists || !entry.is_accessible {
                 continue;
             }
 
+            if self.deadline.expired() {
+                skipped.push(entry.path.clone());
+                continue;
+            }
+
             // Skip Windows system directories - they contain hundreds of system utilities
-            // that aren't relevant for developer tool conflict detection
+            // that aren't relevant for developer tool conflict detection - and
+            // directories the user excluded
             if self.should_skip_directory(&entry.path) {
-                if cfg!(debug_assertions) {
-                    eprintln!("Skipping system directory: {}", entry.path.display());
-                }
+                tracing::debug!(dir = %entry.path.display(), "skipping directory");
                 continue;
             }
 
-            match self.scan_directory(&entry.path, entry.order) {
+            tracing::debug!(dir = %entry.path.display(), order = entry.order, "scanning directory");
+            match self.scan_directory_with_diagnostics(&entry.path, entry.order, &mut diagnostics) {
                 Ok(executables) => {
+                    tracing::debug!(
+                        dir = %entry.path.display(),
+                        count = executables.len(),
+                        "found executables"
+                    );
                     entry.executables = executables;
                 }
                 Err(e) => {
-                    eprintln!("Warning: Failed to scan {}: {}", entry.path.display(), e);
+                    tracing::warn!(dir = %entry.path.display(), error = %e, "failed to scan directory");
                     // Continue with other directories even if one fails
                 }
             }
         }
 
-        Ok(())
+        progress.finish();
+        if !skipped.is_empty() {
+            diagnostics.push(deadline::skipped_diagnostic("directory scan", skipped));
+        }
+
+        Ok(diagnostics)
     }
 
-    fn should_skip_directory(&self, _path: &std::path::Path) -> bool {
+    pub(crate) fn should_skip_directory(&self, path: &std::path::Path) -> bool {
+        if self.skip_dirs.iter().any(|dir| same_dir(dir, path)) {
+            return true;
+        }
+
         // Windows system directories
         #[cfg(windows)]
         {
-            let path_str = _path.to_string_lossy().to_lowercase();
+            let path_str = path.to_string_lossy().to_lowercase();
             if path_str.contains("windows\\system32")
                 || path_str.contains("windows\\syswow64")
                 || path_str.contains("windows\\winsxs")
@@ -73,6 +135,15 @@ impl ExecutableScanner {
     }
 
     pub fn scan_directory(&self, path: &PathBuf, path_order: usize) -> Result<Vec<ExecutableInfo>> {
+        self.scan_directory_with_diagnostics(path, path_order, &mut Vec::new())
+    }
+
+    fn scan_directory_with_diagnostics(
+        &self,
+        path: &PathBuf,
+        path_order: usize,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<Vec<ExecutableInfo>> {
         let mut executables = Vec::new();
         let mut seen_names = HashSet::new();
 
@@ -105,21 +176,37 @@ impl ExecutableScanner {
                 continue;
             }
 
-            // Check if it's an executable
-            if !platform::is_executable(entry_path) {
+            // A symlink that loops back on itself or whose target is gone can't
+            // be stat'ed, so it would otherwise be dropped silently by the
+            // executable check below. Dangling links are kept and flagged.
+            let broken = entry.path_is_symlink() && std::fs::metadata(entry_path).is_err();
+            if broken {
+                if let Err(Error::CircularSymlink { cycle, .. }) =
+                    self.symlink_resolver.resolve_chain(entry_path)
+                {
+                    diagnostics.push(circular_symlink_diagnostic(entry_path, cycle));
+                    continue;
+                }
+            } else if !platform::is_executable(entry_path) {
                 continue;
             }
 
             // Get the binary name (without extension on Windows)
             let binary_name = self.get_binary_name(entry_path);
+            tracing::trace!(path = %entry_path.display(), name = %binary_name, "executable");
 
-            // Skip duplicates in the same directory
-            if seen_names.contains(&binary_name) {
+            // Skip duplicates in the same directory. On Windows `foo.bat`
+            // and `foo.exe` are both `foo` but both kept, so the one PATHEXT
+            // passes over shows up as a conflict.
+            let seen_name = if cfg!(windows) {
+                entry.file_name().to_string_lossy().to_lowercase()
+            } else {
+                binary_name.clone()
+            };
+            if !seen_names.insert(seen_name) {
                 continue;
             }
 
-            seen_names.insert(binary_name.clone());
-
             // Get metadata
             let metadata = match entry.metadata() {
                 Ok(m) => m,
@@ -134,6 +221,9 @@ impl ExecutableScanner {
                 .map(|d| d.as_secs() as i64)
                 .unwrap_or(0);
 
+            // Identifies hard links and bind mounts as the same physical file
+            let (dev, inode) = platform::get_file_id(entry_path).unzip();
+
             let is_symlink = entry_path.is_symlink();
             let symlink_target = if is_symlink {
                 std::fs::read_link(entry_path).ok()
@@ -145,6 +235,8 @@ impl ExecutableScanner {
             // This will be updated by the symlink resolver
             let resolved_path = entry_path.to_path_buf();
 
+            let ownership = platform::get_file_ownership(entry_path);
+
             executables.push(ExecutableInfo {
                 name: binary_name,
                 full_path: entry_path.to_path_buf(),
@@ -152,26 +244,43 @@ impl ExecutableScanner {
                 modified,
                 is_symlink,
                 symlink_target,
+                symlink_chain: Vec::new(), // Will be filled by the symlink resolver
                 resolved_path,
-                version: None,   // Will be filled by version extractor
+                version: None, // Will be filled by version extractor
+                version_probes: Vec::new(),
                 manager: None,   // Will be filled by manager detector
                 file_hash: None, // Optional, can be computed if needed
+                hash_algorithm: None,
+                hash_scope: None,
+                ownership,
+                dev,
+                inode,
+                binary_format: None, // Filled by the binary info extractor
+                origin: None,
                 path_order,
+                is_active: false,
+                shadowed_by: None,
+                immutable: platform::is_immutable(entry_path),
+                broken,
             });
         }
 
+        // The copy cmd runs first within the directory
+        if cfg!(windows) {
+            executables.sort_by_key(|exec| platform::windows::pathext_rank(&exec.full_path));
+        }
+
         Ok(executables)
     }
 
     fn get_binary_name(&self, path: &std::path::Path) -> String {
         let file_name = path.file_name().unwrap_or_default().to_string_lossy();
 
-        // On Windows, remove common executable extensions
+        // On Windows, remove the PATHEXT extension that lets it run by name
         if cfg!(windows) {
-            let name_lower = file_name.to_lowercase();
-            for ext in &[".exe", ".bat", ".cmd", ".ps1", ".com"] {
-                if name_lower.ends_with(ext) {
-                    return file_name[..file_name.len() - ext.len()].to_string();
+            if let Some((stem, ext)) = file_name.rsplit_once('.') {
+                if !stem.is_empty() && platform::windows::is_executable_extension(ext) {
+                    return stem.to_string();
                 }
             }
         }
@@ -186,6 +295,15 @@ impl Default for ExecutableScanner {
     }
 }
 
+/// Path equality that ignores case on Windows
+fn same_dir(a: &std::path::Path, b: &std::path::Path) -> bool {
+    if cfg!(windows) {
+        a.to_string_lossy().to_lowercase() == b.to_string_lossy().to_lowercase()
+    } else {
+        a == b
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,4 +330,75 @@ mod tests {
             assert_eq!(scanner.get_binary_name(&PathBuf::from("node")), "node");
         }
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_circular_symlink_reported_as_diagnostic() {
+        let dir = tempfile::tempdir().unwrap();
+        std::os::unix::fs::symlink("loop-b", dir.path().join("loop-a")).unwrap();
+        std::os::unix::fs::symlink("loop-a", dir.path().join("loop-b")).unwrap();
+
+        let mut entries = vec![PathEntry {
+            path: dir.path().to_path_buf(),
+            order: 0,
+            exists: true,
+            is_accessible: true,
+            executables: Vec::new(),
+            sources: Vec::new(),
+            scope: None,
+        }];
+
+        let scanner = ExecutableScanner::new();
+        let diagnostics = scanner.scan_path_entries(&mut entries).unwrap();
+
+        assert!(entries[0].executables.is_empty());
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics
+            .iter()
+            .all(|d| d.kind == crate::output::types::DiagnosticKind::CircularSymlink));
+        assert!(diagnostics[0].paths.len() >= 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_broken_symlink_is_kept_and_flagged() {
+        let dir = tempfile::tempdir().unwrap();
+        std::os::unix::fs::symlink("/nonexistent/node", dir.path().join("node")).unwrap();
+
+        let executables = ExecutableScanner::new()
+            .scan_directory(&dir.path().to_path_buf(), 0)
+            .unwrap();
+
+        assert_eq!(executables.len(), 1);
+        assert!(executables[0].broken);
+        assert_eq!(
+            executables[0].symlink_target.as_deref(),
+            Some(std::path::Path::new("/nonexistent/node"))
+        );
+    }
+
+    #[test]
+    fn test_expired_deadline_skips_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut entries = vec![PathEntry {
+            path: dir.path().to_path_buf(),
+            order: 0,
+            exists: true,
+            is_accessible: true,
+            executables: Vec::new(),
+            sources: Vec::new(),
+            scope: None,
+        }];
+
+        let scanner =
+            ExecutableScanner::new().with_deadline(Deadline::after(std::time::Duration::ZERO));
+        let diagnostics = scanner.scan_path_entries(&mut entries).unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind,
+            crate::output::types::DiagnosticKind::ScanTimeout
+        );
+        assert_eq!(diagnostics[0].paths, vec![dir.path().to_path_buf()]);
+    }
 }
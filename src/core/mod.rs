@@ -1,9 +1,23 @@
+pub mod binary_format;
 pub mod binary_info;
 pub mod conflict_detector;
+pub mod deadline;
 pub mod executable_scanner;
+pub(crate) mod parallel;
+pub mod path_edit;
 pub mod path_parser;
+pub mod progress;
+pub mod search_path;
+pub mod source_tracer;
+pub mod streaming;
 
 pub use binary_info::BinaryInfoExtractor;
 pub use conflict_detector::ConflictDetector;
+pub use deadline::Deadline;
 pub use executable_scanner::ExecutableScanner;
+pub use path_edit::PathEdit;
 pub use path_parser::PathParser;
+pub use progress::{Progress, ProgressReporter, Stage};
+pub use search_path::SearchPath;
+pub use source_tracer::SourceTracer;
+pub use streaming::{ConflictIndex, StreamConflict, StreamEvent, StreamSummary};
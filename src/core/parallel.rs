@@ -0,0 +1,60 @@
+use std::sync::Mutex;
+
+/// Run `f` over every item using at most `jobs` worker threads.
+///
+/// Workers pull items from a shared iterator, so slow items (large files,
+/// hung processes) don't hold up an entire pre-assigned chunk. A `jobs`
+/// value of 0 uses the available parallelism of the machine.
+pub(crate) fn for_each_bounded<T, F>(items: &mut [T], jobs: usize, f: F)
+where
+    T: Send,
+    F: Fn(&mut T) + Sync,
+{
+    let jobs = effective_jobs(jobs).min(items.len());
+
+    if jobs <= 1 {
+        items.iter_mut().for_each(f);
+        return;
+    }
+
+    let queue = Mutex::new(items.iter_mut());
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                // Hold the lock only long enough to take the next item
+                let next = queue.lock().map(|mut q| q.next()).ok().flatten();
+                match next {
+                    Some(item) => f(item),
+                    None => break,
+                }
+            });
+        }
+    });
+}
+
+pub(crate) fn effective_jobs(jobs: usize) -> usize {
+    if jobs == 0 {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    } else {
+        jobs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_each_bounded_visits_every_item() {
+        let mut items: Vec<usize> = (0..100).collect();
+        for_each_bounded(&mut items, 4, |i| *i *= 2);
+        assert_eq!(items, (0..100).map(|i| i * 2).collect::<Vec<_>>());
+
+        let mut empty: Vec<usize> = Vec::new();
+        for_each_bounded(&mut empty, 0, |i| *i += 1);
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+use crate::error::{Error, Result};
+use crate::platform;
+
+/// A hypothetical change to PATH, for previewing an edit before making it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathEdit {
+    /// Drop every occurrence of a directory
+    Remove(String),
+    /// Move a directory to a 0-based position; past the end means last
+    Move { dir: String, position: usize },
+    /// Move a directory just ahead of another one
+    MoveBefore { dir: String, before: String },
+    /// Put a directory first, dropping any later occurrence
+    Prepend(String),
+    /// Put a directory last, dropping any earlier occurrence
+    Append(String),
+}
+
+impl PathEdit {
+    /// Parse a move spec, `DIR=POS`, where POS is 1-based or `first`/`last`
+    pub fn parse_move(spec: &str) -> Result<Self> {
+        let (dir, position) = spec
+            .rsplit_once('=')
+            .ok_or_else(|| Error::InvalidPathEdit {
+                edit: spec.to_string(),
+                reason: "expected DIR=POSITION".to_string(),
+            })?;
+        Self::move_to(dir, position)
+    }
+
+    /// A move of `dir` to `position`, 1-based or `first`/`last`
+    pub fn move_to(dir: &str, position: &str) -> Result<Self> {
+        let position = match position.trim() {
+            "first" => 0,
+            "last" => usize::MAX,
+            n => match n.parse::<usize>() {
+                Ok(n) if n > 0 => n - 1,
+                _ => {
+                    return Err(Error::InvalidPathEdit {
+                        edit: format!("{}={}", dir, position),
+                        reason: "position must be a number from 1, first or last".to_string(),
+                    })
+                }
+            },
+        };
+
+        Ok(PathEdit::Move {
+            dir: dir.trim().to_string(),
+            position,
+        })
+    }
+}
+
+impl std::fmt::Display for PathEdit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathEdit::Remove(dir) => write!(f, "remove {}", dir),
+            PathEdit::Move { dir, position } if *position == usize::MAX => {
+                write!(f, "move {} last", dir)
+            }
+            PathEdit::Move { dir, position } => write!(f, "move {} to {}", dir, position + 1),
+            PathEdit::MoveBefore { dir, before } => write!(f, "move {} before {}", dir, before),
+            PathEdit::Prepend(dir) => write!(f, "prepend {}", dir),
+            PathEdit::Append(dir) => write!(f, "append {}", dir),
+        }
+    }
+}
+
+/// Apply `edits` in order to a PATH value
+pub fn apply_edits(path: &str, edits: &[PathEdit]) -> Result<String> {
+    let separator = platform::get_path_separator();
+    let mut entries: Vec<String> = path
+        .split(separator)
+        .filter(|entry| !entry.trim().is_empty())
+        .map(str::to_string)
+        .collect();
+
+    for edit in edits {
+        match edit {
+            PathEdit::Remove(dir) => {
+                take(&mut entries, dir, edit)?;
+            }
+            PathEdit::Move { dir, position } => {
+                let entry = take(&mut entries, dir, edit)?;
+                entries.insert((*position).min(entries.len()), entry);
+            }
+            PathEdit::MoveBefore { dir, before } => {
+                let entry = take(&mut entries, dir, edit)?;
+                let position = entries
+                    .iter()
+                    .position(|e| same_entry(e, before))
+                    .ok_or_else(|| Error::InvalidPathEdit {
+                        edit: edit.to_string(),
+                        reason: format!("{} is not on PATH", before),
+                    })?;
+                entries.insert(position, entry);
+            }
+            PathEdit::Prepend(dir) => {
+                entries.retain(|entry| !same_entry(entry, dir));
+                entries.insert(0, dir.clone());
+            }
+            PathEdit::Append(dir) => {
+                entries.retain(|entry| !same_entry(entry, dir));
+                entries.push(dir.clone());
+            }
+        }
+    }
+
+    Ok(entries.join(&separator.to_string()))
+}
+
+/// Whether `dir` comes before the first occurrence of `other` in a PATH
+/// value; false when `dir` is missing
+pub fn is_before(path: &str, dir: &str, other: &str) -> bool {
+    for entry in path.split(platform::get_path_separator()) {
+        if same_entry(entry, dir) {
+            return true;
+        }
+        if same_entry(entry, other) {
+            return false;
+        }
+    }
+    false
+}
+
+/// Remove every occurrence of `dir`, returning the first as written in PATH
+fn take(entries: &mut Vec<String>, dir: &str, edit: &PathEdit) -> Result<String> {
+    let first = entries
+        .iter()
+        .find(|entry| same_entry(entry, dir))
+        .cloned()
+        .ok_or_else(|| Error::InvalidPathEdit {
+            edit: edit.to_string(),
+            reason: format!("{} is not on PATH", dir),
+        })?;
+    entries.retain(|entry| !same_entry(entry, dir));
+    Ok(first)
+}
+
+/// Whether two PATH entries name the same directory once variables are
+/// expanded and trailing separators dropped (case-insensitively on Windows)
+fn same_entry(a: &str, b: &str) -> bool {
+    let normalize = |entry: &str| {
+        let expanded = platform::expand_env_vars(entry.trim());
+        let trimmed = expanded.trim_end_matches(['/', '\\']);
+        let trimmed = if trimmed.is_empty() {
+            &expanded
+        } else {
+            trimmed
+        };
+        if cfg!(windows) {
+            trimmed.to_lowercase()
+        } else {
+            trimmed.to_string()
+        }
+    };
+    normalize(a) == normalize(b)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_edits() {
+        let path = "/usr/local/bin:/usr/bin:/opt/tools/bin/:/bin";
+        let edits = [
+            PathEdit::Remove("/bin".to_string()),
+            PathEdit::parse_move("/opt/tools/bin=first").unwrap(),
+            PathEdit::Prepend("/home/me/.local/bin".to_string()),
+            PathEdit::Append("/usr/local/bin".to_string()),
+        ];
+
+        assert_eq!(
+            apply_edits(path, &edits).unwrap(),
+            "/home/me/.local/bin:/opt/tools/bin/:/usr/bin:/usr/local/bin"
+        );
+    }
+
+    #[test]
+    fn test_move_positions() {
+        let path = "/a:/b:/c";
+        let moved = |spec: &str| apply_edits(path, &[PathEdit::parse_move(spec).unwrap()]);
+
+        assert_eq!(moved("/c=2").unwrap(), "/a:/c:/b");
+        assert_eq!(moved("/a=last").unwrap(), "/b:/c:/a");
+        assert_eq!(moved("/a=99").unwrap(), "/b:/c:/a");
+        assert!(moved("/d=1").is_err());
+        assert!(PathEdit::parse_move("/a=0").is_err());
+        assert!(PathEdit::parse_move("/a").is_err());
+
+        let before = |dir: &str, before: &str| {
+            apply_edits(
+                path,
+                &[PathEdit::MoveBefore {
+                    dir: dir.to_string(),
+                    before: before.to_string(),
+                }],
+            )
+        };
+        assert_eq!(before("/c", "/b/").unwrap(), "/a:/c:/b");
+        assert!(before("/c", "/d").is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::error::Result;
-use crate::output::types::PathEntry;
+use crate::output::types::{PathEntry, PathIssue, PathIssueKind};
 use crate::platform;
 use std::path::PathBuf;
 
@@ -40,6 +40,8 @@ impl PathParser {
                 exists,
                 is_accessible,
                 executables: Vec::new(), // Will be populated by scanner
+                sources: Vec::new(),
+                scope: None,
             });
         }
 
@@ -74,6 +76,31 @@ impl PathParser {
     }
 }
 
+/// Entries that are missing, not directories, unreadable or empty
+pub fn find_path_issues(entries: &[PathEntry]) -> Vec<PathIssue> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let kind = if !entry.exists {
+                PathIssueKind::Missing
+            } else if !entry.path.is_dir() {
+                PathIssueKind::NotADirectory
+            } else if !entry.is_accessible {
+                PathIssueKind::PermissionDenied
+            } else if std::fs::read_dir(&entry.path).ok()?.next().is_none() {
+                PathIssueKind::Empty
+            } else {
+                return None;
+            };
+            Some(PathIssue {
+                directory: entry.path.clone(),
+                order: entry.order,
+                kind,
+            })
+        })
+        .collect()
+}
+
 impl Default for PathParser {
     fn default() -> Self {
         Self::new()
@@ -104,6 +131,37 @@ mod tests {
         assert_eq!(entries[2].order, 2);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_find_path_issues() {
+        let dir = tempfile::tempdir().unwrap();
+        let full = dir.path().join("full");
+        let empty = dir.path().join("empty");
+        let file = dir.path().join("file");
+        std::fs::create_dir(&full).unwrap();
+        std::fs::write(full.join("tool"), "").unwrap();
+        std::fs::create_dir(&empty).unwrap();
+        std::fs::write(&file, "").unwrap();
+
+        let path_var = [&full, &dir.path().join("missing"), &empty, &file]
+            .map(|p| p.display().to_string())
+            .join(":");
+        let entries = PathParser::new().parse_path(&path_var).unwrap();
+
+        let issues: Vec<(usize, PathIssueKind)> = find_path_issues(&entries)
+            .iter()
+            .map(|issue| (issue.order, issue.kind))
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                (1, PathIssueKind::Missing),
+                (2, PathIssueKind::Empty),
+                (3, PathIssueKind::NotADirectory),
+            ]
+        );
+    }
+
     #[test]
     fn test_normalize_path() {
         let parser = PathParser::new();
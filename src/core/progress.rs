@@ -0,0 +1,129 @@
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Stage of `PathAnalyzer::analyze` a progress update belongs to, in the
+/// order they run. Stages that are disabled never report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    ScanningDirectories,
+    ResolvingSymlinks,
+    DetectingManagers,
+    ExtractingVersions,
+    InspectingBinaries,
+    LookingUpOrigins,
+    DetectingConflicts,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Stage::ScanningDirectories => "Scanning directories",
+            Stage::ResolvingSymlinks => "Resolving symlinks",
+            Stage::DetectingManagers => "Detecting managers",
+            Stage::ExtractingVersions => "Extracting versions",
+            Stage::InspectingBinaries => "Inspecting binaries",
+            Stage::LookingUpOrigins => "Looking up package origins",
+            Stage::DetectingConflicts => "Detecting conflicts",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// `done` of `total` items of `stage` are finished. Each stage first
+/// reports `done == 0` and last reports `done == total`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub stage: Stage,
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Where progress updates go. Cheap to clone; does nothing when unset.
+/// The callback may be called from worker threads.
+#[derive(Clone, Default)]
+pub struct ProgressReporter(Option<Arc<dyn Fn(Progress) + Send + Sync>>);
+
+impl ProgressReporter {
+    pub fn new(callback: impl Fn(Progress) + Send + Sync + 'static) -> Self {
+        ProgressReporter(Some(Arc::new(callback)))
+    }
+
+    /// Reporter that drops every update
+    pub fn none() -> Self {
+        ProgressReporter(None)
+    }
+
+    pub fn report(&self, stage: Stage, done: usize, total: usize) {
+        if let Some(callback) = &self.0 {
+            callback(Progress { stage, done, total });
+        }
+    }
+
+    /// Start `stage` with `total` items, reporting zero done
+    pub fn start(&self, stage: Stage, total: usize) -> StageProgress<'_> {
+        self.report(stage, 0, total);
+        StageProgress {
+            reporter: self,
+            stage,
+            total,
+            done: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl fmt::Debug for ProgressReporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ProgressReporter")
+            .field(&self.0.as_ref().map(|_| "callback"))
+            .finish()
+    }
+}
+
+/// Item counter for one running stage; safe to share between workers
+pub struct StageProgress<'a> {
+    reporter: &'a ProgressReporter,
+    stage: Stage,
+    total: usize,
+    done: AtomicUsize,
+}
+
+impl StageProgress<'_> {
+    /// One more item finished
+    pub fn tick(&self) {
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        self.reporter
+            .report(self.stage, done.min(self.total), self.total);
+    }
+
+    /// The stage is over, including items skipped without a tick
+    pub fn finish(self) {
+        if self.done.load(Ordering::Relaxed) < self.total {
+            self.reporter.report(self.stage, self.total, self.total);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_stage_progress() {
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&updates);
+        let reporter = ProgressReporter::new(move |p| sink.lock().unwrap().push(p.done));
+
+        let stage = reporter.start(Stage::ExtractingVersions, 3);
+        stage.tick();
+        stage.finish();
+        assert_eq!(*updates.lock().unwrap(), vec![0, 1, 3]);
+
+        // Nothing is reported twice once every item ticked
+        let stage = reporter.start(Stage::ScanningDirectories, 1);
+        stage.tick();
+        stage.finish();
+        assert_eq!(*updates.lock().unwrap(), vec![0, 1, 3, 0, 1]);
+    }
+}
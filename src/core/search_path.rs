@@ -0,0 +1,159 @@
+use crate::platform;
+use std::path::{Path, PathBuf};
+
+/// A search-path variable other than PATH, such as MANPATH, whose
+/// directories are searched in order and where the first match wins
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchPath {
+    pub variable: String,
+    pub dirs: Vec<PathBuf>,
+}
+
+impl SearchPath {
+    /// Split `value` like PATH. Empty components, which some variables use to
+    /// splice in a default list, are skipped.
+    pub fn parse(variable: &str, value: &str) -> Self {
+        let dirs = value
+            .split(platform::get_path_separator())
+            .map(str::trim)
+            .filter(|dir| !dir.is_empty())
+            .map(|dir| PathBuf::from(platform::expand_env_vars(dir)))
+            .collect();
+
+        SearchPath {
+            variable: variable.to_string(),
+            dirs,
+        }
+    }
+
+    /// The variable from the environment, when it is set and not empty
+    pub fn from_env(variable: &str) -> Option<Self> {
+        std::env::var(variable)
+            .ok()
+            .map(|value| Self::parse(variable, &value))
+            .filter(|search_path| !search_path.dirs.is_empty())
+    }
+
+    /// MANPATH as `man` searches it. Without the variable, and in place of
+    /// the first empty component of it, `man` uses the manual directories
+    /// beside the PATH directories: `<dir>/../share/man` and `<dir>/../man`.
+    pub fn manpath(value: Option<&str>, path_dirs: &[PathBuf]) -> Self {
+        let mut derived: Vec<PathBuf> = Vec::new();
+        for prefix in path_dirs.iter().filter_map(|dir| dir.parent()) {
+            for dir in [prefix.join("share").join("man"), prefix.join("man")] {
+                if dir.is_dir() && !derived.contains(&dir) {
+                    derived.push(dir);
+                }
+            }
+        }
+
+        let dirs = match value {
+            None => derived,
+            Some(value) => {
+                let mut dirs = Vec::new();
+                let mut derived = Some(derived);
+                for component in value.split(platform::get_path_separator()).map(str::trim) {
+                    if component.is_empty() {
+                        dirs.extend(derived.take().unwrap_or_default());
+                    } else {
+                        dirs.push(PathBuf::from(platform::expand_env_vars(component)));
+                    }
+                }
+                dirs
+            }
+        };
+
+        SearchPath {
+            variable: "MANPATH".to_string(),
+            dirs,
+        }
+    }
+
+    /// Files in `subdir` of each directory whose name satisfies `matches`,
+    /// in search order. The first one is what a lookup finds.
+    pub fn find_all(&self, subdir: &str, matches: impl Fn(&str) -> bool) -> Vec<PathBuf> {
+        let mut found = Vec::new();
+        for dir in &self.dirs {
+            let Ok(entries) = std::fs::read_dir(dir.join(subdir)) else {
+                continue;
+            };
+            let mut files: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| matches(&entry.file_name().to_string_lossy()))
+                .map(|entry| entry.path())
+                .collect();
+            files.sort();
+            found.extend(files);
+        }
+        found
+    }
+
+    /// The directory of the search path that `file` was found in
+    pub fn dir_of(&self, file: &Path) -> Option<&Path> {
+        self.dirs
+            .iter()
+            .find(|dir| file.starts_with(dir))
+            .map(PathBuf::as_path)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_all_in_search_order() {
+        let root = tempfile::tempdir().unwrap();
+        let first = root.path().join("first");
+        let second = root.path().join("second");
+        for dir in [&first, &second] {
+            std::fs::create_dir_all(dir.join("man1")).unwrap();
+            std::fs::write(dir.join("man1/git.1.gz"), b"").unwrap();
+        }
+        std::fs::write(second.join("man1/git-log.1"), b"").unwrap();
+
+        let value = format!(
+            "{}::{}:{}",
+            first.display(),
+            root.path().join("missing").display(),
+            second.display()
+        );
+        let search_path = SearchPath::parse("MANPATH", &value);
+        assert_eq!(search_path.dirs.len(), 3);
+
+        let found = search_path.find_all("man1", |name| name.starts_with("git.1"));
+        assert_eq!(
+            found,
+            vec![first.join("man1/git.1.gz"), second.join("man1/git.1.gz")]
+        );
+        assert_eq!(search_path.dir_of(&found[1]), Some(second.as_path()));
+    }
+
+    #[test]
+    fn test_manpath_derived_from_path() {
+        let root = tempfile::tempdir().unwrap();
+        let brew = root.path().join("homebrew");
+        let usr = root.path().join("usr");
+        std::fs::create_dir_all(brew.join("share/man")).unwrap();
+        std::fs::create_dir_all(usr.join("share/man")).unwrap();
+        let path_dirs = vec![brew.join("bin"), brew.join("sbin"), usr.join("bin")];
+
+        let unset = SearchPath::manpath(None, &path_dirs);
+        assert_eq!(
+            unset.dirs,
+            vec![brew.join("share/man"), usr.join("share/man")]
+        );
+
+        // A leading colon puts the derived directories first
+        let extra = root.path().join("extra/man");
+        let value = format!(":{}", extra.display());
+        let spliced = SearchPath::manpath(Some(&value), &path_dirs);
+        assert_eq!(
+            spliced.dirs,
+            vec![brew.join("share/man"), usr.join("share/man"), extra.clone()]
+        );
+
+        let set = SearchPath::manpath(Some(&extra.display().to_string()), &path_dirs);
+        assert_eq!(set.dirs, vec![extra]);
+    }
+}
@@ -0,0 +1,901 @@
+use crate::output::types::{Conflict, PathEntry, PathSource};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// How deep `source`/`.` lines are followed
+const MAX_SOURCE_DEPTH: usize = 4;
+
+static VARIABLE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\$\{?([A-Za-z_][A-Za-z0-9_]*)\}?").unwrap());
+static ASSIGNMENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^(?:export\s+|declare\s+-x\s+|typeset\s+-x\s+)?([A-Za-z_][A-Za-z0-9_]*)(\+?=)(.*)$",
+    )
+    .unwrap()
+});
+static EVAL_INIT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^eval\s+["']?\$\(\s*([^\s)"']+)"#).unwrap());
+/// PowerShell: `$env:Path = ...` or `$env:PATH += ...`
+static PS_PATH: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^\$env:path\s*(\+?=)\s*(.+)$").unwrap());
+/// PowerShell: `$tools = 'C:\tools'`
+static PS_ASSIGNMENT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\$([A-Za-z_][A-Za-z0-9_]*)\s*=\s*(.+)$").unwrap());
+/// PowerShell: `$($env:X)`, `${env:X}`, `$env:X` and `$x`
+static PS_VARIABLE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\$\(\$env:(\w+)\)|\$\{env:(\w+)\}|\$env:(\w+)|\$(\w+)").unwrap());
+/// PowerShell: `Invoke-Expression (& starship init powershell)`
+static PS_INIT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)(?:invoke-expression|\biex\b).*?&\s*['"]?([^\s'")]+)|&\s*['"]?([^\s'")]+).*\|\s*(?:invoke-expression|iex)\b"#)
+        .unwrap()
+});
+/// fish: `eval (brew shellenv)` or `brew shellenv | source`
+static FISH_INIT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^(?:eval\s+\(\s*([^\s)]+)|([^\s|]+)[^|]*\|\s*source\s*$)"#).unwrap()
+});
+
+/// Where a PATH entry comes from: a line of a shell startup file that adds
+/// it, or that runs a tool's init (`eval "$(brew shellenv)"`) that does
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Addition {
+    /// Directory as written, with variables expanded
+    dir: Option<PathBuf>,
+    /// Tool whose `eval` init line adds directories we cannot see
+    tool: Option<String>,
+    source: PathSource,
+}
+
+/// Reads the shell startup files that set PATH (/etc/profile,
+/// /etc/profile.d/*, ~/.profile, ~/.bashrc, ~/.zshrc and friends, fish's
+/// config.fish, conf.d snippets and universal `fish_user_paths`, and the
+/// PowerShell profiles) and finds the lines that add each PATH entry,
+/// following `source` and `.` lines.
+///
+/// This is a line scanner, not a shell: conditionals are ignored, so a line
+/// counts even when it only runs on another machine, and directories built
+/// from command output cannot be traced. Tool init lines are matched to
+/// entries whose path names the tool.
+pub struct SourceTracer {
+    home: Option<PathBuf>,
+    files: Option<Vec<PathBuf>>,
+}
+
+impl SourceTracer {
+    pub fn new() -> Self {
+        SourceTracer {
+            home: std::env::var_os("HOME")
+                .or_else(|| std::env::var_os("USERPROFILE"))
+                .map(PathBuf::from),
+            files: None,
+        }
+    }
+
+    /// Expand `~` and `$HOME` to `home`
+    pub fn with_home(mut self, home: PathBuf) -> Self {
+        self.home = Some(home);
+        self
+    }
+
+    /// Read these files instead of the standard startup files
+    pub fn with_files(mut self, files: Vec<PathBuf>) -> Self {
+        self.files = Some(files);
+        self
+    }
+
+    /// System-wide files first, then the user's, in the order bash and zsh
+    /// login shells read them, then fish's, then PowerShell's
+    pub fn startup_files(&self) -> Vec<PathBuf> {
+        if let Some(files) = &self.files {
+            return files.clone();
+        }
+
+        let mut files = vec![PathBuf::from("/etc/profile")];
+        if let Ok(dir) = std::fs::read_dir("/etc/profile.d") {
+            let mut scripts: Vec<PathBuf> = dir
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.extension().map(|ext| ext == "sh").unwrap_or(false))
+                .collect();
+            scripts.sort();
+            files.extend(scripts);
+        }
+        files.push(PathBuf::from("/etc/zshenv"));
+        files.push(PathBuf::from("/etc/zsh/zshenv"));
+        if let Some(home) = &self.home {
+            for name in [
+                ".profile",
+                ".bash_profile",
+                ".bash_login",
+                ".bashrc",
+                ".zshenv",
+                ".zprofile",
+                ".zshrc",
+            ] {
+                files.push(home.join(name));
+            }
+        }
+
+        // fish loads universal variables, then conf.d snippets, then
+        // config.fish
+        let fish_dirs = std::iter::once(PathBuf::from("/etc/fish"))
+            .chain(self.home.as_ref().map(|home| home.join(".config/fish")));
+        for dir in fish_dirs {
+            files.push(dir.join("fish_variables"));
+            if let Ok(entries) = std::fs::read_dir(dir.join("conf.d")) {
+                let mut snippets: Vec<PathBuf> = entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| is_fish(path))
+                    .collect();
+                snippets.sort();
+                files.extend(snippets);
+            }
+            files.push(dir.join("config.fish"));
+        }
+
+        files.extend(powershell_profiles(self.home.as_deref()));
+        files
+    }
+
+    /// Fill in `sources` of every entry some startup file adds
+    pub fn trace(&self, entries: &mut [PathEntry]) {
+        let additions = self.additions();
+
+        for entry in entries.iter_mut() {
+            entry.sources = additions
+                .iter()
+                .filter(|addition| {
+                    addition
+                        .dir
+                        .as_deref()
+                        .map(|dir| same_dir(dir, &entry.path))
+                        .unwrap_or(false)
+                })
+                .map(|addition| addition.source.clone())
+                .collect();
+        }
+
+        // Tool init lines only explain entries no line names directly
+        for entry in entries.iter_mut().filter(|e| e.sources.is_empty()) {
+            let path = entry.path.to_string_lossy().to_lowercase();
+            entry.sources = additions
+                .iter()
+                .filter(|addition| {
+                    addition
+                        .tool
+                        .as_deref()
+                        .map(|tool| path.contains(tool))
+                        .unwrap_or(false)
+                })
+                .map(|addition| addition.source.clone())
+                .collect();
+        }
+    }
+
+    fn additions(&self) -> Vec<Addition> {
+        let mut additions = Vec::new();
+        let mut variables = HashMap::new();
+        if let Some(home) = &self.home {
+            variables.insert("HOME".to_string(), home.to_string_lossy().into_owned());
+        }
+        let mut visited = HashSet::new();
+        for file in self.startup_files() {
+            self.read_file(&file, 0, &mut variables, &mut visited, &mut additions);
+        }
+        additions
+    }
+
+    fn read_file(
+        &self,
+        file: &Path,
+        depth: usize,
+        variables: &mut HashMap<String, String>,
+        visited: &mut HashSet<PathBuf>,
+        additions: &mut Vec<Addition>,
+    ) {
+        if depth > MAX_SOURCE_DEPTH || !visited.insert(file.to_path_buf()) {
+            return;
+        }
+        let Ok(contents) = std::fs::read_to_string(file) else {
+            return;
+        };
+
+        for (idx, line) in contents.lines().enumerate() {
+            let source = PathSource {
+                file: file.to_path_buf(),
+                line: idx + 1,
+            };
+            if file
+                .file_name()
+                .map(|n| n == "fish_variables")
+                .unwrap_or(false)
+            {
+                for dir in fish_user_paths(line) {
+                    self.push_dir(&dir, variables, &source, additions);
+                }
+                continue;
+            }
+            if is_powershell(file) {
+                self.read_powershell_line(
+                    line, file, &source, depth, variables, visited, additions,
+                );
+                continue;
+            }
+            if is_fish(file) {
+                for statement in statements(line) {
+                    self.read_fish_statement(
+                        statement, &source, depth, variables, visited, additions,
+                    );
+                }
+                continue;
+            }
+            for statement in statements(line) {
+                if let Some(sourced) = sourced_file(statement) {
+                    if let Some(sourced) = self.expand(sourced, variables) {
+                        self.read_file(
+                            Path::new(&sourced),
+                            depth + 1,
+                            variables,
+                            visited,
+                            additions,
+                        );
+                    }
+                } else if let Some(captures) = EVAL_INIT.captures(statement) {
+                    let tool = Path::new(&captures[1])
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_lowercase())
+                        .filter(|tool| tool.len() >= 3);
+                    if let Some(tool) = tool {
+                        additions.push(Addition {
+                            dir: None,
+                            tool: Some(tool),
+                            source: source.clone(),
+                        });
+                    }
+                } else if let Some(captures) = ASSIGNMENT.captures(statement) {
+                    let (name, value) = (&captures[1], unquote(captures[3].trim()));
+                    match name {
+                        "PATH" => {
+                            for dir in value.split(':') {
+                                self.push_dir(dir, variables, &source, additions);
+                            }
+                        }
+                        // zsh's array view of PATH: path=(~/bin $path)
+                        "path" => {
+                            let list = value.trim_start_matches('(').trim_end_matches(')');
+                            for dir in list.split_whitespace() {
+                                self.push_dir(unquote(dir), variables, &source, additions);
+                            }
+                        }
+                        _ => {
+                            if let Some(value) = self.expand(value, variables) {
+                                variables.insert(name.to_string(), value);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// `set -gx PATH ~/bin $PATH`, `set -U fish_user_paths ...`,
+    /// `fish_add_path ...` and `source FILE`
+    fn read_fish_statement(
+        &self,
+        statement: &str,
+        source: &PathSource,
+        depth: usize,
+        variables: &mut HashMap<String, String>,
+        visited: &mut HashSet<PathBuf>,
+        additions: &mut Vec<Addition>,
+    ) {
+        let statement = statement
+            .strip_prefix("and ")
+            .or_else(|| statement.strip_prefix("or "))
+            .unwrap_or(statement)
+            .trim();
+
+        if let Some(captures) = FISH_INIT.captures(statement) {
+            let command = captures.get(1).or_else(|| captures.get(2));
+            let tool = command
+                .map(|m| Path::new(m.as_str()))
+                .and_then(|path| path.file_name())
+                .map(|name| name.to_string_lossy().to_lowercase())
+                .filter(|tool| tool.len() >= 3);
+            if let Some(tool) = tool {
+                additions.push(Addition {
+                    dir: None,
+                    tool: Some(tool),
+                    source: source.clone(),
+                });
+            }
+            return;
+        }
+
+        let words: Vec<&str> = statement.split_whitespace().map(unquote).collect();
+        match words.split_first() {
+            Some((&"source", [file, ..])) => {
+                if let Some(file) = self.expand(file, variables) {
+                    self.read_file(Path::new(&file), depth + 1, variables, visited, additions);
+                }
+            }
+            Some((&"fish_add_path", args)) => {
+                for dir in args.iter().filter(|arg| !arg.starts_with('-')) {
+                    self.push_dir(dir, variables, source, additions);
+                }
+            }
+            Some((&"set", args)) => {
+                let mut args = args.iter().skip_while(|arg| arg.starts_with('-'));
+                let erase = words.iter().any(|w| matches!(*w, "-e" | "--erase"));
+                let Some(&name) = args.next() else {
+                    return;
+                };
+                let values: Vec<&str> = args.copied().collect();
+                match name {
+                    "PATH" | "fish_user_paths" => {
+                        for dir in values.iter().flat_map(|value| value.split(':')) {
+                            self.push_dir(dir, variables, source, additions);
+                        }
+                    }
+                    _ if !erase => {
+                        if let Some(value) = self.expand(&values.join(" "), variables) {
+                            variables.insert(name.to_string(), value);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// `$env:Path = "C:\tools;" + $env:Path`, `$x = ...`, dot-sourced
+    /// scripts and `Invoke-Expression (& tool init powershell)`
+    #[allow(clippy::too_many_arguments)]
+    fn read_powershell_line(
+        &self,
+        line: &str,
+        file: &Path,
+        source: &PathSource,
+        depth: usize,
+        variables: &mut HashMap<String, String>,
+        visited: &mut HashSet<PathBuf>,
+        additions: &mut Vec<Addition>,
+    ) {
+        let line = line.trim();
+        if line.starts_with('#') {
+            return;
+        }
+        let script_root = file.parent().map(|dir| dir.to_string_lossy().into_owned());
+        let expand = |value: &str, variables: &HashMap<String, String>| {
+            expand_powershell(value, variables, script_root.as_deref())
+        };
+
+        if let Some(captures) = PS_PATH.captures(line) {
+            // Only the literal parts: $env:Path itself is the PATH being
+            // extended, so it splits them like a separator would
+            let separator = crate::platform::get_path_separator();
+            let value = PS_SELF.replace_all(&captures[2], separator.to_string().as_str());
+            for literal in string_literals(&value) {
+                let Some(literal) = expand(&literal, variables) else {
+                    continue;
+                };
+                for dir in literal
+                    .split(separator)
+                    .map(str::trim)
+                    .filter(|d| !d.is_empty())
+                {
+                    additions.push(Addition {
+                        dir: Some(PathBuf::from(dir)),
+                        tool: None,
+                        source: source.clone(),
+                    });
+                }
+            }
+        } else if let Some(captures) = PS_INIT.captures(line) {
+            let command = captures.get(1).or_else(|| captures.get(2));
+            let tool = command
+                .map(|m| Path::new(m.as_str()))
+                .and_then(|path| path.file_stem())
+                .map(|name| name.to_string_lossy().to_lowercase())
+                .filter(|tool| tool.len() >= 3);
+            if let Some(tool) = tool {
+                additions.push(Addition {
+                    dir: None,
+                    tool: Some(tool),
+                    source: source.clone(),
+                });
+            }
+        } else if let Some(sourced) = line.strip_prefix(". ") {
+            let sourced = string_literals(sourced.trim())
+                .into_iter()
+                .next()
+                .unwrap_or_else(|| sourced.trim().to_string());
+            if let Some(sourced) = expand(&sourced, variables) {
+                self.read_file(
+                    Path::new(&sourced),
+                    depth + 1,
+                    variables,
+                    visited,
+                    additions,
+                );
+            }
+        } else if let Some(captures) = PS_ASSIGNMENT.captures(line) {
+            let value = string_literals(&captures[2]).concat();
+            if let Some(value) = expand(&value, variables) {
+                variables.insert(format!("ps:{}", captures[1].to_lowercase()), value);
+            }
+        }
+    }
+
+    fn push_dir(
+        &self,
+        dir: &str,
+        variables: &HashMap<String, String>,
+        source: &PathSource,
+        additions: &mut Vec<Addition>,
+    ) {
+        // The PATH being extended, not a new entry
+        if matches!(
+            dir,
+            "$PATH" | "${PATH}" | "$path" | "${path}" | "$fish_user_paths" | ""
+        ) {
+            return;
+        }
+        if let Some(dir) = self.expand(dir, variables) {
+            additions.push(Addition {
+                dir: Some(PathBuf::from(dir)),
+                tool: None,
+                source: source.clone(),
+            });
+        }
+    }
+
+    /// Expand `~` and variables; `None` if one is unknown or the value comes
+    /// from a command
+    fn expand(&self, value: &str, variables: &HashMap<String, String>) -> Option<String> {
+        // Command substitution: $(...), `...` or fish's (...)
+        if value.contains('(') || value.contains('`') {
+            return None;
+        }
+        let value = match (value.strip_prefix('~'), &self.home) {
+            (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+                format!("{}{}", home.display(), rest)
+            }
+            _ => value.to_string(),
+        };
+
+        let mut unknown = false;
+        let expanded = VARIABLE.replace_all(&value, |captures: &regex::Captures| {
+            let name = &captures[1];
+            variables
+                .get(name)
+                .cloned()
+                .or_else(|| std::env::var(name).ok())
+                .unwrap_or_else(|| {
+                    unknown = true;
+                    String::new()
+                })
+        });
+        (!unknown).then(|| expanded.into_owned())
+    }
+}
+
+impl Default for SourceTracer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Point each conflict's recommendation at the startup file that adds the
+/// active copy's directory
+pub fn annotate_recommendations(conflicts: &mut [Conflict], entries: &[PathEntry]) {
+    for conflict in conflicts {
+        let Some(recommendation) = conflict.recommendation.as_mut() else {
+            continue;
+        };
+        let Some(entry) = entries
+            .iter()
+            .find(|e| e.order == conflict.active_instance.path_order)
+        else {
+            continue;
+        };
+        if let Some(source) = entry.sources.first() {
+            recommendation.push_str(&format!(
+                " ({} is added to PATH by {})",
+                entry.path.display(),
+                source
+            ));
+        }
+    }
+}
+
+/// Commands on one line, split on `;` and `&&`
+fn statements(line: &str) -> impl Iterator<Item = &str> {
+    let line = line.trim();
+    let line = if line.starts_with('#') { "" } else { line };
+    line.split(';')
+        .flat_map(|part| part.split("&&"))
+        .map(str::trim)
+        .map(|statement| {
+            // `[ -d ~/bin ] && PATH=...` and `if ...; then PATH=...`
+            statement
+                .strip_prefix("then ")
+                .or_else(|| statement.strip_prefix("else "))
+                .unwrap_or(statement)
+                .trim()
+        })
+        .filter(|statement| !statement.is_empty())
+}
+
+/// `$env:Path`, `${env:PATH}` or `$($env:PATH)` inside a PATH assignment
+static PS_SELF: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\$\(\$env:path\)|\$\{env:path\}|\$env:path\b").unwrap());
+
+/// Current-user profiles of PowerShell 7 and Windows PowerShell, then the
+/// all-users ones, in the order they run
+fn powershell_profiles(home: Option<&Path>) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if cfg!(windows) {
+        if let Some(pshome) = std::env::var_os("ProgramFiles") {
+            dirs.push(PathBuf::from(pshome).join("PowerShell").join("7"));
+        }
+        if let Some(windir) = std::env::var_os("WINDIR") {
+            dirs.push(PathBuf::from(windir).join(r"System32\WindowsPowerShell\v1.0"));
+        }
+        // Documents is often redirected to OneDrive
+        let documents = std::env::var_os("OneDrive")
+            .map(|onedrive| PathBuf::from(onedrive).join("Documents"))
+            .into_iter()
+            .chain(home.map(|home| home.join("Documents")));
+        for documents in documents {
+            dirs.push(documents.join("PowerShell"));
+            dirs.push(documents.join("WindowsPowerShell"));
+        }
+    } else if let Some(home) = home {
+        dirs.push(home.join(".config/powershell"));
+    }
+
+    dirs.into_iter()
+        .flat_map(|dir| {
+            [
+                dir.join("profile.ps1"),
+                dir.join("Microsoft.PowerShell_profile.ps1"),
+            ]
+        })
+        .collect()
+}
+
+fn is_powershell(file: &Path) -> bool {
+    file.extension()
+        .map(|ext| ext.eq_ignore_ascii_case("ps1"))
+        .unwrap_or(false)
+}
+
+/// The contents of each '...' and "..." string in a PowerShell expression,
+/// or the whole expression when it is a bare word
+fn string_literals(expression: &str) -> Vec<String> {
+    let mut literals = Vec::new();
+    let mut chars = expression.chars();
+    while let Some(c) = chars.by_ref().find(|&c| c == '\'' || c == '"') {
+        let literal: String = chars.by_ref().take_while(|&end| end != c).collect();
+        // Variables only expand inside double quotes
+        literals.push(if c == '\'' {
+            literal.replace('$', "\u{0}")
+        } else {
+            literal
+        });
+    }
+    if literals.is_empty() && !expression.contains(['$', '(', '[']) {
+        literals.push(expression.trim().to_string());
+    }
+    literals
+}
+
+/// Expand PowerShell variables; `None` if one is unknown or the value comes
+/// from a command
+fn expand_powershell(
+    value: &str,
+    variables: &HashMap<String, String>,
+    script_root: Option<&str>,
+) -> Option<String> {
+    let mut unknown = false;
+    let expanded = PS_VARIABLE.replace_all(value, |captures: &regex::Captures| {
+        let lookup = if let Some(env) = captures.get(1).or(captures.get(2)).or(captures.get(3)) {
+            std::env::var(env.as_str()).ok()
+        } else {
+            let name = captures[4].to_lowercase();
+            match name.as_str() {
+                "home" => std::env::var("HOME")
+                    .or_else(|_| std::env::var("USERPROFILE"))
+                    .ok(),
+                "psscriptroot" => script_root.map(str::to_string),
+                _ => variables.get(&format!("ps:{}", name)).cloned(),
+            }
+        };
+        lookup.unwrap_or_else(|| {
+            unknown = true;
+            String::new()
+        })
+    });
+    (!unknown && !expanded.contains("$(")).then(|| expanded.replace('\u{0}', "$"))
+}
+
+fn is_fish(file: &Path) -> bool {
+    file.extension().map(|ext| ext == "fish").unwrap_or(false)
+}
+
+/// Directories in a `fish_variables` line such as
+/// `SETUVAR fish_user_paths:/a\x1e/b`
+fn fish_user_paths(line: &str) -> Vec<String> {
+    let Some(rest) = line
+        .strip_prefix("SETUVAR ")
+        .map(|rest| rest.trim_start_matches("--export ").trim_start())
+    else {
+        return Vec::new();
+    };
+    match rest.strip_prefix("fish_user_paths:") {
+        Some(value) => value
+            .split("\\x1e")
+            .map(|dir| dir.replace("\\x20", " "))
+            .filter(|dir| !dir.is_empty())
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+fn sourced_file(statement: &str) -> Option<&str> {
+    let rest = statement
+        .strip_prefix("source ")
+        .or_else(|| statement.strip_prefix(". "))?;
+    rest.split_whitespace().next().map(unquote)
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+/// Entries are canonicalized by the parser, so compare canonical forms too
+fn same_dir(written: &Path, entry: &Path) -> bool {
+    let trim = |path: &Path| {
+        let text = path.to_string_lossy();
+        let trimmed = text.trim_end_matches(['/', '\\']);
+        let trimmed = if trimmed.is_empty() { "/" } else { trimmed };
+        // Windows paths compare case-insensitively
+        PathBuf::from(if cfg!(windows) {
+            trimmed.to_lowercase()
+        } else {
+            trimmed.to_string()
+        })
+    };
+    trim(written) == trim(entry)
+        || written
+            .canonicalize()
+            .map(|canonical| canonical == entry)
+            .unwrap_or(false)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traces_entries_to_startup_lines() {
+        let home = tempfile::tempdir().unwrap();
+        let bin = home.path().join("bin");
+        let cargo = home.path().join(".cargo/bin");
+        let brew = home.path().join("linuxbrew/bin");
+        for dir in [&bin, &cargo, &brew] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        std::fs::write(
+            home.path().join(".cargo/env"),
+            "export PATH=\"$HOME/.cargo/bin:$PATH\"\n",
+        )
+        .unwrap();
+        let zshrc = home.path().join(".zshrc");
+        std::fs::write(
+            &zshrc,
+            "# PATH=/nowhere\n\
+             LOCAL=~/bin\n\
+             [ -d \"$LOCAL\" ] && export PATH=\"$LOCAL:$PATH\"\n\
+             . \"$HOME/.cargo/env\"\n\
+             eval \"$(/home/linuxbrew/.linuxbrew/bin/brew shellenv)\"\n\
+             path=(/opt/tools/bin $path)\n",
+        )
+        .unwrap();
+
+        let mut entries: Vec<PathEntry> = [bin, cargo, brew, PathBuf::from("/opt/tools/bin/")]
+            .into_iter()
+            .enumerate()
+            .map(|(order, path)| PathEntry {
+                path,
+                order,
+                exists: true,
+                is_accessible: true,
+                executables: Vec::new(),
+                sources: Vec::new(),
+                scope: None,
+            })
+            .collect();
+        SourceTracer::new()
+            .with_home(home.path().to_path_buf())
+            .with_files(vec![zshrc.clone()])
+            .trace(&mut entries);
+
+        let traced: Vec<Vec<(PathBuf, usize)>> = entries
+            .iter()
+            .map(|e| e.sources.iter().map(|s| (s.file.clone(), s.line)).collect())
+            .collect();
+        assert_eq!(
+            traced,
+            vec![
+                vec![(zshrc.clone(), 3)],
+                vec![(home.path().join(".cargo/env"), 1)],
+                vec![(zshrc.clone(), 5)],
+                vec![(zshrc, 6)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_traces_fish_configuration() {
+        let home = tempfile::tempdir().unwrap();
+        let fish = home.path().join(".config/fish");
+        std::fs::create_dir_all(fish.join("conf.d")).unwrap();
+        std::fs::write(
+            fish.join("fish_variables"),
+            "# This file contains fish universal variable definitions.\n\
+             SETUVAR --export fish_user_paths:/opt/a\\x1e/opt/b\n",
+        )
+        .unwrap();
+        std::fs::write(
+            fish.join("conf.d/cargo.fish"),
+            "test -d ~/.cargo/bin; and fish_add_path --move ~/.cargo/bin\n",
+        )
+        .unwrap();
+        std::fs::write(
+            fish.join("config.fish"),
+            "set -l tools /opt/tools\n\
+             set -gx PATH $tools/bin $PATH\n\
+             /opt/homebrew/bin/brew shellenv | source\n",
+        )
+        .unwrap();
+
+        let tracer = SourceTracer::new().with_home(home.path().to_path_buf());
+        let files: Vec<PathBuf> = tracer
+            .startup_files()
+            .into_iter()
+            .filter(|file| file.starts_with(&fish))
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                fish.join("fish_variables"),
+                fish.join("conf.d/cargo.fish"),
+                fish.join("config.fish"),
+            ]
+        );
+
+        let mut entries: Vec<PathEntry> = [
+            "/opt/b",
+            &format!("{}/.cargo/bin", home.path().display()),
+            "/opt/tools/bin",
+            "/opt/homebrew/bin",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(order, path)| PathEntry {
+            path: PathBuf::from(path),
+            order,
+            exists: true,
+            is_accessible: true,
+            executables: Vec::new(),
+            sources: Vec::new(),
+            scope: None,
+        })
+        .collect();
+        tracer.with_files(files).trace(&mut entries);
+
+        let traced: Vec<Vec<String>> = entries
+            .iter()
+            .map(|e| {
+                e.sources
+                    .iter()
+                    .map(|s| {
+                        format!(
+                            "{}:{}",
+                            s.file.file_name().unwrap().to_string_lossy(),
+                            s.line
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            traced,
+            vec![
+                vec!["fish_variables:2"],
+                vec!["cargo.fish:1"],
+                vec!["config.fish:2"],
+                vec!["config.fish:3"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_traces_powershell_profile() {
+        let home = tempfile::tempdir().unwrap();
+        let profiles = home.path().join(".config/powershell");
+        std::fs::create_dir_all(&profiles).unwrap();
+        let profile = profiles.join("Microsoft.PowerShell_profile.ps1");
+        std::fs::write(
+            &profile,
+            "# tools\n\
+             $tools = '/opt/tools'\n\
+             $env:PATH += \":$tools/bin\"\n\
+             $env:Path = '/opt/ps:' + $env:Path\n\
+             . \"$PSScriptRoot/extra.ps1\"\n\
+             Invoke-Expression (&starship init powershell)\n",
+        )
+        .unwrap();
+        std::fs::write(
+            profiles.join("extra.ps1"),
+            "$env:PATH = \"${env:PATH}:/opt/extra\"\n",
+        )
+        .unwrap();
+
+        let tracer = SourceTracer::new().with_home(home.path().to_path_buf());
+        assert!(tracer.startup_files().contains(&profile));
+
+        let mut entries: Vec<PathEntry> = [
+            "/opt/ps",
+            "/opt/tools/bin/",
+            "/opt/extra",
+            "/opt/starship/bin",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(order, path)| PathEntry {
+            path: PathBuf::from(path),
+            order,
+            exists: true,
+            is_accessible: true,
+            executables: Vec::new(),
+            sources: Vec::new(),
+            scope: None,
+        })
+        .collect();
+        tracer.with_files(vec![profile]).trace(&mut entries);
+
+        let traced: Vec<String> = entries
+            .iter()
+            .map(|e| {
+                let source = &e.sources[0];
+                format!(
+                    "{}:{}",
+                    source.file.file_name().unwrap().to_string_lossy(),
+                    source.line
+                )
+            })
+            .collect();
+        assert_eq!(
+            traced,
+            vec![
+                "Microsoft.PowerShell_profile.ps1:4",
+                "Microsoft.PowerShell_profile.ps1:3",
+                "extra.ps1:1",
+                "Microsoft.PowerShell_profile.ps1:6",
+            ]
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use crate::output::types::ExecutableInfo;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// A later copy of a name that PATH lookup never reaches, reported as soon
+/// as its directory is scanned
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StreamConflict {
+    pub binary_name: String,
+    /// The first copy on PATH, which is the one that runs
+    pub active: PathBuf,
+    pub shadowed: PathBuf,
+    /// PATH position of the shadowed copy
+    pub path_order: usize,
+    /// Copies of this name seen so far, including both above
+    pub copies: usize,
+    /// Both paths are the same file (hard link, bind mount or duplicated
+    /// PATH entry)
+    pub same_file: bool,
+}
+
+/// Totals once the whole PATH has been streamed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StreamSummary {
+    pub total_path_entries: usize,
+    pub scanned_directories: usize,
+    pub total_executables: usize,
+    pub unique_executables: usize,
+    /// Names with more than one copy
+    pub conflicting_names: usize,
+    pub shadowed_copies: usize,
+    /// `--max-scan-time` ran out before every directory was scanned
+    pub timed_out: bool,
+}
+
+/// One line of streaming output
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum StreamEvent {
+    Conflict(StreamConflict),
+    Summary(StreamSummary),
+}
+
+/// What is kept per name while streaming: the winning copy and a count
+struct NameRecord {
+    active: PathBuf,
+    file_id: Option<(u64, u64)>,
+    copies: usize,
+}
+
+/// The per-name index behind streaming analysis. Directories are added in
+/// PATH order and dropped afterwards, so memory grows with the number of
+/// distinct names rather than the number of files.
+#[derive(Default)]
+pub struct ConflictIndex {
+    names: HashMap<String, NameRecord>,
+    total_executables: usize,
+    shadowed_copies: usize,
+    case_insensitive: bool,
+}
+
+impl ConflictIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count names differing only in case as one binary
+    pub fn with_case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Record one scanned directory, returning the copies it adds that are
+    /// shadowed by an earlier directory
+    pub fn add_directory(&mut self, executables: Vec<ExecutableInfo>) -> Vec<StreamConflict> {
+        let mut conflicts = Vec::new();
+        for exec in executables {
+            self.total_executables += 1;
+            let file_id = exec.dev.zip(exec.inode);
+            let key = if self.case_insensitive {
+                exec.name.to_lowercase()
+            } else {
+                exec.name.clone()
+            };
+            match self.names.get_mut(&key) {
+                Some(record) => {
+                    record.copies += 1;
+                    self.shadowed_copies += 1;
+                    conflicts.push(StreamConflict {
+                        binary_name: exec.name,
+                        active: record.active.clone(),
+                        shadowed: exec.full_path,
+                        path_order: exec.path_order,
+                        copies: record.copies,
+                        same_file: file_id.is_some() && file_id == record.file_id,
+                    });
+                }
+                None => {
+                    self.names.insert(
+                        key,
+                        NameRecord {
+                            active: exec.full_path,
+                            file_id,
+                            copies: 1,
+                        },
+                    );
+                }
+            }
+        }
+        conflicts
+    }
+
+    pub fn summary(&self) -> StreamSummary {
+        StreamSummary {
+            total_executables: self.total_executables,
+            unique_executables: self.names.len(),
+            conflicting_names: self.names.values().filter(|r| r.copies > 1).count(),
+            shadowed_copies: self.shadowed_copies,
+            ..StreamSummary::default()
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn exec(path: &str, order: usize, inode: u64) -> ExecutableInfo {
+        let path = PathBuf::from(path);
+        ExecutableInfo {
+            name: path.file_name().unwrap().to_string_lossy().into(),
+            full_path: path.clone(),
+            size: 100,
+            resolved_path: path,
+            dev: Some(1),
+            inode: Some(inode),
+            path_order: order,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    #[test]
+    fn test_conflicts_reported_per_directory() {
+        let mut index = ConflictIndex::new();
+        assert!(index
+            .add_directory(vec![exec("/a/python3", 0, 10), exec("/a/ls", 0, 11)])
+            .is_empty());
+
+        let conflicts = index.add_directory(vec![exec("/b/python3", 1, 20)]);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].active, PathBuf::from("/a/python3"));
+        assert_eq!(conflicts[0].copies, 2);
+        assert!(!conflicts[0].same_file);
+
+        let conflicts = index.add_directory(vec![exec("/c/python3", 2, 10)]);
+        assert_eq!(conflicts[0].copies, 3);
+        assert!(conflicts[0].same_file);
+
+        let summary = index.summary();
+        assert_eq!(summary.total_executables, 4);
+        assert_eq!(summary.unique_executables, 2);
+        assert_eq!(summary.conflicting_names, 1);
+        assert_eq!(summary.shadowed_copies, 2);
+    }
+}
@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -15,7 +16,7 @@ pub enum Error {
     SymlinkError { path: String },
 
     #[error("Circular symbolic link detected: {path}")]
-    CircularSymlink { path: String },
+    CircularSymlink { path: String, cycle: Vec<PathBuf> },
 
     #[error("Version extraction failed for {binary}: {reason}")]
     VersionExtractionError { binary: String, reason: String },
@@ -29,6 +30,39 @@ pub enum Error {
     #[error("Serialization error: {0}")]
     SerializationError(String),
 
+    #[error("Invalid config file {path}: {reason}")]
+    ConfigError { path: String, reason: String },
+
+    #[error("Invalid version requirement '{requirement}': {reason}")]
+    InvalidRequirement { requirement: String, reason: String },
+
+    #[error("Invalid rule '{rule}': {reason}")]
+    InvalidRule { rule: String, reason: String },
+
+    #[error("Invalid pattern '{pattern}': {reason}")]
+    InvalidPattern { pattern: String, reason: String },
+
+    #[error("{feature} support was not compiled in; rebuild with --features {feature}")]
+    FeatureDisabled { feature: String },
+
+    #[error("Self-update failed: {reason}")]
+    UpdateError { reason: String },
+
+    #[error("Webhook {url} failed: {reason}")]
+    WebhookError { url: String, reason: String },
+
+    #[error("Signature check failed: {reason}")]
+    SignatureError { reason: String },
+
+    #[error("{option} needs {requirement}")]
+    MissingOption { option: String, requirement: String },
+
+    #[error("Plugin {plugin} failed: {reason}")]
+    PluginError { plugin: String, reason: String },
+
+    #[error("Invalid PATH edit '{edit}': {reason}")]
+    InvalidPathEdit { edit: String, reason: String },
+
     #[error("Invalid path format: {path}")]
     InvalidPath { path: String },
 
@@ -54,6 +88,61 @@ pub enum Error {
     RegexError(#[from] regex::Error),
 }
 
+impl Error {
+    /// Stable identifier for machine-readable error reports
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::PathNotFound => "path_not_found",
+            Error::DirectoryAccessError { .. } => "directory_access",
+            Error::MetadataError { .. } => "metadata",
+            Error::SymlinkError { .. } => "symlink",
+            Error::CircularSymlink { .. } => "circular_symlink",
+            Error::VersionExtractionError { .. } => "version_extraction",
+            Error::PlatformError { .. } => "platform",
+            Error::IoError(_) => "io",
+            Error::SerializationError(_) => "serialization",
+            Error::ConfigError { .. } => "config",
+            Error::InvalidRequirement { .. } => "invalid_requirement",
+            Error::InvalidRule { .. } => "invalid_rule",
+            Error::InvalidPattern { .. } => "invalid_pattern",
+            Error::FeatureDisabled { .. } => "feature_disabled",
+            Error::UpdateError { .. } => "update",
+            Error::WebhookError { .. } => "webhook",
+            Error::SignatureError { .. } => "signature",
+            Error::MissingOption { .. } => "missing_option",
+            Error::InvalidPath { .. } => "invalid_path",
+            Error::InvalidPathEdit { .. } => "invalid_path_edit",
+            Error::PluginError { .. } => "plugin",
+            Error::TimeoutError { .. } => "timeout",
+            Error::PermissionDenied { .. } => "permission_denied",
+            Error::VersionParseError { .. } => "version_parse",
+            Error::UnsupportedPlatform { .. } => "unsupported_platform",
+            Error::CommandError { .. } => "command",
+            Error::Utf8Error(_) => "utf8",
+            Error::RegexError(_) => "regex",
+        }
+    }
+
+    /// Process exit status for a failed run. 1 is reserved for "conflicts
+    /// found", so errors use 2 (bad input or configuration) and 3 (the
+    /// analysis itself failed)
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::ConfigError { .. }
+            | Error::InvalidRequirement { .. }
+            | Error::InvalidRule { .. }
+            | Error::InvalidPattern { .. }
+            | Error::InvalidPath { .. }
+            | Error::InvalidPathEdit { .. }
+            | Error::MissingOption { .. }
+            | Error::SignatureError { .. }
+            | Error::FeatureDisabled { .. }
+            | Error::RegexError(_) => 2,
+            _ => 3,
+        }
+    }
+}
+
 impl From<serde_json::Error> for Error {
     fn from(err: serde_json::Error) -> Self {
         Error::SerializationError(err.to_string())
@@ -1,34 +1,122 @@
 pub mod analyzers;
+mod builder;
 pub mod cli;
+pub mod config;
 pub mod core;
 pub mod error;
 pub mod output;
 pub mod platform;
+#[cfg(feature = "tui")]
+pub mod tui;
 
+pub use analyzers::version_extractor::VersionProbe;
+pub use builder::PathAnalyzerBuilder;
+pub use config::Config;
 pub use error::{Error, Result};
 pub use output::types::*;
 
 use chrono::Utc;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Options for configuring the analysis
 #[derive(Debug, Clone)]
 pub struct AnalysisOptions {
     pub extract_versions: bool,
+    /// Extract versions only for binaries involved in conflicts
+    pub lazy_versions: bool,
+    /// Which binaries may be executed to read their version
+    pub version_probe: VersionProbe,
+    /// Binaries executed under `VersionProbe::Allowlist`
+    pub version_allowlist: Vec<String>,
+    /// Per-binary version commands tried before the generic version flags
+    pub version_commands: HashMap<String, config::VersionCommand>,
+    /// Keep each version command attempted, with exit status and output
+    /// samples, in `ExecutableInfo::version_probes`
+    pub record_version_probes: bool,
+    /// Binaries never executed for their version (names or globs)
+    pub version_skip_list: Vec<String>,
+    /// Each version command is killed after this long
+    pub version_timeout: std::time::Duration,
+    /// Version commands run concurrently (0 = one per CPU)
+    pub version_jobs: usize,
     pub resolve_symlinks: bool,
     pub categorize_managers: bool,
     pub include_file_hashes: bool,
+    /// Ask the OS package database which package owns each conflicting file
+    pub detect_origins: bool,
+    /// Read formula versions and link state from the Homebrew Cellar instead
+    /// of running Homebrew-installed binaries
+    pub brew_metadata: bool,
+    /// Parse executable headers to fill in `binary_format` and check the
+    /// `#!` interpreters of scripts; reads every executable on PATH
+    pub detect_binary_kinds: bool,
+    pub hash_algorithm: HashAlgorithm,
+    pub hash_scope: HashScope,
+    /// Worker threads used for hashing (0 = one per CPU)
+    pub hash_jobs: usize,
+    /// Files larger than this many bytes are not hashed
+    pub max_hash_file_size: Option<u64>,
     pub custom_path: Option<String>,
+    /// Wall-clock budget; stages past it are skipped and reported in
+    /// `diagnostics`, so the result may be partial
+    pub max_scan_time: Option<std::time::Duration>,
+    /// User-defined conflict rules, applied in order
+    pub rules: Vec<analyzers::ConflictRule>,
+    /// Binaries (names or globs) whose conflicts are dropped from the result
+    pub ignored_binaries: Vec<String>,
+    /// Drop conflicts whose instances are all the same file or identical copies
+    pub hide_identical: bool,
+    /// Treat names differing only in case as one binary, as the filesystems
+    /// of Windows and macOS do
+    pub case_insensitive_names: bool,
+    /// Compare PATH with the one apps launched from the desktop get, read
+    /// from launchd or the systemd user manager; skipped for `custom_path`
+    /// and `VersionProbe::Never`, since it runs `launchctl` or `systemctl`
+    pub compare_gui_path: bool,
+    /// Find the shell startup and PowerShell profile lines that add each PATH
+    /// entry; skipped for `custom_path`
+    pub trace_path_sources: bool,
+    /// PATH entries that are not scanned
+    pub skip_dirs: Vec<std::path::PathBuf>,
+    /// Managers checked before the built-in patterns
+    pub custom_managers: Vec<analyzers::CustomManager>,
+    /// Version constraints checked against the active instance of each binary
+    pub requirements: Vec<analyzers::VersionRequirement>,
 }
 
 impl Default for AnalysisOptions {
     fn default() -> Self {
         Self {
             extract_versions: false, // Disabled by default due to Windows issues
+            lazy_versions: false,
+            version_probe: VersionProbe::default(),
+            version_allowlist: analyzers::version_extractor::default_allowlist(),
+            version_commands: analyzers::version_extractor::default_commands(),
+            record_version_probes: false,
+            version_skip_list: analyzers::version_extractor::default_skip_list(),
+            version_timeout: analyzers::version_extractor::DEFAULT_TIMEOUT,
+            version_jobs: 0,
             resolve_symlinks: true,
             categorize_managers: true,
             include_file_hashes: false,
+            detect_origins: false,
+            brew_metadata: false,
+            detect_binary_kinds: false,
+            hash_algorithm: HashAlgorithm::default(),
+            hash_scope: HashScope::default(),
+            hash_jobs: 0,
+            max_hash_file_size: None,
             custom_path: None,
+            requirements: Vec::new(),
+            rules: Vec::new(),
+            ignored_binaries: Vec::new(),
+            hide_identical: false,
+            case_insensitive_names: platform::is_case_insensitive(),
+            compare_gui_path: false,
+            trace_path_sources: true,
+            skip_dirs: Vec::new(),
+            custom_managers: Vec::new(),
+            max_scan_time: None,
         }
     }
 }
@@ -36,134 +124,567 @@ impl Default for AnalysisOptions {
 /// Main API for analyzing PATH conflicts
 pub struct PathAnalyzer {
     options: AnalysisOptions,
+    scanner: core::ExecutableScanner,
+    analyzers: Vec<Box<dyn analyzers::Analyzer>>,
+    progress: core::ProgressReporter,
 }
 
 impl PathAnalyzer {
     /// Create a new PathAnalyzer with default options
     pub fn new() -> Self {
-        PathAnalyzer {
-            options: AnalysisOptions::default(),
-        }
+        Self::builder().build()
     }
 
     /// Create a PathAnalyzer with custom options
     pub fn with_options(options: AnalysisOptions) -> Self {
-        PathAnalyzer { options }
+        Self::builder().options(options).build()
+    }
+
+    /// Set options one at a time, and add scanners, analyzers and progress
+    /// callbacks
+    pub fn builder() -> PathAnalyzerBuilder {
+        PathAnalyzerBuilder::new()
+    }
+
+    /// Call `callback` as each stage of `analyze` starts, advances and
+    /// ends. It may be called from worker threads.
+    pub fn with_progress(
+        mut self,
+        callback: impl Fn(core::Progress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = core::ProgressReporter::new(callback);
+        self
     }
 
     /// Run a full PATH analysis
     pub fn analyze(&self) -> Result<AnalysisResult> {
         let scan_time = Utc::now();
+        let deadline = match self.options.max_scan_time {
+            Some(budget) => core::Deadline::after(budget),
+            None => core::Deadline::none(),
+        };
 
         // Detect platform
         let platform = platform::detect_platform()?;
+        tracing::info!(os = %platform.os, wsl = platform.is_wsl, "detected platform");
 
         // Parse PATH
-        let path_parser = core::PathParser::new();
-        let mut path_entries = if let Some(custom_path) = &self.options.custom_path {
-            path_parser.parse_path(custom_path)?
-        } else {
-            path_parser.parse_system_path()?
+        let path_var = match &self.options.custom_path {
+            Some(custom_path) => custom_path.clone(),
+            None => platform::get_path_env_var()?,
         };
+        let mut path_entries = core::PathParser::new().parse_path(&path_var)?;
+        // The startup files traced are this user's, not those a custom PATH
+        // was taken from
+        if self.options.trace_path_sources && self.options.custom_path.is_none() {
+            core::SourceTracer::new().trace(&mut path_entries);
+        }
+
+        // Whether each Windows entry comes from the user or the machine PATH
+        let registry = (cfg!(windows) && self.options.custom_path.is_none())
+            .then(platform::windows::get_registry_path_windows);
+        if let Some(registry) = &registry {
+            analyzers::RegistryPathAnalyzer::new().assign_scopes(registry, &mut path_entries);
+        }
+
+        // Entries that put the current directory on PATH
+        let mut security = analyzers::PathSecurityAnalyzer::new().analyze(&path_var);
+
+        tracing::info!(entries = path_entries.len(), "parsed PATH");
 
         // Scan for executables
-        let scanner = core::ExecutableScanner::new();
-        scanner.scan_path_entries(&mut path_entries)?;
+        let scanner = self
+            .scanner
+            .clone()
+            .with_deadline(deadline)
+            .with_skip_dirs(self.options.skip_dirs.clone())
+            .with_progress(self.progress.clone());
+        let mut diagnostics = scanner.scan_path_entries(&mut path_entries)?;
 
         // Collect all executables
         let mut all_executables: Vec<ExecutableInfo> = path_entries
             .iter()
             .flat_map(|entry| entry.executables.iter().cloned())
             .collect();
+        tracing::info!(executables = all_executables.len(), "scanned PATH entries");
 
         // Resolve symlinks
         if self.options.resolve_symlinks {
+            tracing::info!("resolving symlinks");
+            let progress = self
+                .progress
+                .start(core::Stage::ResolvingSymlinks, all_executables.len());
             let symlink_resolver = analyzers::SymlinkResolver::new();
-            symlink_resolver.resolve_executables(&mut all_executables)?;
-
-            // Update executables in path entries
-            for entry in &mut path_entries {
-                for exec in &mut entry.executables {
-                    if let Some(resolved) = all_executables
-                        .iter()
-                        .find(|e| e.full_path == exec.full_path)
-                    {
-                        exec.resolved_path = resolved.resolved_path.clone();
-                    }
-                }
-            }
+            diagnostics.extend(symlink_resolver.resolve_executables(&mut all_executables)?);
+            progress.finish();
+
+            merge_into_entries(&mut path_entries, &all_executables, |exec, resolved| {
+                exec.resolved_path = resolved.resolved_path.clone();
+                exec.symlink_chain = resolved.symlink_chain.clone();
+            });
         }
 
         // Detect managers
         if self.options.categorize_managers {
-            let manager_detector = analyzers::ManagerDetector::new();
+            tracing::info!("detecting managers");
+            let progress = self
+                .progress
+                .start(core::Stage::DetectingManagers, all_executables.len());
+            let manager_detector = analyzers::ManagerDetector::new()
+                .with_custom_managers(self.options.custom_managers.clone());
             manager_detector.detect_managers(&mut all_executables);
+            progress.finish();
 
-            // Update executables in path entries
-            for entry in &mut path_entries {
-                for exec in &mut entry.executables {
-                    if let Some(detected) = all_executables
-                        .iter()
-                        .find(|e| e.full_path == exec.full_path)
-                    {
-                        exec.manager = detected.manager.clone();
-                    }
-                }
-            }
+            merge_into_entries(&mut path_entries, &all_executables, |exec, detected| {
+                exec.manager = detected.manager.clone();
+            });
+        }
+
+        // Formula versions and link state straight from the Cellar
+        if self.options.brew_metadata {
+            tracing::info!("reading Homebrew metadata");
+            analyzers::HomebrewMetadata::new().enrich(&mut all_executables);
+
+            merge_into_entries(&mut path_entries, &all_executables, |exec, enriched| {
+                exec.version = enriched.version.clone();
+                exec.origin = enriched.origin.clone();
+            });
         }
 
         // Extract versions
-        if self.options.extract_versions {
-            let version_extractor = analyzers::VersionExtractor::new();
-            version_extractor.extract_versions(&mut all_executables);
-
-            // Update executables in path entries
-            for entry in &mut path_entries {
-                for exec in &mut entry.executables {
-                    if let Some(versioned) = all_executables
-                        .iter()
-                        .find(|e| e.full_path == exec.full_path)
-                    {
-                        exec.version = versioned.version.clone();
+        if self.options.extract_versions || self.options.lazy_versions {
+            let version_extractor = self
+                .version_extractor()?
+                .with_progress(self.progress.clone());
+
+            // Lazy mode probes only binaries that already conflict by name,
+            // which is a small fraction of a typical PATH
+            let mut conflicted: Vec<ExecutableInfo>;
+            let targets: &mut [ExecutableInfo] = if self.options.lazy_versions {
+                let detector = core::ConflictDetector::new(platform.clone())
+                    .with_case_insensitive(self.options.case_insensitive_names);
+                let names: HashSet<String> = detector
+                    .detect_conflicts(&path_entries)?
+                    .into_iter()
+                    .map(|c| detector.name_key(&c.binary_name))
+                    .collect();
+                conflicted = all_executables
+                    .iter()
+                    .filter(|e| names.contains(&detector.name_key(&e.name)))
+                    .cloned()
+                    .collect();
+                &mut conflicted
+            } else {
+                &mut all_executables
+            };
+            tracing::info!(binaries = targets.len(), "extracting versions");
+            diagnostics.extend(version_extractor.extract_versions(targets, deadline));
+
+            // Without execution, fall back to the installing package's version
+            if self.options.version_probe == VersionProbe::Never {
+                let mut unversioned: Vec<ExecutableInfo> = targets
+                    .iter()
+                    .filter(|e| e.version.is_none())
+                    .cloned()
+                    .collect();
+                analyzers::OriginDetector::without_exec().detect_origins(&mut unversioned);
+                let versions: HashMap<std::path::PathBuf, VersionInfo> = unversioned
+                    .iter()
+                    .filter_map(|e| {
+                        let origin = e.origin.as_ref()?;
+                        Some((
+                            e.full_path.clone(),
+                            analyzers::origin_detector::package_version(origin)?,
+                        ))
+                    })
+                    .collect();
+                for exec in targets.iter_mut() {
+                    if let Some(version) = versions.get(&exec.full_path) {
+                        exec.version.get_or_insert_with(|| version.clone());
                     }
                 }
             }
+
+            merge_into_entries(&mut path_entries, targets, |exec, versioned| {
+                exec.version = versioned.version.clone();
+                exec.version_probes = versioned.version_probes.clone();
+            });
         }
 
-        // Compute hashes if requested
-        if self.options.include_file_hashes {
-            let binary_info_extractor = core::BinaryInfoExtractor::new(true);
+        // Inspect binary headers and compute hashes if requested
+        let inspect = self.options.include_file_hashes || self.options.detect_binary_kinds;
+        if inspect && deadline.expired() {
+            diagnostics.push(core::deadline::skipped_diagnostic(
+                "binary inspection",
+                Vec::new(),
+            ));
+        } else if inspect {
+            tracing::info!(
+                hashes = self.options.include_file_hashes,
+                kinds = self.options.detect_binary_kinds,
+                "inspecting binaries"
+            );
+            let binary_info_extractor = core::BinaryInfoExtractor::with_algorithm(
+                self.options.include_file_hashes,
+                self.options.hash_algorithm,
+                self.options.hash_scope,
+            )
+            .with_workers(self.options.hash_jobs, self.options.max_hash_file_size)
+            .with_kind_detection(self.options.detect_binary_kinds)
+            .with_progress(self.progress.clone());
             binary_info_extractor.enrich_executables(&mut all_executables)?;
 
-            // Update executables in path entries
-            for entry in &mut path_entries {
-                for exec in &mut entry.executables {
-                    if let Some(hashed) = all_executables
-                        .iter()
-                        .find(|e| e.full_path == exec.full_path)
-                    {
-                        exec.file_hash = hashed.file_hash.clone();
+            merge_into_entries(&mut path_entries, &all_executables, |exec, inspected| {
+                exec.file_hash = inspected.file_hash.clone();
+                exec.hash_algorithm = inspected.hash_algorithm;
+                exec.hash_scope = inspected.hash_scope;
+                exec.binary_format = inspected.binary_format.clone();
+            });
+        }
+
+        // Attribute conflicting files to the packages that installed them
+        let mut origins_checked = false;
+        if self.options.detect_origins && deadline.expired() {
+            diagnostics.push(core::deadline::skipped_diagnostic(
+                "package origin lookup",
+                Vec::new(),
+            ));
+        } else if self.options.detect_origins {
+            let case_insensitive = self.options.case_insensitive_names;
+            let conflicting = conflicting_names(&path_entries, case_insensitive);
+            let mut candidates: Vec<ExecutableInfo> = all_executables
+                .iter()
+                .filter(|e| conflicting.contains(&name_key(&e.name, case_insensitive)))
+                .cloned()
+                .collect();
+
+            tracing::info!(files = candidates.len(), "looking up package origins");
+            let progress = self
+                .progress
+                .start(core::Stage::LookingUpOrigins, candidates.len());
+            let origin_detector = if self.options.version_probe == VersionProbe::Never {
+                analyzers::OriginDetector::without_exec()
+            } else {
+                analyzers::OriginDetector::new()
+            };
+            origin_detector.detect_origins(&mut candidates);
+            progress.finish();
+            origins_checked = true;
+
+            merge_into_entries(&mut path_entries, &candidates, |exec, attributed| {
+                exec.origin = attributed.origin.clone();
+            });
+        }
+
+        // Review which variables WSLENV carries across the Windows boundary.
+        // Like PYTHONPATH, it belongs to this process, not to a custom PATH.
+        if platform.is_wsl && self.options.custom_path.is_none() {
+            if let Ok(wslenv) = std::env::var("WSLENV") {
+                diagnostics.extend(analyzers::WslEnvAnalyzer::new().analyze(
+                    &wslenv,
+                    |name| std::env::var(name).ok(),
+                    &path_entries,
+                ));
+            }
+        }
+
+        // Directories Windows adds twice from the user and machine PATH
+        if let Some(registry) = &registry {
+            diagnostics.extend(analyzers::RegistryPathAnalyzer::new().analyze(registry));
+        }
+
+        // PATHEXT itself, executables cmd.exe hides behind its internal
+        // commands, and Store aliases that win or lose by the position of
+        // WindowsApps
+        if cfg!(windows) {
+            let pathext = std::env::var("PATHEXT").ok();
+            diagnostics.extend(analyzers::PathextAnalyzer::new().analyze(pathext.as_deref()));
+            diagnostics.extend(analyzers::CmdInternalAnalyzer::new().analyze(&path_entries));
+            diagnostics.extend(analyzers::WindowsAppsAnalyzer::new().analyze(&path_entries));
+        }
+
+        // Commands Start-Process and the Run dialog open from App Paths
+        // instead of the copy PATH finds
+        if cfg!(windows) {
+            let app_paths = platform::windows::get_app_paths_windows();
+            diagnostics
+                .extend(analyzers::AppPathsAnalyzer::new().analyze(&app_paths, &path_entries));
+        }
+
+        // Binaries IDEs and other apps launched from the desktop resolve
+        // differently, since they never read the shell's startup files
+        if let Some((source, gui_path)) = self.gui_path() {
+            let mut gui_entries = core::PathParser::new().parse_path(&gui_path)?;
+            for entry in &mut gui_entries {
+                match path_entries.iter().find(|e| e.path == entry.path) {
+                    Some(scanned) => entry.executables = scanned.executables.clone(),
+                    None if entry.exists && entry.is_accessible => {
+                        entry.executables = scanner
+                            .scan_directory(&entry.path, entry.order)
+                            .unwrap_or_default()
                     }
+                    None => {}
                 }
             }
+            let launchers = if cfg!(target_os = "linux") {
+                platform::unix::desktop_launchers()
+            } else {
+                Vec::new()
+            };
+            diagnostics.extend(analyzers::GuiPathAnalyzer::new().analyze(
+                source,
+                &gui_entries,
+                &path_entries,
+                &launchers,
+            ));
         }
 
+        // PYTHONPATH modules hiding the standard library or installed
+        // packages, and pip scripts tied to another python. PYTHONPATH
+        // belongs to this process, not to a custom or simulated PATH.
+        let pythonpath =
+            core::SearchPath::from_env("PYTHONPATH").filter(|_| self.options.custom_path.is_none());
+        diagnostics.extend(
+            analyzers::PythonPathAnalyzer::new().analyze(pythonpath.as_ref(), &path_entries),
+        );
+
+        // pyenv, rbenv, asdf and Scoop shims left behind by an uninstall
+        diagnostics.extend(analyzers::ShimAnalyzer::new().analyze(&path_entries));
+
+        // Check version requirements against the active instances
+        let requirements = if self.options.requirements.is_empty() {
+            Vec::new()
+        } else {
+            let checker = analyzers::RequirementChecker::new(self.version_extractor()?);
+            checker.check(&self.options.requirements, &mut path_entries)
+        };
+
         // Detect conflicts
-        let conflict_detector = core::ConflictDetector::new(platform.clone());
-        let conflicts = conflict_detector.detect_conflicts(&path_entries)?;
+        let progress = self.progress.start(core::Stage::DetectingConflicts, 1);
+        let conflict_detector = core::ConflictDetector::new(platform.clone())
+            .with_rules(self.options.rules.clone())
+            .with_case_insensitive(self.options.case_insensitive_names);
+        let mut conflicts = conflict_detector.detect_conflicts(&path_entries)?;
+        // An active pip that installs for a different python than the active one
+        let pip_mismatches = analyzers::PipMismatchAnalyzer::new(platform.clone());
+        conflicts.extend(pip_mismatches.analyze(&path_entries));
+        conflicts.sort_by_key(|c| std::cmp::Reverse(c.severity));
+        if !self.options.ignored_binaries.is_empty() {
+            let ignored = ignore_set(
+                &self.options.ignored_binaries,
+                self.options.case_insensitive_names,
+            )?;
+            conflicts.retain(|c| !ignored.is_match(&c.binary_name));
+        }
+        if self.options.hide_identical {
+            conflicts.retain(|c| !c.same_content);
+        }
+        core::source_tracer::annotate_recommendations(&mut conflicts, &path_entries);
+        progress.finish();
+        tracing::info!(conflicts = conflicts.len(), "detected conflicts");
+
+        // Copies in user-writable directories that run instead of system commands
+        security.extend(analyzers::WritableShadowAnalyzer::new().analyze(&conflicts));
+
+        // Privileged binaries installed outside the system directories
+        if cfg!(unix) {
+            security.extend(analyzers::SetuidAnalyzer::new().analyze(&path_entries));
+        }
+
+        // Source builds in /usr/local shadowing (or shadowed by) packages
+        if cfg!(unix) {
+            diagnostics
+                .extend(analyzers::LocalBuildAnalyzer::new().analyze(&conflicts, origins_checked));
+        }
+
+        // User PATH tools that run instead of machine-wide ones
+        if registry.is_some() {
+            diagnostics.extend(
+                analyzers::RegistryPathAnalyzer::new().user_shadowing(&conflicts, &path_entries),
+            );
+        }
+
+        // Scripts whose #! interpreter is missing or is itself in conflict
+        if cfg!(unix) && self.options.detect_binary_kinds {
+            diagnostics
+                .extend(analyzers::InterpreterAnalyzer::new().analyze(&path_entries, &conflicts));
+        }
+
+        // Man pages that document a shadowed copy of a conflicting tool.
+        // Without MANPATH, man derives its search path from PATH.
+        if cfg!(unix) {
+            let path_dirs: Vec<std::path::PathBuf> = path_entries
+                .iter()
+                .map(|entry| entry.path.clone())
+                .collect();
+            // A custom or simulated PATH comes without the environment it
+            // was taken from, so man would derive its search path from it
+            let value = std::env::var("MANPATH")
+                .ok()
+                .filter(|_| self.options.custom_path.is_none());
+            let manpath = core::SearchPath::manpath(value.as_deref(), &path_dirs);
+            diagnostics.extend(analyzers::ManPathAnalyzer::new().analyze(&manpath, &conflicts));
+        }
+
+        // Shared libraries the dynamic linker loads from LD_LIBRARY_PATH in
+        // place of other copies. The variable belongs to this process, not
+        // to a custom or simulated PATH.
+        if cfg!(unix) && self.options.custom_path.is_none() {
+            let variable = if cfg!(target_os = "macos") {
+                "DYLD_LIBRARY_PATH"
+            } else {
+                "LD_LIBRARY_PATH"
+            };
+            if let Some(library_path) = core::SearchPath::from_env(variable) {
+                diagnostics.extend(
+                    analyzers::LibraryPathAnalyzer::new()
+                        .analyze(&library_path, &platform::unix::system_library_dirs()),
+                );
+            }
+        }
+
+        // appendWindowsPath when it is what most conflicts come from
+        if let Some(interop) = &platform.wsl_interop {
+            diagnostics.extend(analyzers::WslEnvAnalyzer::new().interop(interop, &conflicts));
+        }
+
+        // Leftover Intel Homebrew next to the Apple Silicon one
+        if cfg!(target_os = "macos") {
+            let intel = platform::macos::homebrew_formulae(std::path::Path::new(
+                platform::macos::HOMEBREW_INTEL_PREFIX,
+            ));
+            let arm = platform::macos::homebrew_formulae(std::path::Path::new(
+                platform::macos::HOMEBREW_ARM_PREFIX,
+            ));
+            diagnostics
+                .extend(analyzers::DualHomebrewAnalyzer::new().analyze(&intel, &arm, &conflicts));
+        }
 
         // Build summary
         let summary = self.build_summary(&path_entries, &conflicts);
+        let root_causes = analyzers::RootCauseAnalyzer::new().analyze(&conflicts);
+        let path_issues = core::path_parser::find_path_issues(&path_entries);
 
-        Ok(AnalysisResult {
+        let mut result = AnalysisResult {
             scan_time,
             platform,
             path_entries,
             conflicts,
             summary,
+            diagnostics,
+            requirements,
+            root_causes,
+            path_issues,
+            security,
+            signature: None,
+        };
+
+        // Analyzers added through the builder, e.g. plugins
+        for analyzer in &self.analyzers {
+            let findings = analyzer.analyze(&result)?;
+            tracing::info!(
+                analyzer = analyzer.name(),
+                findings = findings.len(),
+                "ran analyzer"
+            );
+            result.diagnostics.extend(findings);
+        }
+
+        Ok(result)
+    }
+
+    /// Scan PATH one directory at a time, calling `on_conflict` for each
+    /// shadowed copy as soon as its directory is scanned. Only a per-name
+    /// index is kept, so memory stays bounded on very large PATHs; versions,
+    /// categories and the other enrichments of `analyze` are not computed.
+    pub fn analyze_streaming(
+        &self,
+        mut on_conflict: impl FnMut(core::StreamConflict),
+    ) -> Result<core::StreamSummary> {
+        let deadline = match self.options.max_scan_time {
+            Some(budget) => core::Deadline::after(budget),
+            None => core::Deadline::none(),
+        };
+
+        let path_parser = core::PathParser::new();
+        let path_entries = if let Some(custom_path) = &self.options.custom_path {
+            path_parser.parse_path(custom_path)?
+        } else {
+            path_parser.parse_system_path()?
+        };
+
+        let scanner = self
+            .scanner
+            .clone()
+            .with_skip_dirs(self.options.skip_dirs.clone());
+        let ignored = ignore_set(
+            &self.options.ignored_binaries,
+            self.options.case_insensitive_names,
+        )?;
+        let mut index =
+            core::ConflictIndex::new().with_case_insensitive(self.options.case_insensitive_names);
+        let mut scanned = HashSet::new();
+        let mut timed_out = false;
+        for entry in &path_entries {
+            // A directory listed twice adds nothing new
+            if !entry.exists
+                || !entry.is_accessible
+                || scanned.contains(&entry.path)
+                || scanner.should_skip_directory(&entry.path)
+            {
+                continue;
+            }
+            if deadline.expired() {
+                timed_out = true;
+                break;
+            }
+            scanned.insert(entry.path.clone());
+
+            match scanner.scan_directory(&entry.path, entry.order) {
+                Ok(executables) => index
+                    .add_directory(executables)
+                    .into_iter()
+                    .filter(|conflict| !ignored.is_match(&conflict.binary_name))
+                    .filter(|conflict| !(self.options.hide_identical && conflict.same_file))
+                    .for_each(&mut on_conflict),
+                Err(e) => {
+                    tracing::warn!(dir = %entry.path.display(), error = %e, "failed to scan directory")
+                }
+            }
+        }
+
+        Ok(core::StreamSummary {
+            total_path_entries: path_entries.len(),
+            scanned_directories: scanned.len(),
+            timed_out,
+            ..index.summary()
         })
     }
 
+    /// The PATH desktop apps start with, looked up only when asked for and
+    /// allowed to run the system tool that reports it
+    fn gui_path(&self) -> Option<(&'static str, String)> {
+        if !self.options.compare_gui_path
+            || self.options.custom_path.is_some()
+            || self.options.version_probe == VersionProbe::Never
+        {
+            return None;
+        }
+        platform::get_gui_path()
+    }
+
+    pub(crate) fn version_extractor(&self) -> Result<analyzers::VersionExtractor> {
+        analyzers::VersionExtractor::with_timeout(self.options.version_timeout)
+            .with_probe(
+                self.options.version_probe,
+                self.options.version_allowlist.clone(),
+            )
+            .with_jobs(self.options.version_jobs)
+            .with_commands(self.options.version_commands.clone())
+            .with_probe_recording(self.options.record_version_probes)
+            .with_skip_list(self.options.version_skip_list.clone())
+    }
+
     /// Find conflicts for a specific binary
     pub fn check_binary(&self, binary_name: &str) -> Result<Vec<ExecutableInfo>> {
         let result = self.analyze()?;
@@ -172,7 +693,10 @@ impl PathAnalyzer {
             .path_entries
             .iter()
             .flat_map(|entry| &entry.executables)
-            .filter(|exec| exec.name == binary_name)
+            .filter(|exec| {
+                name_key(&exec.name, self.options.case_insensitive_names)
+                    == name_key(binary_name, self.options.case_insensitive_names)
+            })
             .cloned()
             .collect();
 
@@ -193,7 +717,7 @@ impl PathAnalyzer {
         let unique_names: std::collections::HashSet<_> = path_entries
             .iter()
             .flat_map(|e| &e.executables)
-            .map(|exec| &exec.name)
+            .map(|exec| name_key(&exec.name, self.options.case_insensitive_names))
             .collect();
         let unique_executables = unique_names.len();
 
@@ -211,6 +735,26 @@ impl PathAnalyzer {
             *conflicts_by_severity.entry(conflict.severity).or_insert(0) += 1;
         }
 
+        // Count conflicts by the set of managers involved
+        let mut by_manager: HashMap<Vec<String>, usize> = HashMap::new();
+        for conflict in conflicts {
+            let managers: std::collections::BTreeSet<&str> = conflict
+                .instances
+                .iter()
+                .map(|i| i.manager.as_ref().map_or("unmanaged", |m| m.name.as_str()))
+                .collect();
+            let managers = managers.into_iter().map(str::to_string).collect();
+            *by_manager.entry(managers).or_insert(0) += 1;
+        }
+        let mut conflicts_by_manager: Vec<ManagerConflictCount> = by_manager
+            .into_iter()
+            .map(|(managers, count)| ManagerConflictCount { managers, count })
+            .collect();
+        conflicts_by_manager
+            .sort_by(|a, b| b.count.cmp(&a.count).then(a.managers.cmp(&b.managers)));
+
+        let risk_score = analyzers::RiskScorer::new().path_score(conflicts);
+
         Summary {
             total_path_entries,
             total_executables,
@@ -218,12 +762,149 @@ impl PathAnalyzer {
             total_conflicts,
             conflicts_by_category,
             conflicts_by_severity,
+            risk_score,
+            conflicts_by_manager,
         }
     }
 }
 
+/// Copy fields from `enriched` onto the matching executables (same
+/// `full_path`) in `path_entries`, using `update`. Lookups go through one
+/// index, so this stays linear however many executables PATH holds.
+fn merge_into_entries(
+    path_entries: &mut [PathEntry],
+    enriched: &[ExecutableInfo],
+    update: impl Fn(&mut ExecutableInfo, &ExecutableInfo),
+) {
+    let mut by_path: HashMap<&std::path::Path, &ExecutableInfo> = HashMap::new();
+    for exec in enriched {
+        // A directory listed twice yields the same file twice; keep the
+        // first, as a linear search would
+        by_path.entry(exec.full_path.as_path()).or_insert(exec);
+    }
+
+    for exec in path_entries.iter_mut().flat_map(|e| &mut e.executables) {
+        if let Some(source) = by_path.get(exec.full_path.as_path()) {
+            update(exec, source);
+        }
+    }
+}
+
+/// Matcher for `AnalysisOptions::ignored_binaries`, ignoring case when names do
+fn ignore_set(patterns: &[String], case_insensitive: bool) -> Result<globset::GlobSet> {
+    let invalid = |pattern: &str, e: globset::Error| Error::InvalidPattern {
+        pattern: pattern.to_string(),
+        reason: e.to_string(),
+    };
+    let mut builder = globset::GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(
+            globset::GlobBuilder::new(pattern)
+                .case_insensitive(case_insensitive)
+                .build()
+                .map_err(|e| invalid(pattern, e))?,
+        );
+    }
+    builder
+        .build()
+        .map_err(|e| invalid(&patterns.join(", "), e))
+}
+
+/// The name binaries are grouped by: lowercased when names ignore case
+fn name_key(name: &str, case_insensitive: bool) -> String {
+    if case_insensitive {
+        name.to_lowercase()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Names of binaries found in more than one PATH entry, keyed by [`name_key`]
+fn conflicting_names(path_entries: &[PathEntry], case_insensitive: bool) -> HashSet<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for exec in path_entries.iter().flat_map(|e| &e.executables) {
+        *counts
+            .entry(name_key(&exec.name, case_insensitive))
+            .or_insert(0) += 1;
+    }
+
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(name, _)| name)
+        .collect()
+}
+
 impl Default for PathAnalyzer {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn exec(path: &str, size: u64) -> ExecutableInfo {
+        let path = PathBuf::from(path);
+        ExecutableInfo {
+            name: path.file_name().unwrap().to_string_lossy().into(),
+            full_path: path.clone(),
+            size,
+            resolved_path: path,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    #[test]
+    fn test_no_exec_skips_gui_path_lookup() {
+        let analyzer = PathAnalyzer::builder()
+            .compare_gui_path(true)
+            .no_exec()
+            .build();
+        assert!(analyzer.gui_path().is_none());
+        assert!(PathAnalyzer::new().gui_path().is_none());
+    }
+
+    #[test]
+    fn test_ignore_set_follows_name_case() {
+        let patterns = vec!["python*".to_string()];
+        assert!(ignore_set(&patterns, true).unwrap().is_match("Python3"));
+        assert!(!ignore_set(&patterns, false).unwrap().is_match("Python3"));
+    }
+
+    #[test]
+    fn test_merge_into_entries() {
+        let entry = |executables| PathEntry {
+            path: PathBuf::from("/usr/bin"),
+            order: 0,
+            exists: true,
+            is_accessible: true,
+            executables,
+            sources: Vec::new(),
+            scope: None,
+        };
+        // /usr/bin listed twice on PATH
+        let mut path_entries = vec![
+            entry(vec![exec("/usr/bin/ls", 0), exec("/usr/bin/cat", 0)]),
+            entry(vec![exec("/usr/bin/ls", 0)]),
+        ];
+        let enriched = vec![
+            exec("/usr/bin/ls", 1),
+            exec("/usr/bin/ls", 2),
+            exec("/opt/bin/ls", 3),
+        ];
+
+        merge_into_entries(&mut path_entries, &enriched, |exec, source| {
+            exec.size = source.size;
+        });
+
+        let sizes: Vec<u64> = path_entries
+            .iter()
+            .flat_map(|e| &e.executables)
+            .map(|e| e.size)
+            .collect();
+        assert_eq!(sizes, vec![1, 0, 1]);
+    }
+}
@@ -1,12 +1,20 @@
 use clap::Parser;
+use path_conflict_detector::cli::args::OutputFormat;
 use path_conflict_detector::cli::{run, Args};
+use path_conflict_detector::output::json_output;
 use std::process;
 
 fn main() {
     let args = Args::parse();
+    let output_format = args.output_format();
 
     if let Err(e) = run(args) {
-        eprintln!("Error: {}", e);
-        process::exit(1);
+        // JSON consumers get a parseable error object instead of free text
+        match output_format {
+            OutputFormat::Json => println!("{}", json_output::format_error(&e, false)),
+            OutputFormat::JsonPretty => println!("{}", json_output::format_error(&e, true)),
+            _ => eprintln!("Error: {}", e),
+        }
+        process::exit(e.exit_code());
     }
 }
@@ -0,0 +1,356 @@
+use crate::output::types::{AnalysisResult, Conflict, Severity};
+use chrono::{DateTime, Utc};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// How a conflict differs between two runs
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// Only in the current run
+    Added,
+    /// Only in the earlier run
+    Resolved,
+    /// In both runs at the same severity
+    Unchanged,
+    SeverityChanged {
+        from: Severity,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConflictChange {
+    pub binary_name: String,
+    #[serde(flatten)]
+    pub kind: ChangeKind,
+    /// Current severity, or the last known one for a resolved conflict
+    pub severity: Severity,
+    /// The copy that runs now, or the last known one for a resolved conflict
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<PathBuf>,
+    /// The copy that ran in the earlier run, when a different one runs now
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_active: Option<PathBuf>,
+}
+
+/// How many conflicts fall under each `ChangeKind`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChangeSummary {
+    pub added: usize,
+    pub resolved: usize,
+    pub unchanged: usize,
+    pub severity_changed: usize,
+    /// Conflicts in both runs whose active copy differs, whatever their kind
+    #[serde(default)]
+    pub active_changed: usize,
+}
+
+/// Every conflict of two runs labeled with how it changed, sorted by binary
+/// name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeReport {
+    /// Scan time of the earlier run; `None` when there was no earlier run
+    pub since: Option<DateTime<Utc>>,
+    pub summary: ChangeSummary,
+    pub changes: Vec<ConflictChange>,
+}
+
+impl ChangeReport {
+    /// Compare conflicts by binary name. Without a previous result every
+    /// current conflict counts as added.
+    pub fn compare(previous: Option<&AnalysisResult>, current: &[Conflict]) -> Self {
+        let before = by_name(previous.map(|p| p.conflicts.as_slice()).unwrap_or_default());
+        let after = by_name(current);
+        let mut changes = Vec::new();
+
+        for (name, conflict) in &after {
+            let severity = conflict.severity;
+            let active = &conflict.active_instance.full_path;
+            let kind = match before.get(name) {
+                None => ChangeKind::Added,
+                Some(earlier) if earlier.severity != severity => ChangeKind::SeverityChanged {
+                    from: earlier.severity,
+                },
+                Some(_) => ChangeKind::Unchanged,
+            };
+            let previous_active = before
+                .get(name)
+                .map(|earlier| &earlier.active_instance.full_path)
+                .filter(|earlier| *earlier != active)
+                .cloned();
+            changes.push(ConflictChange {
+                binary_name: name.to_string(),
+                kind,
+                severity,
+                active: Some(active.clone()),
+                previous_active,
+            });
+        }
+        for (name, conflict) in &before {
+            if !after.contains_key(name) {
+                changes.push(ConflictChange {
+                    binary_name: name.to_string(),
+                    kind: ChangeKind::Resolved,
+                    severity: conflict.severity,
+                    active: Some(conflict.active_instance.full_path.clone()),
+                    previous_active: None,
+                });
+            }
+        }
+        changes.sort_by(|a, b| a.binary_name.cmp(&b.binary_name));
+
+        let mut summary = ChangeSummary::default();
+        for change in &changes {
+            match change.kind {
+                ChangeKind::Added => summary.added += 1,
+                ChangeKind::Resolved => summary.resolved += 1,
+                ChangeKind::Unchanged => summary.unchanged += 1,
+                ChangeKind::SeverityChanged { .. } => summary.severity_changed += 1,
+            }
+            if change.previous_active.is_some() {
+                summary.active_changed += 1;
+            }
+        }
+
+        ChangeReport {
+            since: previous.map(|p| p.scan_time),
+            summary,
+            changes,
+        }
+    }
+
+    /// Whether any conflict was added, resolved, changed severity or now
+    /// runs a different copy
+    pub fn has_changes(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|change| change.kind != ChangeKind::Unchanged || change.previous_active.is_some())
+    }
+
+    /// `format_changes` under a heading naming the earlier run
+    pub fn format_human(&self) -> String {
+        let mut output = match self.since {
+            Some(since) => format!(
+                "{}\n",
+                format!(
+                    "Conflicts changed since {}:",
+                    since.format("%Y-%m-%d %H:%M:%S UTC")
+                )
+                .bold()
+            ),
+            None => format!("{}\n", "Conflicts (no earlier snapshot):".bold()),
+        };
+        output.push_str(&self.format_changes());
+        output
+    }
+
+    /// Changed conflicts one per line, then the counts; unchanged conflicts
+    /// are only counted
+    pub fn format_changes(&self) -> String {
+        let mut output = String::new();
+        for change in &self.changes {
+            let mut line = match change.kind {
+                ChangeKind::Added => format!(
+                    "  {} {} [{}]",
+                    "+".red(),
+                    change.binary_name,
+                    change.severity
+                ),
+                ChangeKind::Resolved => format!(
+                    "  {} {} [{}] resolved",
+                    "-".green(),
+                    change.binary_name,
+                    change.severity
+                ),
+                ChangeKind::SeverityChanged { from } => {
+                    let marker = if change.severity > from {
+                        "~".red()
+                    } else {
+                        "~".green()
+                    };
+                    format!(
+                        "  {} {} [{} -> {}]",
+                        marker, change.binary_name, from, change.severity
+                    )
+                }
+                ChangeKind::Unchanged if change.previous_active.is_some() => format!(
+                    "  {} {} [{}]",
+                    "~".yellow(),
+                    change.binary_name,
+                    change.severity
+                ),
+                ChangeKind::Unchanged => continue,
+            };
+            if let (Some(active), Some(previous)) = (&change.active, &change.previous_active) {
+                line.push_str(&format!(
+                    " now runs {} (was {})",
+                    active.display(),
+                    previous.display()
+                ));
+            }
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        let summary = &self.summary;
+        output.push_str(&format!(
+            "{} added, {} resolved, {} severity changed, {} active copy changed, {} unchanged\n",
+            summary.added,
+            summary.resolved,
+            summary.severity_changed,
+            summary.active_changed,
+            summary.unchanged
+        ));
+
+        output
+    }
+}
+
+fn by_name(conflicts: &[Conflict]) -> BTreeMap<&str, &Conflict> {
+    conflicts
+        .iter()
+        .map(|c| (c.binary_name.as_str(), c))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::types::{ConflictCategory, ExecutableInfo, PlatformInfo, Summary};
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn conflict(name: &str, severity: Severity) -> Conflict {
+        let instance = ExecutableInfo {
+            name: name.to_string(),
+            full_path: PathBuf::from("/usr/bin").join(name),
+            size: 1000,
+            resolved_path: PathBuf::from("/usr/bin").join(name),
+            ..ExecutableInfo::fixture()
+        };
+        Conflict {
+            binary_name: name.to_string(),
+            instances: vec![instance.clone()],
+            active_instance: instance,
+            category: ConflictCategory::ShadowedBinary,
+            severity,
+            severity_reason: String::new(),
+            description: String::new(),
+            recommendation: None,
+            commands: Vec::new(),
+            same_content: false,
+            risk_score: 0.0,
+        }
+    }
+
+    fn result(conflicts: Vec<Conflict>) -> AnalysisResult {
+        AnalysisResult {
+            scan_time: Utc::now(),
+            platform: PlatformInfo {
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                is_wsl: false,
+                wsl_version: None,
+                wsl_distro: None,
+                wsl_interop: None,
+            },
+            path_entries: vec![],
+            conflicts,
+            summary: Summary {
+                total_path_entries: 0,
+                total_executables: 0,
+                unique_executables: 0,
+                total_conflicts: 0,
+                conflicts_by_category: HashMap::new(),
+                conflicts_by_severity: HashMap::new(),
+                risk_score: 0.0,
+                conflicts_by_manager: vec![],
+            },
+            diagnostics: vec![],
+            requirements: vec![],
+            root_causes: vec![],
+            path_issues: vec![],
+            security: vec![],
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let previous = result(vec![
+            conflict("node", Severity::Low),
+            conflict("python", Severity::Medium),
+            conflict("git", Severity::Low),
+        ]);
+        let current = vec![
+            conflict("python", Severity::High),
+            conflict("git", Severity::Low),
+            conflict("java", Severity::Medium),
+        ];
+
+        let report = ChangeReport::compare(Some(&previous), &current);
+        let changes: Vec<(&str, ChangeKind)> = report
+            .changes
+            .iter()
+            .map(|c| (c.binary_name.as_str(), c.kind))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("git", ChangeKind::Unchanged),
+                ("java", ChangeKind::Added),
+                ("node", ChangeKind::Resolved),
+                (
+                    "python",
+                    ChangeKind::SeverityChanged {
+                        from: Severity::Medium
+                    }
+                ),
+            ]
+        );
+        assert_eq!(
+            report.summary,
+            ChangeSummary {
+                added: 1,
+                resolved: 1,
+                unchanged: 1,
+                severity_changed: 1,
+                active_changed: 0,
+            }
+        );
+        assert!(report.has_changes());
+    }
+
+    #[test]
+    fn test_active_instance_changed() {
+        let mut moved = conflict("python", Severity::Medium);
+        moved.active_instance.full_path = PathBuf::from("/usr/local/bin/python");
+        let previous = result(vec![conflict("python", Severity::Medium)]);
+
+        let report = ChangeReport::compare(Some(&previous), &[moved]);
+        assert_eq!(report.changes[0].kind, ChangeKind::Unchanged);
+        assert_eq!(
+            report.changes[0].previous_active,
+            Some(PathBuf::from("/usr/bin/python"))
+        );
+        assert_eq!(report.summary.active_changed, 1);
+        assert!(report.has_changes());
+        assert!(report
+            .format_changes()
+            .contains("python [MEDIUM] now runs /usr/local/bin/python (was /usr/bin/python)"));
+    }
+
+    #[test]
+    fn test_unchanged_and_first_run() {
+        let current = vec![conflict("git", Severity::Low)];
+        let report = ChangeReport::compare(Some(&result(current.clone())), &current);
+        assert!(!report.has_changes());
+        assert_eq!(report.summary.unchanged, 1);
+
+        let report = ChangeReport::compare(None, &current);
+        assert_eq!(report.changes.len(), 1);
+        assert_eq!(report.changes[0].kind, ChangeKind::Added);
+    }
+}
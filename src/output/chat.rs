@@ -0,0 +1,267 @@
+use crate::output::types::{AnalysisResult, Conflict};
+use serde_json::{json, Value};
+
+/// Conflicts shown in a chat message; the rest are only counted
+const TOP_CONFLICTS: usize = 5;
+
+/// Slack Block Kit message with the summary and the most severe conflicts.
+/// `headline` replaces the default first line, e.g. to say what changed.
+pub fn format_slack(result: &AnalysisResult, headline: Option<&str>) -> Value {
+    let headline = headline
+        .map(str::to_string)
+        .unwrap_or_else(|| default_headline(result));
+    let summary = &result.summary;
+
+    let mut blocks = vec![
+        json!({
+            "type": "header",
+            "text": { "type": "plain_text", "text": "PATH Conflict Report" },
+        }),
+        json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": slack_escape(&headline) },
+        }),
+        json!({
+            "type": "section",
+            "fields": [
+                { "type": "mrkdwn", "text": format!("*Conflicts*\n{}", summary.total_conflicts) },
+                { "type": "mrkdwn", "text": format!("*Risk score*\n{:.0}/100", summary.risk_score) },
+                { "type": "mrkdwn", "text": format!("*PATH entries*\n{}", summary.total_path_entries) },
+                { "type": "mrkdwn", "text": format!("*Platform*\n{}", platform(result)) },
+            ],
+        }),
+    ];
+
+    let top = top_conflicts(result);
+    if !top.is_empty() {
+        blocks.push(json!({ "type": "divider" }));
+    }
+    for conflict in &top {
+        let mut text = format!(
+            "*{}* · {} · {}\nActive: `{}`",
+            slack_escape(&conflict.binary_name),
+            conflict.severity,
+            conflict.category,
+            slack_escape(&conflict.active_instance.full_path.display().to_string())
+        );
+        if let Some(recommendation) = &conflict.recommendation {
+            text.push_str(&format!("\n{}", slack_escape(recommendation)));
+        }
+        blocks.push(json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": text },
+        }));
+    }
+    if let Some(more) = more_line(result, top.len()) {
+        blocks.push(json!({
+            "type": "context",
+            "elements": [{ "type": "mrkdwn", "text": more }],
+        }));
+    }
+
+    json!({ "text": headline, "blocks": blocks })
+}
+
+/// Microsoft Teams message carrying an Adaptive Card with the same content
+/// as the Slack message
+pub fn format_teams(result: &AnalysisResult, headline: Option<&str>) -> Value {
+    let headline = headline
+        .map(str::to_string)
+        .unwrap_or_else(|| default_headline(result));
+    let summary = &result.summary;
+
+    let mut body = vec![
+        json!({
+            "type": "TextBlock",
+            "text": "PATH Conflict Report",
+            "size": "Large",
+            "weight": "Bolder",
+        }),
+        json!({ "type": "TextBlock", "text": headline, "wrap": true }),
+        json!({
+            "type": "FactSet",
+            "facts": [
+                { "title": "Conflicts", "value": summary.total_conflicts.to_string() },
+                { "title": "Risk score", "value": format!("{:.0}/100", summary.risk_score) },
+                { "title": "PATH entries", "value": summary.total_path_entries.to_string() },
+                { "title": "Platform", "value": platform(result) },
+            ],
+        }),
+    ];
+
+    let top = top_conflicts(result);
+    for conflict in &top {
+        let mut items = vec![
+            json!({
+                "type": "TextBlock",
+                "text": format!(
+                    "**{}** · {} · {}",
+                    conflict.binary_name, conflict.severity, conflict.category
+                ),
+                "wrap": true,
+            }),
+            json!({
+                "type": "TextBlock",
+                "text": format!("Active: {}", conflict.active_instance.full_path.display()),
+                "isSubtle": true,
+                "wrap": true,
+            }),
+        ];
+        if let Some(recommendation) = &conflict.recommendation {
+            items.push(json!({ "type": "TextBlock", "text": recommendation, "wrap": true }));
+        }
+        body.push(json!({ "type": "Container", "separator": true, "items": items }));
+    }
+    if let Some(more) = more_line(result, top.len()) {
+        body.push(json!({ "type": "TextBlock", "text": more, "isSubtle": true }));
+    }
+
+    json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "content": {
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "type": "AdaptiveCard",
+                "version": "1.4",
+                "body": body,
+            },
+        }],
+    })
+}
+
+fn default_headline(result: &AnalysisResult) -> String {
+    match result.conflicts.len() {
+        0 => "No PATH conflicts found".to_string(),
+        1 => "1 PATH conflict found".to_string(),
+        n => format!("{} PATH conflicts found", n),
+    }
+}
+
+/// Most severe first, then by risk score
+fn top_conflicts(result: &AnalysisResult) -> Vec<&Conflict> {
+    let mut conflicts: Vec<&Conflict> = result.conflicts.iter().collect();
+    conflicts.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then(b.risk_score.total_cmp(&a.risk_score))
+    });
+    conflicts.truncate(TOP_CONFLICTS);
+    conflicts
+}
+
+fn more_line(result: &AnalysisResult, shown: usize) -> Option<String> {
+    let hidden = result.conflicts.len().saturating_sub(shown);
+    (hidden > 0).then(|| format!("and {} more", hidden))
+}
+
+fn platform(result: &AnalysisResult) -> String {
+    let platform = &result.platform;
+    if platform.is_wsl {
+        format!("{} {} (WSL)", platform.os, platform.arch)
+    } else {
+        format!("{} {}", platform.os, platform.arch)
+    }
+}
+
+/// Slack treats `&`, `<` and `>` as markup
+fn slack_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::types::{ConflictCategory, ExecutableInfo, PlatformInfo, Severity, Summary};
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn conflict(name: &str, severity: Severity) -> Conflict {
+        let instance = ExecutableInfo {
+            name: name.to_string(),
+            full_path: PathBuf::from("/usr/local/bin").join(name),
+            size: 1000,
+            resolved_path: PathBuf::from("/usr/local/bin").join(name),
+            is_active: true,
+            ..ExecutableInfo::fixture()
+        };
+        Conflict {
+            binary_name: name.to_string(),
+            instances: vec![instance.clone()],
+            active_instance: instance,
+            category: ConflictCategory::ShadowedBinary,
+            severity,
+            severity_reason: String::new(),
+            description: String::new(),
+            recommendation: Some("Remove <dir> & retry".to_string()),
+            commands: Vec::new(),
+            same_content: false,
+            risk_score: 0.0,
+        }
+    }
+
+    fn result() -> AnalysisResult {
+        let mut conflicts: Vec<Conflict> = ["a", "b", "c", "d", "e", "f"]
+            .iter()
+            .map(|name| conflict(name, Severity::Low))
+            .collect();
+        conflicts.push(conflict("python", Severity::High));
+
+        AnalysisResult {
+            scan_time: Utc::now(),
+            platform: PlatformInfo {
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                is_wsl: false,
+                wsl_version: None,
+                wsl_distro: None,
+                wsl_interop: None,
+            },
+            path_entries: vec![],
+            summary: Summary {
+                total_path_entries: 4,
+                total_executables: 14,
+                unique_executables: 7,
+                total_conflicts: conflicts.len(),
+                conflicts_by_category: HashMap::new(),
+                conflicts_by_severity: HashMap::new(),
+                risk_score: 40.0,
+                conflicts_by_manager: vec![],
+            },
+            conflicts,
+            diagnostics: vec![],
+            requirements: vec![],
+            root_causes: vec![],
+            path_issues: vec![],
+            security: vec![],
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn test_slack() {
+        let message = format_slack(&result(), None);
+        let blocks = message["blocks"].as_array().unwrap();
+
+        assert_eq!(message["text"], "7 PATH conflicts found");
+        // header, headline, fields, divider, 5 conflicts, "and 2 more"
+        assert_eq!(blocks.len(), 10);
+        let first = blocks[4]["text"]["text"].as_str().unwrap();
+        assert!(first.starts_with("*python* · HIGH"));
+        assert!(first.contains("Remove &lt;dir&gt; &amp; retry"));
+        assert_eq!(blocks[9]["elements"][0]["text"], "and 2 more");
+    }
+
+    #[test]
+    fn test_teams() {
+        let message = format_teams(&result(), Some("python became HIGH"));
+        let card = &message["attachments"][0]["content"];
+
+        assert_eq!(card["type"], "AdaptiveCard");
+        assert_eq!(card["body"][1]["text"], "python became HIGH");
+        assert_eq!(card["body"].as_array().unwrap().len(), 9);
+    }
+}
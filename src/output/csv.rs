@@ -0,0 +1,113 @@
+use crate::output::types::AnalysisResult;
+
+const HEADER: [&str; 9] = [
+    "binary_name",
+    "path_order",
+    "active",
+    "path",
+    "resolved_path",
+    "version",
+    "manager",
+    "category",
+    "severity",
+];
+
+/// Field separator of a delimited export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    /// RFC 4180: fields with commas, quotes or line breaks are quoted
+    Comma,
+    /// Tabs and line breaks inside fields become spaces; nothing is quoted
+    Tab,
+}
+
+/// One row per copy of each conflicting binary, in PATH order, for
+/// spreadsheet pivots across machines
+pub fn format_delimited(result: &AnalysisResult, delimiter: Delimiter) -> String {
+    let mut out = String::new();
+    push_row(&mut out, &HEADER.map(str::to_string), delimiter);
+
+    for conflict in &result.conflicts {
+        for instance in &conflict.instances {
+            let version = instance
+                .version
+                .as_ref()
+                .map(|v| v.parsed.clone().unwrap_or_else(|| v.raw.clone()))
+                .unwrap_or_default();
+            let manager = instance
+                .manager
+                .as_ref()
+                .map(|m| m.name.clone())
+                .unwrap_or_default();
+            let row = [
+                conflict.binary_name.clone(),
+                instance.path_order.to_string(),
+                (instance.full_path == conflict.active_instance.full_path).to_string(),
+                instance.full_path.display().to_string(),
+                instance.resolved_path.display().to_string(),
+                version,
+                manager,
+                conflict.category.to_string(),
+                conflict.severity.to_string(),
+            ];
+            push_row(&mut out, &row, delimiter);
+        }
+    }
+
+    out
+}
+
+fn push_row(out: &mut String, fields: &[String], delimiter: Delimiter) {
+    for (i, field) in fields.iter().enumerate() {
+        match delimiter {
+            Delimiter::Comma => {
+                if i > 0 {
+                    out.push(',');
+                }
+                if field.contains([',', '"', '\n', '\r']) {
+                    out.push('"');
+                    out.push_str(&field.replace('"', "\"\""));
+                    out.push('"');
+                } else {
+                    out.push_str(field);
+                }
+            }
+            Delimiter::Tab => {
+                if i > 0 {
+                    out.push('\t');
+                }
+                out.push_str(&field.replace(['\t', '\n', '\r'], " "));
+            }
+        }
+    }
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_row_escaping() {
+        let fields = vec![
+            "node".to_string(),
+            "C:\\Program Files, x86\\node.exe".to_string(),
+            "say \"hi\"".to_string(),
+            "a\tb".to_string(),
+        ];
+
+        let mut csv = String::new();
+        push_row(&mut csv, &fields, Delimiter::Comma);
+        assert_eq!(
+            csv,
+            "node,\"C:\\Program Files, x86\\node.exe\",\"say \"\"hi\"\"\",a\tb\n"
+        );
+
+        let mut tsv = String::new();
+        push_row(&mut tsv, &fields, Delimiter::Tab);
+        assert_eq!(
+            tsv,
+            "node\tC:\\Program Files, x86\\node.exe\tsay \"hi\"\ta b\n"
+        );
+    }
+}
@@ -25,12 +25,60 @@ impl HumanFormatter {
         output.push_str(&self.format_summary(&result.summary));
         output.push('\n');
 
+        // PATH entries that let other people's programs run
+        if !result.security.is_empty() {
+            output.push_str(&self.format_security(&result.security));
+            output.push('\n');
+        }
+
+        // Installer relationships behind the conflicts
+        if !result.summary.conflicts_by_manager.is_empty() {
+            output.push_str(&self.format_conflicts_by_manager(&result.summary));
+            output.push('\n');
+        }
+
+        // Version requirements
+        if !result.requirements.is_empty() {
+            output.push_str(&self.format_requirements(&result.requirements));
+            output.push('\n');
+        }
+
         // Conflicts by category
         if !result.conflicts.is_empty() {
             output.push_str(&self.format_conflicts_by_category(&result.summary));
             output.push('\n');
         }
 
+        // Entries behind many conflicts at once
+        if !result.root_causes.is_empty() {
+            output.push_str(&self.format_root_causes(&result.root_causes, &result.path_entries));
+            output.push('\n');
+        }
+
+        // Non-conflict findings
+        if !result.diagnostics.is_empty() {
+            output.push_str(&self.format_diagnostics(&result.diagnostics));
+            output.push('\n');
+        }
+
+        // Entries that contribute nothing
+        if !result.path_issues.is_empty() {
+            output.push_str(&self.format_path_issues(&result.path_issues, &result.path_entries));
+            output.push('\n');
+        }
+
+        // Dangling links left in PATH directories
+        let broken: Vec<&ExecutableInfo> = result
+            .path_entries
+            .iter()
+            .flat_map(|e| &e.executables)
+            .filter(|e| e.broken)
+            .collect();
+        if !broken.is_empty() {
+            output.push_str(&self.format_broken_symlinks(&broken));
+            output.push('\n');
+        }
+
         // Detailed conflicts
         if !result.conflicts.is_empty() {
             output.push_str(&self.format_detailed_conflicts(&result.conflicts));
@@ -68,6 +116,16 @@ impl HumanFormatter {
                     .unwrap_or(&"WSL".to_string())
             );
             output.push_str(&wsl_info.cyan().to_string());
+            if let Some(interop) = &result.platform.wsl_interop {
+                let appended = if !interop.enabled {
+                    "interop disabled"
+                } else if interop.append_windows_path {
+                    "Windows PATH appended"
+                } else {
+                    "Windows PATH not appended"
+                };
+                output.push_str(&format!(" ({})", appended));
+            }
         }
 
         output.push('\n');
@@ -115,6 +173,10 @@ impl HumanFormatter {
             );
         }
 
+        if summary.total_conflicts > 0 {
+            output.push_str(&format!("PATH Risk Score: {:.1}/100\n", summary.risk_score));
+        }
+
         output
     }
 
@@ -132,6 +194,9 @@ impl HumanFormatter {
             (ConflictCategory::MultipleVersionManagers, "🟡"),
             (ConflictCategory::DuplicateVersions, "🔵"),
             (ConflictCategory::ShadowedBinary, "⚪"),
+            (ConflictCategory::BrokenSymlink, "⚪"),
+            (ConflictCategory::PathextShadowing, "🟡"),
+            (ConflictCategory::PipMismatch, "🟠"),
         ];
 
         for (category, icon) in categories {
@@ -145,6 +210,181 @@ impl HumanFormatter {
         output
     }
 
+    fn format_conflicts_by_manager(&self, summary: &Summary) -> String {
+        let mut output = String::new();
+
+        output.push('\n');
+        output.push_str(&"CONFLICTS BY MANAGER\n".bold().to_string());
+        output.push_str(&"─".repeat(60));
+        output.push('\n');
+
+        for entry in &summary.conflicts_by_manager {
+            output.push_str(&format!("{}: {}\n", entry, entry.count));
+        }
+
+        output
+    }
+
+    fn format_requirements(&self, requirements: &[RequirementCheck]) -> String {
+        let mut output = String::new();
+
+        output.push('\n');
+        output.push_str(&"REQUIREMENTS\n".bold().to_string());
+        output.push_str(&"─".repeat(60));
+        output.push('\n');
+
+        for check in requirements {
+            if check.satisfied {
+                output.push_str(&format!("✓ {}\n", check.message).green().to_string());
+                continue;
+            }
+
+            output.push_str(&format!("✗ {}\n", check.message).red().bold().to_string());
+            for path in &check.satisfying_paths {
+                output.push_str(&format!("   Satisfied by: {}\n", path.display()));
+            }
+        }
+
+        output
+    }
+
+    fn format_root_causes(&self, root_causes: &[RootCause], entries: &[PathEntry]) -> String {
+        let mut output = String::new();
+
+        output.push('\n');
+        output.push_str(&"ROOT CAUSES\n".bold().to_string());
+        output.push_str(&"─".repeat(60));
+        output.push('\n');
+
+        for root_cause in root_causes {
+            output.push_str(&format!("➜ {}\n", root_cause.message).yellow().to_string());
+
+            let shown: Vec<&str> = root_cause
+                .binaries
+                .iter()
+                .take(8)
+                .map(String::as_str)
+                .collect();
+            let more = root_cause.binaries.len() - shown.len();
+            if more > 0 {
+                output.push_str(&format!("   {} and {} more\n", shown.join(", "), more));
+            } else {
+                output.push_str(&format!("   {}\n", shown.join(", ")));
+            }
+            if let Some(origin) = entry_origin(entries, |e| e.path == root_cause.directory) {
+                output.push_str(&format!("   {}\n", origin).dimmed().to_string());
+            }
+        }
+
+        output
+    }
+
+    fn format_diagnostics(&self, diagnostics: &[Diagnostic]) -> String {
+        let mut output = String::new();
+
+        output.push('\n');
+        output.push_str(&"DIAGNOSTICS\n".bold().to_string());
+        output.push_str(&"─".repeat(60));
+        output.push('\n');
+
+        for diagnostic in diagnostics {
+            let header = format!(
+                "{} {} ({})",
+                self.severity_icon(&diagnostic.severity),
+                diagnostic.kind,
+                diagnostic.severity
+            );
+            output.push_str(
+                &self
+                    .colorize_by_severity(&header, &diagnostic.severity)
+                    .to_string(),
+            );
+            output.push('\n');
+            output.push_str(&format!("   {}\n", diagnostic.message));
+        }
+
+        output
+    }
+
+    fn format_security(&self, findings: &[SecurityFinding]) -> String {
+        let mut output = String::new();
+
+        output.push('\n');
+        output.push_str(&"SECURITY\n".bold().to_string());
+        output.push_str(&"─".repeat(60));
+        output.push('\n');
+
+        for finding in findings {
+            let header = format!(
+                "{} {} ({})",
+                self.severity_icon(&finding.severity),
+                finding.kind,
+                finding.severity
+            );
+            output.push_str(
+                &self
+                    .colorize_by_severity(&header, &finding.severity)
+                    .bold()
+                    .to_string(),
+            );
+            output.push('\n');
+            output.push_str(&format!("   {}\n", finding.message));
+        }
+
+        output
+    }
+
+    fn format_path_issues(&self, issues: &[PathIssue], entries: &[PathEntry]) -> String {
+        let mut output = String::new();
+
+        output.push('\n');
+        output.push_str(&"PATH ISSUES\n".bold().to_string());
+        output.push_str(&"─".repeat(60));
+        output.push('\n');
+
+        for issue in issues {
+            output.push_str(
+                &format!(
+                    "⚠ [{}] {}: {}\n",
+                    issue.order + 1,
+                    issue.directory.display(),
+                    issue.kind
+                )
+                .yellow()
+                .to_string(),
+            );
+            if let Some(origin) = entry_origin(entries, |e| e.order == issue.order) {
+                output.push_str(&format!("  {}\n", origin).dimmed().to_string());
+            }
+        }
+
+        output
+    }
+
+    fn format_broken_symlinks(&self, broken: &[&ExecutableInfo]) -> String {
+        let mut output = String::new();
+
+        output.push('\n');
+        output.push_str(&"BROKEN SYMLINKS\n".bold().to_string());
+        output.push_str(&"─".repeat(60));
+        output.push('\n');
+
+        for exec in broken {
+            let target = exec
+                .symlink_target
+                .as_ref()
+                .map(|t| t.display().to_string())
+                .unwrap_or_else(|| "?".to_string());
+            output.push_str(
+                &format!("✗ {} → {}\n", exec.full_path.display(), target)
+                    .red()
+                    .to_string(),
+            );
+        }
+
+        output
+    }
+
     fn format_detailed_conflicts(&self, conflicts: &[Conflict]) -> String {
         let mut output = String::new();
 
@@ -167,8 +407,13 @@ impl HumanFormatter {
         // Conflict header
         let severity_icon = self.severity_icon(&conflict.severity);
         let header = format!(
-            "[{}] {} {}: {} ({})",
-            number, severity_icon, conflict.severity, conflict.binary_name, conflict.category
+            "[{}] {} {}: {} ({}) - risk {:.1}",
+            number,
+            severity_icon,
+            conflict.severity,
+            conflict.binary_name,
+            conflict.category,
+            conflict.risk_score
         );
 
         output.push_str(
@@ -181,6 +426,22 @@ impl HumanFormatter {
         output.push_str(&"─".repeat(60));
         output.push('\n');
 
+        if !conflict.severity_reason.is_empty() {
+            output.push_str(
+                &format!("Why {}: {}\n", conflict.severity, conflict.severity_reason)
+                    .dimmed()
+                    .to_string(),
+            );
+        }
+
+        if conflict.same_content {
+            output.push_str(
+                &"Identical copies installed in multiple locations\n"
+                    .dimmed()
+                    .to_string(),
+            );
+        }
+
         // Active instance
         output.push_str(&"Active: ".green().bold().to_string());
         output.push_str(&self.format_executable(&conflict.active_instance, true));
@@ -190,7 +451,12 @@ impl HumanFormatter {
         if conflict.instances.len() > 1 {
             output.push('\n');
             output.push_str(&"Shadowed instances:\n".yellow().to_string());
-            for (idx, instance) in conflict.instances.iter().enumerate().skip(1) {
+            for (idx, instance) in conflict
+                .instances
+                .iter()
+                .enumerate()
+                .filter(|(_, i)| i.full_path != conflict.active_instance.full_path)
+            {
                 output.push_str(&format!("   [{}] ", idx + 1));
                 output.push_str(&self.format_executable(instance, false));
                 output.push('\n');
@@ -205,6 +471,13 @@ impl HumanFormatter {
                 output.push_str(recommendation);
                 output.push('\n');
             }
+
+            if !conflict.commands.is_empty() {
+                output.push_str(&"Commands:\n".cyan().to_string());
+                for command in &conflict.commands {
+                    output.push_str(&format!("   $ {}\n", command));
+                }
+            }
         }
 
         output
@@ -219,13 +492,77 @@ impl HumanFormatter {
             parts.push(format!("→ {}", version.raw));
         }
 
+        if exec.immutable {
+            parts.push("[SIP-protected]".dimmed().to_string());
+        }
+
+        if exec.broken {
+            parts.push("[broken symlink]".red().to_string());
+        }
+
         if self.verbose {
             if let Some(manager) = &exec.manager {
                 parts.push(format!("({})", manager.name));
             }
+            match &exec.binary_format {
+                Some(format) if format.is_native() => parts.push(format!("[native {}]", format)),
+                Some(format) => parts.push(format!("[{}]", format)),
+                None => {}
+            }
+            if let Some(origin) = &exec.origin {
+                let version = origin
+                    .version
+                    .as_ref()
+                    .map(|v| format!(" {}", v))
+                    .unwrap_or_default();
+                let linked = match origin.linked {
+                    Some(true) => ", linked",
+                    Some(false) => ", not linked",
+                    None => "",
+                };
+                parts.push(format!(
+                    "<{}: {}{}{}>",
+                    origin.source, origin.package, version, linked
+                ));
+            }
         }
 
-        parts.join(" ")
+        let mut line = parts.join(" ");
+
+        // Show what was run when no version could be read
+        if self.verbose && exec.version.is_none() {
+            for probe in &exec.version_probes {
+                let outcome = match (&probe.error, probe.exit_code) {
+                    (Some(error), _) => error.clone(),
+                    (None, Some(code)) => format!("exit {}", code),
+                    (None, None) => "killed".to_string(),
+                };
+                let sample = [&probe.stdout, &probe.stderr]
+                    .into_iter()
+                    .find(|s| !s.is_empty())
+                    .and_then(|s| s.lines().next())
+                    .map(|line| format!(": {}", line))
+                    .unwrap_or_default();
+                line.push_str(
+                    &format!("\n       Probe: {} → {}{}", probe.command, outcome, sample)
+                        .dimmed()
+                        .to_string(),
+                );
+            }
+        }
+
+        // Intermediate hops explain which manager owns the link
+        if self.verbose && !exec.symlink_chain.is_empty() {
+            let mut hops = vec![exec.full_path.display().to_string()];
+            hops.extend(exec.symlink_chain.iter().map(|p| p.display().to_string()));
+            line.push_str(
+                &format!("\n       Symlink: {}", hops.join(" → "))
+                    .dimmed()
+                    .to_string(),
+            );
+        }
+
+        line
     }
 
     fn severity_icon(&self, severity: &Severity) -> &str {
@@ -254,3 +591,18 @@ impl Default for HumanFormatter {
         Self::new(false, false)
     }
 }
+
+/// "added by ~/.zshrc:42" or "from the user PATH" for the first entry
+/// matching `is_entry`
+fn entry_origin(entries: &[PathEntry], is_entry: impl Fn(&PathEntry) -> bool) -> Option<String> {
+    let entry = entries.iter().find(|e| is_entry(e))?;
+    let sources: Vec<String> = entry.sources.iter().map(ToString::to_string).collect();
+    let mut origin = Vec::new();
+    if !sources.is_empty() {
+        origin.push(format!("added by {}", sources.join(", ")));
+    }
+    if let Some(scope) = entry.scope {
+        origin.push(format!("from the {} PATH", scope));
+    }
+    (!origin.is_empty()).then(|| origin.join("; "))
+}
@@ -0,0 +1,154 @@
+use crate::output::types::{AnalysisResult, Conflict, Severity};
+use std::fmt::Write;
+
+const STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; vertical-align: top; }
+th { background: #f0f0f0; }
+code { font-size: 90%; }
+.critical, .high { color: #b00020; font-weight: bold; }
+.medium { color: #b36b00; }
+.low, .info { color: #555; }
+";
+
+/// Self-contained HTML page with the summary, conflicts, PATH order and
+/// diagnostics
+pub fn format_html(result: &AnalysisResult) -> String {
+    let mut html = String::new();
+    let summary = &result.summary;
+
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>PATH Conflict Report</title>\n");
+    let _ = writeln!(html, "<style>\n{}</style>\n</head>\n<body>", STYLE);
+    let _ = writeln!(html, "<h1>PATH Conflict Report</h1>");
+    let _ = writeln!(
+        html,
+        "<p>Scanned {} on {} ({}{})</p>",
+        result.scan_time.format("%Y-%m-%d %H:%M:%S UTC"),
+        escape(&result.platform.os),
+        escape(&result.platform.arch),
+        if result.platform.is_wsl { ", WSL" } else { "" }
+    );
+
+    html.push_str("<h2>Summary</h2>\n<table>\n");
+    for (label, value) in [
+        ("PATH entries", summary.total_path_entries.to_string()),
+        ("Executables", summary.total_executables.to_string()),
+        ("Unique executables", summary.unique_executables.to_string()),
+        ("Conflicts", summary.total_conflicts.to_string()),
+        ("Risk score", format!("{:.0}/100", summary.risk_score)),
+    ] {
+        let _ = writeln!(html, "<tr><th>{}</th><td>{}</td></tr>", label, value);
+    }
+    html.push_str("</table>\n");
+
+    if !result.conflicts.is_empty() {
+        html.push_str("<h2>Conflicts</h2>\n<table>\n");
+        html.push_str(
+            "<tr><th>Binary</th><th>Severity</th><th>Category</th><th>Active</th>\
+             <th>Shadowed</th><th>Recommendation</th></tr>\n",
+        );
+        for conflict in &result.conflicts {
+            html.push_str(&conflict_row(conflict));
+        }
+        html.push_str("</table>\n");
+    }
+
+    if !result.diagnostics.is_empty() {
+        html.push_str("<h2>Diagnostics</h2>\n<table>\n");
+        html.push_str("<tr><th>Kind</th><th>Severity</th><th>Message</th></tr>\n");
+        for diagnostic in &result.diagnostics {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td>{}<td>{}</td></tr>",
+                escape(&diagnostic.kind.to_string()),
+                severity_cell(diagnostic.severity),
+                escape(&diagnostic.message)
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("<h2>PATH</h2>\n<table>\n");
+    html.push_str("<tr><th>#</th><th>Directory</th><th>Executables</th></tr>\n");
+    for entry in &result.path_entries {
+        let status = if !entry.exists {
+            "missing".to_string()
+        } else if !entry.is_accessible {
+            "not accessible".to_string()
+        } else {
+            entry.executables.len().to_string()
+        };
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td><code>{}</code></td><td>{}</td></tr>",
+            entry.order + 1,
+            escape(&entry.path.display().to_string()),
+            status
+        );
+    }
+    html.push_str("</table>\n</body>\n</html>\n");
+
+    html
+}
+
+fn conflict_row(conflict: &Conflict) -> String {
+    let shadowed: Vec<String> = conflict
+        .instances
+        .iter()
+        .filter(|i| i.full_path != conflict.active_instance.full_path)
+        .map(|i| {
+            format!(
+                "<code>{}</code>",
+                escape(&i.full_path.display().to_string())
+            )
+        })
+        .collect();
+
+    format!(
+        "<tr><td>{}</td>{}<td>{}</td><td><code>{}</code></td><td>{}</td><td>{}</td></tr>\n",
+        escape(&conflict.binary_name),
+        severity_cell(conflict.severity),
+        escape(&conflict.category.to_string()),
+        escape(&conflict.active_instance.full_path.display().to_string()),
+        shadowed.join("<br>"),
+        escape(conflict.recommendation.as_deref().unwrap_or(""))
+    )
+}
+
+fn severity_cell(severity: Severity) -> String {
+    format!(
+        "<td class=\"{}\">{}</td>",
+        severity.to_string().to_lowercase(),
+        severity
+    )
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape(r#"<a href="x">Tom & 'Jerry'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#39;Jerry&#39;&lt;/a&gt;"
+        );
+    }
+}
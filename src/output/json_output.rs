@@ -9,6 +9,23 @@ pub fn format_json(result: &AnalysisResult, pretty: bool) -> Result<String> {
     }
 }
 
+/// `{"error": {"code": ..., "message": ...}}`, printed on stdout in place of
+/// the report when a JSON run fails
+pub fn format_error(error: &Error, pretty: bool) -> String {
+    let report = serde_json::json!({
+        "error": {
+            "code": error.code(),
+            "message": error.to_string(),
+        }
+    });
+
+    if pretty {
+        serde_json::to_string_pretty(&report).unwrap_or_default()
+    } else {
+        report.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -25,6 +42,7 @@ mod tests {
                 is_wsl: false,
                 wsl_version: None,
                 wsl_distro: None,
+                wsl_interop: None,
             },
             path_entries: vec![],
             conflicts: vec![],
@@ -35,10 +53,33 @@ mod tests {
                 total_conflicts: 0,
                 conflicts_by_category: HashMap::new(),
                 conflicts_by_severity: HashMap::new(),
+                risk_score: 0.0,
+                conflicts_by_manager: vec![],
             },
+            diagnostics: vec![],
+            requirements: vec![],
+            root_causes: vec![],
+            path_issues: vec![],
+            security: vec![],
+            signature: None,
         }
     }
 
+    #[test]
+    fn test_format_error() {
+        let error = Error::ConfigError {
+            path: "pcd.toml".to_string(),
+            reason: "bad key".to_string(),
+        };
+        let value: serde_json::Value = serde_json::from_str(&format_error(&error, false)).unwrap();
+
+        assert_eq!(value["error"]["code"], "config");
+        assert_eq!(
+            value["error"]["message"],
+            "Invalid config file pcd.toml: bad key"
+        );
+    }
+
     #[test]
     fn test_format_json() {
         let result = create_test_result();
@@ -0,0 +1,225 @@
+use crate::output::types::{AnalysisResult, Conflict, Severity};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+const SUITE_NAME: &str = env!("CARGO_PKG_NAME");
+
+/// JUnit XML report with one test case per binary name on PATH. Conflicts
+/// at or above `fail_on` are failures; milder ones pass with the
+/// description in `<system-out>`.
+pub fn format_junit(result: &AnalysisResult, fail_on: Severity) -> String {
+    let conflicts: BTreeMap<&str, &Conflict> = result
+        .conflicts
+        .iter()
+        .map(|c| (c.binary_name.as_str(), c))
+        .collect();
+    let names: BTreeSet<&str> = result
+        .path_entries
+        .iter()
+        .flat_map(|e| &e.executables)
+        .map(|exec| exec.name.as_str())
+        .chain(conflicts.keys().copied())
+        .collect();
+    let failures = conflicts.values().filter(|c| c.severity >= fail_on).count();
+
+    let mut cases = String::new();
+    for name in &names {
+        let Some(conflict) = conflicts.get(name) else {
+            let _ = writeln!(
+                cases,
+                "    <testcase name=\"{}\" classname=\"{}\"/>",
+                escape(name),
+                SUITE_NAME
+            );
+            continue;
+        };
+
+        let _ = writeln!(
+            cases,
+            "    <testcase name=\"{}\" classname=\"{}.{:?}\">",
+            escape(name),
+            SUITE_NAME,
+            conflict.category
+        );
+        if conflict.severity >= fail_on {
+            let _ = writeln!(
+                cases,
+                "      <failure message=\"{}\" type=\"{} {}\">{}</failure>",
+                escape(&conflict.description),
+                conflict.severity,
+                escape(&conflict.category.to_string()),
+                escape(&details(conflict))
+            );
+        } else {
+            let _ = writeln!(
+                cases,
+                "      <system-out>{}</system-out>",
+                escape(&format!("{}\n{}", conflict.description, details(conflict)))
+            );
+        }
+        cases.push_str("    </testcase>\n");
+    }
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\">",
+        SUITE_NAME,
+        names.len(),
+        failures
+    );
+    let _ = writeln!(
+        xml,
+        "  <testsuite name=\"PATH\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\" \
+         timestamp=\"{}\">",
+        names.len(),
+        failures,
+        result.scan_time.format("%Y-%m-%dT%H:%M:%S")
+    );
+    xml.push_str(&cases);
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+/// Every copy in PATH order, then the recommendation
+fn details(conflict: &Conflict) -> String {
+    let mut text = String::new();
+    for instance in &conflict.instances {
+        let marker = if instance.full_path == conflict.active_instance.full_path {
+            "active"
+        } else {
+            "shadowed"
+        };
+        let _ = write!(text, "{} ({})", instance.full_path.display(), marker);
+        if let Some(version) = &instance.version {
+            let _ = write!(text, " {}", version.raw);
+        }
+        text.push('\n');
+    }
+    if let Some(recommendation) = &conflict.recommendation {
+        text.push_str(recommendation);
+        text.push('\n');
+    }
+    text
+}
+
+/// Escape text for XML attributes and content, dropping control characters
+/// XML 1.0 cannot represent
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' | '\t' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::output::types::{
+        ConflictCategory, ExecutableInfo, PathEntry, PlatformInfo, Summary,
+    };
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn exec(path: &str) -> ExecutableInfo {
+        let path = PathBuf::from(path);
+        ExecutableInfo {
+            name: path.file_name().unwrap().to_string_lossy().into(),
+            full_path: path.clone(),
+            size: 100,
+            resolved_path: path,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    fn conflict(name: &str, severity: Severity) -> Conflict {
+        let instances = vec![exec(&format!("/a/{}", name)), exec(&format!("/b/{}", name))];
+        Conflict {
+            binary_name: name.to_string(),
+            active_instance: instances[0].clone(),
+            instances,
+            category: ConflictCategory::ShadowedBinary,
+            severity,
+            severity_reason: String::new(),
+            description: format!("{} <shadowed>", name),
+            recommendation: None,
+            commands: Vec::new(),
+            same_content: false,
+            risk_score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_format_junit() {
+        let result = AnalysisResult {
+            scan_time: Utc::now(),
+            platform: PlatformInfo {
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                is_wsl: false,
+                wsl_version: None,
+                wsl_distro: None,
+                wsl_interop: None,
+            },
+            path_entries: vec![PathEntry {
+                path: PathBuf::from("/a"),
+                order: 0,
+                exists: true,
+                is_accessible: true,
+                executables: vec![exec("/a/cc"), exec("/a/ls"), exec("/a/python3")],
+                sources: Vec::new(),
+                scope: None,
+            }],
+            conflicts: vec![
+                conflict("python3", Severity::High),
+                conflict("cc", Severity::Low),
+            ],
+            summary: Summary {
+                total_path_entries: 2,
+                total_executables: 5,
+                unique_executables: 3,
+                total_conflicts: 2,
+                conflicts_by_category: HashMap::new(),
+                conflicts_by_severity: HashMap::new(),
+                risk_score: 0.0,
+                conflicts_by_manager: vec![],
+            },
+            diagnostics: vec![],
+            requirements: vec![],
+            root_causes: vec![],
+            path_issues: vec![],
+            security: vec![],
+            signature: None,
+        };
+
+        let xml = format_junit(&result, Severity::Medium);
+        assert!(
+            xml.contains("<testsuites name=\"path-conflict-detector\" tests=\"3\" failures=\"1\">")
+        );
+        assert!(xml.contains("<testcase name=\"ls\" classname=\"path-conflict-detector\"/>"));
+        assert!(xml.contains(
+            "<failure message=\"python3 &lt;shadowed&gt;\" type=\"HIGH Shadowed Binary\">"
+        ));
+        assert!(xml.contains(
+            "<system-out>cc &lt;shadowed&gt;\n/a/cc (active)\n/b/cc (shadowed)\n</system-out>"
+        ));
+
+        assert_eq!(
+            format_junit(&result, Severity::Low)
+                .matches("<failure")
+                .count(),
+            2
+        );
+    }
+}
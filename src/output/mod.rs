@@ -1,5 +1,15 @@
+pub mod changes;
+pub mod chat;
+pub mod csv;
 pub mod formatter;
+pub mod html;
 pub mod json_output;
+pub mod junit;
+pub mod sarif;
+pub mod sbom;
+#[cfg(feature = "signing")]
+pub mod signing;
+pub mod snapshots;
 pub mod types;
 
 pub use types::*;
@@ -0,0 +1,232 @@
+use crate::error::Result;
+use crate::output::types::{AnalysisResult, ConflictCategory, Severity};
+use serde_json::{json, Value};
+use std::path::Path;
+
+const TOOL_NAME: &str = env!("CARGO_PKG_NAME");
+const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// SARIF 2.1.0 log with one result per conflict, for code scanning
+/// uploads. The rule is the conflict category; each instance's PATH entry
+/// is a location, the active one first.
+pub fn format_sarif(result: &AnalysisResult) -> Result<String> {
+    let mut categories: Vec<ConflictCategory> = Vec::new();
+    for conflict in &result.conflicts {
+        if !categories.contains(&conflict.category) {
+            categories.push(conflict.category);
+        }
+    }
+
+    let rules: Vec<Value> = categories
+        .iter()
+        .map(|category| {
+            json!({
+                "id": rule_id(*category),
+                "name": category.to_string(),
+                "shortDescription": { "text": format!("PATH conflict: {}", category) },
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = result
+        .conflicts
+        .iter()
+        .map(|conflict| {
+            let locations: Vec<Value> = conflict
+                .instances
+                .iter()
+                .map(|instance| {
+                    let entry = instance.full_path.parent().unwrap_or(&instance.full_path);
+                    let role = if instance.full_path == conflict.active_instance.full_path {
+                        "active"
+                    } else {
+                        "shadowed"
+                    };
+                    json!({
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": file_uri(entry) },
+                        },
+                        "message": {
+                            "text": format!("{} ({})", instance.full_path.display(), role),
+                        },
+                    })
+                })
+                .collect();
+
+            let mut text = conflict.description.clone();
+            if let Some(recommendation) = &conflict.recommendation {
+                text.push_str("\n\n");
+                text.push_str(recommendation);
+            }
+
+            json!({
+                "ruleId": rule_id(conflict.category),
+                "ruleIndex": categories
+                    .iter()
+                    .position(|c| *c == conflict.category)
+                    .unwrap_or_default(),
+                "level": level(conflict.severity),
+                "message": { "text": text },
+                "locations": locations,
+                // Lets code scanning track a conflict across runs
+                "partialFingerprints": { "binaryName/v1": conflict.binary_name },
+                "properties": {
+                    "binaryName": conflict.binary_name,
+                    "severity": conflict.severity.to_string(),
+                    "riskScore": conflict.risk_score,
+                },
+            })
+        })
+        .collect();
+
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "version": TOOL_VERSION,
+                    "rules": rules,
+                },
+            },
+            "invocations": [{
+                "executionSuccessful": true,
+                "startTimeUtc": result.scan_time.to_rfc3339(),
+            }],
+            "results": results,
+        }],
+    });
+
+    Ok(serde_json::to_string_pretty(&log)?)
+}
+
+/// `ShadowedBinary`, matching the category's name in JSON output
+fn rule_id(category: ConflictCategory) -> String {
+    format!("{:?}", category)
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium | Severity::Low => "warning",
+        Severity::Info => "note",
+    }
+}
+
+/// `file://` URI for an absolute path, percent-encoding anything outside
+/// the unreserved set (Windows paths become `file:///C:/...`)
+fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from(if path.starts_with('/') {
+        "file://"
+    } else {
+        "file:///"
+    });
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::types::{Conflict, ExecutableInfo, PlatformInfo, Summary};
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn instance(path: &str) -> ExecutableInfo {
+        ExecutableInfo {
+            name: "python3".to_string(),
+            full_path: PathBuf::from(path),
+            size: 100,
+            resolved_path: PathBuf::from(path),
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    #[test]
+    fn test_format_sarif() {
+        let instances = vec![
+            instance("/opt/py/bin/python3"),
+            instance("/usr/bin/python3"),
+        ];
+        let result = AnalysisResult {
+            scan_time: Utc::now(),
+            platform: PlatformInfo {
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                is_wsl: false,
+                wsl_version: None,
+                wsl_distro: None,
+                wsl_interop: None,
+            },
+            path_entries: vec![],
+            conflicts: vec![Conflict {
+                binary_name: "python3".to_string(),
+                active_instance: instances[0].clone(),
+                instances,
+                category: ConflictCategory::DuplicateVersions,
+                severity: Severity::High,
+                severity_reason: String::new(),
+                description: "python3: 2 versions".to_string(),
+                recommendation: Some("Pick one".to_string()),
+                commands: Vec::new(),
+                same_content: false,
+                risk_score: 60.0,
+            }],
+            summary: Summary {
+                total_path_entries: 2,
+                total_executables: 2,
+                unique_executables: 1,
+                total_conflicts: 1,
+                conflicts_by_category: HashMap::new(),
+                conflicts_by_severity: HashMap::new(),
+                risk_score: 60.0,
+                conflicts_by_manager: vec![],
+            },
+            diagnostics: vec![],
+            requirements: vec![],
+            root_causes: vec![],
+            path_issues: vec![],
+            security: vec![],
+            signature: None,
+        };
+
+        let log: Value = serde_json::from_str(&format_sarif(&result).unwrap()).unwrap();
+        let run = &log["runs"][0];
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "DuplicateVersions");
+
+        let finding = &run["results"][0];
+        assert_eq!(finding["ruleId"], "DuplicateVersions");
+        assert_eq!(finding["level"], "error");
+        assert_eq!(
+            finding["message"]["text"],
+            "python3: 2 versions\n\nPick one"
+        );
+        assert_eq!(
+            finding["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "file:///opt/py/bin"
+        );
+        assert_eq!(
+            finding["locations"][1]["message"]["text"],
+            "/usr/bin/python3 (shadowed)"
+        );
+    }
+
+    #[test]
+    fn test_file_uri() {
+        assert_eq!(
+            file_uri(Path::new("C:\\Program Files\\Git\\cmd")),
+            "file:///C:/Program%20Files/Git/cmd"
+        );
+    }
+}
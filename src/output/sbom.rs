@@ -0,0 +1,298 @@
+use crate::core::binary_info::to_hex;
+use crate::error::Result;
+use crate::output::types::{AnalysisResult, ExecutableInfo, HashAlgorithm, HashScope, ManagerType};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+const TOOL_NAME: &str = env!("CARGO_PKG_NAME");
+const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// CycloneDX 1.5 JSON listing every executable on PATH
+pub fn format_cyclonedx(result: &AnalysisResult) -> Result<String> {
+    let components: Vec<Value> = executables(result)
+        .map(|exec| {
+            let path = exec.full_path.display().to_string();
+            let mut component = json!({
+                "type": "application",
+                "bom-ref": path,
+                "name": exec.name,
+                "properties": [{ "name": "path-conflict-detector:path", "value": path }],
+            });
+            if let Some(version) = version(exec) {
+                component["version"] = json!(version);
+            }
+            if let Some(supplier) = supplier(exec) {
+                component["supplier"] = json!({ "name": supplier });
+            }
+            if let Some((alg, digest)) = digest(exec, cyclonedx_algorithm) {
+                component["hashes"] = json!([{ "alg": alg, "content": digest }]);
+            }
+            if let Some(origin) = &exec.origin {
+                component["properties"]
+                    .as_array_mut()
+                    .expect("properties is an array")
+                    .push(json!({
+                        "name": "path-conflict-detector:package",
+                        "value": format!("{}:{}", origin.source, origin.package),
+                    }));
+            }
+            component
+        })
+        .collect();
+
+    let bom = json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "metadata": {
+            "timestamp": result.scan_time.to_rfc3339(),
+            "tools": {
+                "components": [{
+                    "type": "application",
+                    "name": TOOL_NAME,
+                    "version": TOOL_VERSION,
+                }],
+            },
+        },
+        "components": components,
+    });
+
+    Ok(serde_json::to_string_pretty(&bom)?)
+}
+
+/// SPDX 2.3 JSON with one package per executable on PATH
+pub fn format_spdx(result: &AnalysisResult) -> Result<String> {
+    let mut packages = Vec::new();
+    let mut relationships = Vec::new();
+
+    for (idx, exec) in executables(result).enumerate() {
+        let id = format!("SPDXRef-Executable-{}", idx + 1);
+        let mut package = json!({
+            "SPDXID": id,
+            "name": exec.name,
+            "packageFileName": exec.full_path.display().to_string(),
+            "versionInfo": version(exec).unwrap_or_else(|| "NOASSERTION".to_string()),
+            "supplier": supplier(exec)
+                .map(|name| format!("Organization: {}", name))
+                .unwrap_or_else(|| "NOASSERTION".to_string()),
+            "downloadLocation": "NOASSERTION",
+            "filesAnalyzed": false,
+            "primaryPackagePurpose": "APPLICATION",
+        });
+        if let Some((algorithm, digest)) = digest(exec, spdx_algorithm) {
+            package["checksums"] = json!([{ "algorithm": algorithm, "checksumValue": digest }]);
+        }
+
+        packages.push(package);
+        relationships.push(json!({
+            "spdxElementId": "SPDXRef-DOCUMENT",
+            "relationshipType": "DESCRIBES",
+            "relatedSpdxElement": id,
+        }));
+    }
+
+    let document = json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": "PATH executables",
+        "documentNamespace": document_namespace(result),
+        "creationInfo": {
+            "created": result.scan_time.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            "creators": [format!("Tool: {}-{}", TOOL_NAME, TOOL_VERSION)],
+        },
+        "packages": packages,
+        "relationships": relationships,
+    });
+
+    Ok(serde_json::to_string_pretty(&document)?)
+}
+
+/// Executables in PATH order, each file once even if its directory is listed
+/// twice
+fn executables(result: &AnalysisResult) -> impl Iterator<Item = &ExecutableInfo> {
+    let mut seen = HashSet::new();
+    result
+        .path_entries
+        .iter()
+        .flat_map(|entry| &entry.executables)
+        .filter(move |exec| seen.insert(&exec.full_path))
+}
+
+fn version(exec: &ExecutableInfo) -> Option<String> {
+    exec.version
+        .as_ref()
+        .and_then(|v| v.parsed.clone())
+        .or_else(|| exec.origin.as_ref().and_then(|o| o.version.clone()))
+}
+
+/// The manager that installed the file, or the package database that owns it
+fn supplier(exec: &ExecutableInfo) -> Option<&str> {
+    exec.manager
+        .as_ref()
+        .filter(|m| {
+            matches!(
+                m.manager_type,
+                ManagerType::VersionManager | ManagerType::PackageManager
+            )
+        })
+        .map(|m| m.name.as_str())
+        .or_else(|| exec.origin.as_ref().map(|o| o.source.as_str()))
+}
+
+/// Only full-file digests identify the file; prefix hashes are left out
+fn digest(
+    exec: &ExecutableInfo,
+    name: fn(HashAlgorithm) -> Option<&'static str>,
+) -> Option<(&'static str, &str)> {
+    if exec.hash_scope != Some(HashScope::Full) {
+        return None;
+    }
+    Some((name(exec.hash_algorithm?)?, exec.file_hash.as_deref()?))
+}
+
+fn cyclonedx_algorithm(algorithm: HashAlgorithm) -> Option<&'static str> {
+    match algorithm {
+        HashAlgorithm::Sha256 => Some("SHA-256"),
+        HashAlgorithm::Blake3 => Some("BLAKE3"),
+        HashAlgorithm::Xxh3 => None,
+    }
+}
+
+fn spdx_algorithm(algorithm: HashAlgorithm) -> Option<&'static str> {
+    match algorithm {
+        HashAlgorithm::Sha256 => Some("SHA256"),
+        HashAlgorithm::Blake3 => Some("BLAKE3"),
+        HashAlgorithm::Xxh3 => None,
+    }
+}
+
+/// SPDX requires a unique URI per document; derive it from the scan time
+/// and the PATH that was scanned
+fn document_namespace(result: &AnalysisResult) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(result.scan_time.to_rfc3339().as_bytes());
+    for entry in &result.path_entries {
+        hasher.update(entry.path.to_string_lossy().as_bytes());
+        hasher.update([0]);
+    }
+    let digest = to_hex(&hasher.finalize()[..16]);
+    format!("https://spdx.org/spdxdocs/{}-{}", TOOL_NAME, digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::types::{ManagerInfo, PathEntry, PlatformInfo, Summary, VersionInfo};
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn exec(dir: &str, name: &str) -> ExecutableInfo {
+        let path = PathBuf::from(dir).join(name);
+        ExecutableInfo {
+            name: name.to_string(),
+            full_path: path.clone(),
+            size: 1000,
+            resolved_path: path,
+            ..ExecutableInfo::fixture()
+        }
+    }
+
+    fn result() -> AnalysisResult {
+        let mut node = exec("/opt/homebrew/bin", "node");
+        node.version = Some(VersionInfo {
+            raw: "v20.11.0".to_string(),
+            parsed: Some("20.11.0".to_string()),
+            extraction_method: "--version".to_string(),
+        });
+        node.manager = Some(ManagerInfo {
+            manager_type: ManagerType::PackageManager,
+            name: "Homebrew".to_string(),
+            description: String::new(),
+        });
+        node.file_hash = Some("ab12".to_string());
+        node.hash_algorithm = Some(HashAlgorithm::Sha256);
+        node.hash_scope = Some(HashScope::Full);
+
+        let mut git = exec("/usr/bin", "git");
+        git.file_hash = Some("cd34".to_string());
+        git.hash_algorithm = Some(HashAlgorithm::Sha256);
+        git.hash_scope = Some(HashScope::Prefix8K);
+
+        let entry = |path: &str, executables: Vec<ExecutableInfo>| PathEntry {
+            path: PathBuf::from(path),
+            order: 0,
+            exists: true,
+            is_accessible: true,
+            executables,
+            sources: Vec::new(),
+            scope: None,
+        };
+
+        AnalysisResult {
+            scan_time: Utc::now(),
+            platform: PlatformInfo {
+                os: "macos".to_string(),
+                arch: "aarch64".to_string(),
+                is_wsl: false,
+                wsl_version: None,
+                wsl_distro: None,
+                wsl_interop: None,
+            },
+            path_entries: vec![
+                entry("/opt/homebrew/bin", vec![node.clone()]),
+                entry("/usr/bin", vec![git]),
+                entry("/opt/homebrew/bin", vec![node]),
+            ],
+            conflicts: vec![],
+            summary: Summary {
+                total_path_entries: 3,
+                total_executables: 3,
+                unique_executables: 2,
+                total_conflicts: 0,
+                conflicts_by_category: HashMap::new(),
+                conflicts_by_severity: HashMap::new(),
+                risk_score: 0.0,
+                conflicts_by_manager: vec![],
+            },
+            diagnostics: vec![],
+            requirements: vec![],
+            root_causes: vec![],
+            path_issues: vec![],
+            security: vec![],
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn test_cyclonedx() {
+        let bom: Value = serde_json::from_str(&format_cyclonedx(&result()).unwrap()).unwrap();
+        let components = bom["components"].as_array().unwrap();
+
+        assert_eq!(bom["bomFormat"], "CycloneDX");
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0]["version"], "20.11.0");
+        assert_eq!(components[0]["supplier"]["name"], "Homebrew");
+        assert_eq!(components[0]["hashes"][0]["alg"], "SHA-256");
+        // A prefix hash does not identify the file
+        assert!(components[1].get("hashes").is_none());
+    }
+
+    #[test]
+    fn test_spdx() {
+        let doc: Value = serde_json::from_str(&format_spdx(&result()).unwrap()).unwrap();
+        let packages = doc["packages"].as_array().unwrap();
+
+        assert_eq!(packages.len(), 2);
+        assert_eq!(doc["relationships"].as_array().unwrap().len(), 2);
+        assert_eq!(packages[0]["supplier"], "Organization: Homebrew");
+        assert_eq!(packages[0]["checksums"][0]["algorithm"], "SHA256");
+        assert_eq!(packages[1]["versionInfo"], "NOASSERTION");
+        assert!(doc["documentNamespace"]
+            .as_str()
+            .unwrap()
+            .starts_with("https://spdx.org/spdxdocs/"));
+    }
+}
@@ -0,0 +1,263 @@
+use crate::core::binary_info::to_hex;
+use crate::error::{Error, Result};
+use crate::output::types::{AnalysisResult, ReportSignature};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const ALGORITHM: &str = "ed25519";
+
+/// Create a key pair: the secret key at `path` and the public key next to
+/// it with a `.pub` suffix. Returns the public key path.
+pub fn generate_key(path: &Path) -> Result<PathBuf> {
+    let mut seed = [0u8; 32];
+    getrandom::getrandom(&mut seed).map_err(|e| signature_error(&e.to_string()))?;
+    let key = SigningKey::from_bytes(&seed);
+
+    let public_path = public_key_path(path);
+    write_secret(path, &key_file("secret", &key.to_bytes()))?;
+    fs::write(
+        &public_path,
+        key_file("public", key.verifying_key().as_bytes()),
+    )?;
+    Ok(public_path)
+}
+
+/// Sign `result` in place with the secret key at `key_path`
+pub fn sign(result: &mut AnalysisResult, key_path: &Path) -> Result<()> {
+    let key = SigningKey::from_bytes(&read_key(key_path)?);
+
+    result.signature = None;
+    let digest = digest(&serde_json::to_value(&*result)?);
+    result.signature = Some(ReportSignature {
+        algorithm: ALGORITHM.to_string(),
+        digest: format!("sha256:{}", to_hex(&digest)),
+        public_key: BASE64.encode(key.verifying_key().as_bytes()),
+        signature: BASE64.encode(key.sign(&digest).to_bytes()),
+    });
+    Ok(())
+}
+
+/// Check a signed JSON report. With `public_key_path`, the report must also
+/// have been signed by that key; otherwise only its integrity is checked.
+pub fn verify(report: &str, public_key_path: Option<&Path>) -> Result<ReportSignature> {
+    let mut value: Value = serde_json::from_str(report)?;
+    let signature: ReportSignature = match value.as_object_mut().and_then(|o| o.remove("signature"))
+    {
+        Some(signature) => serde_json::from_value(signature)?,
+        None => return Err(signature_error("report is not signed")),
+    };
+    if signature.algorithm != ALGORITHM {
+        return Err(signature_error(&format!(
+            "unsupported algorithm '{}'",
+            signature.algorithm
+        )));
+    }
+
+    let digest = digest(&value);
+    if signature.digest != format!("sha256:{}", to_hex(&digest)) {
+        return Err(signature_error(
+            "report contents do not match the signed digest",
+        ));
+    }
+
+    let embedded = decode_32(&signature.public_key, "public key")?;
+    if let Some(path) = public_key_path {
+        if read_key(path)? != embedded {
+            return Err(signature_error(&format!(
+                "report was signed by a different key than {}",
+                path.display()
+            )));
+        }
+    }
+
+    let key = VerifyingKey::from_bytes(&embedded).map_err(|e| signature_error(&e.to_string()))?;
+    let bytes: [u8; 64] = BASE64
+        .decode(&signature.signature)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| signature_error("malformed signature"))?;
+    key.verify(&digest, &Signature::from_bytes(&bytes))
+        .map_err(|_| signature_error("signature does not match the report"))?;
+
+    Ok(signature)
+}
+
+/// SHA-256 of the canonical JSON encoding
+fn digest(value: &Value) -> [u8; 32] {
+    let mut canonical = String::new();
+    write_canonical(value, &mut canonical);
+    Sha256::digest(canonical.as_bytes()).into()
+}
+
+/// JSON with object keys sorted and no insignificant whitespace, so the
+/// digest does not depend on how the report was pretty-printed
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (idx, key) in keys.iter().enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.to_string()).to_string());
+                out.push(':');
+                write_canonical(&map[key.as_str()], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (idx, item) in items.iter().enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+fn public_key_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".pub");
+    PathBuf::from(name)
+}
+
+fn key_file(kind: &str, bytes: &[u8; 32]) -> String {
+    format!(
+        "# path-conflict-detector {} key\n{}\n",
+        kind,
+        BASE64.encode(bytes)
+    )
+}
+
+/// Key bytes from a key file, skipping `#` comment lines
+fn read_key(path: &Path) -> Result<[u8; 32]> {
+    let contents = fs::read_to_string(path)?;
+    let encoded = contents
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .ok_or_else(|| signature_error(&format!("{} holds no key", path.display())))?;
+    decode_32(encoded, &path.display().to_string())
+}
+
+fn decode_32(encoded: &str, what: &str) -> Result<[u8; 32]> {
+    BASE64
+        .decode(encoded)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| signature_error(&format!("malformed key in {}", what)))
+}
+
+/// Write the secret key readable by the owner only, refusing to replace an
+/// existing key
+fn write_secret(path: &Path, contents: &str) -> Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(contents.as_bytes())?;
+    Ok(())
+}
+
+fn signature_error(reason: &str) -> Error {
+    Error::SignatureError {
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::json_output;
+    use crate::output::types::{PlatformInfo, Summary};
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    fn result() -> AnalysisResult {
+        AnalysisResult {
+            scan_time: Utc::now(),
+            platform: PlatformInfo {
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                is_wsl: false,
+                wsl_version: None,
+                wsl_distro: None,
+                wsl_interop: None,
+            },
+            path_entries: vec![],
+            conflicts: vec![],
+            summary: Summary {
+                total_path_entries: 3,
+                total_executables: 10,
+                unique_executables: 8,
+                total_conflicts: 0,
+                conflicts_by_category: HashMap::new(),
+                conflicts_by_severity: HashMap::new(),
+                risk_score: 12.5,
+                conflicts_by_manager: vec![],
+            },
+            diagnostics: vec![],
+            requirements: vec![],
+            root_causes: vec![],
+            path_issues: vec![],
+            security: vec![],
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = dir.path().join("report.key");
+        let public_key = generate_key(&key).unwrap();
+
+        let mut result = result();
+        sign(&mut result, &key).unwrap();
+
+        // Pretty-printing does not change the canonical form
+        let report = json_output::format_json(&result, true).unwrap();
+        assert!(verify(&report, Some(&public_key)).is_ok());
+        assert!(verify(&report, None).is_ok());
+
+        let tampered = report.replace("\"total_executables\": 10", "\"total_executables\": 11");
+        assert_ne!(tampered, report);
+        assert!(verify(&tampered, None).is_err());
+
+        let other = dir.path().join("other.key");
+        let other_public = generate_key(&other).unwrap();
+        assert!(verify(&report, Some(&other_public)).is_err());
+    }
+
+    #[test]
+    fn test_computed_scores_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = dir.path().join("report.key");
+        generate_key(&key).unwrap();
+
+        // Not exactly representable in decimal, so it only verifies if the
+        // report parses back to the same f64
+        let mut result = result();
+        result.summary.risk_score = 1.0 / 11.0;
+        sign(&mut result, &key).unwrap();
+        let report = json_output::format_json(&result, false).unwrap();
+        assert!(verify(&report, None).is_ok());
+    }
+
+    #[test]
+    fn test_unsigned_report() {
+        let report = json_output::format_json(&result(), false).unwrap();
+        assert!(verify(&report, None).is_err());
+    }
+}
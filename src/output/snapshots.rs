@@ -0,0 +1,217 @@
+use crate::error::Result;
+use crate::output::json_output;
+use crate::output::types::AnalysisResult;
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const FILE_PREFIX: &str = "path-conflicts-";
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%.3fZ";
+
+/// Which snapshots survive pruning. A zero disables that limit; the newest
+/// snapshot is always kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retention {
+    /// Keep at most this many snapshots
+    pub keep_last: usize,
+    /// Drop snapshots older than this many days
+    pub max_age_days: u64,
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Retention {
+            keep_last: 50,
+            max_age_days: 30,
+        }
+    }
+}
+
+/// Directory of timestamped JSON results, one per run
+/// (`path-conflicts-20240131T083000.000Z.json`)
+pub struct SnapshotStore {
+    dir: PathBuf,
+    retention: Retention,
+}
+
+impl SnapshotStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        SnapshotStore {
+            dir: dir.into(),
+            retention: Retention::default(),
+        }
+    }
+
+    pub fn with_retention(mut self, retention: Retention) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Write `result` as a new snapshot, then apply the retention policy
+    pub fn save(&self, result: &AnalysisResult) -> Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+
+        let path = self.dir.join(format!(
+            "{}{}.json",
+            FILE_PREFIX,
+            result.scan_time.format(TIMESTAMP_FORMAT)
+        ));
+        fs::write(&path, json_output::format_json(result, true)?)?;
+
+        self.prune(result.scan_time)?;
+        Ok(path)
+    }
+
+    /// Snapshots with their scan times, oldest first. A missing directory
+    /// has none.
+    pub fn list(&self) -> Result<Vec<(DateTime<Utc>, PathBuf)>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut snapshots: Vec<(DateTime<Utc>, PathBuf)> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let time = parse_timestamp(&entry.file_name().to_string_lossy())?;
+                Some((time, entry.path()))
+            })
+            .collect();
+        snapshots.sort();
+        Ok(snapshots)
+    }
+
+    /// The most recent snapshot, if any
+    pub fn latest(&self) -> Result<Option<AnalysisResult>> {
+        match self.list()?.pop() {
+            Some((_, path)) => Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Delete snapshots outside the retention policy; returns how many
+    fn prune(&self, now: DateTime<Utc>) -> Result<usize> {
+        let snapshots = self.list()?;
+        let count = snapshots.len();
+        let oldest_allowed = now - Duration::days(self.retention.max_age_days as i64);
+        let mut removed = 0;
+
+        for (idx, (time, path)) in snapshots.iter().enumerate() {
+            let newer_count = count - idx - 1;
+            let too_many = self.retention.keep_last > 0 && newer_count >= self.retention.keep_last;
+            let too_old = self.retention.max_age_days > 0 && *time < oldest_allowed;
+
+            if newer_count > 0 && (too_many || too_old) {
+                fs::remove_file(path)?;
+                removed += 1;
+            }
+        }
+
+        tracing::debug!(removed, dir = %self.dir.display(), "pruned snapshots");
+        Ok(removed)
+    }
+}
+
+fn parse_timestamp(file_name: &str) -> Option<DateTime<Utc>> {
+    let timestamp = file_name.strip_prefix(FILE_PREFIX)?.strip_suffix(".json")?;
+    let naive = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()?;
+    Some(Utc.from_utc_datetime(&naive))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::types::{PlatformInfo, Summary};
+    use std::collections::HashMap;
+
+    fn result_at(scan_time: DateTime<Utc>) -> AnalysisResult {
+        AnalysisResult {
+            scan_time,
+            platform: PlatformInfo {
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                is_wsl: false,
+                wsl_version: None,
+                wsl_distro: None,
+                wsl_interop: None,
+            },
+            path_entries: vec![],
+            conflicts: vec![],
+            summary: Summary {
+                total_path_entries: 0,
+                total_executables: 0,
+                unique_executables: 0,
+                total_conflicts: 0,
+                conflicts_by_category: HashMap::new(),
+                conflicts_by_severity: HashMap::new(),
+                risk_score: 0.0,
+                conflicts_by_manager: vec![],
+            },
+            diagnostics: vec![],
+            requirements: vec![],
+            root_causes: vec![],
+            path_issues: vec![],
+            security: vec![],
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_latest() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::new(dir.path().join("snapshots"));
+        assert!(store.latest().unwrap().is_none());
+
+        let now = Utc::now();
+        store.save(&result_at(now - Duration::hours(1))).unwrap();
+        store.save(&result_at(now)).unwrap();
+        fs::write(dir.path().join("snapshots").join("notes.txt"), "").unwrap();
+
+        assert_eq!(store.list().unwrap().len(), 2);
+        let latest = store.latest().unwrap().unwrap();
+        assert_eq!(latest.scan_time.timestamp_millis(), now.timestamp_millis());
+    }
+
+    #[test]
+    fn test_retention() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::new(dir.path()).with_retention(Retention {
+            keep_last: 3,
+            max_age_days: 30,
+        });
+
+        let now = Utc::now();
+        for days_ago in [60, 40, 5, 4, 3, 2] {
+            store
+                .save(&result_at(now - Duration::days(days_ago)))
+                .unwrap();
+        }
+        store.save(&result_at(now)).unwrap();
+
+        let kept: Vec<i64> = store
+            .list()
+            .unwrap()
+            .iter()
+            .map(|(time, _)| (now - *time).num_days())
+            .collect();
+        assert_eq!(kept, vec![3, 2, 0]);
+    }
+
+    #[test]
+    fn test_newest_snapshot_survives_age_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::new(dir.path()).with_retention(Retention {
+            keep_last: 0,
+            max_age_days: 1,
+        });
+
+        let long_ago = Utc::now() - Duration::days(90);
+        store.save(&result_at(long_ago)).unwrap();
+        assert_eq!(store.list().unwrap().len(), 1);
+    }
+}
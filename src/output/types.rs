@@ -10,6 +10,35 @@ pub struct AnalysisResult {
     pub path_entries: Vec<PathEntry>,
     pub conflicts: Vec<Conflict>,
     pub summary: Summary,
+    #[serde(default)]
+    pub diagnostics: Vec<Diagnostic>,
+    #[serde(default)]
+    pub requirements: Vec<RequirementCheck>,
+    /// PATH entries responsible for several conflicts at once
+    #[serde(default)]
+    pub root_causes: Vec<RootCause>,
+    /// PATH entries that contribute nothing: missing, unreadable or empty
+    #[serde(default)]
+    pub path_issues: Vec<PathIssue>,
+    /// Ways PATH lets someone else's programs run in place of the intended ones
+    #[serde(default)]
+    pub security: Vec<SecurityFinding>,
+    /// Present when the report was signed (`--sign-key`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<ReportSignature>,
+}
+
+/// ed25519 signature over the SHA-256 digest of the report's canonical JSON
+/// (keys sorted, no whitespace, this field removed)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReportSignature {
+    pub algorithm: String,
+    /// `sha256:<hex>`
+    pub digest: String,
+    /// Base64 public key of the signer
+    pub public_key: String,
+    /// Base64 signature of the raw digest bytes
+    pub signature: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +48,22 @@ pub struct PlatformInfo {
     pub is_wsl: bool,
     pub wsl_version: Option<String>,
     pub wsl_distro: Option<String>,
+    /// Interop settings from /etc/wsl.conf and WSLENV, under WSL only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wsl_interop: Option<WslInterop>,
+}
+
+/// How a WSL distribution shares programs and variables with Windows
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WslInterop {
+    /// `[interop] enabled`: whether Windows executables can be run at all
+    pub enabled: bool,
+    /// `[interop] appendWindowsPath`: whether the Windows PATH is added to
+    /// the end of the WSL one
+    pub append_windows_path: bool,
+    /// The `WSLENV` variable, when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wslenv: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +73,58 @@ pub struct PathEntry {
     pub exists: bool,
     pub is_accessible: bool,
     pub executables: Vec<ExecutableInfo>,
+    /// Shell startup lines that add this entry, in the order they run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<PathSource>,
+    /// Whether Windows took this entry from the user or the machine PATH
+    /// in the registry; `None` when it is in neither
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<PathScope>,
+}
+
+/// The registry PATH value a Windows PATH entry comes from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PathScope {
+    /// `HKCU\Environment`
+    User,
+    /// `HKLM\...\Session Manager\Environment`
+    Machine,
+}
+
+impl std::fmt::Display for PathScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathScope::User => write!(f, "user"),
+            PathScope::Machine => write!(f, "machine"),
+        }
+    }
+}
+
+/// A line of a shell startup file, e.g. `~/.zshrc:42`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PathSource {
+    pub file: PathBuf,
+    /// 1-based
+    pub line: usize,
+}
+
+impl std::fmt::Display for PathSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let home = std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(PathBuf::from);
+        match home.and_then(|home| self.file.strip_prefix(home).ok().map(PathBuf::from)) {
+            Some(relative) => write!(
+                f,
+                "~{}{}:{}",
+                std::path::MAIN_SEPARATOR,
+                relative.display(),
+                self.line
+            ),
+            None => write!(f, "{}:{}", self.file.display(), self.line),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -38,11 +135,88 @@ pub struct ExecutableInfo {
     pub modified: i64, // Unix timestamp for easier comparison
     pub is_symlink: bool,
     pub symlink_target: Option<PathBuf>,
+    /// Every hop followed while resolving a symlink, ending at the final target
+    #[serde(default)]
+    pub symlink_chain: Vec<PathBuf>,
     pub resolved_path: PathBuf,
     pub version: Option<VersionInfo>,
+    #[serde(default)]
+    pub version_probes: Vec<ProbeAttempt>,
     pub manager: Option<ManagerInfo>,
     pub file_hash: Option<String>,
+    pub hash_algorithm: Option<HashAlgorithm>,
+    pub hash_scope: Option<HashScope>,
+    pub ownership: Option<FileOwnership>,
+    /// Device id (volume serial number on Windows) of the target file
+    pub dev: Option<u64>,
+    /// Inode (file index on Windows) of the target file
+    pub inode: Option<u64>,
+    /// Format, architectures and interpreter read from the file header;
+    /// displays as e.g. "ELF x86_64" or "script (/bin/sh)"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary_format: Option<BinaryFormat>,
+    /// Package that installed this file, according to the OS package database
+    pub origin: Option<PackageOrigin>,
     pub path_order: usize, // Position in PATH (lower = higher priority)
+    /// Set on conflict instances: this is the copy that gets executed
+    #[serde(default)]
+    pub is_active: bool,
+    /// Set on conflict instances: index in `instances` of the copy that
+    /// shadows this one (always the active copy, the first that is not
+    /// `broken`)
+    #[serde(default)]
+    pub shadowed_by: Option<usize>,
+    /// Under a location the OS prevents even root from changing (SIP on
+    /// macOS), so the file cannot be removed
+    #[serde(default)]
+    pub immutable: bool,
+    /// A symlink whose target does not exist. Shells skip it while searching
+    /// PATH, so it never runs.
+    #[serde(default)]
+    pub broken: bool,
+}
+
+impl ExecutableInfo {
+    /// Both are one file on disk: they resolve to the same path, or they are
+    /// hard links sharing a device and inode (file ID on Windows)
+    pub fn is_same_file(&self, other: &ExecutableInfo) -> bool {
+        self.resolved_path == other.resolved_path
+            || (self.inode.is_some() && self.dev == other.dev && self.inode == other.inode)
+    }
+}
+
+#[cfg(test)]
+impl ExecutableInfo {
+    /// An executable with every field empty or unset, for tests to fill in
+    /// the ones they care about with struct-update syntax
+    pub(crate) fn fixture() -> Self {
+        ExecutableInfo {
+            name: String::new(),
+            full_path: PathBuf::new(),
+            size: 0,
+            modified: 0,
+            is_symlink: false,
+            symlink_target: None,
+            symlink_chain: Vec::new(),
+            resolved_path: PathBuf::new(),
+            version: None,
+            version_probes: Vec::new(),
+            manager: None,
+            file_hash: None,
+            hash_algorithm: None,
+            hash_scope: None,
+            ownership: None,
+            dev: None,
+            inode: None,
+            binary_format: None,
+            origin: None,
+            path_order: 0,
+            is_active: false,
+            shadowed_by: None,
+            immutable: false,
+            broken: false,
+        }
+    }
 }
 
 impl std::hash::Hash for ExecutableInfo {
@@ -52,6 +226,148 @@ impl std::hash::Hash for ExecutableInfo {
     }
 }
 
+/// Who controls a file and what they are allowed to do with it
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FileOwnership {
+    pub uid: Option<u32>,
+    pub user: Option<String>,
+    pub gid: Option<u32>,
+    pub group: Option<String>,
+    pub sid: Option<String>, // Owner SID on Windows
+    pub mode: Option<u32>,   // Unix permission bits, e.g. 0o755
+    pub readonly: bool,
+}
+
+impl FileOwnership {
+    /// Runs with its owner's privileges (Unix setuid bit)
+    pub fn setuid(&self) -> bool {
+        self.mode.is_some_and(|mode| mode & 0o4000 != 0)
+    }
+
+    /// Runs with its group's privileges (Unix setgid bit)
+    pub fn setgid(&self) -> bool {
+        self.mode.is_some_and(|mode| mode & 0o2000 != 0)
+    }
+}
+
+/// What an executable's header says it is
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BinaryFormat {
+    pub format: FileFormat,
+    /// Target CPU architectures; several for universal binaries, none for
+    /// scripts and DOS executables
+    #[serde(default)]
+    pub architectures: Vec<String>,
+    /// Rest of a script's `#!` line, e.g. "/usr/bin/env python3"
+    #[serde(default)]
+    pub interpreter: Option<String>,
+}
+
+impl BinaryFormat {
+    /// Run through an interpreter named on its `#!` line
+    pub fn is_script(&self) -> bool {
+        self.format == FileFormat::Script
+    }
+
+    /// Machine code loaded directly by the OS
+    pub fn is_native(&self) -> bool {
+        !self.is_script()
+    }
+}
+
+impl std::fmt::Display for BinaryFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.format, &self.interpreter) {
+            (FileFormat::Script, Some(interpreter)) => write!(f, "script ({})", interpreter),
+            (FileFormat::MachOUniversal, _) => {
+                write!(f, "{} ({})", self.format, self.architectures.join(", "))
+            }
+            _ if self.architectures.is_empty() => write!(f, "{}", self.format),
+            _ => write!(f, "{} {}", self.format, self.architectures.join(", ")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum FileFormat {
+    Elf,
+    #[serde(rename = "macho")]
+    MachO,
+    #[serde(rename = "macho-universal")]
+    MachOUniversal,
+    Pe,
+    Dos,
+    Script,
+}
+
+impl std::fmt::Display for FileFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileFormat::Elf => write!(f, "ELF"),
+            FileFormat::MachO => write!(f, "Mach-O"),
+            FileFormat::MachOUniversal => write!(f, "Mach-O universal"),
+            FileFormat::Pe => write!(f, "PE"),
+            FileFormat::Dos => write!(f, "DOS executable"),
+            FileFormat::Script => write!(f, "script"),
+        }
+    }
+}
+
+/// Digest used to produce `ExecutableInfo::file_hash`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Blake3,
+    Xxh3, // Fast non-cryptographic digest, only suitable for deduplication
+}
+
+impl std::fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HashAlgorithm::Sha256 => write!(f, "sha256"),
+            HashAlgorithm::Blake3 => write!(f, "blake3"),
+            HashAlgorithm::Xxh3 => write!(f, "xxh3"),
+        }
+    }
+}
+
+/// How much of the file was fed into the digest. Hashes produced with
+/// different scopes are not comparable.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum HashScope {
+    #[default]
+    #[serde(rename = "full")]
+    Full,
+    #[serde(rename = "8k")]
+    Prefix8K,
+    #[serde(rename = "1m")]
+    Prefix1M,
+}
+
+impl HashScope {
+    /// Maximum number of bytes read, or `None` for the whole file
+    pub fn byte_limit(&self) -> Option<u64> {
+        match self {
+            HashScope::Full => None,
+            HashScope::Prefix8K => Some(8 * 1024),
+            HashScope::Prefix1M => Some(1024 * 1024),
+        }
+    }
+}
+
+impl std::fmt::Display for HashScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HashScope::Full => write!(f, "full"),
+            HashScope::Prefix8K => write!(f, "8k"),
+            HashScope::Prefix1M => write!(f, "1m"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct VersionInfo {
     pub raw: String,
@@ -59,6 +375,19 @@ pub struct VersionInfo {
     pub extraction_method: String,
 }
 
+/// One attempt to run a binary for its version, kept to debug failed or
+/// odd version results
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProbeAttempt {
+    pub command: String,
+    pub exit_code: Option<i32>,
+    /// Why the command could not be started
+    pub error: Option<String>,
+    /// Truncated output samples
+    pub stdout: String,
+    pub stderr: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ManagerInfo {
     pub manager_type: ManagerType,
@@ -66,12 +395,23 @@ pub struct ManagerInfo {
     pub description: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PackageOrigin {
+    pub source: String, // dpkg, rpm, pacman, brew
+    pub package: String,
+    pub version: Option<String>,
+    /// Homebrew only: this keg is the version linked into the prefix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linked: Option<bool>,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum ManagerType {
-    VersionManager, // nvm, pyenv, rbenv, rustup
-    PackageManager, // brew, apt, chocolatey
-    SystemInstall,  // System-installed
-    ManualInstall,  // User-installed manually
+    VersionManager,    // nvm, pyenv, rbenv, rustup
+    PackageManager,    // brew, apt, chocolatey
+    SystemInstall,     // System-installed
+    EnvironmentModule, // Lmod / Environment Modules on HPC systems
+    ManualInstall,     // User-installed manually
     Unknown,
 }
 
@@ -82,8 +422,20 @@ pub struct Conflict {
     pub active_instance: ExecutableInfo,
     pub category: ConflictCategory,
     pub severity: Severity,
+    /// Why this severity was chosen, e.g. "major versions differ"
+    #[serde(default)]
+    pub severity_reason: String,
     pub description: String,
     pub recommendation: Option<String>,
+    /// Shell commands that apply the recommendation
+    #[serde(default)]
+    pub commands: Vec<String>,
+    /// Every instance has the same full-file digest
+    #[serde(default)]
+    pub same_content: bool,
+    /// 0-100; see `RiskScorer` for the weighting
+    #[serde(default)]
+    pub risk_score: f64,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -94,6 +446,12 @@ pub enum ConflictCategory {
     PackageManagerVsSystem,
     DuplicateVersions,
     ShadowedBinary,
+    /// A broken symlink comes before a working copy
+    BrokenSymlink,
+    /// On Windows, a script such as `foo.bat` runs instead of `foo.exe`
+    PathextShadowing,
+    /// The active pip installs for a different Python than the active python
+    PipMismatch,
     Other,
 }
 
@@ -106,11 +464,43 @@ impl std::fmt::Display for ConflictCategory {
             ConflictCategory::PackageManagerVsSystem => write!(f, "Package Manager vs System"),
             ConflictCategory::DuplicateVersions => write!(f, "Duplicate Versions"),
             ConflictCategory::ShadowedBinary => write!(f, "Shadowed Binary"),
+            ConflictCategory::BrokenSymlink => write!(f, "Broken Symlink"),
+            ConflictCategory::PathextShadowing => write!(f, "PATHEXT Shadowing"),
+            ConflictCategory::PipMismatch => write!(f, "pip/python Mismatch"),
             ConflictCategory::Other => write!(f, "Other"),
         }
     }
 }
 
+/// Lowercase with separators removed, so `shadowed-binary`, `Shadowed Binary`
+/// and `ShadowedBinary` all parse
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '-' | '_' | ' '))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+impl std::str::FromStr for ConflictCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match normalize_name(s).as_str() {
+            "wslvswindows" => Ok(ConflictCategory::WslVsWindows),
+            "versionmanagervssystem" => Ok(ConflictCategory::VersionManagerVsSystem),
+            "multipleversionmanagers" => Ok(ConflictCategory::MultipleVersionManagers),
+            "packagemanagervssystem" => Ok(ConflictCategory::PackageManagerVsSystem),
+            "duplicateversions" => Ok(ConflictCategory::DuplicateVersions),
+            "shadowedbinary" => Ok(ConflictCategory::ShadowedBinary),
+            "brokensymlink" => Ok(ConflictCategory::BrokenSymlink),
+            "pathextshadowing" => Ok(ConflictCategory::PathextShadowing),
+            "pipmismatch" => Ok(ConflictCategory::PipMismatch),
+            "other" => Ok(ConflictCategory::Other),
+            _ => Err(format!("unknown conflict category '{}'", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Severity {
     Info,
@@ -132,6 +522,206 @@ impl std::fmt::Display for Severity {
     }
 }
 
+impl std::str::FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match normalize_name(s).as_str() {
+            "info" => Ok(Severity::Info),
+            "low" => Ok(Severity::Low),
+            "medium" => Ok(Severity::Medium),
+            "high" => Ok(Severity::High),
+            "critical" => Ok(Severity::Critical),
+            _ => Err(format!("unknown severity '{}'", s)),
+        }
+    }
+}
+
+/// A finding about the PATH that is not a conflict between two binaries
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    pub severity: Severity,
+    pub message: String,
+    #[serde(default)]
+    pub paths: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum DiagnosticKind {
+    CircularSymlink,
+    /// Work skipped because `--max-scan-time` ran out
+    ScanTimeout,
+    /// WSLENV entry that leaks Windows paths or tools into WSL
+    WslEnv,
+    /// Directory listed in both the user and machine PATH on Windows
+    DuplicatePathEntry,
+    /// User PATH entry that runs instead of a machine PATH tool on Windows
+    UserShadowsMachine,
+    /// App Paths registration that opens a different file than PATH runs
+    AppPaths,
+    /// PYTHONPATH module hiding a standard library or installed module
+    PythonPath,
+    /// pip script that installs for a different python than the one beside it
+    PipInterpreter,
+    /// Shared library in LD_LIBRARY_PATH loaded instead of another copy
+    LibraryPath,
+    /// Man page from a shadowed install found before the active one's
+    ManPage,
+    /// WSL interop setting worth changing given the conflicts found
+    WslInterop,
+    /// Binary the shell and desktop-launched apps resolve differently
+    GuiPath,
+    /// Executable named after a cmd.exe internal command
+    CmdInternal,
+    /// Position of the WindowsApps directory and its App Execution Aliases
+    WindowsApps,
+    /// Formulae installed by both the Intel and Apple Silicon Homebrew
+    DualHomebrew,
+    /// Source build in /usr/local over the distribution's package
+    LocalBuild,
+    /// PATHEXT entry that breaks command lookup on Windows
+    Pathext,
+    /// What a directory added with --simulate-prepend/--simulate-append changes
+    SimulatedEntry,
+    /// Version-manager shim whose target was uninstalled
+    OrphanedShim,
+    /// Script whose #! interpreter is missing or depends on PATH order
+    ScriptInterpreter,
+    /// Finding reported by an analyzer plugin
+    Plugin,
+}
+
+impl std::fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiagnosticKind::CircularSymlink => write!(f, "Circular Symlink"),
+            DiagnosticKind::ScanTimeout => write!(f, "Scan Timeout"),
+            DiagnosticKind::WslEnv => write!(f, "WSLENV"),
+            DiagnosticKind::DuplicatePathEntry => write!(f, "Duplicate PATH Entry"),
+            DiagnosticKind::UserShadowsMachine => write!(f, "User PATH Shadowing"),
+            DiagnosticKind::AppPaths => write!(f, "App Paths"),
+            DiagnosticKind::PythonPath => write!(f, "PYTHONPATH"),
+            DiagnosticKind::PipInterpreter => write!(f, "pip Interpreter"),
+            DiagnosticKind::LibraryPath => write!(f, "Library Path"),
+            DiagnosticKind::ManPage => write!(f, "MANPATH"),
+            DiagnosticKind::WslInterop => write!(f, "WSL Interop"),
+            DiagnosticKind::GuiPath => write!(f, "GUI PATH"),
+            DiagnosticKind::CmdInternal => write!(f, "cmd Internal Command"),
+            DiagnosticKind::WindowsApps => write!(f, "WindowsApps Order"),
+            DiagnosticKind::DualHomebrew => write!(f, "Dual Homebrew"),
+            DiagnosticKind::LocalBuild => write!(f, "Local Build"),
+            DiagnosticKind::Pathext => write!(f, "PATHEXT"),
+            DiagnosticKind::SimulatedEntry => write!(f, "Simulated Entry"),
+            DiagnosticKind::OrphanedShim => write!(f, "Orphaned Shim"),
+            DiagnosticKind::ScriptInterpreter => write!(f, "Script Interpreter"),
+            DiagnosticKind::Plugin => write!(f, "Plugin"),
+        }
+    }
+}
+
+/// One PATH entry whose change fixes a cluster of conflicts
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RootCause {
+    /// The entry to change
+    pub directory: PathBuf,
+    /// The entry it conflicts with
+    pub other_directory: PathBuf,
+    pub action: RootCauseAction,
+    /// Conflicting binaries the change resolves
+    pub binaries: Vec<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum RootCauseAction {
+    /// Drop `directory` from PATH
+    Remove,
+    /// Place `directory` after `other_directory`
+    MoveAfter,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SecurityFinding {
+    pub kind: SecurityKind,
+    pub severity: Severity,
+    pub message: String,
+    /// PATH entries or files involved, as written
+    #[serde(default)]
+    pub paths: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum SecurityKind {
+    /// Empty PATH segment, which POSIX shells treat as the current directory
+    EmptyEntry,
+    /// `.` in PATH
+    CurrentDirectory,
+    /// Entry that resolves against the current directory
+    RelativeEntry,
+    /// Copy in a user-writable directory that runs instead of a system command
+    WritableShadow,
+    /// Setuid or setgid executable outside the system directories
+    SetuidBinary,
+}
+
+impl std::fmt::Display for SecurityKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecurityKind::EmptyEntry => write!(f, "Empty PATH Entry"),
+            SecurityKind::CurrentDirectory => write!(f, "Current Directory in PATH"),
+            SecurityKind::RelativeEntry => write!(f, "Relative PATH Entry"),
+            SecurityKind::WritableShadow => write!(f, "User-Writable Shadow"),
+            SecurityKind::SetuidBinary => write!(f, "Setuid Binary"),
+        }
+    }
+}
+
+/// A PATH entry that contributes no executables, and why
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PathIssue {
+    pub directory: PathBuf,
+    /// Position in PATH
+    pub order: usize,
+    pub kind: PathIssueKind,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum PathIssueKind {
+    Missing,
+    NotADirectory,
+    PermissionDenied,
+    Empty,
+}
+
+impl std::fmt::Display for PathIssueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathIssueKind::Missing => write!(f, "does not exist"),
+            PathIssueKind::NotADirectory => write!(f, "is not a directory"),
+            PathIssueKind::PermissionDenied => write!(f, "permission denied"),
+            PathIssueKind::Empty => write!(f, "empty directory"),
+        }
+    }
+}
+
+/// Outcome of checking a version requirement such as `python>=3.10`
+/// against the active instance of the binary
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RequirementCheck {
+    pub requirement: String,
+    pub binary_name: String,
+    pub satisfied: bool,
+    pub active_path: Option<PathBuf>,
+    pub active_version: Option<String>,
+    /// Shadowed instances that would satisfy the requirement
+    #[serde(default)]
+    pub satisfying_paths: Vec<PathBuf>,
+    pub message: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Summary {
     pub total_path_entries: usize,
@@ -140,6 +730,26 @@ pub struct Summary {
     pub total_conflicts: usize,
     pub conflicts_by_category: HashMap<ConflictCategory, usize>,
     pub conflicts_by_severity: HashMap<Severity, usize>,
+    /// Overall 0-100 PATH risk combining every conflict's `risk_score`
+    #[serde(default)]
+    pub risk_score: f64,
+    /// Conflict counts per set of managers involved, most frequent first
+    #[serde(default)]
+    pub conflicts_by_manager: Vec<ManagerConflictCount>,
+}
+
+/// How many conflicts involve exactly this set of managers
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ManagerConflictCount {
+    /// Sorted manager names; "unmanaged" stands for instances without one
+    pub managers: Vec<String>,
+    pub count: usize,
+}
+
+impl std::fmt::Display for ManagerConflictCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.managers.join(" vs "))
+    }
 }
 
 impl Summary {
@@ -151,6 +761,8 @@ impl Summary {
             total_conflicts: 0,
             conflicts_by_category: HashMap::new(),
             conflicts_by_severity: HashMap::new(),
+            risk_score: 0.0,
+            conflicts_by_manager: Vec::new(),
         }
     }
 }
@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 
 pub fn detect_homebrew_paths() -> Vec<PathBuf> {
@@ -30,6 +31,24 @@ pub fn detect_homebrew_paths() -> Vec<PathBuf> {
     paths
 }
 
+/// Homebrew's default prefix on Intel Macs
+pub const HOMEBREW_INTEL_PREFIX: &str = "/usr/local";
+/// Homebrew's default prefix on Apple Silicon
+pub const HOMEBREW_ARM_PREFIX: &str = "/opt/homebrew";
+
+/// Formulae installed under a Homebrew prefix, from the `Cellar` directory
+pub fn homebrew_formulae(prefix: &Path) -> BTreeSet<String> {
+    std::fs::read_dir(prefix.join("Cellar"))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 pub fn is_homebrew_path(path: &Path) -> bool {
     let path_str = path.to_string_lossy();
     path_str.starts_with("/opt/homebrew/")
@@ -37,10 +56,61 @@ pub fn is_homebrew_path(path: &Path) -> bool {
         || path_str.contains("/Homebrew/")
 }
 
-pub fn get_macos_bundle_version(_path: &Path) -> Option<String> {
-    // TODO: Implement parsing of Info.plist for .app bundles
-    // This would require plist parsing library
-    None
+/// Version of the innermost `.app` bundle containing `path` (after
+/// following symlinks), from `CFBundleShortVersionString` in its
+/// `Contents/Info.plist`, falling back to `CFBundleVersion`. Nothing is
+/// executed.
+pub fn get_macos_bundle_version(path: &Path) -> Option<String> {
+    let resolved = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let bundle = app_bundle_root(&resolved)?;
+    let info = plist::Value::from_file(bundle.join("Contents").join("Info.plist")).ok()?;
+    let info = info.as_dictionary()?;
+
+    ["CFBundleShortVersionString", "CFBundleVersion"]
+        .iter()
+        .filter_map(|key| info.get(key)?.as_string())
+        .map(str::trim)
+        .find(|version| !version.is_empty())
+        .map(str::to_string)
+}
+
+/// The nearest ancestor directory named `*.app`
+fn app_bundle_root(path: &Path) -> Option<&Path> {
+    path.ancestors().skip(1).find(|dir| {
+        dir.extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("app"))
+    })
+}
+
+/// Locations covered by System Integrity Protection. `/usr/local` is the
+/// exception carved out for user-installed software.
+pub fn is_sip_protected(path: &Path) -> bool {
+    if path.starts_with("/usr/local") {
+        return false;
+    }
+
+    ["/System", "/usr", "/bin", "/sbin"]
+        .iter()
+        .any(|root| path.starts_with(root))
+}
+
+/// PATH launchd gives apps when none was set with `launchctl setenv`
+pub const LAUNCHD_DEFAULT_PATH: &str = "/usr/bin:/bin:/usr/sbin:/sbin";
+
+/// PATH set in launchd with `launchctl setenv`, which apps started from the
+/// Dock, Finder and Spotlight inherit instead of the shell's. `launchctl` is
+/// run by its absolute path so a copy on the PATH being audited is never
+/// executed.
+pub fn get_launchd_path() -> Option<String> {
+    let mut command = std::process::Command::new("/bin/launchctl");
+    command
+        .args(["getenv", "PATH"])
+        .stdin(std::process::Stdio::null());
+    let output =
+        super::process::output_with_timeout(&mut command, std::time::Duration::from_secs(2))
+            .ok()?;
+    let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !path.is_empty()).then_some(path)
 }
 
 pub fn is_macos_system_path(path: &Path) -> bool {
@@ -66,6 +136,47 @@ mod tests {
         assert!(!is_homebrew_path(Path::new("/usr/bin/python")));
     }
 
+    #[test]
+    fn test_get_macos_bundle_version() {
+        let root = tempfile::tempdir().unwrap();
+        let app = root.path().join("Visual Studio Code.app");
+        let bin = app.join("Contents/Resources/app/bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::write(bin.join("code"), "#!/bin/sh\n").unwrap();
+        std::fs::write(
+            app.join("Contents/Info.plist"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>CFBundleShortVersionString</key>
+    <string>1.85.1</string>
+    <key>CFBundleVersion</key>
+    <string>1.85.1.24</string>
+</dict>
+</plist>
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_macos_bundle_version(&bin.join("code")).as_deref(),
+            Some("1.85.1")
+        );
+        assert_eq!(get_macos_bundle_version(&root.path().join("code")), None);
+    }
+
+    #[test]
+    fn test_is_sip_protected() {
+        assert!(is_sip_protected(Path::new("/usr/bin/python3")));
+        assert!(is_sip_protected(Path::new(
+            "/System/Cryptexes/App/usr/bin/safaridriver"
+        )));
+        assert!(!is_sip_protected(Path::new("/usr/local/bin/python3")));
+        assert!(!is_sip_protected(Path::new("/usr2/bin/tool")));
+        assert!(!is_sip_protected(Path::new("/opt/homebrew/bin/python3")));
+    }
+
     #[test]
     fn test_is_macos_system_path() {
         assert!(is_macos_system_path(Path::new("/usr/bin/python")));
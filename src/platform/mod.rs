@@ -1,10 +1,12 @@
 pub mod macos;
+pub mod process;
+pub mod shell;
 pub mod unix;
 pub mod windows;
 pub mod wsl;
 
 use crate::error::{Error, Result};
-use crate::output::types::PlatformInfo;
+use crate::output::types::{FileOwnership, PlatformInfo};
 use std::path::Path;
 
 pub fn detect_platform() -> Result<PlatformInfo> {
@@ -23,6 +25,7 @@ pub fn detect_platform() -> Result<PlatformInfo> {
         is_wsl,
         wsl_version,
         wsl_distro,
+        wsl_interop: is_wsl.then(wsl::detect_wsl_interop),
     })
 }
 
@@ -34,6 +37,26 @@ pub fn get_path_separator() -> char {
     }
 }
 
+/// Whether the default filesystem treats `Python.exe` and `python.exe` as
+/// one file (NTFS, APFS)
+pub fn is_case_insensitive() -> bool {
+    cfg!(any(windows, target_os = "macos"))
+}
+
+/// The PATH apps launched from the desktop start with, and where it was read
+/// from: launchd on macOS, the systemd user environment on Linux
+pub fn get_gui_path() -> Option<(&'static str, String)> {
+    if cfg!(target_os = "macos") {
+        let path =
+            macos::get_launchd_path().unwrap_or_else(|| macos::LAUNCHD_DEFAULT_PATH.to_string());
+        Some(("launchd", path))
+    } else if cfg!(target_os = "linux") {
+        unix::get_systemd_user_path().map(|path| ("systemd user environment", path))
+    } else {
+        None
+    }
+}
+
 pub fn get_path_env_var() -> Result<String> {
     std::env::var("PATH").map_err(|_| Error::PathNotFound)
 }
@@ -46,6 +69,86 @@ pub fn is_executable(path: &Path) -> bool {
     }
 }
 
+pub fn get_file_ownership(path: &Path) -> Option<FileOwnership> {
+    if cfg!(windows) {
+        windows::get_file_ownership_windows(path)
+    } else {
+        unix::get_file_ownership_unix(path)
+    }
+}
+
+/// Device and inode (volume serial and file index on Windows) of the file a
+/// path ultimately points to
+pub fn get_file_id(path: &Path) -> Option<(u64, u64)> {
+    if cfg!(windows) {
+        windows::get_file_id_windows(path)
+    } else {
+        unix::get_file_id_unix(path)
+    }
+}
+
+/// Who besides the administrator can add files to a directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DirWriters {
+    /// Only root, or Administrators on Windows
+    System,
+    /// The regular user who owns it
+    Owner,
+    /// Everyone, or a group other than root's
+    Others,
+}
+
+/// Who can write to `dir`, from its owner and mode bits. On Windows,
+/// directories outside Windows, Program Files and ProgramData are assumed
+/// to belong to a user.
+pub fn dir_writers(dir: &Path) -> DirWriters {
+    if cfg!(windows) {
+        return if windows::is_windows_system_path(dir) {
+            DirWriters::System
+        } else {
+            DirWriters::Owner
+        };
+    }
+
+    match get_file_ownership(dir) {
+        Some(FileOwnership {
+            mode: Some(mode),
+            uid,
+            gid,
+            ..
+        }) => {
+            if mode & 0o002 != 0 || (mode & 0o020 != 0 && gid != Some(0)) {
+                DirWriters::Others
+            } else if uid != Some(0) {
+                DirWriters::Owner
+            } else {
+                DirWriters::System
+            }
+        }
+        _ => DirWriters::System,
+    }
+}
+
+/// Directories holding the OS's own commands: /bin, /usr/bin and their
+/// sbin siblings, or the Windows directory and System32
+pub fn is_system_bin_dir(dir: &Path) -> bool {
+    if cfg!(windows) {
+        let dir = dir.to_string_lossy().to_lowercase();
+        let dir = dir.trim_end_matches('\\');
+        dir.ends_with("\\windows\\system32") || dir.ends_with("\\windows")
+    } else {
+        ["/bin", "/sbin", "/usr/bin", "/usr/sbin"]
+            .iter()
+            .any(|system| dir == Path::new(system))
+    }
+}
+
+/// Whether the OS forbids modifying files at `path` (System Integrity
+/// Protection on macOS)
+pub fn is_immutable(path: &Path) -> bool {
+    cfg!(target_os = "macos") && macos::is_sip_protected(path)
+}
+
 pub fn expand_env_vars(path: &str) -> String {
     let mut result = path.to_string();
 
@@ -0,0 +1,105 @@
+use std::io::{self, Read};
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How often a running child is polled
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Run `command` to completion like `Command::output`, but kill it once
+/// `timeout` passes and fail with `io::ErrorKind::TimedOut`. On Unix the
+/// child gets its own process group, so helpers it started are killed too.
+/// Output is read on background threads, so a grandchild that keeps the
+/// pipes open cannot block the caller past the timeout either.
+pub fn output_with_timeout(command: &mut Command, timeout: Duration) -> io::Result<Output> {
+    command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    let deadline = Instant::now() + timeout;
+    let mut child = command.spawn()?;
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            kill(&mut child);
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("timed out after {}", format_duration(timeout)),
+            ));
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    };
+
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    Ok(Output {
+        status,
+        stdout: stdout.recv_timeout(remaining).unwrap_or_default(),
+        stderr: stderr.recv_timeout(remaining).unwrap_or_default(),
+    })
+}
+
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> mpsc::Receiver<Vec<u8>> {
+    let (sender, receiver) = mpsc::channel();
+    if let Some(mut pipe) = pipe {
+        std::thread::spawn(move || {
+            let mut buffer = Vec::new();
+            let _ = pipe.read_to_end(&mut buffer);
+            let _ = sender.send(buffer);
+        });
+    }
+    receiver
+}
+
+fn kill(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        use nix::sys::signal::{killpg, Signal};
+        use nix::unistd::Pid;
+        let _ = killpg(Pid::from_raw(child.id() as i32), Signal::SIGKILL);
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// `5s`, or `500ms` below a second
+fn format_duration(duration: Duration) -> String {
+    if duration.as_millis() < 1000 || duration.subsec_millis() != 0 {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{}s", duration.as_secs())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_with_timeout() {
+        let output = output_with_timeout(
+            Command::new("sh").args(["-c", "echo 1.2.3"]),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        assert_eq!(output.stdout, b"1.2.3\n");
+
+        // The sleeping grandchild holds stdout open but is killed with the group
+        let started = Instant::now();
+        let error = output_with_timeout(
+            Command::new("sh").args(["-c", "sleep 30 & sleep 30"]),
+            Duration::from_millis(200),
+        )
+        .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        assert_eq!(error.to_string(), "timed out after 200ms");
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+/// The user's interactive shell, used to phrase copy-pasteable commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShellKind {
+    Bash,
+    Zsh,
+    Fish,
+    Sh,
+    PowerShell,
+    Cmd,
+}
+
+impl ShellKind {
+    /// Startup file where PATH changes persist, relative to the home directory
+    pub fn rc_file(&self) -> Option<&'static str> {
+        match self {
+            ShellKind::Bash => Some("~/.bashrc"),
+            ShellKind::Zsh => Some("~/.zshrc"),
+            ShellKind::Sh => Some("~/.profile"),
+            ShellKind::Fish | ShellKind::PowerShell | ShellKind::Cmd => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ShellKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShellKind::Bash => write!(f, "bash"),
+            ShellKind::Zsh => write!(f, "zsh"),
+            ShellKind::Fish => write!(f, "fish"),
+            ShellKind::Sh => write!(f, "sh"),
+            ShellKind::PowerShell => write!(f, "PowerShell"),
+            ShellKind::Cmd => write!(f, "cmd"),
+        }
+    }
+}
+
+/// Detect the user's shell from `$SHELL`, falling back to the platform
+/// default (PowerShell on Windows, sh elsewhere)
+pub fn detect_shell() -> ShellKind {
+    std::env::var("SHELL")
+        .ok()
+        .and_then(|shell| shell_from_path(&shell))
+        .unwrap_or(if cfg!(windows) {
+            ShellKind::PowerShell
+        } else {
+            ShellKind::Sh
+        })
+}
+
+fn shell_from_path(shell: &str) -> Option<ShellKind> {
+    // Split on both separators so Windows paths parse on any host
+    let file_name = shell.trim().rsplit(['/', '\\']).next()?.to_lowercase();
+    let name = file_name.strip_suffix(".exe").unwrap_or(&file_name);
+
+    match name {
+        "bash" => Some(ShellKind::Bash),
+        "zsh" => Some(ShellKind::Zsh),
+        "fish" => Some(ShellKind::Fish),
+        "sh" | "dash" | "ksh" => Some(ShellKind::Sh),
+        "pwsh" | "powershell" => Some(ShellKind::PowerShell),
+        "cmd" => Some(ShellKind::Cmd),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_from_path() {
+        assert_eq!(shell_from_path("/bin/bash"), Some(ShellKind::Bash));
+        assert_eq!(shell_from_path("/usr/local/bin/zsh"), Some(ShellKind::Zsh));
+        assert_eq!(
+            shell_from_path("/opt/homebrew/bin/fish"),
+            Some(ShellKind::Fish)
+        );
+        assert_eq!(
+            shell_from_path("C:\\Program Files\\PowerShell\\7\\pwsh.exe"),
+            Some(ShellKind::PowerShell)
+        );
+        assert_eq!(shell_from_path("/bin/tcsh"), None);
+    }
+}
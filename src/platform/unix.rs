@@ -1,4 +1,7 @@
-use std::path::Path;
+use crate::output::types::FileOwnership;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
 
 pub fn is_executable_unix(path: &Path) -> bool {
     #[cfg(unix)]
@@ -19,6 +22,86 @@ pub fn is_executable_unix(path: &Path) -> bool {
     }
 }
 
+pub fn get_file_ownership_unix(path: &Path) -> Option<FileOwnership> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let metadata = path.metadata().ok()?;
+        Some(FileOwnership {
+            uid: Some(metadata.uid()),
+            user: user_name(metadata.uid()),
+            gid: Some(metadata.gid()),
+            group: group_name(metadata.gid()),
+            sid: None,
+            mode: Some(metadata.mode() & 0o7777),
+            readonly: metadata.permissions().readonly(),
+        })
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+pub fn get_file_id_unix(path: &Path) -> Option<(u64, u64)> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let metadata = path.metadata().ok()?;
+        Some((metadata.dev(), metadata.ino()))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+// Name lookups hit the user database, so cache them per id: a PATH
+// typically has thousands of files owned by a handful of accounts
+#[cfg(unix)]
+static USER_NAMES: once_cell::sync::Lazy<
+    std::sync::Mutex<std::collections::HashMap<u32, Option<String>>>,
+> = once_cell::sync::Lazy::new(Default::default);
+
+#[cfg(unix)]
+static GROUP_NAMES: once_cell::sync::Lazy<
+    std::sync::Mutex<std::collections::HashMap<u32, Option<String>>>,
+> = once_cell::sync::Lazy::new(Default::default);
+
+#[cfg(unix)]
+fn user_name(uid: u32) -> Option<String> {
+    use nix::unistd::{Uid, User};
+    let mut cache = USER_NAMES.lock().ok()?;
+    cache
+        .entry(uid)
+        .or_insert_with(|| {
+            User::from_uid(Uid::from_raw(uid))
+                .ok()
+                .flatten()
+                .map(|u| u.name)
+        })
+        .clone()
+}
+
+#[cfg(unix)]
+fn group_name(gid: u32) -> Option<String> {
+    use nix::unistd::{Gid, Group};
+    let mut cache = GROUP_NAMES.lock().ok()?;
+    cache
+        .entry(gid)
+        .or_insert_with(|| {
+            Group::from_gid(Gid::from_raw(gid))
+                .ok()
+                .flatten()
+                .map(|g| g.name)
+        })
+        .clone()
+}
+
 pub fn expand_unix_env_vars(path: &str) -> String {
     let mut result = path.to_string();
 
@@ -84,6 +167,137 @@ pub fn is_system_path(path: &Path) -> bool {
         || path_str.starts_with("/sbin")
 }
 
+/// PATH in the systemd user manager's environment, which is what desktop
+/// sessions and the apps they launch start from. `systemctl` is run by its
+/// absolute path so a copy on the PATH being audited is never executed.
+pub fn get_systemd_user_path() -> Option<String> {
+    let mut command = Command::new("/usr/bin/systemctl");
+    command
+        .args(["--user", "show-environment"])
+        .stdin(Stdio::null());
+    let output = super::process::output_with_timeout(&mut command, Duration::from_secs(2)).ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_environment_path(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// The `PATH=` line of `systemctl --user show-environment` output
+fn parse_environment_path(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("PATH="))
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+}
+
+/// Directories the dynamic linker searches after LD_LIBRARY_PATH (or
+/// DYLD_LIBRARY_PATH on macOS) when a library is not in its cache, including
+/// the Debian multiarch ones that exist
+pub fn system_library_dirs() -> Vec<PathBuf> {
+    if cfg!(target_os = "macos") {
+        return vec![PathBuf::from("/usr/local/lib"), PathBuf::from("/usr/lib")];
+    }
+
+    let mut dirs = Vec::new();
+    for root in ["/lib", "/usr/lib"] {
+        for triple in [
+            "x86_64-linux-gnu",
+            "aarch64-linux-gnu",
+            "arm-linux-gnueabihf",
+        ] {
+            let dir = Path::new(root).join(triple);
+            if dir.is_dir() {
+                dirs.push(dir);
+            }
+        }
+    }
+    dirs.extend(
+        ["/lib64", "/usr/lib64", "/lib", "/usr/lib", "/usr/local/lib"]
+            .iter()
+            .map(PathBuf::from),
+    );
+    dirs
+}
+
+/// An application menu entry and the command its `Exec` line runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesktopLauncher {
+    pub file: PathBuf,
+    pub command: String,
+}
+
+/// `.desktop` files in the XDG application directories whose `Exec` runs a
+/// bare command name, which is looked up in the GUI session's PATH. A user
+/// file hides a system file of the same name.
+pub fn desktop_launchers() -> Vec<DesktopLauncher> {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")));
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+
+    let mut seen = std::collections::HashSet::new();
+    let mut launchers = Vec::new();
+    let dirs = data_home
+        .into_iter()
+        .chain(data_dirs.split(':').map(PathBuf::from));
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir.join("applications")) else {
+            continue;
+        };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "desktop"))
+            .collect();
+        files.sort();
+        for file in files {
+            if !seen.insert(file.file_name().map(|name| name.to_os_string())) {
+                continue;
+            }
+            let Ok(contents) = std::fs::read_to_string(&file) else {
+                continue;
+            };
+            if let Some(command) = parse_desktop_exec(&contents) {
+                launchers.push(DesktopLauncher { file, command });
+            }
+        }
+    }
+
+    launchers
+}
+
+/// The command run by the `Exec` key of the `[Desktop Entry]` group, when it
+/// is a bare name rather than a path. `env VAR=value` prefixes are skipped.
+fn parse_desktop_exec(contents: &str) -> Option<String> {
+    let mut in_entry = false;
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+            continue;
+        }
+        let Some(exec) = line.strip_prefix("Exec").map(str::trim_start) else {
+            continue;
+        };
+        let Some(exec) = exec.strip_prefix('=') else {
+            continue;
+        };
+        if !in_entry {
+            continue;
+        }
+
+        let command = exec
+            .split_whitespace()
+            .map(|word| word.trim_matches('"'))
+            .find(|word| *word != "env" && !word.contains('='))?;
+        return (!command.is_empty() && !command.contains('/')).then(|| command.to_string());
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -96,10 +310,66 @@ mod tests {
         std::env::remove_var("TEST_VAR");
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_get_file_ownership_unix() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tool");
+        std::fs::write(&path, b"").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o750)).unwrap();
+
+        let ownership = get_file_ownership_unix(&path).unwrap();
+        assert_eq!(ownership.mode, Some(0o750));
+        assert_eq!(ownership.uid, Some(nix::unistd::getuid().as_raw()));
+        assert!(ownership.sid.is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_get_file_id_unix_matches_hard_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = dir.path().join("busybox");
+        let link = dir.path().join("ls");
+        let copy = dir.path().join("cp");
+        std::fs::write(&original, b"binary").unwrap();
+        std::fs::hard_link(&original, &link).unwrap();
+        std::fs::copy(&original, &copy).unwrap();
+
+        assert_eq!(get_file_id_unix(&original), get_file_id_unix(&link));
+        assert_ne!(get_file_id_unix(&original), get_file_id_unix(&copy));
+    }
+
     #[test]
     fn test_is_system_path() {
         assert!(is_system_path(Path::new("/usr/bin/python")));
         assert!(is_system_path(Path::new("/usr/local/bin/node")));
         assert!(!is_system_path(Path::new("/home/user/.nvm/bin/node")));
     }
+
+    #[test]
+    fn test_parse_environment_path() {
+        let output = "HOME=/home/dev\nLANG=C.UTF-8\nPATH=/usr/local/bin:/usr/bin:/bin\n";
+        assert_eq!(
+            parse_environment_path(output).as_deref(),
+            Some("/usr/local/bin:/usr/bin:/bin")
+        );
+        assert_eq!(parse_environment_path("HOME=/home/dev\n"), None);
+    }
+
+    #[test]
+    fn test_parse_desktop_exec() {
+        let desktop = "[Desktop Entry]\nName=Code\nExec=env GDK_BACKEND=x11 code --new-window %F\n\
+                       [Desktop Action new-empty-window]\nExec=other\n";
+        assert_eq!(parse_desktop_exec(desktop).as_deref(), Some("code"));
+        assert_eq!(
+            parse_desktop_exec("[Desktop Entry]\nExec=/opt/app/bin/app %U\n"),
+            None
+        );
+        assert_eq!(
+            parse_desktop_exec("[Desktop Action x]\nExec=other\n[Desktop Entry]\nExec=\"gimp\"\n")
+                .as_deref(),
+            Some("gimp")
+        );
+    }
 }
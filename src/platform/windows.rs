@@ -1,17 +1,152 @@
+use crate::output::types::{FileOwnership, PathScope};
 use std::path::Path;
+use std::sync::OnceLock;
+
+/// Windows' stock PATHEXT, used when the variable is unset
+pub const DEFAULT_PATHEXT: &str = ".COM;.EXE;.BAT;.CMD;.VBS;.VBE;.JS;.JSE;.WSF;.WSH;.MSC";
 
 pub fn is_executable_windows(path: &Path) -> bool {
     if !path.is_file() {
         return false;
     }
 
-    // On Windows, check for executable extensions
-    if let Some(ext) = path.extension() {
-        let ext_lower = ext.to_string_lossy().to_lowercase();
-        matches!(ext_lower.as_str(), "exe" | "bat" | "cmd" | "ps1" | "com")
-    } else {
-        false
-    }
+    path.extension()
+        .map(|ext| is_executable_extension(&ext.to_string_lossy()))
+        .unwrap_or(false)
+}
+
+/// Whether files with this extension (no dot, any case) run by name
+pub fn is_executable_extension(ext: &str) -> bool {
+    let ext = ext.to_lowercase();
+    executable_extensions().contains(&ext)
+}
+
+/// Where a file's extension comes in PATHEXT: cmd tries a directory's
+/// candidates in that order, so `foo.com` runs before `foo.exe`. Files
+/// with other extensions rank last.
+pub fn pathext_rank(path: &Path) -> usize {
+    let extensions = executable_extensions();
+    path.extension()
+        .and_then(|ext| {
+            let ext = ext.to_string_lossy().to_lowercase();
+            extensions.iter().position(|e| *e == ext)
+        })
+        .unwrap_or(extensions.len())
+}
+
+/// Extensions that run without being typed: PATHEXT, plus `ps1`, which
+/// PowerShell resolves from PATH regardless
+fn executable_extensions() -> &'static [String] {
+    static EXTENSIONS: OnceLock<Vec<String>> = OnceLock::new();
+    EXTENSIONS.get_or_init(|| {
+        let pathext = std::env::var("PATHEXT").unwrap_or_else(|_| DEFAULT_PATHEXT.to_string());
+        let mut extensions = parse_pathext(&pathext);
+        if !extensions.iter().any(|e| e == "ps1") {
+            extensions.push("ps1".to_string());
+        }
+        extensions
+    })
+}
+
+/// Extensions listed in a PATHEXT value, lowercased without the dot.
+/// Entries missing the dot never match anything, so they are dropped.
+pub fn parse_pathext(value: &str) -> Vec<String> {
+    value
+        .split(';')
+        .map(str::trim)
+        .filter_map(|entry| entry.strip_prefix('.'))
+        .filter(|ext| !ext.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(windows)]
+pub fn get_file_ownership_windows(path: &Path) -> Option<FileOwnership> {
+    use widestring::{U16CStr, U16CString};
+    use winapi::shared::sddl::ConvertSidToStringSidW;
+    use winapi::um::accctrl::SE_FILE_OBJECT;
+    use winapi::um::aclapi::GetNamedSecurityInfoW;
+    use winapi::um::winbase::LocalFree;
+    use winapi::um::winnt::{OWNER_SECURITY_INFORMATION, PSECURITY_DESCRIPTOR, PSID};
+
+    let readonly = path.metadata().ok()?.permissions().readonly();
+    let wide_path = U16CString::from_os_str(path.as_os_str()).ok()?;
+    let mut owner: PSID = std::ptr::null_mut();
+    let mut descriptor: PSECURITY_DESCRIPTOR = std::ptr::null_mut();
+
+    // SAFETY: all out-pointers are valid; `owner` points into `descriptor`,
+    // which is released with LocalFree once the SID has been converted
+    let sid = unsafe {
+        let status = GetNamedSecurityInfoW(
+            wide_path.as_ptr(),
+            SE_FILE_OBJECT,
+            OWNER_SECURITY_INFORMATION,
+            &mut owner,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            &mut descriptor,
+        );
+
+        if status != 0 {
+            None
+        } else {
+            let mut sid_string = std::ptr::null_mut();
+            let sid = if ConvertSidToStringSidW(owner, &mut sid_string) != 0 {
+                let sid = U16CStr::from_ptr_str(sid_string).to_string_lossy();
+                LocalFree(sid_string as _);
+                Some(sid)
+            } else {
+                None
+            };
+            LocalFree(descriptor as _);
+            sid
+        }
+    };
+
+    Some(FileOwnership {
+        sid,
+        readonly,
+        ..FileOwnership::default()
+    })
+}
+
+#[cfg(not(windows))]
+pub fn get_file_ownership_windows(_path: &Path) -> Option<FileOwnership> {
+    None
+}
+
+#[cfg(windows)]
+pub fn get_file_id_windows(path: &Path) -> Option<(u64, u64)> {
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::os::windows::io::AsRawHandle;
+    use winapi::um::fileapi::{GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION};
+    use winapi::um::winbase::FILE_FLAG_BACKUP_SEMANTICS;
+
+    // Opening with no access rights is enough to query the file index
+    let file = std::fs::OpenOptions::new()
+        .access_mode(0)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+        .open(path)
+        .ok()?;
+
+    // SAFETY: the handle is valid for the lifetime of `file` and `info` is a
+    // plain-old-data out parameter
+    let info = unsafe {
+        let mut info: BY_HANDLE_FILE_INFORMATION = std::mem::zeroed();
+        if GetFileInformationByHandle(file.as_raw_handle() as _, &mut info) == 0 {
+            return None;
+        }
+        info
+    };
+
+    let file_index = ((info.nFileIndexHigh as u64) << 32) | info.nFileIndexLow as u64;
+    Some((info.dwVolumeSerialNumber as u64, file_index))
+}
+
+#[cfg(not(windows))]
+pub fn get_file_id_windows(_path: &Path) -> Option<(u64, u64)> {
+    None
 }
 
 pub fn expand_windows_env_vars(path: &str) -> String {
@@ -47,6 +182,32 @@ pub fn expand_windows_env_vars(path: &str) -> String {
     result
 }
 
+/// Commands built into cmd.exe. cmd runs these before searching PATH, so an
+/// executable with the same name only runs when invoked with its extension
+/// or full path.
+pub const CMD_INTERNAL_COMMANDS: &[&str] = &[
+    "assoc", "break", "call", "cd", "chdir", "cls", "color", "copy", "date", "del", "dir", "echo",
+    "endlocal", "erase", "exit", "for", "ftype", "goto", "if", "md", "mkdir", "mklink", "move",
+    "path", "pause", "popd", "prompt", "pushd", "rd", "rem", "ren", "rename", "rmdir", "set",
+    "setlocal", "shift", "start", "time", "title", "type", "ver", "verify", "vol",
+];
+
+/// Whether cmd.exe would run its internal command instead of this file
+pub fn is_cmd_internal(file_name: &str) -> bool {
+    let path = Path::new(file_name);
+    // cmd runs PATHEXT extensions, but not PowerShell scripts
+    let runnable = path.extension().is_some_and(|ext| {
+        let ext = ext.to_string_lossy();
+        !ext.eq_ignore_ascii_case("ps1") && is_executable_extension(&ext)
+    });
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    runnable && CMD_INTERNAL_COMMANDS.contains(&stem.as_str())
+}
+
 pub fn is_windows_system_path(path: &Path) -> bool {
     let path_str = path.to_string_lossy().to_lowercase();
     path_str.contains("windows\\system32")
@@ -55,6 +216,181 @@ pub fn is_windows_system_path(path: &Path) -> bool {
         || path_str.contains("programdata")
 }
 
+/// Unexpanded PATH values stored in the registry. Windows builds the process
+/// PATH from the machine value followed by the user value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegistryPath {
+    /// `HKCU\Environment`
+    pub user: Option<String>,
+    /// `HKLM\SYSTEM\CurrentControlSet\Control\Session Manager\Environment`
+    pub machine: Option<String>,
+}
+
+#[cfg(windows)]
+pub fn get_registry_path_windows() -> RegistryPath {
+    use winapi::um::winreg::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+
+    RegistryPath {
+        user: read_registry_string(HKEY_CURRENT_USER, "Environment", "Path"),
+        machine: read_registry_string(
+            HKEY_LOCAL_MACHINE,
+            "SYSTEM\\CurrentControlSet\\Control\\Session Manager\\Environment",
+            "Path",
+        ),
+    }
+}
+
+#[cfg(not(windows))]
+pub fn get_registry_path_windows() -> RegistryPath {
+    RegistryPath::default()
+}
+
+/// A command registered under `App Paths`. `Start-Process`, the Run dialog
+/// and other `ShellExecute` callers look there before searching PATH.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppPath {
+    /// The subkey name, such as `python.exe`
+    pub name: String,
+    /// The key's default value, unexpanded and unquoted
+    pub target: String,
+    pub scope: PathScope,
+}
+
+#[cfg(windows)]
+const APP_PATHS_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\App Paths";
+
+/// `App Paths` entries, the user's (which take precedence) first
+#[cfg(windows)]
+pub fn get_app_paths_windows() -> Vec<AppPath> {
+    use winapi::um::winreg::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+
+    let mut app_paths = Vec::new();
+    for (root, scope) in [
+        (HKEY_CURRENT_USER, PathScope::User),
+        (HKEY_LOCAL_MACHINE, PathScope::Machine),
+    ] {
+        for name in registry_subkeys(root, APP_PATHS_KEY) {
+            let subkey = format!("{}\\{}", APP_PATHS_KEY, name);
+            if let Some(target) = read_registry_string(root, &subkey, "") {
+                app_paths.push(AppPath {
+                    name,
+                    target: target.trim().trim_matches('"').to_string(),
+                    scope,
+                });
+            }
+        }
+    }
+    app_paths
+}
+
+#[cfg(not(windows))]
+pub fn get_app_paths_windows() -> Vec<AppPath> {
+    Vec::new()
+}
+
+/// Names of the subkeys of `root\subkey`
+#[cfg(windows)]
+fn registry_subkeys(root: winapi::shared::minwindef::HKEY, subkey: &str) -> Vec<String> {
+    use widestring::{U16CStr, U16CString};
+    use winapi::shared::minwindef::HKEY;
+    use winapi::um::winnt::KEY_READ;
+    use winapi::um::winreg::{RegCloseKey, RegEnumKeyExW, RegOpenKeyExW};
+
+    let Ok(subkey) = U16CString::from_str(subkey) else {
+        return Vec::new();
+    };
+    let mut names = Vec::new();
+
+    // SAFETY: the key handle is opened and closed here; each enumeration
+    // call gets the buffer's length in characters and NUL-terminates the
+    // name it writes
+    unsafe {
+        let mut key: HKEY = std::ptr::null_mut();
+        if RegOpenKeyExW(root, subkey.as_ptr(), 0, KEY_READ, &mut key) != 0 {
+            return names;
+        }
+
+        // Key names are at most 255 characters
+        let mut buffer = [0u16; 256];
+        for index in 0.. {
+            let mut len = buffer.len() as u32;
+            let status = RegEnumKeyExW(
+                key,
+                index,
+                buffer.as_mut_ptr(),
+                &mut len,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            );
+            if status != 0 {
+                break;
+            }
+            if let Ok(name) = U16CStr::from_slice_truncate(&buffer) {
+                names.push(name.to_string_lossy());
+            }
+        }
+        RegCloseKey(key);
+    }
+    names
+}
+
+/// Read a `REG_SZ`/`REG_EXPAND_SZ` value without expanding `%VAR%`
+/// references
+#[cfg(windows)]
+fn read_registry_string(
+    root: winapi::shared::minwindef::HKEY,
+    subkey: &str,
+    value: &str,
+) -> Option<String> {
+    use widestring::{U16CStr, U16CString};
+    use winapi::um::winreg::{RegGetValueW, RRF_NOEXPAND, RRF_RT_REG_EXPAND_SZ, RRF_RT_REG_SZ};
+
+    let subkey = U16CString::from_str(subkey).ok()?;
+    let value = U16CString::from_str(value).ok()?;
+    let flags = RRF_RT_REG_SZ | RRF_RT_REG_EXPAND_SZ | RRF_NOEXPAND;
+
+    // SAFETY: the first call only reports the size in bytes; the buffer
+    // passed to the second is at least that large and RegGetValueW
+    // NUL-terminates string data
+    unsafe {
+        let mut size = 0u32;
+        let status = RegGetValueW(
+            root,
+            subkey.as_ptr(),
+            value.as_ptr(),
+            flags,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            &mut size,
+        );
+        if status != 0 {
+            return None;
+        }
+
+        let mut buffer = vec![0u16; size as usize / 2 + 1];
+        let status = RegGetValueW(
+            root,
+            subkey.as_ptr(),
+            value.as_ptr(),
+            flags,
+            std::ptr::null_mut(),
+            buffer.as_mut_ptr() as _,
+            &mut size,
+        );
+        if status != 0 {
+            return None;
+        }
+
+        Some(
+            U16CStr::from_slice_truncate(&buffer)
+                .ok()?
+                .to_string_lossy(),
+        )
+    }
+}
+
 #[cfg(windows)]
 pub fn get_file_version_windows(_path: &Path) -> Option<String> {
     // TODO: Implement Windows file version extraction using winapi
@@ -1,4 +1,5 @@
 use crate::error::Result;
+use crate::output::types::WslInterop;
 use std::fs;
 use std::path::Path;
 
@@ -37,6 +38,60 @@ fn detect_wsl_version(proc_version: &str) -> Option<String> {
     }
 }
 
+/// Where WSL reads a distribution's settings
+pub const WSL_CONF: &str = "/etc/wsl.conf";
+
+/// Interop settings from /etc/wsl.conf, both on unless turned off, and the
+/// current WSLENV
+pub fn detect_wsl_interop() -> WslInterop {
+    let conf = fs::read_to_string(WSL_CONF).unwrap_or_default();
+    let mut interop = parse_wsl_conf(&conf);
+    interop.wslenv = std::env::var("WSLENV").ok().filter(|v| !v.is_empty());
+    interop
+}
+
+/// The `[interop]` section of a wsl.conf file. Keys are matched without
+/// regard to case, as WSL does.
+pub fn parse_wsl_conf(contents: &str) -> WslInterop {
+    let mut interop = WslInterop {
+        enabled: true,
+        append_windows_path: true,
+        wslenv: None,
+    };
+
+    let mut section = String::new();
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_lowercase();
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if section != "interop" {
+            continue;
+        }
+        let value = value.trim().trim_matches('"').to_lowercase();
+        let Some(value) = (match value.as_str() {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        }) else {
+            continue;
+        };
+        match key.trim().to_lowercase().as_str() {
+            "enabled" => interop.enabled = value,
+            "appendwindowspath" => interop.append_windows_path = value,
+            _ => {}
+        }
+    }
+
+    interop
+}
+
 pub fn is_wsl_path(path: &Path) -> bool {
     let path_str = path.to_string_lossy();
     path_str.starts_with("/mnt/") || is_unix_style_path(&path_str)
@@ -106,6 +161,42 @@ pub fn convert_wsl_to_windows_path(path: &Path) -> Option<String> {
     None
 }
 
+/// One `NAME/flags` item of `WSLENV`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WslEnvEntry {
+    pub name: String,
+    /// Flags after the slash: `p` (translate a path), `l` (translate a path
+    /// list), `u` (only WSL to Windows), `w` (only Windows to WSL)
+    pub flags: String,
+}
+
+impl WslEnvEntry {
+    /// Whether the value is converted between Windows and WSL paths
+    pub fn translates_paths(&self) -> bool {
+        self.flags.contains('p') || self.flags.contains('l')
+    }
+
+    /// Whether the variable is imported from Windows into WSL
+    pub fn reaches_wsl(&self) -> bool {
+        !self.flags.contains('u')
+    }
+}
+
+/// Split a `WSLENV` value (colon-separated `NAME/flags` items)
+pub fn parse_wslenv(value: &str) -> Vec<WslEnvEntry> {
+    value
+        .split(':')
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            let (name, flags) = item.split_once('/').unwrap_or((item, ""));
+            WslEnvEntry {
+                name: name.to_string(),
+                flags: flags.to_string(),
+            }
+        })
+        .collect()
+}
+
 pub fn categorize_wsl_path_mix(path1: &Path, path2: &Path) -> bool {
     let is_path1_windows = is_windows_path_in_wsl(path1);
     let is_path2_windows = is_windows_path_in_wsl(path2);
@@ -127,6 +218,29 @@ mod tests {
         assert!(!is_windows_path_in_wsl(Path::new("/home/user")));
     }
 
+    #[test]
+    fn test_parse_wslenv() {
+        let entries = parse_wslenv("GOPATH/l:USERPROFILE/pu::TERM");
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].name, "GOPATH");
+        assert!(entries[0].translates_paths() && entries[0].reaches_wsl());
+        assert!(!entries[1].reaches_wsl());
+        assert_eq!(entries[2].flags, "");
+        assert!(!entries[2].translates_paths());
+    }
+
+    #[test]
+    fn test_parse_wsl_conf() {
+        let defaults = parse_wsl_conf("");
+        assert!(defaults.enabled && defaults.append_windows_path);
+
+        let conf = "[automount]\nenabled = false\n\n[interop]\n# keep Windows tools out\n\
+                    appendWindowsPath = false\nenabled=true\n";
+        let interop = parse_wsl_conf(conf);
+        assert!(interop.enabled);
+        assert!(!interop.append_windows_path);
+    }
+
     #[test]
     fn test_convert_wsl_to_windows_path() {
         assert_eq!(
@@ -0,0 +1,252 @@
+use crate::output::types::{Conflict, ConflictCategory, Severity};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::widgets::ListState;
+
+/// Minimum severities cycled through with `s`; `None` shows everything
+const SEVERITY_FILTERS: [Option<Severity>; 5] = [
+    None,
+    Some(Severity::Low),
+    Some(Severity::Medium),
+    Some(Severity::High),
+    Some(Severity::Critical),
+];
+
+/// Browser state, kept separate from rendering so it can be tested
+pub struct App {
+    conflicts: Vec<Conflict>,
+    /// Indices into `conflicts` that pass the current filters
+    visible: Vec<usize>,
+    pub list_state: ListState,
+    severity_filter: usize,
+    /// Categories present in the result, cycled through with `c`
+    categories: Vec<ConflictCategory>,
+    category_filter: Option<usize>,
+    pub detail_scroll: u16,
+    pub status: Option<String>,
+    /// Text to put on the clipboard on the next draw
+    pub pending_copy: Option<String>,
+    pub should_quit: bool,
+}
+
+impl App {
+    pub fn new(conflicts: Vec<Conflict>) -> Self {
+        let mut categories: Vec<ConflictCategory> = Vec::new();
+        for conflict in &conflicts {
+            if !categories.contains(&conflict.category) {
+                categories.push(conflict.category);
+            }
+        }
+
+        let mut app = App {
+            conflicts,
+            visible: Vec::new(),
+            list_state: ListState::default(),
+            severity_filter: 0,
+            categories,
+            category_filter: None,
+            detail_scroll: 0,
+            status: None,
+            pending_copy: None,
+            should_quit: false,
+        };
+        app.apply_filters();
+        app
+    }
+
+    pub fn visible(&self) -> impl Iterator<Item = &Conflict> {
+        self.visible.iter().map(|&idx| &self.conflicts[idx])
+    }
+
+    pub fn selected(&self) -> Option<&Conflict> {
+        let idx = *self.visible.get(self.list_state.selected()?)?;
+        self.conflicts.get(idx)
+    }
+
+    pub fn total(&self) -> usize {
+        self.conflicts.len()
+    }
+
+    /// Human-readable description of the active filters
+    pub fn filter_label(&self) -> String {
+        let severity = match SEVERITY_FILTERS[self.severity_filter] {
+            Some(severity) => format!(">= {}", severity),
+            None => "all".to_string(),
+        };
+        let category = match self.category_filter {
+            Some(idx) => self.categories[idx].to_string(),
+            None => "all".to_string(),
+        };
+        format!("severity {} | category {}", severity, category)
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        self.status = None;
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.should_quit = true
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::Home | KeyCode::Char('g') => self.select(0),
+            KeyCode::End | KeyCode::Char('G') => self.select(self.visible.len().saturating_sub(1)),
+            KeyCode::Char('J') => self.detail_scroll = self.detail_scroll.saturating_add(1),
+            KeyCode::Char('K') => self.detail_scroll = self.detail_scroll.saturating_sub(1),
+            KeyCode::Char('s') => {
+                self.severity_filter = (self.severity_filter + 1) % SEVERITY_FILTERS.len();
+                self.apply_filters();
+            }
+            KeyCode::Char('c') => {
+                self.category_filter = match self.category_filter {
+                    None if !self.categories.is_empty() => Some(0),
+                    Some(idx) if idx + 1 < self.categories.len() => Some(idx + 1),
+                    _ => None,
+                };
+                self.apply_filters();
+            }
+            KeyCode::Char('r') => {
+                self.severity_filter = 0;
+                self.category_filter = None;
+                self.apply_filters();
+            }
+            KeyCode::Char('y') => self.copy_commands(),
+            _ => {}
+        }
+    }
+
+    fn copy_commands(&mut self) {
+        let commands = match self.selected() {
+            Some(conflict) if !conflict.commands.is_empty() => conflict.commands.join("\n"),
+            Some(_) => {
+                self.status = Some("No fix commands for this conflict".to_string());
+                return;
+            }
+            None => return,
+        };
+
+        self.status = Some(format!(
+            "Copied {} command(s) to the clipboard",
+            commands.lines().count()
+        ));
+        self.pending_copy = Some(commands);
+    }
+
+    fn apply_filters(&mut self) {
+        let min_severity = SEVERITY_FILTERS[self.severity_filter];
+        let category = self.category_filter.map(|idx| self.categories[idx]);
+
+        self.visible = self
+            .conflicts
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| min_severity.map_or(true, |min| c.severity >= min))
+            .filter(|(_, c)| category.map_or(true, |category| c.category == category))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        self.select(0);
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let current = self.list_state.selected().unwrap_or(0) as isize;
+        let last = self.visible.len().saturating_sub(1) as isize;
+        self.select((current + delta).clamp(0, last) as usize);
+    }
+
+    fn select(&mut self, idx: usize) {
+        self.detail_scroll = 0;
+        if self.visible.is_empty() {
+            self.list_state.select(None);
+        } else {
+            self.list_state
+                .select(Some(idx.min(self.visible.len() - 1)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::types::ExecutableInfo;
+    use std::path::PathBuf;
+
+    fn conflict(name: &str, category: ConflictCategory, severity: Severity) -> Conflict {
+        let instance = ExecutableInfo {
+            name: name.to_string(),
+            full_path: PathBuf::from("/usr/bin").join(name),
+            size: 1000,
+            resolved_path: PathBuf::from("/usr/bin").join(name),
+            is_active: true,
+            ..ExecutableInfo::fixture()
+        };
+        Conflict {
+            binary_name: name.to_string(),
+            instances: vec![instance.clone()],
+            active_instance: instance,
+            category,
+            severity,
+            severity_reason: String::new(),
+            description: String::new(),
+            recommendation: None,
+            commands: vec![format!("export PATH=\"/opt/{}:$PATH\"", name)],
+            same_content: false,
+            risk_score: 0.0,
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn sample_app() -> App {
+        App::new(vec![
+            conflict(
+                "python",
+                ConflictCategory::VersionManagerVsSystem,
+                Severity::Critical,
+            ),
+            conflict("git", ConflictCategory::ShadowedBinary, Severity::Medium),
+            conflict("ls", ConflictCategory::ShadowedBinary, Severity::Info),
+        ])
+    }
+
+    #[test]
+    fn test_filters_cycle() {
+        let mut app = sample_app();
+        assert_eq!(app.visible().count(), 3);
+
+        // all -> low -> medium
+        app.handle_key(key(KeyCode::Char('s')));
+        app.handle_key(key(KeyCode::Char('s')));
+        assert_eq!(app.visible().count(), 2);
+
+        // first category present is VersionManagerVsSystem
+        app.handle_key(key(KeyCode::Char('c')));
+        let names: Vec<_> = app.visible().map(|c| c.binary_name.as_str()).collect();
+        assert_eq!(names, vec!["python"]);
+
+        app.handle_key(key(KeyCode::Char('r')));
+        assert_eq!(app.visible().count(), 3);
+    }
+
+    #[test]
+    fn test_navigation_and_copy() {
+        let mut app = sample_app();
+        app.handle_key(key(KeyCode::Down));
+        app.handle_key(key(KeyCode::Down));
+        app.handle_key(key(KeyCode::Down));
+        assert_eq!(app.selected().unwrap().binary_name, "ls");
+
+        app.handle_key(key(KeyCode::Char('y')));
+        assert_eq!(
+            app.pending_copy.as_deref(),
+            Some("export PATH=\"/opt/ls:$PATH\"")
+        );
+
+        app.handle_key(key(KeyCode::Char('q')));
+        assert!(app.should_quit);
+    }
+}
@@ -0,0 +1,96 @@
+//! Interactive browser for analysis results, behind the `tui` feature
+
+pub mod app;
+mod ui;
+
+pub use app::App;
+
+use crate::error::Result;
+use crate::output::types::AnalysisResult;
+use crossterm::event::{self, Event, KeyEventKind};
+use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::ExecutableCommand;
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+use std::io::{Stdout, Write};
+
+/// Browse `result` until the user quits
+pub fn run(result: &AnalysisResult) -> Result<()> {
+    let mut terminal = setup()?;
+    let outcome = event_loop(&mut terminal, App::new(result.conflicts.clone()));
+    restore(&mut terminal)?;
+    outcome
+}
+
+fn event_loop(terminal: &mut Terminal<CrosstermBackend<Stdout>>, mut app: App) -> Result<()> {
+    while !app.should_quit {
+        terminal.draw(|frame| ui::draw(frame, &mut app))?;
+
+        if let Some(text) = app.pending_copy.take() {
+            copy_to_clipboard(terminal.backend_mut(), &text)?;
+        }
+
+        if let Event::Key(key) = event::read()? {
+            // Windows reports releases too
+            if key.kind == KeyEventKind::Press {
+                app.handle_key(key);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// OSC 52 asks the terminal itself to set the clipboard, which also works
+/// over SSH and needs no platform clipboard library
+fn copy_to_clipboard(out: &mut impl Write, text: &str) -> Result<()> {
+    write!(out, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    out.flush()?;
+    Ok(())
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let n = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+fn setup() -> Result<Terminal<CrosstermBackend<Stdout>>> {
+    terminal::enable_raw_mode()?;
+    let mut stdout = std::io::stdout();
+    stdout.execute(EnterAlternateScreen)?;
+    Ok(Terminal::new(CrosstermBackend::new(stdout))?)
+}
+
+fn restore(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
+    terminal::disable_raw_mode()?;
+    terminal.backend_mut().execute(LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"export PATH"), "ZXhwb3J0IFBBVEg=");
+    }
+}
@@ -0,0 +1,154 @@
+use crate::output::types::{Conflict, ExecutableInfo, Severity};
+use crate::tui::app::App;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Wrap};
+use ratatui::Frame;
+
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(frame.size());
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(rows[0]);
+
+    let items: Vec<ListItem> = app
+        .visible()
+        .map(|conflict| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<9}", conflict.severity.to_string()),
+                    severity_style(conflict.severity),
+                ),
+                Span::raw(conflict.binary_name.clone()),
+            ]))
+        })
+        .collect();
+    let title = format!(
+        " Conflicts ({}/{}) ",
+        app.list_state.selected().map_or(0, |idx| idx + 1),
+        items.len()
+    );
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(list, panes[0], &mut app.list_state);
+
+    let detail = match app.selected() {
+        Some(conflict) => detail_lines(conflict),
+        None => vec![Line::from("No conflicts match the current filters")],
+    };
+    let detail = Paragraph::new(detail)
+        .block(Block::default().borders(Borders::ALL).title(" Details "))
+        .wrap(Wrap { trim: false })
+        .scroll((app.detail_scroll, 0));
+    frame.render_widget(detail, panes[1]);
+
+    let footer = match &app.status {
+        Some(status) => Line::from(Span::styled(
+            status.clone(),
+            Style::default().fg(Color::Green),
+        )),
+        None => Line::from(vec![
+            Span::styled(
+                format!("{} ({} total) | ", app.filter_label(), app.total()),
+                Style::default().add_modifier(Modifier::DIM),
+            ),
+            Span::raw("j/k move  J/K scroll  s severity  c category  r reset  y copy fix  q quit"),
+        ]),
+    };
+    frame.render_widget(Paragraph::new(footer), rows[1]);
+}
+
+fn detail_lines(conflict: &Conflict) -> Vec<Line<'static>> {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut lines = vec![
+        Line::from(Span::styled(conflict.binary_name.clone(), bold)),
+        Line::from(vec![
+            Span::styled(
+                conflict.severity.to_string(),
+                severity_style(conflict.severity),
+            ),
+            Span::raw(format!(
+                " - {} - risk {:.1}",
+                conflict.category, conflict.risk_score
+            )),
+        ]),
+    ];
+    if !conflict.severity_reason.is_empty() {
+        lines.push(Line::from(format!("Why: {}", conflict.severity_reason)));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(conflict.description.clone()));
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Active", bold.fg(Color::Green))));
+    lines.extend(instance_lines(&conflict.active_instance));
+
+    if conflict.instances.len() > 1 {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Shadowed", bold.fg(Color::Yellow))));
+        for instance in conflict
+            .instances
+            .iter()
+            .filter(|i| i.full_path != conflict.active_instance.full_path)
+        {
+            lines.extend(instance_lines(instance));
+        }
+    }
+
+    if let Some(recommendation) = &conflict.recommendation {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Recommendation", bold)));
+        lines.push(Line::from(recommendation.clone()));
+    }
+
+    if !conflict.commands.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Fix commands (y to copy)", bold)));
+        for command in &conflict.commands {
+            lines.push(Line::from(format!("  $ {}", command)));
+        }
+    }
+
+    lines
+}
+
+fn instance_lines(instance: &ExecutableInfo) -> Vec<Line<'static>> {
+    let mut path = format!("  {}", instance.full_path.display());
+    if instance.immutable {
+        path.push_str(" [SIP-protected]");
+    }
+    let mut lines = vec![Line::from(path)];
+
+    if let Some(version) = &instance.version {
+        let version = version.parsed.as_ref().unwrap_or(&version.raw);
+        lines.push(Line::from(format!("    Version: {}", version)));
+    }
+    if let Some(manager) = &instance.manager {
+        lines.push(Line::from(format!("    Managed by: {}", manager.name)));
+    }
+    if !instance.symlink_chain.is_empty() {
+        let chain: Vec<String> = std::iter::once(&instance.full_path)
+            .chain(instance.symlink_chain.iter())
+            .map(|p| p.display().to_string())
+            .collect();
+        lines.push(Line::from(format!("    Symlinks: {}", chain.join(" → "))));
+    }
+
+    lines
+}
+
+fn severity_style(severity: Severity) -> Style {
+    match severity {
+        Severity::Critical => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        Severity::High => Style::default().fg(Color::Red),
+        Severity::Medium => Style::default().fg(Color::Yellow),
+        Severity::Low => Style::default().fg(Color::Blue),
+        Severity::Info => Style::default(),
+    }
+}